@@ -0,0 +1,120 @@
+use crate::image::Image;
+use image::{ExtendedColorType, Rgba, RgbaImage};
+use std::fmt::Write;
+
+/* Height of a single channel in the rendered histogram */
+const CHANNEL_HEIGHT: u32 = 64;
+/* Background color of the rendered histogram */
+const BACKGROUND_COLOR: Rgba<u8> = Rgba([24, 24, 24, 255]);
+/* Names and colors of the histogram channels */
+const CHANNELS: [(&str, Rgba<u8>); 4] = [
+	("red", Rgba([255, 64, 64, 255])),
+	("green", Rgba([64, 255, 64, 255])),
+	("blue", Rgba([64, 128, 255, 255])),
+	("luma", Rgba([224, 224, 224, 255])),
+];
+
+/* Per-channel distribution of the pixel values */
+#[derive(Debug)]
+pub struct Histogram {
+	channels: [[u32; 256]; 4],
+}
+
+impl Histogram {
+	/**
+	 * Create a new Histogram object from the given image.
+	 *
+	 * @param  image
+	 * @return Histogram
+	 */
+	pub fn from_image(image: &Image) -> Self {
+		let mut channels = [[0; 256]; 4];
+		for rgba in image.get_data(ExtendedColorType::Rgba8).chunks_exact(4) {
+			for (channel, value) in channels.iter_mut().zip(&rgba[..3]) {
+				channel[*value as usize] += 1;
+			}
+		}
+		for luma in image.get_data(ExtendedColorType::L8) {
+			channels[3][luma as usize] += 1;
+		}
+		Self { channels }
+	}
+
+	/**
+	 * Get the histogram data in CSV format.
+	 *
+	 * @return String
+	 */
+	pub fn to_csv(&self) -> String {
+		let mut csv =
+			format!("value,{}\n", CHANNELS.map(|(name, _)| name).join(","));
+		for value in 0..256 {
+			let _ = writeln!(
+				csv,
+				"{},{}",
+				value,
+				self.channels
+					.map(|channel| channel[value].to_string())
+					.join(",")
+			);
+		}
+		csv
+	}
+
+	/**
+	 * Render the channels as bar charts below each other.
+	 *
+	 * @return RgbaImage
+	 */
+	pub fn render(&self) -> RgbaImage {
+		let mut image = RgbaImage::from_pixel(
+			256,
+			CHANNEL_HEIGHT * CHANNELS.len() as u32,
+			BACKGROUND_COLOR,
+		);
+		for (i, (channel, (_, color))) in
+			self.channels.iter().zip(CHANNELS).enumerate()
+		{
+			let max = channel.iter().max().copied().unwrap_or_default().max(1);
+			let bottom = CHANNEL_HEIGHT * (i as u32 + 1);
+			for (x, count) in channel.iter().enumerate() {
+				let height = (*count as u64 * CHANNEL_HEIGHT as u64)
+					.div_ceil(max as u64) as u32;
+				for y in (bottom - height)..bottom {
+					image.put_pixel(x as u32, y, color);
+				}
+			}
+		}
+		image
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_histogram() {
+		let mut data = vec![Rgba([255, 0, 0, 255]); 3];
+		data.push(Rgba([255, 255, 255, 255]));
+		let histogram = Histogram::from_image(&Image::new(
+			data,
+			false,
+			Geometry::new(0, 0, 2, 2),
+		));
+		assert_eq!(4, histogram.channels[0][255]);
+		assert_eq!(3, histogram.channels[1][0]);
+		assert_eq!(3, histogram.channels[3][54]);
+		let csv = histogram.to_csv();
+		assert_eq!(257, csv.lines().count());
+		assert_eq!(Some("value,red,green,blue,luma"), csv.lines().next());
+		assert_eq!(Some("255,4,1,1,1"), csv.lines().last());
+		let image = histogram.render();
+		assert_eq!((256, 256), image.dimensions());
+		assert_eq!(CHANNELS[0].1, *image.get_pixel(255, 0));
+		assert_eq!(BACKGROUND_COLOR, *image.get_pixel(128, 0));
+		assert_eq!(CHANNELS[1].1, *image.get_pixel(255, 127));
+		assert_eq!(BACKGROUND_COLOR, *image.get_pixel(255, 100));
+	}
+}
@@ -1,3 +1,4 @@
+pub mod histogram;
 pub mod settings;
 
 use crate::analyze::settings::AnalyzeSettings;
@@ -130,6 +130,62 @@ impl AnalyzeSettings {
 	}
 }
 
+/* Histogram settings */
+#[derive(Debug, Default)]
+pub struct HistogramSettings {
+	pub file: Option<PathBuf>,
+}
+
+impl HistogramSettings {
+	/**
+	 * Create a new HistogramSettings object.
+	 *
+	 * @param  file (Option)
+	 * @return HistogramSettings
+	 */
+	pub fn new(file: Option<PathBuf>) -> Self {
+		Self { file }
+	}
+
+	/**
+	 * Create a new HistogramSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return HistogramSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::from_parser(ArgParser::from_subcommand(matches, "capture"))
+	}
+
+	/**
+	 * Create a HistogramSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return HistogramSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>) -> Self {
+		match parser.args {
+			Some(matches) => Self::new(matches.value_of("histogram").map(|file| {
+				PathBuf::from(
+					shellexpand::full(file)
+						.map(|s| s.to_string())
+						.unwrap_or(file.to_string()),
+				)
+			})),
+			None => Self::default(),
+		}
+	}
+
+	/**
+	 * Check if the histogram data should be printed to stdout.
+	 *
+	 * @return bool
+	 */
+	pub fn is_stdout(&self) -> bool {
+		self.file.as_ref().and_then(|v| v.to_str()) == Some("-")
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -146,6 +202,17 @@ mod tests {
 		let analyze_settings = AnalyzeSettings::default();
 		assert_eq!(Some(""), analyze_settings.file.to_str());
 		assert_eq!(Color::White, analyze_settings.color);
+		let args = App::new("test")
+			.arg(
+				Arg::with_name("histogram")
+					.long("histogram")
+					.takes_value(true),
+			)
+			.get_matches_from(vec!["test", "--histogram", "-"]);
+		let histogram_settings =
+			HistogramSettings::from_parser(ArgParser::from_args(&args));
+		assert!(histogram_settings.is_stdout());
+		assert!(!HistogramSettings::default().is_stdout());
 	}
 	#[test]
 	fn test_time_zone() {
@@ -1,10 +1,28 @@
 use crate::anim::settings::AnimSettings;
+use crate::anim::sprite::Spritesheet;
 use crate::anim::Frames;
 use crate::app::{AppError, AppResult};
+use crate::edit::settings::{ContactSheet, Flatten, Resize};
 use crate::edit::ImageOps;
-use image::Frame;
+use crate::image::font;
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use crate::montage::settings::MontageSettings;
+use crate::montage::Montage;
+use image::imageops::FilterType;
+use image::{
+	imageops, ExtendedColorType, Frame, Frames as ImageFrames, ImageResult, Rgba,
+	RgbaImage,
+};
 use std::io::{self, Write};
 
+/* Gap between the frames of the contact sheet */
+const SHEET_GAP: u32 = 4;
+/* Padding around the frame labels */
+const LABEL_PADDING: u32 = 2;
+/* Opacity of the frame label background */
+const LABEL_OPACITY: f32 = 0.6;
+
 /* Animation decoder and settings */
 pub struct AnimDecoder<'a> {
 	imageops: ImageOps<'a>,
@@ -24,32 +42,327 @@ impl<'a> AnimDecoder<'a> {
 	}
 
 	/**
-	 * Update frames to cut the duration.
+	 * Composite the partial frames onto the full canvas.
+	 *
+	 * Decoders already apply the disposal methods, so the frames that cover
+	 * the canvas are kept as is and the partial ones replace the pixels of
+	 * the previous frame instead of being blended onto it.
 	 *
 	 * @param  frames
-	 * @param  cut
-	 * @param  fps
 	 * @return Vector of Frame
 	 */
-	fn cut_duration(
-		frames: &mut Vec<Frame>,
-		cut: (f32, f32),
-		fps: u32,
-	) -> Vec<Frame> {
-		if cut != (0., 0.) {
-			let (start, end) = cut;
-			let frame_delay = 1000_u32.checked_div(fps).unwrap_or_default() as f32;
-			frames
-				.drain(
-					((start / frame_delay) as u32)
-						.try_into()
-						.unwrap_or_default()
-						..frames.len().saturating_sub((end / frame_delay) as usize),
+	fn composite_frames(frames: Vec<Frame>) -> Vec<Frame> {
+		let (width, height) =
+			frames.iter().fold((0, 0), |(width, height), frame| {
+				(
+					width.max(frame.left() + frame.buffer().width()),
+					height.max(frame.top() + frame.buffer().height()),
 				)
-				.collect()
-		} else {
-			frames.to_vec()
+			});
+		let mut canvas = RgbaImage::new(width, height);
+		frames
+			.into_iter()
+			.map(|frame| {
+				let (left, top, delay) = (frame.left(), frame.top(), frame.delay());
+				let buffer = frame.into_buffer();
+				if (left, top) == (0, 0) && buffer.dimensions() == (width, height) {
+					canvas = buffer;
+				} else {
+					imageops::replace(&mut canvas, &buffer, left.into(), top.into());
+				}
+				Frame::from_parts(canvas.clone(), 0, 0, delay)
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the composited frame at the given index.
+	 *
+	 * Only the frames up to the index are decoded.
+	 *
+	 * @param  frames
+	 * @param  index
+	 * @return RgbaImage (Result)
+	 */
+	pub fn get_frame(frames: ImageFrames<'_>, index: usize) -> AppResult<RgbaImage> {
+		let frames = frames
+			.take(index.saturating_add(1))
+			.collect::<ImageResult<Vec<Frame>>>()?;
+		let count = frames.len();
+		Self::composite_frames(frames)
+			.into_iter()
+			.nth(index)
+			.map(Frame::into_buffer)
+			.ok_or_else(|| {
+				AppError::FrameError(format!(
+					"Frame index is out of range: {index} (found {count} frames)"
+				))
+			})
+	}
+
+	/**
+	 * Flatten the frames into a single image.
+	 *
+	 * @param  frames
+	 * @param  flatten
+	 * @return RgbaImage (Result)
+	 */
+	pub fn flatten(
+		frames: ImageFrames<'_>,
+		flatten: Flatten,
+	) -> AppResult<RgbaImage> {
+		let frames = match flatten {
+			Flatten::First => frames.take(1).collect::<ImageResult<Vec<Frame>>>(),
+			_ => frames.collect_frames(),
+		}?;
+		let mut images = Self::composite_frames(frames)
+			.into_iter()
+			.map(Frame::into_buffer);
+		let first = images.next().ok_or_else(|| {
+			AppError::FrameError(String::from("No frames found to flatten"))
+		})?;
+		info!("Flattening the frames... ({:?})", flatten);
+		Ok(match flatten {
+			Flatten::First => first,
+			Flatten::Last => images.next_back().unwrap_or(first),
+			Flatten::Max => images.fold(first, |mut image, next| {
+				for (pixel, next) in image.pixels_mut().zip(next.pixels()) {
+					for c in 0..4 {
+						pixel[c] = pixel[c].max(next[c]);
+					}
+				}
+				image
+			}),
+			Flatten::Average => {
+				let (width, height) = first.dimensions();
+				let mut sum: Vec<u32> =
+					first.into_raw().into_iter().map(u32::from).collect();
+				let mut count = 1;
+				for image in images {
+					for (value, next) in sum.iter_mut().zip(image.into_raw()) {
+						*value += u32::from(next);
+					}
+					count += 1;
+				}
+				RgbaImage::from_raw(
+					width,
+					height,
+					sum.into_iter()
+						.map(|value| ((value + count / 2) / count) as u8)
+						.collect(),
+				)
+				.ok_or_else(|| {
+					AppError::FrameError(String::from(
+						"Failed to flatten the frames",
+					))
+				})?
+			}
+		})
+	}
+
+	/**
+	 * Tile the frames into a contact sheet.
+	 *
+	 * @param  frames
+	 * @param  sheet
+	 * @param  filter
+	 * @return RgbaImage (Result)
+	 */
+	pub fn get_contact_sheet(
+		frames: ImageFrames<'_>,
+		sheet: ContactSheet,
+		filter: FilterType,
+	) -> AppResult<RgbaImage> {
+		let frames = frames.collect_frames()?;
+		let count = frames.len();
+		let images = Self::composite_frames(frames)
+			.into_iter()
+			.map(Frame::into_buffer)
+			.enumerate()
+			.step_by(sheet.every)
+			.map(|(index, image)| {
+				let image = match sheet.size {
+					Some(size) => {
+						let (width, height) = image.dimensions();
+						let size = Resize::LongEdge(size)
+							.get_geometry(Geometry::new(0, 0, width, height));
+						imageops::resize(&image, size.width, size.height, filter)
+					}
+					None => image,
+				};
+				if sheet.labels {
+					Self::draw_label(image, index)
+				} else {
+					image
+				}
+			})
+			.collect::<Vec<RgbaImage>>();
+		if images.is_empty() {
+			return Err(AppError::FrameError(String::from(
+				"No frames found for the contact sheet",
+			)));
 		}
+		info!(
+			"Creating a contact sheet from {} of {} frames...",
+			images.len(),
+			count
+		);
+		let settings = MontageSettings {
+			cols: sheet.cols,
+			gap: SHEET_GAP,
+			background: Rgba([0, 0, 0, 255]),
+			..Default::default()
+		};
+		let image = Montage::new(images, &settings).get_image();
+		RgbaImage::from_raw(
+			image.geometry.width,
+			image.geometry.height,
+			image.get_data(ExtendedColorType::Rgba8),
+		)
+		.ok_or_else(|| {
+			AppError::FrameError(String::from("Failed to create the contact sheet"))
+		})
+	}
+
+	/**
+	 * Tile the frames into a spritesheet.
+	 *
+	 * @param  frames
+	 * @param  cols
+	 * @return Spritesheet (Result)
+	 */
+	pub fn get_spritesheet(
+		frames: ImageFrames<'_>,
+		cols: u32,
+	) -> AppResult<Spritesheet> {
+		let frames = Self::composite_frames(frames.collect_frames()?)
+			.into_iter()
+			.map(|frame| {
+				let (numerator, denominator) = frame.delay().numer_denom_ms();
+				(frame.into_buffer(), numerator / denominator.max(1))
+			})
+			.collect::<Vec<(RgbaImage, u32)>>();
+		info!("Creating a spritesheet from {} frames...", frames.len());
+		Spritesheet::new(frames, cols)
+	}
+
+	/**
+	 * Draw the frame number on the top left corner of the image.
+	 *
+	 * @param  image
+	 * @param  index
+	 * @return RgbaImage
+	 */
+	fn draw_label(image: RgbaImage, index: usize) -> RgbaImage {
+		let (width, height) = image.dimensions();
+		let mut label = Image::new(
+			image.pixels().copied().collect(),
+			true,
+			Geometry::new(0, 0, width, height),
+		);
+		let text = index.to_string();
+		let scale = (height / 100).clamp(1, 3);
+		let (text_width, text_height) = font::get_text_size(&text, scale);
+		label.shade_area(
+			Geometry::new(
+				0,
+				0,
+				text_width + LABEL_PADDING * 2,
+				text_height + LABEL_PADDING * 2,
+			),
+			Rgba([0, 0, 0, 255]),
+			LABEL_OPACITY,
+		);
+		let padding = i32::try_from(LABEL_PADDING).unwrap_or_default();
+		label.draw_text(
+			&text,
+			(padding, padding),
+			scale,
+			Rgba([255, 255, 255, 255]),
+			1.,
+		);
+		RgbaImage::from_raw(width, height, label.get_data(ExtendedColorType::Rgba8))
+			.unwrap_or(image)
+	}
+
+	/**
+	 * Get the area of the frames without the dark bars around them.
+	 *
+	 * Only the bars that are present in all of the frames are detected.
+	 *
+	 * @param  frames
+	 * @param  threshold
+	 * @return Geometry (Option)
+	 */
+	fn get_letterbox(frames: &[Frame], threshold: u8) -> Option<Geometry> {
+		let (width, height) = frames.first()?.buffer().dimensions();
+		let (mut top, mut right, mut bottom, mut left) =
+			(height, width, height, width);
+		for frame in frames {
+			let mut rows = vec![true; height as usize];
+			let mut cols = vec![true; width as usize];
+			for (x, y, pixel) in frame.buffer().enumerate_pixels() {
+				if pixel.0[..3].iter().any(|value| *value > threshold) {
+					rows[y as usize] = false;
+					cols[x as usize] = false;
+				}
+			}
+			let is_dark = |dark: &&bool| **dark;
+			top = top.min(rows.iter().take_while(is_dark).count() as u32);
+			bottom =
+				bottom.min(rows.iter().rev().take_while(is_dark).count() as u32);
+			left = left.min(cols.iter().take_while(is_dark).count() as u32);
+			right = right.min(cols.iter().rev().take_while(is_dark).count() as u32);
+		}
+		if top + bottom >= height
+			|| left + right >= width
+			|| (top, right, bottom, left) == (0, 0, 0, 0)
+		{
+			return None;
+		}
+		Some(Geometry::new(
+			left as i32,
+			top as i32,
+			width - left - right,
+			height - top - bottom,
+		))
+	}
+
+	/**
+	 * Crop the dark bars around the frames.
+	 *
+	 * @param  frames
+	 * @param  threshold
+	 * @return Vector of Frame
+	 */
+	fn deletterbox(frames: Vec<Frame>, threshold: u8) -> Vec<Frame> {
+		let area = match Self::get_letterbox(&frames, threshold) {
+			Some(area) => area,
+			None => {
+				info!("No letterbox found in the frames.");
+				return frames;
+			}
+		};
+		info!("Removing the letterbox... ({}x{})", area.width, area.height);
+		frames
+			.into_iter()
+			.map(|frame| {
+				let delay = frame.delay();
+				Frame::from_parts(
+					imageops::crop_imm(
+						frame.buffer(),
+						area.x as u32,
+						area.y as u32,
+						area.width,
+						area.height,
+					)
+					.to_image(),
+					0,
+					0,
+					delay,
+				)
+			})
+			.collect()
 	}
 
 	/**
@@ -58,16 +371,28 @@ impl<'a> AnimDecoder<'a> {
 	 * @param  frames
 	 * @return Frames (Result)
 	 */
-	pub fn update_frames(mut self, mut frames: Vec<Frame>) -> AppResult<Frames> {
+	pub fn update_frames(mut self, frames: Vec<Frame>) -> AppResult<Frames> {
+		let mut frames = Self::composite_frames(frames);
 		let first_frame = frames.first().ok_or_else(|| {
 			AppError::FrameError(String::from("No frames found to process"))
 		})?;
-		self.imageops
-			.init(first_frame.clone().into_buffer().dimensions());
-		let fps = ((1e3 / first_frame.delay().numer_denom_ms().0 as f32)
-			* self.settings.speed) as u32;
+		let size = first_frame.buffer().dimensions();
+		let (numerator, denominator) = first_frame.delay().numer_denom_ms();
+		let fps =
+			(1e3 * denominator as f32 / numerator as f32) * self.settings.speed;
 		debug!("FPS: {:?}", fps);
-		let frames = Self::cut_duration(&mut frames, self.settings.cut, fps);
+		let frames: Vec<Frame> = frames
+			.drain(self.settings.get_cut_range(frames.len(), fps))
+			.collect();
+		let frames = match self.settings.deletterbox {
+			Some(threshold) => Self::deletterbox(frames, threshold),
+			None => frames,
+		};
+		self.imageops.init(
+			frames
+				.first()
+				.map_or(size, |frame| frame.buffer().dimensions()),
+		)?;
 		let mut images = Vec::new();
 		for (i, frame) in frames.iter().enumerate() {
 			let percentage = ((i + 1) as f64 / frames.len() as f64) * 100.;
@@ -95,10 +420,13 @@ mod tests {
 	use super::*;
 	use crate::edit::settings::EditSettings;
 	use crate::image::geometry::Geometry;
-	use image::{Delay, Frame, RgbaImage};
+	use image::codecs::gif::GifDecoder;
+	use image::{
+		AnimationDecoder, Delay, ExtendedColorType, Frame, Rgba, RgbaImage,
+	};
 	use pretty_assertions::assert_eq;
 	#[test]
-	fn test_anim_decoder() {
+	fn test_anim_decoder() -> AppResult<()> {
 		let anim_settings = AnimSettings {
 			cut: (500., 0.),
 			speed: 2.0,
@@ -106,7 +434,7 @@ mod tests {
 		};
 		let mut edit_settings = EditSettings::default();
 		edit_settings.image.ratio = 2.0;
-		let frames = AnimDecoder::new(edit_settings.get_imageops(), &anim_settings)
+		let frames = AnimDecoder::new(edit_settings.get_imageops()?, &anim_settings)
 			.update_frames(vec![
 				Frame::from_parts(
 					RgbaImage::new(1, 1),
@@ -122,8 +450,210 @@ mod tests {
 				),
 			])
 			.unwrap();
-		assert_eq!(2, frames.1);
+		assert_eq!(2., frames.1);
 		assert_eq!(1, frames.0.len());
 		assert_eq!(Geometry::new(0, 0, 2, 2), frames.0[0].geometry);
+		let get_frames = || {
+			ImageFrames::new(Box::new(
+				vec![
+					Frame::from_parts(
+						RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 255])),
+						0,
+						0,
+						Delay::from_numer_denom_ms(100, 1),
+					),
+					Frame::from_parts(
+						RgbaImage::from_pixel(1, 1, Rgba([0, 0, 255, 255])),
+						1,
+						1,
+						Delay::from_numer_denom_ms(100, 1),
+					),
+				]
+				.into_iter()
+				.map(Ok),
+			))
+		};
+		let frame = AnimDecoder::get_frame(get_frames(), 1).unwrap();
+		assert_eq!(Rgba([255, 0, 0, 255]), *frame.get_pixel(0, 0));
+		assert_eq!(Rgba([0, 0, 255, 255]), *frame.get_pixel(1, 1));
+		assert!(AnimDecoder::get_frame(get_frames(), 2).is_err());
+		for (flatten, pixel) in [
+			(Flatten::First, [255, 0, 0, 255]),
+			(Flatten::Last, [0, 0, 255, 255]),
+			(Flatten::Average, [128, 0, 128, 255]),
+			(Flatten::Max, [255, 0, 255, 255]),
+		] {
+			let image = AnimDecoder::flatten(get_frames(), flatten).unwrap();
+			assert_eq!((2, 2), image.dimensions());
+			assert_eq!(Rgba([255, 0, 0, 255]), *image.get_pixel(0, 0));
+			assert_eq!(Rgba(pixel), *image.get_pixel(1, 1));
+		}
+		assert!(AnimDecoder::flatten(
+			ImageFrames::new(Box::new(std::iter::empty())),
+			Flatten::Max
+		)
+		.is_err());
+		let get_frames = || {
+			ImageFrames::new(Box::new(
+				(0..7)
+					.map(|i| {
+						Frame::from_parts(
+							RgbaImage::from_pixel(20, 10, Rgba([i * 30, 0, 0, 255])),
+							0,
+							0,
+							Delay::from_numer_denom_ms(100, 1),
+						)
+					})
+					.map(Ok),
+			))
+		};
+		let sheet = AnimDecoder::get_contact_sheet(
+			get_frames(),
+			ContactSheet::new(3, 2, Some(10), false),
+			FilterType::Nearest,
+		)
+		.unwrap();
+		assert_eq!(
+			(3 * 10 + 2 * SHEET_GAP, 2 * 5 + SHEET_GAP),
+			sheet.dimensions()
+		);
+		assert_eq!(Rgba([60, 0, 0, 255]), *sheet.get_pixel(14, 2));
+		assert_eq!(Rgba([180, 0, 0, 255]), *sheet.get_pixel(0, 9));
+		assert_eq!(Rgba([0, 0, 0, 255]), *sheet.get_pixel(14, 9));
+		let sheet = AnimDecoder::get_contact_sheet(
+			get_frames(),
+			ContactSheet::new(7, 1, None, true),
+			FilterType::Nearest,
+		)
+		.unwrap();
+		assert_eq!((7 * 20 + 6 * SHEET_GAP, 10), sheet.dimensions());
+		assert_eq!(Rgba([255, 255, 255, 255]), *sheet.get_pixel(3, 2));
+		assert_eq!(Rgba([0, 0, 0, 255]), *sheet.get_pixel(19, 9));
+		assert!(AnimDecoder::get_contact_sheet(
+			ImageFrames::new(Box::new(std::iter::empty())),
+			ContactSheet::default(),
+			FilterType::Nearest
+		)
+		.is_err());
+		Ok(())
+	}
+	#[test]
+	fn test_offset_frames() -> AppResult<()> {
+		let mut data = Vec::new();
+		{
+			let mut encoder = gif::Encoder::new(&mut data, 4, 4, &[])?;
+			let mut frame = gif::Frame::from_rgba(4, 4, &mut [255; 64]);
+			frame.delay = 10;
+			encoder.write_frame(&frame)?;
+			let mut frame =
+				gif::Frame::from_rgba(2, 2, &mut [0, 0, 0, 255].repeat(4));
+			frame.left = 2;
+			frame.top = 2;
+			frame.delay = 10;
+			encoder.write_frame(&frame)?;
+		}
+		let mut edit_settings = EditSettings::default();
+		edit_settings.image.crop.left = 1;
+		edit_settings.image.crop.top = 1;
+		let (images, fps) = AnimDecoder::new(
+			edit_settings.get_imageops()?,
+			&AnimSettings::default(),
+		)
+		.update_frames(
+			GifDecoder::new(data.as_slice())?
+				.into_frames()
+				.collect_frames()?,
+		)?;
+		assert_eq!(10., fps);
+		assert_eq!(2, images.len());
+		for image in &images {
+			assert_eq!(Geometry::new(1, 1, 3, 3), image.geometry);
+		}
+		let black = [0, 0, 0, 255];
+		let white = [255, 255, 255, 255];
+		assert_eq!(
+			white.repeat(9),
+			images[0].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(
+			[white, white, white, white, black, black, white, black, black].concat(),
+			images[1].get_data(ExtendedColorType::Rgba8)
+		);
+		let red = Rgba([255, 0, 0, 255]);
+		let frames = AnimDecoder::composite_frames(vec![
+			Frame::from_parts(
+				RgbaImage::from_pixel(1, 1, red),
+				0,
+				0,
+				Delay::from_numer_denom_ms(10, 1),
+			),
+			Frame::from_parts(
+				RgbaImage::new(2, 1),
+				1,
+				2,
+				Delay::from_numer_denom_ms(10, 1),
+			),
+		]);
+		for frame in &frames {
+			assert_eq!((3, 3), frame.buffer().dimensions());
+			assert_eq!((0, 0), (frame.left(), frame.top()));
+		}
+		assert_eq!(red, *frames[1].buffer().get_pixel(0, 0));
+		assert_eq!(Rgba([0, 0, 0, 0]), *frames[1].buffer().get_pixel(1, 2));
+		Ok(())
+	}
+	#[test]
+	fn test_background_disposal() -> AppResult<()> {
+		let mut data = Vec::new();
+		{
+			let mut encoder = gif::Encoder::new(&mut data, 2, 1, &[])?;
+			let mut frame =
+				gif::Frame::from_rgba(2, 1, &mut [255, 0, 0, 255].repeat(2));
+			frame.dispose = gif::DisposalMethod::Background;
+			encoder.write_frame(&frame)?;
+			let mut frame = gif::Frame::from_rgba(1, 1, &mut [0, 0, 255, 255]);
+			frame.left = 1;
+			encoder.write_frame(&frame)?;
+		}
+		let frame = AnimDecoder::get_frame(
+			GifDecoder::new(data.as_slice())?.into_frames(),
+			1,
+		)?;
+		assert_eq!(Rgba([0, 0, 0, 0]), *frame.get_pixel(0, 0));
+		assert_eq!(Rgba([0, 0, 255, 255]), *frame.get_pixel(1, 0));
+		Ok(())
+	}
+	#[test]
+	fn test_deletterbox() {
+		let get_frame = |(x, y, width, height): (u32, u32, u32, u32)| {
+			Frame::from_parts(
+				RgbaImage::from_fn(8, 6, |px, py| {
+					if (x..x + width).contains(&px) && (y..y + height).contains(&py)
+					{
+						Rgba([200, 100, 50, 255])
+					} else {
+						Rgba([8, 8, 8, 255])
+					}
+				}),
+				0,
+				0,
+				Delay::from_numer_denom_ms(100, 1),
+			)
+		};
+		let frames = vec![get_frame((2, 1, 4, 3)), get_frame((1, 1, 2, 4))];
+		assert_eq!(
+			Some(Geometry::new(1, 1, 5, 4)),
+			AnimDecoder::get_letterbox(&frames, 16)
+		);
+		assert_eq!(None, AnimDecoder::get_letterbox(&frames, 0));
+		let frames = AnimDecoder::deletterbox(frames, 16);
+		assert_eq!((5, 4), frames[1].buffer().dimensions());
+		assert_eq!(
+			Rgba([200, 100, 50, 255]),
+			*frames[1].buffer().get_pixel(0, 0)
+		);
+		assert_eq!(Rgba([8, 8, 8, 255]), *frames[1].buffer().get_pixel(4, 3));
+		let frames = vec![get_frame((0, 0, 8, 6)), get_frame((0, 0, 0, 0))];
+		assert_eq!(None, AnimDecoder::get_letterbox(&frames, 16));
 	}
 }
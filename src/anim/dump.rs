@@ -0,0 +1,110 @@
+use crate::anim::Frames;
+use crate::app::{AppError, AppResult};
+use crate::image::Image;
+use std::io::{self, Read, Write};
+
+/* Signature and version of the raw dump format */
+const DUMP_SIGNATURE: &[u8; 8] = b"MENYOKI\0";
+const DUMP_VERSION: u8 = 2;
+
+/* Raw frame dump for deferring the encoding */
+pub struct RawDump;
+
+impl RawDump {
+	/**
+	 * Write the frames to the raw dump.
+	 *
+	 * @param  frames
+	 * @param  output
+	 * @return Result
+	 */
+	pub fn write<Output: Write>(
+		frames: &Frames,
+		mut output: Output,
+	) -> AppResult<()> {
+		let (images, fps) = frames;
+		output.write_all(DUMP_SIGNATURE)?;
+		output.write_all(&[DUMP_VERSION])?;
+		output.write_all(&fps.to_le_bytes())?;
+		output.write_all(&(images.len() as u64).to_le_bytes())?;
+		for (i, image) in images.iter().enumerate() {
+			let percentage = ((i + 1) as f64 / images.len() as f64) * 100.;
+			info!("Dumping... ({:.1}%)\r", percentage);
+			io::stdout().flush()?;
+			image.write_raw(&mut output)?;
+		}
+		info!("\n");
+		output.flush()?;
+		Ok(())
+	}
+
+	/**
+	 * Read the frames from the raw dump.
+	 *
+	 * @param  input
+	 * @return Frames (Result)
+	 */
+	pub fn read<Input: Read>(mut input: Input) -> AppResult<Frames> {
+		let mut header = [0; 9];
+		input.read_exact(&mut header)?;
+		if &header[..8] != DUMP_SIGNATURE {
+			return Err(AppError::FrameError(String::from(
+				"Invalid raw dump signature",
+			)));
+		} else if !(1..=DUMP_VERSION).contains(&header[8]) {
+			return Err(AppError::FrameError(format!(
+				"Unsupported raw dump version: {}",
+				header[8]
+			)));
+		}
+		let mut fps = [0; 4];
+		input.read_exact(&mut fps)?;
+		let fps = if header[8] == 1 {
+			u32::from_le_bytes(fps) as f32
+		} else {
+			f32::from_le_bytes(fps)
+		};
+		let mut frame_count = [0; 8];
+		input.read_exact(&mut frame_count)?;
+		let frame_count = u64::from_le_bytes(frame_count);
+		let mut images = Vec::new();
+		for i in 0..frame_count {
+			debug!("Reading the frames... [{}/{}]\r", i + 1, frame_count);
+			images.push(Image::read_raw(&mut input)?);
+		}
+		debug!("\n");
+		Ok((images, fps))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use image::{ExtendedColorType, Rgba};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_raw_dump() -> AppResult<()> {
+		let geometry = Geometry::new(0, 0, 1, 2);
+		let data = vec![Rgba::from([0, 0, 0, 0]), Rgba::from([255, 255, 255, 0])];
+		let images = vec![
+			Image::new(data.clone(), false, geometry),
+			Image::new(data.into_iter().rev().collect(), true, geometry),
+		];
+		let mut output = Vec::new();
+		RawDump::write(&(images.clone(), 15.5), &mut output)?;
+		let (dump_images, fps) = RawDump::read(output.as_slice())?;
+		assert_eq!(15.5, fps);
+		assert_eq!(images.len(), dump_images.len());
+		for (image, dump_image) in images.iter().zip(dump_images.iter()) {
+			assert_eq!(image.geometry, dump_image.geometry);
+			assert_eq!(
+				image.get_data(ExtendedColorType::Rgba8),
+				dump_image.get_data(ExtendedColorType::Rgba8)
+			);
+		}
+		output[0] = b'X';
+		assert!(RawDump::read(output.as_slice()).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,120 @@
+use crate::anim::Frames;
+use bytesize::ByteSize;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/* Step between the frames to encode for the estimation */
+const SAMPLE_STEP: usize = 10;
+
+/* Estimated size of the encoded frames */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeEstimate {
+	sample_size: u64,
+	sample_count: usize,
+	frame_count: usize,
+}
+
+/* Display implementation for user-facing output */
+impl fmt::Display for SizeEstimate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"~{} ({} frames, sampled {})",
+			ByteSize(self.get_size()),
+			self.frame_count,
+			self.sample_count
+		)
+	}
+}
+
+impl SizeEstimate {
+	/**
+	 * Create a new SizeEstimate object.
+	 *
+	 * @param  sample_size
+	 * @param  sample_count
+	 * @param  frame_count
+	 * @return SizeEstimate
+	 */
+	pub fn new(sample_size: u64, sample_count: usize, frame_count: usize) -> Self {
+		Self {
+			sample_size,
+			sample_count,
+			frame_count,
+		}
+	}
+
+	/**
+	 * Get the representative frames to encode.
+	 *
+	 * @param  frames
+	 * @return Frames
+	 */
+	pub fn get_sample(frames: &Frames) -> Frames {
+		(
+			frames.0.iter().step_by(SAMPLE_STEP).cloned().collect(),
+			frames.1,
+		)
+	}
+
+	/**
+	 * Get the extrapolated size of all frames.
+	 *
+	 * @return u64
+	 */
+	pub fn get_size(&self) -> u64 {
+		if self.sample_count == 0 {
+			return self.sample_size;
+		}
+		(self.sample_size as f64 * self.frame_count as f64
+			/ self.sample_count as f64)
+			.round() as u64
+	}
+
+	/**
+	 * Ask for confirmation to continue with the encoding.
+	 *
+	 * @param  input
+	 * @param  output
+	 * @return bool (Result)
+	 */
+	pub fn confirm<Input: BufRead, Output: Write>(
+		&self,
+		mut input: Input,
+		mut output: Output,
+	) -> io::Result<bool> {
+		write!(output, "Estimated size: {self}. Continue? [y/N] ")?;
+		output.flush()?;
+		let mut answer = String::new();
+		input.read_line(&mut answer)?;
+		Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use crate::image::Image;
+	use image::Rgba;
+	use pretty_assertions::assert_eq;
+	use std::io::Cursor;
+	#[test]
+	fn test_size_estimate() -> io::Result<()> {
+		let image =
+			Image::new(vec![Rgba([0, 0, 0, 255])], false, Geometry::new(0, 0, 1, 1));
+		let sample = SizeEstimate::get_sample(&(vec![image; 25], 10.));
+		assert_eq!(3, sample.0.len());
+		assert_eq!(10., sample.1);
+		let estimate = SizeEstimate::new(300, 3, 25);
+		assert_eq!(2500, estimate.get_size());
+		assert_eq!("~2.5 KB (25 frames, sampled 3)", estimate.to_string());
+		assert_eq!(0, SizeEstimate::new(0, 0, 0).get_size());
+		let mut output = Vec::new();
+		assert!(estimate.confirm(Cursor::new("Y\n"), &mut output)?);
+		assert!(String::from_utf8_lossy(&output).ends_with("[y/N] "));
+		assert!(!estimate.confirm(Cursor::new("\n"), io::sink())?);
+		assert!(!estimate.confirm(Cursor::new(""), io::sink())?);
+		Ok(())
+	}
+}
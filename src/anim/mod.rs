@@ -1,11 +1,15 @@
 pub mod decoder;
+pub mod dump;
+pub mod estimate;
 pub mod settings;
+pub mod sprite;
+pub mod video;
 
 use crate::image::Image;
 use std::fmt;
 
 /* Images to encode and FPS value */
-pub type Frames = (Vec<Image>, u32);
+pub type Frames = (Vec<Image>, f32);
 
 /* Animation format */
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,6 +30,7 @@ impl fmt::Display for AnimFormat {
 pub enum AnimMode {
 	Record(AnimFormat),
 	Edit(AnimFormat),
+	Encode(AnimFormat),
 	Make,
 }
 
@@ -38,6 +43,14 @@ impl AnimMode {
 		matches!(self, Self::Edit(_))
 	}
 
+	/* Check if the animation mode is record.
+	 *
+	 * @return bool
+	 */
+	pub fn is_record(&self) -> bool {
+		matches!(self, Self::Record(_))
+	}
+
 	/* Check if the animation mode has a certain format.
 	 *
 	 * @param  format
@@ -45,7 +58,7 @@ impl AnimMode {
 	 */
 	pub fn has_format(&self, format: AnimFormat) -> bool {
 		match self {
-			Self::Record(f) | Self::Edit(f) => f == &format,
+			Self::Record(f) | Self::Edit(f) | Self::Encode(f) => f == &format,
 			_ => false,
 		}
 	}
@@ -56,10 +69,12 @@ impl AnimMode {
 	 */
 	pub fn get_description<'a>(&self) -> &'a str {
 		match self {
-			Self::Record(format) | Self::Edit(format) => match format {
-				AnimFormat::Gif => "Use the GIF encoder",
-				AnimFormat::Apng => "Use the APNG encoder",
-			},
+			Self::Record(format) | Self::Edit(format) | Self::Encode(format) => {
+				match format {
+					AnimFormat::Gif => "Use the GIF encoder",
+					AnimFormat::Apng => "Use the APNG encoder",
+				}
+			}
 			Self::Make => "Make an animation from frames",
 		}
 	}
@@ -72,7 +87,9 @@ impl fmt::Display for AnimMode {
 			f,
 			"{}",
 			match self {
-				Self::Record(format) | Self::Edit(format) => format.to_string(),
+				Self::Record(format) | Self::Edit(format) | Self::Encode(format) => {
+					format.to_string()
+				}
 				_ => format!("{self:?}"),
 			}
 			.to_lowercase()
@@ -98,6 +115,10 @@ mod tests {
 		assert!(anim_mode.has_format(AnimFormat::Gif));
 		assert_eq!("Use the GIF encoder", anim_mode.get_description());
 		assert_eq!("gif", anim_mode.to_string().as_str());
+		let anim_mode = AnimMode::Encode(AnimFormat::Gif);
+		assert!(!anim_mode.is_edit());
+		assert!(anim_mode.has_format(AnimFormat::Gif));
+		assert_eq!("gif", anim_mode.to_string().as_str());
 		let anim_mode = AnimMode::Make;
 		assert!(!anim_mode.is_edit());
 		assert!(!anim_mode.has_format(AnimFormat::Apng));
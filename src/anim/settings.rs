@@ -1,33 +1,125 @@
+use crate::anim::video::VideoImport;
 use crate::args::matches::ArgMatches;
 use crate::args::parser::ArgParser;
 use crate::file::format::FileFormat;
 use crate::file::File;
+use crate::gif::palette::PaletteSize;
+use crate::image::geometry::Geometry;
+use crate::image::settings::PnmSettings;
 use std::fs;
+use std::ops::Range;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+/* Maximum value of the lossy compression level */
+const LOSSY_LIMIT: u8 = 200;
+/* Maximum brightness of the letterbox bars */
+const DELETTERBOX_THRESHOLD: u8 = 16;
+
+/* Curve of the playback speed along the animation */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeedCurve {
+	Linear,
+	EaseIn,
+	EaseOut,
+	EaseInOut,
+}
+
+/* Implementation for parsing SpeedCurve from a string */
+impl FromStr for SpeedCurve {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"linear" => Ok(Self::Linear),
+			"ease-in" => Ok(Self::EaseIn),
+			"ease-out" => Ok(Self::EaseOut),
+			"ease-in-out" => Ok(Self::EaseInOut),
+			_ => Err(()),
+		}
+	}
+}
+
+impl SpeedCurve {
+	/**
+	 * Get the delay of the frame at the given index.
+	 *
+	 * Speed changes between half and twice the speed along the curve,
+	 * e.g. ease-in starts slow and ends fast.
+	 *
+	 * @param  delay
+	 * @param  index
+	 * @param  count
+	 * @return f32
+	 */
+	pub fn get_delay(&self, delay: f32, index: usize, count: usize) -> f32 {
+		let position = if count > 1 {
+			index as f32 / (count - 1) as f32
+		} else {
+			0.
+		};
+		let speed = match self {
+			Self::Linear => return delay,
+			Self::EaseIn => position,
+			Self::EaseOut => 1. - position,
+			Self::EaseInOut => (position * std::f32::consts::PI).sin(),
+		};
+		delay * 2_f32.powf(1. - 2. * speed)
+	}
+}
 
 /* Animation and frame settings */
 #[derive(Debug)]
 pub struct AnimSettings {
-	pub fps: u32,
+	pub fps: f32,
 	pub repeat: i32,
 	pub quality: u8,
+	pub lossy: u8,
+	pub interpolate: Option<f32>,
+	pub output_fps: Option<f32>,
+	pub max_dimension: Option<u32>,
 	pub speed: f32,
+	pub speed_curve: SpeedCurve,
 	pub cut: (f32, f32),
 	pub frames: Vec<PathBuf>,
 	pub gifski: (bool, bool),
+	pub colors: PaletteSize,
+	pub estimate: (bool, bool),
+	pub boomerang: bool,
+	pub palette_from: Option<PathBuf>,
+	pub dither: bool,
+	pub stream: bool,
+	pub comment: Option<String>,
+	pub delay: Option<u16>,
+	pub deletterbox: Option<u8>,
+	pub video_range: (Option<f32>, Option<f32>),
 }
 
 /* Default initialization values for AnimSettings */
 impl Default for AnimSettings {
 	fn default() -> Self {
 		Self {
-			fps: 20,
+			fps: 20.,
 			repeat: -1,
 			quality: 75,
+			lossy: 0,
+			interpolate: None,
+			output_fps: None,
+			max_dimension: None,
 			speed: 1.,
+			speed_curve: SpeedCurve::Linear,
 			cut: (0., 0.),
 			frames: Vec::new(),
 			gifski: (false, false),
+			colors: PaletteSize::default(),
+			estimate: (false, false),
+			boomerang: false,
+			palette_from: None,
+			dither: false,
+			stream: false,
+			comment: None,
+			delay: None,
+			deletterbox: None,
+			video_range: (None, None),
 		}
 	}
 }
@@ -39,29 +131,75 @@ impl AnimSettings {
 	 * @param  fps
 	 * @param  repeat
 	 * @param  quality
+	 * @param  lossy
+	 * @param  interpolate (Option)
+	 * @param  output_fps (Option)
+	 * @param  max_dimension (Option)
 	 * @param  speed
+	 * @param  speed_curve
 	 * @param  cut
 	 * @param  frames
 	 * @param  gifski
+	 * @param  colors
+	 * @param  estimate
+	 * @param  boomerang
+	 * @param  palette_from (Option)
+	 * @param  dither
+	 * @param  stream
+	 * @param  comment (Option)
+	 * @param  delay (Option)
+	 * @param  deletterbox (Option)
+	 * @param  video_range
 	 * @return AnimSettings
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
-		fps: u32,
+		fps: f32,
 		repeat: i32,
 		quality: u8,
+		lossy: u8,
+		interpolate: Option<f32>,
+		output_fps: Option<f32>,
+		max_dimension: Option<u32>,
 		speed: f32,
+		speed_curve: SpeedCurve,
 		cut: (f32, f32),
 		frames: Vec<PathBuf>,
 		gifski: (bool, bool),
+		colors: PaletteSize,
+		estimate: (bool, bool),
+		boomerang: bool,
+		palette_from: Option<PathBuf>,
+		dither: bool,
+		stream: bool,
+		comment: Option<String>,
+		delay: Option<u16>,
+		deletterbox: Option<u8>,
+		video_range: (Option<f32>, Option<f32>),
 	) -> Self {
 		Self {
 			fps,
 			repeat,
 			quality,
+			lossy,
+			interpolate,
+			output_fps,
+			max_dimension,
 			speed,
+			speed_curve,
 			cut,
 			frames,
 			gifski,
+			colors,
+			estimate,
+			boomerang,
+			palette_from,
+			dither,
+			stream,
+			comment,
+			delay,
+			deletterbox,
+			video_range,
 		}
 	}
 
@@ -93,27 +231,128 @@ impl AnimSettings {
 	fn from_parser(parser: ArgParser<'_>) -> Self {
 		match parser.args {
 			Some(ref matches) => Self::new(
-				match parser.parse("fps", Self::default().fps) {
-					fps if fps > 0 => fps,
+				match (
+					Self::get_delay(matches),
+					parser.parse("fps", Self::default().fps),
+				) {
+					(Some(delay), _) => 1e2 / f32::from(delay),
+					(None, fps) if fps > 0. => fps,
 					_ => Self::default().fps,
 				},
-				parser.parse("repeat", Self::default().repeat) - 1,
+				if matches.is_present("loop-once") {
+					0
+				} else {
+					parser.parse("repeat", Self::default().repeat) - 1
+				},
 				parser.parse("quality", Self::default().quality),
+				parser
+					.parse("lossy", Self::default().lossy)
+					.min(LOSSY_LIMIT),
+				matches
+					.value_of("interpolate")
+					.and_then(|v| v.parse().ok())
+					.filter(|fps: &f32| *fps > 0.),
+				matches
+					.value_of("output-fps")
+					.and_then(|v| v.parse().ok())
+					.filter(|fps: &f32| *fps > 0.),
+				matches
+					.value_of("max-dimension")
+					.and_then(|v| v.parse().ok())
+					.filter(|size: &u32| *size > 0),
 				parser.parse("speed", Self::default().speed),
+				matches
+					.value_of("speed-curve")
+					.and_then(|v| SpeedCurve::from_str(v).ok())
+					.unwrap_or(Self::default().speed_curve),
 				(
 					parser.parse("cut-beginning", Self::default().cut.0) * 1000.,
 					parser.parse("cut-end", Self::default().cut.1) * 1000.,
 				),
 				Self::get_frames(matches),
 				(
-					matches.is_present("gifski") || matches.is_present("fast"),
+					match matches.value_of("gif-encoder") {
+						Some(encoder) => encoder == "ski",
+						None => {
+							matches.is_present("gifski")
+								|| matches.is_present("fast")
+						}
+					},
 					matches.is_present("fast"),
 				),
+				matches
+					.value_of("colors")
+					.and_then(|v| {
+						PaletteSize::from_str(v)
+							.map_err(|_| warn!("Invalid number of colors: {:?}", v))
+							.ok()
+					})
+					.unwrap_or_default(),
+				(matches.is_present("estimate"), matches.is_present("yes")),
+				matches.is_present("boomerang"),
+				matches.value_of("palette-from").map(|path| {
+					PathBuf::from(
+						shellexpand::full(path)
+							.map(|s| s.to_string())
+							.unwrap_or(path.to_string()),
+					)
+				}),
+				matches.is_present("dither"),
+				matches.is_present("stream"),
+				matches.value_of("gif-comment").map(String::from),
+				Self::get_delay(matches),
+				if matches.is_present("deletterbox") {
+					Some(
+						matches
+							.value_of("deletterbox")
+							.and_then(|v| v.parse().ok())
+							.unwrap_or(DELETTERBOX_THRESHOLD),
+					)
+				} else {
+					None
+				},
+				(
+					Self::get_video_time(matches, "from"),
+					Self::get_video_time(matches, "to"),
+				),
 			),
 			None => Self::default(),
 		}
 	}
 
+	/**
+	 * Get the frame delay in centiseconds from parsed arguments.
+	 *
+	 * @param  args
+	 * @return u16 (Option)
+	 */
+	fn get_delay(args: &ArgMatches<'_>) -> Option<u16> {
+		let value = args.value_of("delay-cs")?;
+		match value.parse() {
+			Ok(delay) if delay > 0 => Some(delay),
+			_ => {
+				warn!("Invalid frame delay: {:?}", value);
+				None
+			}
+		}
+	}
+
+	/**
+	 * Get the time in seconds for importing the frames from a video.
+	 *
+	 * @param  args
+	 * @param  arg
+	 * @return f32 (Option)
+	 */
+	fn get_video_time(args: &ArgMatches<'_>, arg: &str) -> Option<f32> {
+		let value = args.value_of(arg)?;
+		let time = VideoImport::parse_time(value);
+		if time.is_none() {
+			warn!("Invalid video time: {:?}", value);
+		}
+		time
+	}
+
 	/**
 	 * Get the frame files from parsed arguments.
 	 *
@@ -147,6 +386,71 @@ impl AnimSettings {
 		values.into_iter().map(PathBuf::from).collect()
 	}
 
+	/**
+	 * Get the number of times that the animation is played.
+	 *
+	 * @return u32 (Option)
+	 */
+	pub fn get_play_count(&self) -> Option<u32> {
+		u32::try_from(self.repeat).ok().map(|repeat| repeat + 1)
+	}
+
+	/**
+	 * Get the playback FPS with the animation speed applied.
+	 *
+	 * Integer frame rates are rounded to keep them integral.
+	 *
+	 * @param  fps
+	 * @return f32
+	 */
+	pub fn get_playback_fps(&self, fps: f32) -> f32 {
+		if fps.fract() == 0. {
+			(fps * self.speed).round().max(1.)
+		} else {
+			fps * self.speed
+		}
+	}
+
+	/**
+	 * Get the range of frames to keep after cutting the duration.
+	 *
+	 * @param  count
+	 * @param  fps
+	 * @return Range
+	 */
+	pub fn get_cut_range(&self, count: usize, fps: f32) -> Range<usize> {
+		if self.cut == (0., 0.) || fps <= 0. {
+			return 0..count;
+		}
+		let frame_delay = 1e3 / fps;
+		let (start, end) = (
+			(self.cut.0 / frame_delay) as usize,
+			(self.cut.1 / frame_delay) as usize,
+		);
+		let end = count.saturating_sub(end);
+		start.min(end)..end
+	}
+
+	/**
+	 * Get the size to downscale the frames for fitting the maximum dimension.
+	 *
+	 * @param  geometry
+	 * @return Geometry (Option)
+	 */
+	pub fn get_max_size(&self, geometry: Geometry) -> Option<Geometry> {
+		let max_dimension = self.max_dimension?;
+		let size = geometry.width.max(geometry.height);
+		if size <= max_dimension {
+			return None;
+		}
+		let scale = f64::from(max_dimension) / f64::from(size);
+		Some(Geometry {
+			width: ((f64::from(geometry.width) * scale).round() as u32).max(1),
+			height: ((f64::from(geometry.height) * scale).round() as u32).max(1),
+			..geometry
+		})
+	}
+
 	/**
 	 * Map the given number from a range to another range.
 	 *
@@ -172,6 +476,7 @@ impl AnimSettings {
 pub struct SplitSettings {
 	pub file: PathBuf,
 	pub dir: PathBuf,
+	pub format: FileFormat,
 }
 
 /* Default initialization values for SplitSettings */
@@ -180,6 +485,7 @@ impl Default for SplitSettings {
 		Self {
 			file: PathBuf::new(),
 			dir: PathBuf::new(),
+			format: FileFormat::Png,
 		}
 	}
 }
@@ -190,29 +496,35 @@ impl SplitSettings {
 	 *
 	 * @param  file
 	 * @param  dir
+	 * @param  format
 	 * @return SplitSettings
 	 */
-	pub fn new(file: PathBuf, dir: PathBuf) -> Self {
-		Self { file, dir }
+	pub fn new(file: PathBuf, dir: PathBuf, format: FileFormat) -> Self {
+		Self { file, dir, format }
 	}
 
 	/**
 	 * Create a new SplitSettings object from arguments.
 	 *
 	 * @param  matches
+	 * @param  pnm
 	 * @return SplitSettings
 	 */
-	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
-		Self::from_parser(ArgParser::from_subcommand(matches, "split"))
+	pub fn from_args(matches: &ArgMatches<'_>, pnm: &PnmSettings) -> Self {
+		Self::from_parser(
+			ArgParser::from_subcommand(matches, "split"),
+			FileFormat::from_args(matches, Some(pnm.subtype)),
+		)
 	}
 
 	/**
 	 * Create a SplitSettings object from an argument parser.
 	 *
 	 * @param  parser
+	 * @param  format
 	 * @return SplitSettings
 	 */
-	fn from_parser(parser: ArgParser<'_>) -> Self {
+	fn from_parser(parser: ArgParser<'_>, format: FileFormat) -> Self {
 		match parser.args {
 			Some(matches) => {
 				let file = matches.value_of("file").unwrap_or_default();
@@ -235,13 +547,117 @@ impl SplitSettings {
 							.unwrap_or_default(),
 					)),
 				};
-				Self::new(file, dir)
+				Self::new(file, dir, format)
 			}
 			None => Self::default(),
 		}
 	}
 }
 
+/* Spritesheet settings */
+#[derive(Debug, Default)]
+pub struct SpritesheetSettings {
+	pub file: PathBuf,
+	pub cols: u32,
+}
+
+impl SpritesheetSettings {
+	/**
+	 * Create a new SpritesheetSettings object.
+	 *
+	 * @param  file
+	 * @param  cols
+	 * @return SpritesheetSettings
+	 */
+	pub fn new(file: PathBuf, cols: u32) -> Self {
+		Self { file, cols }
+	}
+
+	/**
+	 * Create a new SpritesheetSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return SpritesheetSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::from_parser(ArgParser::from_subcommand(matches, "spritesheet"))
+	}
+
+	/**
+	 * Create a SpritesheetSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return SpritesheetSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>) -> Self {
+		match parser.args {
+			Some(ref matches) => {
+				let file = matches.value_of("file").unwrap_or_default();
+				let file = shellexpand::full(file)
+					.map(|s| s.to_string())
+					.unwrap_or(file.to_string());
+				Self::new(PathBuf::from(file), parser.parse("cols", 0))
+			}
+			None => Self::default(),
+		}
+	}
+}
+
+/* Raw frame dump settings */
+#[derive(Debug, Default)]
+pub struct DumpSettings {
+	pub file: Option<PathBuf>,
+}
+
+impl DumpSettings {
+	/**
+	 * Create a new DumpSettings object.
+	 *
+	 * @param  file (Option)
+	 * @return DumpSettings
+	 */
+	pub fn new(file: Option<PathBuf>) -> Self {
+		Self { file }
+	}
+
+	/**
+	 * Create a new DumpSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return DumpSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		if matches.is_present("encode") {
+			Self::from_parser(ArgParser::from_subcommand(matches, "encode"), "file")
+		} else {
+			Self::from_parser(
+				ArgParser::from_subcommand(matches, "record"),
+				"raw-dump",
+			)
+		}
+	}
+
+	/**
+	 * Create a DumpSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @param  arg
+	 * @return DumpSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>, arg: &str) -> Self {
+		match parser.args {
+			Some(matches) => Self::new(matches.value_of(arg).map(|file| {
+				PathBuf::from(
+					shellexpand::full(file)
+						.map(|s| s.to_string())
+						.unwrap_or(file.to_string()),
+				)
+			})),
+			None => Self::default(),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -253,16 +669,49 @@ mod tests {
 		let args = App::new("test")
 			.arg(Arg::with_name("fps").long("fps").takes_value(true))
 			.arg(Arg::with_name("repeat").long("repeat").takes_value(true))
+			.arg(Arg::with_name("loop-once").long("loop-once"))
 			.arg(Arg::with_name("quality").long("quality").takes_value(true))
+			.arg(Arg::with_name("lossy").long("lossy").takes_value(true))
+			.arg(
+				Arg::with_name("interpolate")
+					.long("interpolate")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("output-fps")
+					.long("output-fps")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("max-dimension")
+					.long("max-dimension")
+					.takes_value(true),
+			)
 			.arg(Arg::with_name("gifski").long("gifski"))
 			.arg(Arg::with_name("fast").long("fast"))
 			.arg(Arg::with_name("speed").long("speed").takes_value(true))
+			.arg(
+				Arg::with_name("speed-curve")
+					.long("speed-curve")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("cut-beginning")
 					.long("cut-beginning")
 					.takes_value(true),
 			)
 			.arg(Arg::with_name("cut-end").long("cut-end").takes_value(true))
+			.arg(Arg::with_name("colors").long("colors").takes_value(true))
+			.arg(
+				Arg::with_name("gif-encoder")
+					.long("gif-encoder")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("gif-comment")
+					.long("gif-comment")
+					.takes_value(true),
+			)
 			.get_matches_from(vec![
 				"test",
 				"--fps",
@@ -271,43 +720,157 @@ mod tests {
 				"5",
 				"--quality",
 				"10",
+				"--lossy",
+				"80",
+				"--interpolate",
+				"30",
+				"--output-fps",
+				"10",
+				"--max-dimension",
+				"480",
 				"--gifski",
 				"--fast",
 				"--speed",
 				"1.1",
+				"--speed-curve",
+				"ease-in",
 				"--cut-beginning",
 				"0.9",
 				"--cut-end",
 				"0.8",
+				"--colors",
+				"auto",
+				"--gif-comment",
+				"source: test",
 			]);
 		let anim_settings = AnimSettings::from_parser(ArgParser::from_args(&args));
-		assert_eq!(15, anim_settings.fps);
+		assert_eq!(15., anim_settings.fps);
 		assert_eq!(4, anim_settings.repeat);
 		assert_eq!(10, anim_settings.quality);
+		assert_eq!(80, anim_settings.lossy);
+		assert_eq!(Some(30.), anim_settings.interpolate);
+		assert_eq!(Some(10.), anim_settings.output_fps);
+		assert_eq!(
+			Some(Geometry::new(0, 0, 480, 270)),
+			anim_settings.get_max_size(Geometry::new(0, 0, 1920, 1080))
+		);
+		assert_eq!(
+			None,
+			anim_settings.get_max_size(Geometry::new(0, 0, 480, 100))
+		);
 		assert_eq!(true, anim_settings.gifski.0);
 		assert_eq!(true, anim_settings.gifski.1);
 		assert_eq!(1.1, anim_settings.speed);
+		assert_eq!(SpeedCurve::EaseIn, anim_settings.speed_curve);
+		assert_eq!(20., SpeedCurve::EaseIn.get_delay(10., 0, 5));
+		assert_eq!(5., SpeedCurve::EaseIn.get_delay(10., 4, 5));
+		assert_eq!(10., SpeedCurve::EaseOut.get_delay(10., 2, 5));
+		assert_eq!(5., SpeedCurve::EaseInOut.get_delay(10., 2, 5));
+		assert_eq!(10., SpeedCurve::Linear.get_delay(10., 4, 5));
 		assert_eq!((900., 800.), anim_settings.cut);
+		assert_eq!(PaletteSize::Auto, anim_settings.colors);
+		assert_eq!(Some(String::from("source: test")), anim_settings.comment);
+		assert_eq!(None, anim_settings.delay);
+		let args = App::new("test")
+			.arg(Arg::with_name("fps").long("fps").takes_value(true))
+			.arg(
+				Arg::with_name("delay-cs")
+					.long("delay-cs")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("from").long("from").takes_value(true))
+			.arg(Arg::with_name("to").long("to").takes_value(true))
+			.get_matches_from(vec![
+				"test",
+				"--fps",
+				"15",
+				"--delay-cs",
+				"7",
+				"--from",
+				"0:05",
+				"--to",
+				"x",
+			]);
+		let delay_settings = AnimSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(Some(7), delay_settings.delay);
+		assert_eq!((Some(5.), None), delay_settings.video_range);
+		assert_eq!(1e2 / 7., delay_settings.fps);
+		assert_eq!(1..3, anim_settings.get_cut_range(4, 2.));
+		assert_eq!(0..0, anim_settings.get_cut_range(1, 2.));
+		assert_eq!(17., anim_settings.get_playback_fps(15.));
+		assert_eq!(0.55, anim_settings.get_playback_fps(0.5));
+		let args = App::new("test")
+			.arg(Arg::with_name("repeat").long("repeat").takes_value(true))
+			.arg(Arg::with_name("loop-once").long("loop-once"))
+			.get_matches_from(vec!["test", "--repeat", "5", "--loop-once"]);
+		let loop_settings = AnimSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(0, loop_settings.repeat);
+		assert_eq!(Some(1), loop_settings.get_play_count());
 		let anim_settings = AnimSettings::from_parser(ArgParser::new(None));
 		assert_eq!(-1, anim_settings.repeat);
+		assert_eq!(None, anim_settings.get_play_count());
 		assert_eq!(75, anim_settings.quality);
 		assert_eq!(false, anim_settings.gifski.0);
 		assert_eq!(false, anim_settings.gifski.1);
 		assert_eq!(1.0, anim_settings.speed);
 		assert_eq!((0., 0.), anim_settings.cut);
+		assert_eq!(0..4, anim_settings.get_cut_range(4, 2.));
+		assert_eq!(20., anim_settings.get_playback_fps(20.));
+		let args = App::new("test")
+			.arg(Arg::with_name("gifski").long("gifski"))
+			.arg(
+				Arg::with_name("gif-encoder")
+					.long("gif-encoder")
+					.takes_value(true),
+			)
+			.get_matches_from(vec!["test", "--gifski", "--gif-encoder", "image"]);
+		let anim_settings = AnimSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(false, anim_settings.gifski.0);
 	}
 	#[test]
 	fn test_split_settings() {
 		let args = App::new("test")
 			.arg(Arg::with_name("file").required(true))
 			.get_matches_from(vec!["test", "x"]);
-		let split_settings = SplitSettings::from_parser(ArgParser::from_args(&args));
+		let split_settings =
+			SplitSettings::from_parser(ArgParser::from_args(&args), FileFormat::Jpg);
 		assert_eq!(PathBuf::from("x"), split_settings.file);
+		assert_eq!(FileFormat::Jpg, split_settings.format);
 		assert_eq!(Some(OsStr::new("x_frames")), split_settings.dir.file_name());
 		let args = App::new("test")
 			.arg(Arg::with_name("dir").long("dir").takes_value(true))
 			.get_matches_from(vec!["test", "--dir", "~/"]);
-		let split_settings = SplitSettings::from_parser(ArgParser::from_args(&args));
+		let split_settings =
+			SplitSettings::from_parser(ArgParser::from_args(&args), FileFormat::Png);
 		assert_eq!(dirs::home_dir().unwrap(), split_settings.dir)
 	}
+	#[test]
+	fn test_spritesheet_settings() {
+		let args = App::new("test")
+			.arg(Arg::with_name("file").required(true))
+			.arg(Arg::with_name("cols").long("cols").takes_value(true))
+			.get_matches_from(vec!["test", "x.gif", "--cols", "4"]);
+		let spritesheet_settings =
+			SpritesheetSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(PathBuf::from("x.gif"), spritesheet_settings.file);
+		assert_eq!(4, spritesheet_settings.cols);
+		let spritesheet_settings =
+			SpritesheetSettings::from_parser(ArgParser::new(None));
+		assert_eq!(0, spritesheet_settings.cols);
+	}
+	#[test]
+	fn test_dump_settings() {
+		let args = App::new("test")
+			.arg(
+				Arg::with_name("raw-dump")
+					.long("raw-dump")
+					.takes_value(true),
+			)
+			.get_matches_from(vec!["test", "--raw-dump", "frames.raw"]);
+		let dump_settings =
+			DumpSettings::from_parser(ArgParser::from_args(&args), "raw-dump");
+		assert_eq!(Some(PathBuf::from("frames.raw")), dump_settings.file);
+		let dump_settings = DumpSettings::from_parser(ArgParser::new(None), "file");
+		assert_eq!(None, dump_settings.file);
+	}
 }
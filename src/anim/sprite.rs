@@ -0,0 +1,129 @@
+use crate::app::{AppError, AppResult};
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use crate::montage::settings::MontageSettings;
+use crate::montage::Montage;
+use image::{Rgba, RgbaImage};
+
+/* Grid of animation frames with the frame positions */
+#[derive(Debug)]
+pub struct Spritesheet {
+	pub image: Image,
+	pub frames: Vec<(Geometry, u32)>,
+}
+
+impl Spritesheet {
+	/**
+	 * Create a new Spritesheet object.
+	 *
+	 * @param  frames
+	 * @param  cols
+	 * @return Spritesheet (Result)
+	 */
+	pub fn new(frames: Vec<(RgbaImage, u32)>, cols: u32) -> AppResult<Self> {
+		let (width, height) = frames
+			.first()
+			.map(|(image, _)| image.dimensions())
+			.ok_or_else(|| {
+				AppError::FrameError(String::from(
+					"No frames found for the spritesheet",
+				))
+			})?;
+		if frames
+			.iter()
+			.any(|(image, _)| image.dimensions() != (width, height))
+		{
+			return Err(AppError::FrameError(String::from(
+				"Frames of the spritesheet must have the same size",
+			)));
+		}
+		let cols = match cols {
+			0 => frames.len() as u32,
+			cols => cols.min(frames.len() as u32),
+		};
+		let rects = frames
+			.iter()
+			.enumerate()
+			.map(|(i, (_, duration))| {
+				let (col, row) = (i as u32 % cols, i as u32 / cols);
+				(
+					Geometry::new(
+						(col * width) as i32,
+						(row * height) as i32,
+						width,
+						height,
+					),
+					*duration,
+				)
+			})
+			.collect();
+		let settings = MontageSettings {
+			cols,
+			background: Rgba([0, 0, 0, 0]),
+			..Default::default()
+		};
+		Ok(Self {
+			image: Montage::new(
+				frames.into_iter().map(|(image, _)| image).collect(),
+				&settings,
+			)
+			.get_image(),
+			frames: rects,
+		})
+	}
+
+	/**
+	 * Get the JSON metadata of the frames.
+	 *
+	 * @param  file_name
+	 * @return String
+	 */
+	pub fn to_json(&self, file_name: &str) -> String {
+		let frames = self
+			.frames
+			.iter()
+			.map(|(rect, duration)| {
+				format!(
+					"    {{\"x\": {}, \"y\": {}, \"w\": {}, \"h\": {}, \"duration\": {}}}",
+					rect.x, rect.y, rect.width, rect.height, duration
+				)
+			})
+			.collect::<Vec<String>>()
+			.join(",\n");
+		format!(
+			"{{\n  \"image\": \"{}\",\n  \"size\": {{\"w\": {}, \"h\": {}}},\n  \"frames\": [\n{}\n  ]\n}}\n",
+			file_name.replace('\\', "\\\\").replace('"', "\\\""),
+			self.image.geometry.width,
+			self.image.geometry.height,
+			frames
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_spritesheet() -> AppResult<()> {
+		let frames = (0..3)
+			.map(|i| (RgbaImage::from_pixel(4, 2, Rgba([i, 0, 0, 255])), 100))
+			.collect::<Vec<(RgbaImage, u32)>>();
+		let sheet = Spritesheet::new(frames.clone(), 2)?;
+		assert_eq!(Geometry::new(0, 0, 8, 4), sheet.image.geometry);
+		assert_eq!((Geometry::new(4, 0, 4, 2), 100), sheet.frames[1]);
+		assert_eq!((Geometry::new(0, 2, 4, 2), 100), sheet.frames[2]);
+		assert!(sheet.to_json("sheet.png").contains(
+			"{\"x\": 0, \"y\": 2, \"w\": 4, \"h\": 2, \"duration\": 100}"
+		));
+		assert_eq!(
+			Geometry::new(0, 0, 12, 2),
+			Spritesheet::new(frames.clone(), 0)?.image.geometry
+		);
+		let mut frames = frames;
+		frames.push((RgbaImage::new(2, 2), 100));
+		assert!(Spritesheet::new(frames, 2).is_err());
+		assert!(Spritesheet::new(Vec::new(), 2).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,190 @@
+use crate::app::{AppError, AppResult};
+use crate::util::command::Command;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/* Extensions of the video files that frames can be imported from */
+const VIDEO_EXTENSIONS: &[&str] = &[
+	"mp4", "m4v", "mkv", "webm", "mov", "avi", "flv", "wmv", "mpg", "mpeg", "ogv",
+];
+/* File name pattern of the extracted frames */
+const FRAME_PATTERN: &str = "%06d.png";
+
+/* Video file to extract the frames from using ffmpeg */
+#[derive(Debug)]
+pub struct VideoImport<'a> {
+	path: &'a Path,
+	fps: f32,
+	range: (Option<f32>, Option<f32>),
+}
+
+impl<'a> VideoImport<'a> {
+	/**
+	 * Create a new VideoImport object.
+	 *
+	 * @param  path
+	 * @param  fps
+	 * @param  range
+	 * @return VideoImport
+	 */
+	pub fn new(path: &'a Path, fps: f32, range: (Option<f32>, Option<f32>)) -> Self {
+		Self { path, fps, range }
+	}
+
+	/**
+	 * Check if the given file is a video.
+	 *
+	 * @param  path
+	 * @return bool
+	 */
+	pub fn is_video(path: &Path) -> bool {
+		path.extension()
+			.and_then(|extension| extension.to_str())
+			.is_some_and(|extension| {
+				VIDEO_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+			})
+	}
+
+	/**
+	 * Parse a time value in the "[[HH:]MM:]SS[.MS]" format as seconds.
+	 *
+	 * @param  value
+	 * @return f32 (Option)
+	 */
+	pub fn parse_time(value: &str) -> Option<f32> {
+		let parts = value.split(':').collect::<Vec<&str>>();
+		if parts.len() > 3 {
+			return None;
+		}
+		parts.iter().try_fold(0., |time, part| {
+			part.parse::<f32>()
+				.ok()
+				.filter(|value| value.is_finite() && *value >= 0.)
+				.map(|value| time * 60. + value)
+		})
+	}
+
+	/**
+	 * Get the ffmpeg arguments for extracting the frames.
+	 *
+	 * Seeking is done before the input for skipping the decoding.
+	 *
+	 * @param  dir
+	 * @return Vector of String (Result)
+	 */
+	fn get_args(&self, dir: &Path) -> AppResult<Vec<String>> {
+		let mut args = vec![
+			String::from("-hide_banner"),
+			String::from("-loglevel"),
+			String::from("error"),
+		];
+		if let Some(start) = self.range.0 {
+			args.extend([String::from("-ss"), format!("{start:.3}")]);
+		}
+		args.extend([String::from("-i"), self.path.to_string_lossy().to_string()]);
+		if let Some(end) = self.range.1 {
+			let duration = end - self.range.0.unwrap_or_default();
+			if duration <= 0. {
+				return Err(AppError::FrameError(format!(
+					"Invalid time range: {:?}",
+					self.range
+				)));
+			}
+			args.extend([String::from("-t"), format!("{duration:.3}")]);
+		}
+		args.extend([
+			String::from("-vf"),
+			format!("fps={}", self.fps),
+			dir.join(FRAME_PATTERN).to_string_lossy().to_string(),
+		]);
+		Ok(args)
+	}
+
+	/**
+	 * Extract the frames into the given directory.
+	 *
+	 * @param  dir
+	 * @return Vector of PathBuf (Result)
+	 */
+	pub fn extract(&self, dir: &Path) -> AppResult<Vec<PathBuf>> {
+		let args = self.get_args(dir)?;
+		fs::create_dir_all(dir)?;
+		debug!("ffmpeg {}", args.join(" "));
+		let status =
+			Command::new("ffmpeg", args.iter().map(String::as_str).collect())
+				.execute()
+				.map_err(|e| {
+					AppError::CommandError(if e.kind() == ErrorKind::NotFound {
+						String::from(
+							"ffmpeg is required for importing frames from videos",
+						)
+					} else {
+						format!("Failed to run ffmpeg: {e}")
+					})
+				})?;
+		if !status.success() {
+			return Err(AppError::CommandError(format!(
+				"ffmpeg failed to extract the frames ({status})"
+			)));
+		}
+		let mut frames = fs::read_dir(dir)?
+			.map(|entry| entry.map(|entry| entry.path()))
+			.collect::<Result<Vec<PathBuf>, _>>()?;
+		frames.sort();
+		if frames.is_empty() {
+			Err(AppError::FrameError(String::from(
+				"No frames were extracted from the video",
+			)))
+		} else {
+			Ok(frames)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_video_import() -> AppResult<()> {
+		assert!(VideoImport::is_video(Path::new("clip.MP4")));
+		assert!(!VideoImport::is_video(Path::new("frame.png")));
+		assert!(!VideoImport::is_video(Path::new("mp4")));
+		assert_eq!(Some(5.), VideoImport::parse_time("0:05"));
+		assert_eq!(Some(3723.5), VideoImport::parse_time("1:02:03.5"));
+		assert_eq!(Some(7.25), VideoImport::parse_time("7.25"));
+		assert_eq!(None, VideoImport::parse_time("1:-2"));
+		assert_eq!(None, VideoImport::parse_time("1:2:3:4"));
+		assert_eq!(None, VideoImport::parse_time(""));
+		let path = PathBuf::from("in.mp4");
+		assert_eq!(
+			vec![
+				"-hide_banner",
+				"-loglevel",
+				"error",
+				"-ss",
+				"5.000",
+				"-i",
+				"in.mp4",
+				"-t",
+				"5.000",
+				"-vf",
+				"fps=10",
+				"out/%06d.png",
+			],
+			VideoImport::new(&path, 10., (Some(5.), Some(10.)))
+				.get_args(Path::new("out"))?
+		);
+		assert_eq!(
+			vec!["-i", "in.mp4", "-t", "2.500"],
+			VideoImport::new(&path, 10., (None, Some(2.5)))
+				.get_args(Path::new("out"))?[3..7]
+				.to_vec()
+		);
+		assert!(VideoImport::new(&path, 10., (Some(5.), Some(5.)))
+			.get_args(Path::new("out"))
+			.is_err());
+		Ok(())
+	}
+}
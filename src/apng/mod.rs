@@ -1,3 +1,5 @@
+pub mod stream;
+
 use crate::anim::settings::AnimSettings;
 use crate::app::AppResult;
 use crate::image::geometry::Geometry;
@@ -10,7 +12,7 @@ use std::io::{self, Write};
 /* APNG encoder and settings */
 pub struct ApngEncoder<'a, Output: Write> {
 	encoder: Encoder<'a, Output>,
-	settings: &'a AnimSettings,
+	fps: f32,
 }
 
 impl<'a, Output: Write> ApngEncoder<'a, Output> {
@@ -18,6 +20,7 @@ impl<'a, Output: Write> ApngEncoder<'a, Output> {
 	 * Create a new ApngEncoder object.
 	 *
 	 * @param  frame_count
+	 * @param  fps
 	 * @param  geometry
 	 * @param  output
 	 * @param  settings
@@ -25,6 +28,7 @@ impl<'a, Output: Write> ApngEncoder<'a, Output> {
 	 */
 	pub fn new(
 		frame_count: u32,
+		fps: f32,
 		geometry: Geometry,
 		output: Output,
 		settings: &'a AnimSettings,
@@ -32,12 +36,42 @@ impl<'a, Output: Write> ApngEncoder<'a, Output> {
 		let mut encoder = Encoder::new(output, geometry.width, geometry.height);
 		encoder.set_animated(
 			frame_count,
-			settings.repeat.try_into().unwrap_or_default(),
+			settings.get_play_count().unwrap_or_default(),
 		)?;
 		encoder.set_color(ColorType::Rgba);
 		encoder.set_depth(BitDepth::Eight);
 		encoder.set_filter(FilterType::NoFilter);
-		Ok(Self { encoder, settings })
+		Ok(Self { encoder, fps })
+	}
+
+	/**
+	 * Get the frame delay as a fraction of seconds.
+	 *
+	 * @param  fps
+	 * @return Tuple of u16
+	 */
+	pub fn get_frame_delay(fps: f32) -> (u16, u16) {
+		if fps.fract() == 0. && fps >= 1. {
+			(1, fps.min(u16::MAX.into()) as u16)
+		} else {
+			((1e2 / fps).round().clamp(1., u16::MAX.into()) as u16, 100)
+		}
+	}
+
+	/**
+	 * Get the delay of the image as a fraction of seconds.
+	 *
+	 * @param  image
+	 * @param  fps
+	 * @return Tuple of u16
+	 */
+	pub fn get_image_delay(image: &Image, fps: f32) -> (u16, u16) {
+		match image.get_delay() {
+			Some(delay) => {
+				(delay.as_millis().clamp(1, u16::MAX.into()) as u16, 1000)
+			}
+			None => Self::get_frame_delay(fps),
+		}
 	}
 
 	/**
@@ -53,7 +87,6 @@ impl<'a, Output: Write> ApngEncoder<'a, Output> {
 		input_state: Option<&'static InputState>,
 	) -> AppResult<()> {
 		let mut writer = self.encoder.write_header()?;
-		writer.set_frame_delay(1, self.settings.fps.try_into().unwrap_or(1))?;
 		for (i, image) in images.iter().enumerate() {
 			let percentage = ((i + 1) as f64 / images.len() as f64) * 100.;
 			info!("Saving... ({:.1}%)\r", percentage);
@@ -71,6 +104,8 @@ impl<'a, Output: Write> ApngEncoder<'a, Output> {
 					panic!("Failed to write the frames")
 				}
 			}
+			let (numerator, denominator) = Self::get_image_delay(image, self.fps);
+			writer.set_frame_delay(numerator, denominator)?;
 			writer.write_image_data(&image.get_data(ExtendedColorType::Rgba8))?;
 		}
 		info!("\n");
@@ -93,6 +128,7 @@ mod tests {
 		let mut output = Vec::new();
 		ApngEncoder::new(
 			images.len().try_into().unwrap(),
+			20.,
 			geometry,
 			&mut output,
 			&AnimSettings::default(),
@@ -100,6 +136,9 @@ mod tests {
 		.save(images, None)?;
 		output.truncate(6);
 		assert_eq!(vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a], output);
+		assert_eq!((1, 20), ApngEncoder::<Vec<u8>>::get_frame_delay(20.));
+		assert_eq!((200, 100), ApngEncoder::<Vec<u8>>::get_frame_delay(0.5));
+		assert_eq!((67, 100), ApngEncoder::<Vec<u8>>::get_frame_delay(1.5));
 		Ok(())
 	}
 }
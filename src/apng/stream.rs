@@ -0,0 +1,251 @@
+use crate::apng::ApngEncoder;
+use crate::app::{AppError, AppResult};
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use image::ExtendedColorType;
+use std::io::{self, Seek, SeekFrom, Write};
+
+/* PNG file signature */
+const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+/* Compression level that keeps up with the capture rate */
+const COMPRESSION_LEVEL: u8 = 3;
+
+/* APNG writer that encodes the frames as they arrive */
+#[derive(Debug)]
+pub struct ApngStream<Output: Write + Seek> {
+	output: Output,
+	fps: f32,
+	plays: u32,
+	geometry: Option<Geometry>,
+	control_offset: u64,
+	sequence: u32,
+	frame_count: u32,
+}
+
+impl<Output: Write + Seek> ApngStream<Output> {
+	/**
+	 * Create a new ApngStream object.
+	 *
+	 * @param  output
+	 * @param  fps
+	 * @param  plays
+	 * @return ApngStream
+	 */
+	pub fn new(output: Output, fps: f32, plays: u32) -> Self {
+		Self {
+			output,
+			fps,
+			plays,
+			geometry: None,
+			control_offset: 0,
+			sequence: 0,
+			frame_count: 0,
+		}
+	}
+
+	/**
+	 * Get the number of the written frames.
+	 *
+	 * @return u32
+	 */
+	pub fn get_frame_count(&self) -> u32 {
+		self.frame_count
+	}
+
+	/**
+	 * Get the geometry of the written frames.
+	 *
+	 * @return Geometry (Option)
+	 */
+	pub fn get_geometry(&self) -> Option<Geometry> {
+		self.geometry
+	}
+
+	/**
+	 * Write a chunk with its length and checksum.
+	 *
+	 * @param  chunk_type
+	 * @param  data
+	 * @return Result
+	 */
+	fn write_chunk(&mut self, chunk_type: &[u8; 4], data: &[u8]) -> io::Result<()> {
+		let mut hasher = crc32fast::Hasher::new();
+		hasher.update(chunk_type);
+		hasher.update(data);
+		self.output.write_all(
+			&u32::try_from(data.len()).unwrap_or_default().to_be_bytes(),
+		)?;
+		self.output.write_all(chunk_type)?;
+		self.output.write_all(data)?;
+		self.output.write_all(&hasher.finalize().to_be_bytes())
+	}
+
+	/**
+	 * Write the animation control chunk.
+	 *
+	 * @return Result
+	 */
+	fn write_control(&mut self) -> io::Result<()> {
+		let mut data = self.frame_count.to_be_bytes().to_vec();
+		data.extend_from_slice(&self.plays.to_be_bytes());
+		self.write_chunk(b"acTL", &data)
+	}
+
+	/**
+	 * Write the signature, header and animation control chunks.
+	 *
+	 * The frame count is left as zero until the stream is finished.
+	 *
+	 * @param  geometry
+	 * @return Result
+	 */
+	fn write_header(&mut self, geometry: Geometry) -> io::Result<()> {
+		self.output.write_all(&PNG_SIGNATURE)?;
+		let mut data = geometry.width.to_be_bytes().to_vec();
+		data.extend_from_slice(&geometry.height.to_be_bytes());
+		/* 8-bit RGBA, default compression/filter methods, no interlace */
+		data.extend_from_slice(&[8, 6, 0, 0, 0]);
+		self.write_chunk(b"IHDR", &data)?;
+		self.control_offset = self.output.stream_position()?;
+		self.write_control()
+	}
+
+	/**
+	 * Get the next sequence number of the frame chunks.
+	 *
+	 * @return u32
+	 */
+	fn next_sequence(&mut self) -> u32 {
+		self.sequence += 1;
+		self.sequence - 1
+	}
+
+	/**
+	 * Encode the image and append it as a frame.
+	 *
+	 * @param  image
+	 * @return Result
+	 */
+	pub fn write_frame(&mut self, image: &Image) -> AppResult<()> {
+		let geometry = match self.geometry {
+			Some(geometry) => geometry,
+			None => {
+				self.write_header(image.geometry)?;
+				self.geometry = Some(image.geometry);
+				image.geometry
+			}
+		};
+		if (image.geometry.width, image.geometry.height)
+			!= (geometry.width, geometry.height)
+		{
+			return Err(AppError::FrameError(format!(
+				"Frame size changed while streaming ({}x{} -> {}x{})",
+				geometry.width,
+				geometry.height,
+				image.geometry.width,
+				image.geometry.height
+			)));
+		}
+		let mut control = self.next_sequence().to_be_bytes().to_vec();
+		control.extend_from_slice(&geometry.width.to_be_bytes());
+		control.extend_from_slice(&geometry.height.to_be_bytes());
+		control.extend_from_slice(&[0; 8]);
+		let delay = ApngEncoder::<Output>::get_image_delay(image, self.fps);
+		control.extend_from_slice(&delay.0.to_be_bytes());
+		control.extend_from_slice(&delay.1.to_be_bytes());
+		/* Dispose to none and replace the previous frame */
+		control.extend_from_slice(&[0, 0]);
+		self.write_chunk(b"fcTL", &control)?;
+		let row_size = usize::try_from(geometry.width).unwrap_or_default() * 4;
+		let mut data = Vec::new();
+		for row in image
+			.get_data(ExtendedColorType::Rgba8)
+			.chunks(row_size.max(1))
+		{
+			/* No filtering on the scanline */
+			data.push(0);
+			data.extend_from_slice(row);
+		}
+		let data =
+			miniz_oxide::deflate::compress_to_vec_zlib(&data, COMPRESSION_LEVEL);
+		if self.frame_count == 0 {
+			self.write_chunk(b"IDAT", &data)?;
+		} else {
+			let mut chunk = self.next_sequence().to_be_bytes().to_vec();
+			chunk.extend(data);
+			self.write_chunk(b"fdAT", &chunk)?;
+		}
+		self.frame_count += 1;
+		Ok(())
+	}
+
+	/**
+	 * Finish the stream with the final frame count.
+	 *
+	 * @return Output (Result)
+	 */
+	pub fn finish(mut self) -> AppResult<Output> {
+		if self.frame_count == 0 {
+			return Err(AppError::FrameError(String::from(
+				"No frames found to save",
+			)));
+		}
+		self.write_chunk(b"IEND", &[])?;
+		self.output.seek(SeekFrom::Start(self.control_offset))?;
+		self.write_control()?;
+		self.output.seek(SeekFrom::End(0))?;
+		self.output.flush()?;
+		Ok(self.output)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::codecs::png::PngDecoder;
+	use image::{AnimationDecoder, Rgba};
+	use pretty_assertions::assert_eq;
+	use std::io::Cursor;
+	use std::time::Duration;
+	#[test]
+	fn test_apng_stream() -> AppResult<()> {
+		let geometry = Geometry::new(0, 0, 2, 1);
+		let mut stream = ApngStream::new(Cursor::new(Vec::new()), 10., 0);
+		assert_eq!(None, stream.get_geometry());
+		for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+			stream.write_frame(&Image::new(
+				vec![Rgba(color); 2],
+				false,
+				geometry,
+			))?;
+		}
+		stream.write_frame(
+			&Image::new(vec![Rgba([0, 255, 0, 255]); 2], false, geometry)
+				.with_delay(Duration::from_millis(1250)),
+		)?;
+		assert_eq!(3, stream.get_frame_count());
+		assert_eq!(Some(geometry), stream.get_geometry());
+		assert!(stream
+			.write_frame(&Image::new(
+				vec![Rgba([0, 0, 0, 255]); 4],
+				false,
+				Geometry::new(0, 0, 2, 2),
+			))
+			.is_err());
+		let output = stream.finish()?.into_inner();
+		let frames = PngDecoder::new(Cursor::new(output))?
+			.apng()
+			.into_frames()
+			.collect_frames()?;
+		assert_eq!(3, frames.len());
+		assert_eq!((2, 1), frames[2].buffer().dimensions());
+		assert_eq!(&Rgba([0, 0, 255, 255]), frames[1].buffer().get_pixel(1, 0));
+		assert_eq!(&Rgba([0, 255, 0, 255]), frames[2].buffer().get_pixel(0, 0));
+		assert_eq!((100, 1), frames[0].delay().numer_denom_ms());
+		assert_eq!((1250, 1), frames[2].delay().numer_denom_ms());
+		assert!(ApngStream::new(Cursor::new(Vec::new()), 10., 0)
+			.finish()
+			.is_err());
+		Ok(())
+	}
+}
@@ -1,16 +1,41 @@
+use crate::analyze::histogram::Histogram;
 use crate::anim::decoder::AnimDecoder;
+use crate::anim::dump::RawDump;
+use crate::anim::estimate::SizeEstimate;
+use crate::anim::video::VideoImport;
 use crate::anim::Frames;
+use crate::apng::stream::ApngStream;
 use crate::apng::ApngEncoder;
 use crate::args::Args;
+use crate::edit::orient::Orientation;
+use crate::edit::profile::ColorProfile;
+#[cfg(feature = "svg")]
+use crate::edit::svg::SvgRasterizer;
+use crate::edit::watch::InputWatcher;
 use crate::file::format::FileFormat;
+use crate::file::settings::AlphaChannel;
+use crate::file::verify::OutputInfo;
 use crate::file::File as FileUtil;
+use crate::gif::comment::GifComment;
 use crate::gif::encoder::{Encoder, EncoderConfig};
+use crate::gif::interpolate::Interpolator;
+use crate::gif::lossy::LossyFilter;
+use crate::gif::resample::Resampler;
 #[cfg(feature = "ski")]
 use crate::gif::ski::GifskiEncoder;
 use crate::gif::GifEncoder;
+use crate::image::cmyk::CmykEncoder;
+use crate::image::settings::{EmbedProfile, TiffCompression};
 use crate::image::Image;
+use crate::montage::Montage;
+use crate::pick::ColorPicker;
+use crate::record::disk::DiskWatchdog;
+use crate::record::region::RegionStore;
+use crate::record::stitch::Stitcher;
 use crate::record::Recorder;
 use crate::settings::AppSettings;
+use crate::util::command::Command;
+use crate::util::summary::Summary;
 use crate::view::ImageViewer;
 use crate::window::Capture;
 use bytesize::ByteSize;
@@ -18,27 +43,35 @@ use image::codecs::bmp::BmpEncoder;
 use image::codecs::farbfeld::FarbfeldEncoder;
 use image::codecs::gif::GifDecoder;
 use image::codecs::ico::IcoEncoder;
-use image::codecs::jpeg::JpegEncoder;
+use image::codecs::jpeg::{JpegEncoder, PixelDensity};
 use image::codecs::openexr::OpenExrEncoder;
 use image::codecs::png::PngDecoder;
 use image::codecs::png::PngEncoder;
 use image::codecs::pnm::{PnmEncoder, PnmSubtype};
 use image::codecs::tga::TgaEncoder;
-use image::codecs::tiff::TiffEncoder;
 use image::codecs::webp::WebPEncoder;
 use image::error::{
 	ImageError, ImageFormatHint, UnsupportedError, UnsupportedErrorKind,
 };
 use image::io::Reader;
 use image::{
-	AnimationDecoder, ColorType, ExtendedColorType, ImageEncoder, ImageFormat,
+	AnimationDecoder, ColorType, ExtendedColorType, Frames as ImageFrames,
+	ImageEncoder, ImageFormat, Rgba, RgbaImage,
 };
+use std::env;
 use std::fmt::Debug;
-use std::fs::{self, File};
-use std::io::{self, Cursor, Read, Seek, Write};
-use std::path::Path;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Cursor, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::slice;
 use std::thread;
+use std::time::Instant;
 use thiserror::Error as ThisError;
+use tiff::encoder::colortype::{ColorType as TiffColorType, RGB8, RGBA8};
+use tiff::encoder::compression::{Compression, Deflate, Lzw, Uncompressed};
+use tiff::encoder::{Rational, TiffEncoder};
+use tiff::tags::ResolutionUnit;
 
 /* Custom error implementation */
 #[derive(Debug, ThisError)]
@@ -53,6 +86,8 @@ pub enum AppError {
 	GifEncoding(#[from] gif::EncodingError),
 	#[error("PNG encoding error: `{0}`")]
 	PngEncoding(#[from] png::EncodingError),
+	#[error("TIFF encoding error: `{0}`")]
+	TiffEncoding(#[from] tiff::TiffError),
 	#[cfg(feature = "ski")]
 	#[error("gifski error: `{0}`")]
 	Gifski(#[from] gifski::Error),
@@ -64,6 +99,95 @@ pub enum AppError {
 	FrameError(String),
 	#[error("Command error: `{0}`")]
 	CommandError(String),
+	#[error("Thread error: `{0}`")]
+	ThreadError(String),
+	#[error("Edit error: `{0}`")]
+	EditError(String),
+	#[error("Verify error: `{0}`")]
+	VerifyError(String),
+	#[error("Watch error: `{0}`")]
+	Watch(#[from] notify::Error),
+	#[cfg(feature = "svg")]
+	#[error("SVG error: `{0}`")]
+	Svg(String),
+	#[error("Panic: `{0}`")]
+	Panic(String),
+	#[error("{1}")]
+	Stage(AppStage, Box<AppError>),
+}
+
+/* Stage of the application that the error occurred in */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppStage {
+	Capture,
+	Encode,
+	Save,
+}
+
+impl AppStage {
+	/**
+	 * Get the name of the stage.
+	 *
+	 * @return str
+	 */
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Capture => "capture",
+			Self::Encode => "encode",
+			Self::Save => "save",
+		}
+	}
+}
+
+impl AppError {
+	/**
+	 * Set the stage that the error occurred in (unless it is already set).
+	 *
+	 * @param  stage
+	 * @return AppError
+	 */
+	pub fn with_stage(self, stage: AppStage) -> Self {
+		match self {
+			Self::Stage(..) => self,
+			error => Self::Stage(stage, Box::new(error)),
+		}
+	}
+
+	/**
+	 * Get the JSON representation of the error.
+	 *
+	 * The given stage is used if the error does not have one.
+	 *
+	 * @param  stage
+	 * @return String
+	 */
+	pub fn to_json(&self, stage: &str) -> String {
+		let escape = |value: &str| {
+			value.chars().fold(String::new(), |mut escaped, c| {
+				match c {
+					'"' => escaped.push_str("\\\""),
+					'\\' => escaped.push_str("\\\\"),
+					'\n' => escaped.push_str("\\n"),
+					'\r' => escaped.push_str("\\r"),
+					'\t' => escaped.push_str("\\t"),
+					c if c.is_control() => {
+						escaped.push_str(&format!("\\u{:04x}", c as u32))
+					}
+					c => escaped.push(c),
+				}
+				escaped
+			})
+		};
+		let stage = match self {
+			Self::Stage(stage, _) => stage.as_str(),
+			_ => stage,
+		};
+		format!(
+			"{{\"error\": \"{}\", \"stage\": \"{}\"}}",
+			escape(&self.to_string()),
+			escape(stage)
+		)
+	}
 }
 
 /* Application output and result types */
@@ -75,6 +199,7 @@ pub type AppResult<T> = std::result::Result<T, AppError>;
 pub struct App<'a, Window> {
 	window: Option<Window>,
 	settings: &'a AppSettings<'a>,
+	release: bool,
 }
 
 impl<'a, Window> App<'a, Window>
@@ -89,7 +214,32 @@ where
 	 * @return App
 	 */
 	pub fn new(window: Option<Window>, settings: &'a AppSettings<'a>) -> Self {
-		Self { window, settings }
+		Self {
+			window,
+			settings,
+			release: true,
+		}
+	}
+
+	/**
+	 * Set whether the window is released after using it.
+	 *
+	 * Windows that share the display with other captures
+	 * need to be released once after all of them are done.
+	 *
+	 * @param  release
+	 * @return App
+	 */
+	pub fn with_release(mut self, release: bool) -> Self {
+		self.release = release;
+		self
+	}
+
+	/* Release the window unless it is shared with other captures */
+	fn release_window(self) {
+		if let (Some(window), true) = (self.window, self.release) {
+			window.release();
+		}
 	}
 
 	/**
@@ -98,9 +248,13 @@ where
 	 * @return Result
 	 */
 	pub fn start(&self) -> AppResult<()> {
+		let start_time = Instant::now();
 		trace!("Window: {:?}", self.window);
 		debug!("{:?}", self.settings.save.file);
 		debug!("Command: {:?}", self.settings.record.get_command());
+		let title = self
+			.window
+			.map(|window| window.get_title().unwrap_or_else(|| String::from("(?)")));
 		if let Some(misc_args) = self.settings.args.subcommand_matches("misc") {
 			if let Some(shell) = misc_args.value_of("gen-completions") {
 				Args::gen_completions(shell, &mut io::stdout());
@@ -111,7 +265,7 @@ where
 			info!(
 				"Frames saved to {:?} in {} format.",
 				self.settings.split.dir,
-				self.settings.save.file.format.as_extension().to_uppercase(),
+				self.settings.split.format.as_extension().to_uppercase(),
 			);
 		} else if self.settings.args.is_present("analyze") {
 			debug!("Analyzing the image... ({:?})", self.settings.analyze.file);
@@ -119,40 +273,396 @@ where
 		} else if self.settings.args.is_present("view") {
 			debug!("Viewing the image... ({:?})", self.settings.view.file);
 			self.view_image()?;
-		} else if self.settings.save.file.path.to_str() == Some("-") {
-			let mut buffer = Cursor::new(Vec::new());
-			self.save_output(self.get_app_output()?, &mut buffer)?;
-			io::stdout().write_all(&buffer.into_inner())?;
-		} else {
-			self.save_output(
-				self.get_app_output()?,
-				File::create(&self.settings.save.file.path)?,
-			)?;
+		} else if self.settings.args.is_present("pick") {
+			self.pick_color()?;
+		} else if let (true, Some(path)) = (
+			self.settings.args.is_present("record"),
+			&self.settings.dump.file,
+		) {
+			self.dump_frames(path)?;
 			info!(
-				"{} saved to: {:?} ({})",
-				self.settings.save.file.format.as_extension().to_uppercase(),
-				self.settings.save.file.path,
-				ByteSize(fs::metadata(&self.settings.save.file.path)?.len())
+				"Frames dumped to: {:?} ({})",
+				path,
+				ByteSize(fs::metadata(path)?.len())
 			);
+		} else if self.settings.args.is_present("record")
+			&& self.settings.anim.stream
+		{
+			self.stream_frames()?;
+		} else if self.settings.args.is_present("edit") && self.settings.edit.watch {
+			self.watch_input(title)?;
+		} else {
+			self.save_app_output(title, start_time)?;
 		}
 		Ok(())
 	}
 
+	/**
+	 * Get the application output and save it.
+	 *
+	 * @param  title (Option)
+	 * @param  start_time
+	 * @return Result
+	 */
+	fn save_app_output(
+		&self,
+		title: Option<String>,
+		start_time: Instant,
+	) -> AppResult<()> {
+		if self.settings.save.file.path.to_str() != Some("-")
+			&& !self.settings.save.file.is_stream()
+		{
+			self.settings
+				.save
+				.file
+				.check_dir(self.settings.save.mkdir)
+				.map_err(|e| AppError::from(e).with_stage(AppStage::Save))?;
+		}
+		let output = self
+			.get_app_output()
+			.map_err(|e| e.with_stage(AppStage::Capture))?;
+		if let Some(output) = output {
+			if let (true, (_, Some(frames))) =
+				(self.settings.anim.estimate.0, &output)
+			{
+				if !self
+					.confirm_estimate(frames)
+					.map_err(|e| e.with_stage(AppStage::Encode))?
+				{
+					warn!("The output is discarded.");
+					return Ok(());
+				}
+			}
+			let mut summary = self.get_summary(&output, title);
+			if let (Some(path), (Some(image), _)) =
+				(&self.settings.histogram.file, &output)
+			{
+				self.save_histogram(image, path)
+					.map_err(|e| e.with_stage(AppStage::Save))?;
+			}
+			if self.settings.save.verify
+				&& (self.settings.save.file.path.to_str() == Some("-")
+					|| self.settings.save.file.is_stream())
+			{
+				warn!("The output written to a stream cannot be verified.");
+			}
+			let size = if self.settings.save.file.path.to_str() == Some("-") {
+				self.save_to_stream(output, io::stdout())
+					.map_err(|e| e.with_stage(AppStage::Encode))?
+			} else if self.settings.save.file.is_stream() {
+				debug!("Writing to a stream: {:?}", self.settings.save.file.path);
+				let stream = OpenOptions::new()
+					.write(true)
+					.open(&self.settings.save.file.path)
+					.map_err(|e| AppError::from(e).with_stage(AppStage::Save))?;
+				let size = self
+					.save_to_stream(output, stream)
+					.map_err(|e| e.with_stage(AppStage::Encode))?;
+				info!(
+					"{} written to: {:?} ({})",
+					self.settings.save.file.format.as_extension().to_uppercase(),
+					self.settings.save.file.path,
+					ByteSize(size)
+				);
+				size
+			} else {
+				let file = File::create(&self.settings.save.file.path)
+					.map_err(|e| AppError::from(e).with_stage(AppStage::Save))?;
+				let output_info = self
+					.save_output(output, &self.settings.save.file.format, file)
+					.map_err(|e| e.with_stage(AppStage::Encode))?;
+				let size = fs::metadata(&self.settings.save.file.path)
+					.map_err(|e| AppError::from(e).with_stage(AppStage::Save))?
+					.len();
+				info!(
+					"{} saved to: {:?} ({})",
+					self.settings.save.file.format.as_extension().to_uppercase(),
+					self.settings.save.file.path,
+					ByteSize(size)
+				);
+				if self.settings.save.verify {
+					self.verify_output(output_info)
+						.map_err(|e| e.with_stage(AppStage::Save))?;
+				}
+				if let Some(opener) = &self.settings.save.open {
+					self.open_output(opener, &self.settings.save.file.path);
+				}
+				if let Some(post) = &self.settings.save.post {
+					self.run_post_command(post, &self.settings.save.file.path);
+				}
+				size
+			};
+			if self.settings.args.occurrences_of("verbose") > 0 {
+				summary.add("File size", ByteSize(size));
+				summary.add(
+					"Elapsed",
+					format!("{:.2}s", start_time.elapsed().as_secs_f32()),
+				);
+				info!("{}#", summary);
+			}
+		} else {
+			warn!("The output is discarded.");
+		}
+		Ok(())
+	}
+
+	/**
+	 * Decode the saved file and compare it with the encoded output.
+	 *
+	 * Encoders might merge the identical frames,
+	 * so a different frame count is only reported as a warning.
+	 *
+	 * @param  expected
+	 * @return Result
+	 */
+	fn verify_output(&self, expected: OutputInfo) -> AppResult<()> {
+		info!("Verifying the output...");
+		let decoded = OutputInfo::read(
+			&self.settings.save.file.path,
+			&self.settings.save.file.format,
+		)?;
+		if (decoded.width, decoded.height) != (expected.width, expected.height) {
+			return Err(AppError::VerifyError(format!(
+				"Expected {expected}, decoded {decoded}"
+			)));
+		} else if decoded.frames != expected.frames {
+			warn!(
+				"Frame count mismatch: expected {}, decoded {}",
+				expected.frames, decoded.frames
+			);
+		} else {
+			info!("Output verified: {}", decoded);
+		}
+		if let (FileFormat::Gif, Some(comment)) =
+			(&self.settings.save.file.format, &self.settings.anim.comment)
+		{
+			let comments =
+				GifComment::read(&fs::read(&self.settings.save.file.path)?)?;
+			if !comments.contains(comment) {
+				return Err(AppError::VerifyError(String::from(
+					"GIF comment is missing",
+				)));
+			}
+		}
+		Ok(())
+	}
+
+	/**
+	 * Edit the input file again whenever it changes.
+	 *
+	 * @param  title (Option)
+	 * @return Result
+	 */
+	fn watch_input(&self, title: Option<String>) -> AppResult<()> {
+		let path = &self.settings.edit.path;
+		let watcher = InputWatcher::new(path)?;
+		if fs::canonicalize(&self.settings.save.file.path).ok()
+			== fs::canonicalize(path).ok()
+		{
+			return Err(AppError::EditError(String::from(
+				"The output file cannot be the same as the watched input",
+			)));
+		}
+		loop {
+			if let Err(e) = self.save_app_output(title.clone(), Instant::now()) {
+				error!("{}", e);
+			}
+			info!("Watching {:?} for changes... (Ctrl-C to stop)", path);
+			if !watcher.wait() {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/**
+	 * Encode the output into a buffer and write it to the given stream.
+	 *
+	 * Streams such as stdout and named pipes are not seekable,
+	 * so the output is written after the encoding is done.
+	 *
+	 * @param  output
+	 * @param  stream
+	 * @return u64 (Result)
+	 */
+	fn save_to_stream<Stream: Write>(
+		&self,
+		output: AppOutput,
+		mut stream: Stream,
+	) -> AppResult<u64> {
+		let mut buffer = Cursor::new(Vec::new());
+		self.save_output(output, &self.settings.save.file.format, &mut buffer)?;
+		let buffer = buffer.into_inner();
+		stream.write_all(&buffer)?;
+		stream.flush()?;
+		Ok(buffer.len() as u64)
+	}
+
+	/**
+	 * Estimate the output size by encoding a sample of the frames.
+	 *
+	 * @param  frames
+	 * @return bool (Result)
+	 */
+	fn confirm_estimate(&self, frames: &Frames) -> AppResult<bool> {
+		let sample = SizeEstimate::get_sample(frames);
+		let sample_count = sample.0.len();
+		let mut buffer = Cursor::new(Vec::new());
+		self.save_output(
+			(None, Some(sample)),
+			&self.settings.save.file.format,
+			&mut buffer,
+		)?;
+		let estimate = SizeEstimate::new(
+			buffer.into_inner().len() as u64,
+			sample_count,
+			frames.0.len(),
+		);
+		if self.settings.anim.estimate.1 {
+			info!("Estimated size: {}", estimate);
+			Ok(true)
+		} else {
+			Ok(estimate.confirm(io::stdin().lock(), io::stderr())?)
+		}
+	}
+
+	/**
+	 * Save the histogram of the image as PNG or print it as CSV.
+	 *
+	 * @param  image
+	 * @param  path
+	 * @return Result
+	 */
+	fn save_histogram(&self, image: &Image, path: &Path) -> AppResult<()> {
+		let histogram = Histogram::from_image(image);
+		if self.settings.histogram.is_stdout() {
+			io::stdout().write_all(histogram.to_csv().as_bytes())?;
+		} else {
+			histogram
+				.render()
+				.save_with_format(path, ImageFormat::Png)?;
+			info!("Histogram saved to: {:?}", path);
+		}
+		Ok(())
+	}
+
+	/**
+	 * Get the summary of the run from the application output.
+	 *
+	 * @param  output
+	 * @param  title (Option)
+	 * @return Summary
+	 */
+	fn get_summary(&self, output: &AppOutput, title: Option<String>) -> Summary {
+		let mut summary = Summary::new();
+		summary.add("Command", self.settings.args.subcommand().0);
+		if let Some(title) = title {
+			summary.add("Window", title);
+		}
+		let geometry = match output {
+			(Some(image), _) => Some(image.geometry),
+			(None, Some((images, _))) => images.first().map(|image| image.geometry),
+			_ => None,
+		};
+		if let Some(geometry) = geometry {
+			summary.add("Geometry", RegionStore::format(&geometry));
+		}
+		if let (_, Some((images, fps))) = output {
+			summary.add("Frames", images.len());
+			summary.add("Duration", format!("{:.2}s", images.len() as f32 / fps));
+		}
+		summary.add(
+			"Format",
+			self.settings.save.file.format.as_extension().to_uppercase(),
+		);
+		summary
+	}
+
+	/**
+	 * Open the output file with the given command.
+	 *
+	 * @param opener
+	 * @param path
+	 */
+	fn open_output(&self, opener: &str, path: &Path) {
+		let path = path.to_str().unwrap_or_default();
+		let script = format!("{opener} \"$1\"");
+		let command = if opener.contains(' ') {
+			Command::new("sh", vec!["-c", &script, "sh", path])
+		} else {
+			Command::new(opener, vec![path])
+		};
+		match command.spawn() {
+			Ok(()) => info!("Opening the output with {:?}...", opener),
+			Err(e) => warn!("Failed to open the output with {:?}: {}", opener, e),
+		}
+	}
+
+	/**
+	 * Run the post command on the saved file.
+	 *
+	 * The path is passed to the shell as an argument
+	 * in place of the "{}" placeholder (or appended)
+	 * so that it does not need to be quoted.
+	 *
+	 * @param  post
+	 * @param  path
+	 */
+	fn run_post_command(&self, post: &str, path: &Path) {
+		let path = path.to_str().unwrap_or_default();
+		let script = if post.contains("{}") {
+			post.replace("{}", "\"$1\"")
+		} else {
+			format!("{post} \"$1\"")
+		};
+		debug!("Post command: {:?}", script);
+		match Command::new("sh", vec!["-c", &script, "sh", path]).execute() {
+			Ok(status) if status.success() => {
+				info!("Post command finished ({})", status)
+			}
+			Ok(status) => warn!("Post command failed ({})", status),
+			Err(e) => warn!("Failed to run the post command {:?}: {}", post, e),
+		}
+	}
+
 	/**
 	 * Get the application output.
 	 *
-	 * @return AppOutput (Result)
+	 * @return AppOutput (Option) (Result)
 	 */
-	fn get_app_output(self) -> AppResult<AppOutput> {
+	fn get_app_output(self) -> AppResult<Option<AppOutput>> {
 		let output = if self.settings.save.file.format.is_animation() {
 			(None, Some(self.get_frames()?))
 		} else {
 			(Some(self.get_image()?), None)
 		};
+		let mut confirmed = true;
 		if let Some(window) = self.window {
-			window.release();
+			if self.settings.record.flag.preview {
+				info!("Waiting for confirmation... (Enter: save, Escape: discard)");
+				confirmed = window.preview(&Self::get_preview_images(&output));
+			}
+		}
+		self.release_window();
+		Ok(if confirmed { Some(output) } else { None })
+	}
+
+	/**
+	 * Get the images to preview from the output.
+	 *
+	 * @param  output
+	 * @return Vector of Image
+	 */
+	fn get_preview_images(output: &AppOutput) -> Vec<&Image> {
+		match output {
+			(Some(image), _) => vec![image],
+			(None, Some((images, _))) => match (images.first(), images.last()) {
+				(Some(first), Some(last)) if images.len() > 1 => vec![first, last],
+				(Some(first), _) => vec![first],
+				_ => Vec::new(),
+			},
+			(None, None) => Vec::new(),
 		}
-		Ok(output)
 	}
 
 	/**
@@ -165,8 +675,12 @@ where
 			debug!("{:?}", self.settings.edit);
 			info!("Opening {:?}...", self.settings.edit.path);
 			self.edit_image(&self.settings.edit.path)
+		} else if self.settings.args.is_present("montage") {
+			self.montage()
+		} else if self.settings.args.is_present("spritesheet") {
+			self.spritesheet()
 		} else {
-			self.capture()
+			Ok(self.apply_canvas(vec![self.capture()?]).remove(0))
 		}
 	}
 
@@ -183,23 +697,251 @@ where
 				&self.settings.edit.path,
 			)
 		} else if self.settings.args.is_present("make") {
+			let video_dir =
+				env::temp_dir().join(format!("menyoki_video_{}", process::id()));
+			let frames = match self.settings.anim.frames.as_slice() {
+				[path] if VideoImport::is_video(path) => {
+					info!("Extracting frames from {:?}...", path);
+					VideoImport::new(
+						path,
+						self.settings.anim.fps,
+						self.settings.anim.video_range,
+					)
+					.extract(&video_dir)
+				}
+				frames => Ok(frames.to_vec()),
+			};
+			let images = frames.and_then(|frames| {
+				info!("Making an animation from {} frames...", frames.len());
+				let mut images = Vec::new();
+				for path in &frames {
+					debug!("Reading a frame from {:?}   \r", path);
+					io::stdout().flush()?;
+					images.push(self.edit_image(path)?);
+				}
+				debug!("\n");
+				Ok(images)
+			});
+			if video_dir.exists() {
+				fs::remove_dir_all(&video_dir)?;
+			}
+			Ok((images?, self.settings.anim.fps))
+		} else if self.settings.args.is_present("encode") {
+			let path = self.settings.dump.file.as_ref().ok_or_else(|| {
+				AppError::FrameError(String::from("No raw dump file specified"))
+			})?;
+			info!("Reading frames from {:?}...", path);
+			let (images, fps) = RawDump::read(BufReader::new(File::open(path)?))?;
+			Ok((images, self.settings.anim.get_playback_fps(fps)))
+		} else {
+			let fps = self.settings.anim.get_playback_fps(self.settings.anim.fps);
+			debug!("Playback FPS: {}", fps);
+			Ok((self.apply_canvas(self.apply_cut(self.record()?)), fps))
+		}
+	}
+
+	/**
+	 * Cut the beginning and end of the recorded frames.
+	 *
+	 * @param  images
+	 * @return Vector of Image
+	 */
+	fn apply_cut(self, mut images: Vec<Image>) -> Vec<Image> {
+		let range = self
+			.settings
+			.anim
+			.get_cut_range(images.len(), self.settings.anim.fps);
+		if range.len() != images.len() {
 			info!(
-				"Making an animation from {} frames...",
-				self.settings.anim.frames.len()
+				"Cutting the recording... ({} frames -> {} frames)",
+				images.len(),
+				range.len()
 			);
-			let mut images = Vec::new();
-			for path in &self.settings.anim.frames {
-				debug!("Reading a frame from {:?}   \r", path);
-				io::stdout().flush()?;
-				images.push(self.edit_image(path)?);
-			}
-			debug!("\n");
-			Ok((images, self.settings.anim.fps))
-		} else {
-			Ok((self.record()?, self.settings.anim.fps))
+		}
+		images.drain(range).collect()
+	}
+
+	/**
+	 * Center the images on the canvas if specified.
+	 *
+	 * @param  images
+	 * @return Vector of Image
+	 */
+	fn apply_canvas(self, images: Vec<Image>) -> Vec<Image> {
+		match self.settings.record.canvas {
+			Some(canvas) => {
+				if images.iter().any(|image| {
+					image.geometry.width > canvas.size.width
+						|| image.geometry.height > canvas.size.height
+				}) {
+					warn!(
+						"Output is larger than the canvas ({}x{}), clipping.",
+						canvas.size.width, canvas.size.height
+					);
+				}
+				images
+					.iter()
+					.map(|image| image.with_canvas(canvas.size, canvas.background))
+					.collect()
+			}
+			None => images,
 		}
 	}
 
+	/**
+	 * Append the frames in reverse order for playing back and forth.
+	 *
+	 * The first and last frames are not repeated for a seamless loop.
+	 *
+	 * @param  frames
+	 * @return Frames
+	 */
+	fn apply_boomerang(self, frames: Frames) -> Frames {
+		let (mut images, fps) = frames;
+		let reversed = images
+			.iter()
+			.rev()
+			.skip(1)
+			.take(images.len().saturating_sub(2))
+			.cloned()
+			.collect::<Vec<Image>>();
+		debug!("Appending {} reversed frames...", reversed.len());
+		images.extend(reversed);
+		(images, fps)
+	}
+
+	/**
+	 * Downscale the frames if they exceed the maximum dimension.
+	 *
+	 * @param  frames
+	 * @return Frames
+	 */
+	fn apply_max_dimension(self, frames: Frames) -> Frames {
+		let (images, fps) = frames;
+		let geometry = match images.first() {
+			Some(image) => image.geometry,
+			None => return (images, fps),
+		};
+		match self.settings.anim.get_max_size(geometry) {
+			Some(size) => {
+				info!(
+					"Downscaling the frames to {}x{}... (scale: {:.3})",
+					size.width,
+					size.height,
+					f64::from(size.width) / f64::from(geometry.width)
+				);
+				(
+					images
+						.iter()
+						.map(|image| {
+							image.resize(
+								size.width,
+								size.height,
+								self.settings.edit.image.filter,
+							)
+						})
+						.collect(),
+					fps,
+				)
+			}
+			None => (images, fps),
+		}
+	}
+
+	/**
+	 * Record the frames and dump them to the given file.
+	 *
+	 * @param  path
+	 * @return Result
+	 */
+	fn dump_frames(self, path: &Path) -> AppResult<()> {
+		let frames = (
+			self.apply_canvas(self.apply_cut(
+				self.record().map_err(|e| e.with_stage(AppStage::Capture))?,
+			)),
+			self.settings.anim.fps,
+		);
+		self.release_window();
+		File::create(path)
+			.map_err(AppError::from)
+			.and_then(|file| RawDump::write(&frames, BufWriter::new(file)))
+			.map_err(|e| e.with_stage(AppStage::Save))
+	}
+
+	/**
+	 * Record the frames and write them to the APNG file as they are captured.
+	 *
+	 * @return Result
+	 */
+	fn stream_frames(self) -> AppResult<()> {
+		let path = &self.settings.save.file.path;
+		if path.to_str() == Some("-") || self.settings.save.file.is_stream() {
+			return Err(AppError::FrameError(String::from(
+				"Streaming requires a seekable output file",
+			)));
+		} else if self.settings.record.command.is_some() {
+			return Err(AppError::CommandError(String::from(
+				"Streaming is not supported with a command",
+			)));
+		}
+		if self.settings.record.time.replay.is_some() {
+			warn!("Replay buffer is not supported while streaming.");
+		}
+		if self.settings.anim.cut != (0., 0.) {
+			warn!("Cutting is not supported while streaming.");
+		}
+		self.settings
+			.save
+			.file
+			.check_dir(self.settings.save.mkdir)?;
+		let mut recorder = self.get_recorder()?;
+		let mut stream = ApngStream::new(
+			BufWriter::new(File::create(path)?),
+			self.settings.anim.get_playback_fps(self.settings.anim.fps),
+			self.settings.anim.get_play_count().unwrap_or_default(),
+		);
+		let completed = recorder.record_frames(
+			if self.settings.record.flag.action_keys.is_some() {
+				self.settings.input_state
+			} else {
+				None
+			},
+			|image| {
+				stream.write_frame(&self.apply_canvas(vec![image]).remove(0))?;
+				Ok(usize::try_from(stream.get_frame_count()).unwrap_or_default())
+			},
+		);
+		self.release_window();
+		if !completed.map_err(|e| e.with_stage(AppStage::Capture))? {
+			warn!("The output is discarded.");
+			fs::remove_file(path)?;
+			return Ok(());
+		}
+		let output_info = stream.get_geometry().map(|geometry| {
+			OutputInfo::new(
+				geometry.width,
+				geometry.height,
+				usize::try_from(stream.get_frame_count()).unwrap_or_default(),
+			)
+		});
+		stream.finish().map_err(|e| e.with_stage(AppStage::Save))?;
+		info!(
+			"APNG streamed to: {:?} ({})",
+			path,
+			ByteSize(fs::metadata(path)?.len())
+		);
+		if let (true, Some(output_info)) = (self.settings.save.verify, output_info) {
+			self.verify_output(output_info)?;
+		}
+		if let Some(opener) = &self.settings.save.open {
+			self.open_output(opener, path);
+		}
+		if let Some(post) = &self.settings.save.post {
+			self.run_post_command(post, path);
+		}
+		Ok(())
+	}
+
 	/**
 	 * Capture the image of window.
 	 *
@@ -209,11 +951,11 @@ where
 		let window = self.window.ok_or_else(|| {
 			AppError::WsError(String::from("Failed to get the window"))
 		})?;
-		if self.settings.record.command.is_some() {
+		let image = if self.settings.record.command.is_some() {
 			let image_thread = thread::spawn(move || {
 				window.show_countdown();
 				info!("Capturing an image...");
-				window.get_image()
+				window.exists().then(|| window.get_image()).flatten()
 			});
 			self.settings
 				.record
@@ -224,33 +966,105 @@ where
 					))
 				})?
 				.execute()?;
-			image_thread
-				.join()
-				.expect("Failed to join the image thread.")
+			image_thread.join().map_err(|_| {
+				AppError::ThreadError(String::from(
+					"Failed to join the image thread",
+				))
+			})?
 		} else {
 			window.show_countdown();
 			info!("Capturing an image...");
-			window.get_image()
+			window.exists().then(|| window.get_image()).flatten()
+		}
+		.ok_or_else(|| {
+			AppError::WsError(String::from(if window.exists() {
+				"Failed to get image"
+			} else {
+				"Target window closed"
+			}))
+		})?;
+		if self.settings.record.flag.sanity_check {
+			if let Some(color) = image.get_uniform_color() {
+				warn!(
+					"Captured image is a single color (#{:02x}{:02x}{:02x}), \
+					the window contents might not be accessible.",
+					color[0], color[1], color[2]
+				);
+				warn!(
+					"Try running a compositor or capturing with --root/--parent \
+					(use --no-sanity-check to disable this warning)."
+				);
+			}
+		}
+		match (self.settings.record.flag.stitch, self.settings.input_state) {
+			(true, Some(input_state)) => {
+				let mut stitcher = Stitcher::new(self.settings.record.flag.alpha);
+				stitcher.add(&image);
+				if !stitcher.capture(&window, input_state) {
+					return Err(AppError::WsError(String::from(
+						"Stitching cancelled",
+					)));
+				}
+				let image = stitcher.get_image().ok_or_else(|| {
+					AppError::FrameError(String::from("Failed to stitch the image"))
+				})?;
+				info!(
+					"Stitched the captures into a {}x{} image.",
+					image.geometry.width, image.geometry.height
+				);
+				Ok(image)
+			}
+			(true, None) => {
+				warn!("Stitching is not supported without the input state.");
+				Ok(image)
+			}
+			_ => Ok(image),
 		}
-		.ok_or_else(|| AppError::WsError(String::from("Failed to get image")))
 	}
 
 	/**
-	 * Start recording the frames.
+	 * Get the recorder of the window.
 	 *
-	 * @return Vector of Image (Result)
+	 * @return Recorder (Result)
 	 */
-	fn record(self) -> AppResult<Vec<Image>> {
-		let mut recorder = Recorder::new(
+	fn get_recorder(self) -> AppResult<Recorder<Window>> {
+		Ok(Recorder::new(
 			self.window.ok_or_else(|| {
 				AppError::WsError(String::from("Failed to get the window"))
 			})?,
 			self.settings.anim.fps,
-			self.settings.anim.gifski.0,
 			self.settings.record,
-		);
+		)
+		.with_watchdog(
+			self.settings
+				.record
+				.min_free
+				.map(|min_free| DiskWatchdog::new(self.get_output_dir(), min_free)),
+		))
+	}
+
+	/**
+	 * Start recording the frames.
+	 *
+	 * @return Vector of Image (Result)
+	 */
+	fn record(self) -> AppResult<Vec<Image>> {
+		let mut recorder = self.get_recorder()?;
 		if self.settings.record.command.is_some() {
+			if self.settings.record.flag.on_keypress {
+				warn!(
+					"Keypress-triggered recording is not supported with a command."
+				);
+			}
+			if self.settings.record.time.replay.is_some() {
+				warn!("Replay buffer is not supported with a command.");
+			}
 			let record = recorder.record_async();
+			if !record.wait() {
+				return Err(AppError::ThreadError(String::from(
+					"Failed to start the recording",
+				)));
+			}
 			self.settings
 				.record
 				.get_command()
@@ -260,10 +1074,14 @@ where
 					))
 				})?
 				.execute()?;
-			Ok(match record.get() {
-				Some(frames) => frames.expect("Failed to retrieve the frames."),
-				None => Vec::new(),
-			})
+			match record.get() {
+				Some(frames) => frames.map_err(|_| {
+					AppError::ThreadError(String::from(
+						"Failed to retrieve the frames",
+					))
+				}),
+				None => Ok(Vec::new()),
+			}
 		} else {
 			Ok(recorder.record_sync(
 				if self.settings.record.flag.action_keys.is_some() {
@@ -275,6 +1093,22 @@ where
 		}
 	}
 
+	/**
+	 * Get the directory that the recording will be written to.
+	 *
+	 * @return PathBuf
+	 */
+	fn get_output_dir(self) -> PathBuf {
+		self.settings
+			.dump
+			.file
+			.as_ref()
+			.unwrap_or(&self.settings.save.file.path)
+			.parent()
+			.filter(|dir| !dir.as_os_str().is_empty())
+			.map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+	}
+
 	/**
 	 * Edit and return the image.
 	 *
@@ -282,17 +1116,126 @@ where
 	 * @return Image (Result)
 	 */
 	fn edit_image(self, path: &Path) -> AppResult<Image> {
+		if self.settings.edit.strip {
+			self.check_profile(path);
+		}
+		let image = self.read_image(path)?;
+		self.settings.edit.get_imageops()?.edit(image)
+	}
+
+	/**
+	 * Read the image from the given file.
+	 *
+	 * @param  path
+	 * @return RgbaImage (Result)
+	 */
+	fn read_image(self, path: &Path) -> AppResult<RgbaImage> {
+		if let Some(sheet) = self.settings.edit.contact_sheet {
+			return AnimDecoder::get_contact_sheet(
+				self.decode_anim(File::open(path)?, path)?,
+				sheet,
+				self.settings.edit.image.filter,
+			);
+		}
+		if let Some(flatten) = self.settings.edit.flatten {
+			return AnimDecoder::flatten(
+				self.decode_anim(File::open(path)?, path)?,
+				flatten,
+			);
+		}
+		if let Some(index) = self.settings.edit.frame {
+			info!("Extracting the frame #{}...", index);
+			return AnimDecoder::get_frame(
+				self.decode_anim(File::open(path)?, path)?,
+				index,
+			);
+		}
+		#[cfg(feature = "svg")]
+		if SvgRasterizer::is_svg(path) {
+			return SvgRasterizer::open(path)?
+				.rasterize(self.settings.edit.image.resize);
+		}
 		let image = Reader::open(path)?
 			.with_guessed_format()?
 			.decode()?
 			.to_rgba8();
-		Ok(self
-			.settings
-			.edit
-			.get_imageops()
-			.init(image.dimensions())
-			.process(image)
-			.get_image())
+		match Orientation::read(path) {
+			Some(orientation)
+				if self.settings.edit.auto_orient
+					&& orientation.is_transformed() =>
+			{
+				info!("Applying the EXIF orientation...");
+				Ok(orientation.apply(image))
+			}
+			_ => Ok(image),
+		}
+	}
+
+	/**
+	 * Check the color profile of the image before stripping it.
+	 *
+	 * @param path
+	 */
+	fn check_profile(&self, path: &Path) {
+		match ColorProfile::from_file(path) {
+			Some(profile) if !profile.is_srgb() => {
+				warn!(
+					"Stripping a non-sRGB color profile ({} bytes), colors might look different.",
+					profile.size()
+				);
+			}
+			Some(profile) => {
+				info!(
+					"Stripping the sRGB color profile ({} bytes)...",
+					profile.size()
+				);
+			}
+			None => info!("Stripping the metadata..."),
+		}
+	}
+
+	/**
+	 * Combine the images into a grid and return it.
+	 *
+	 * @return Image (Result)
+	 */
+	fn montage(self) -> AppResult<Image> {
+		debug!("{:?}", self.settings.montage);
+		let mut images = Vec::new();
+		for path in &self.settings.montage.files {
+			info!("Opening {:?}...", path);
+			images.push(self.read_image(path)?);
+		}
+		Ok(Montage::new(images, &self.settings.montage).get_image())
+	}
+
+	/**
+	 * Tile the animation frames into a spritesheet and save the metadata.
+	 *
+	 * @return Image (Result)
+	 */
+	fn spritesheet(self) -> AppResult<Image> {
+		debug!("{:?}", self.settings.spritesheet);
+		let path = &self.settings.spritesheet.file;
+		info!("Reading frames from {:?}...", path);
+		let sheet = AnimDecoder::get_spritesheet(
+			self.decode_anim(File::open(path)?, path)?,
+			self.settings.spritesheet.cols,
+		)?;
+		let output = &self.settings.save.file.path;
+		if output.to_str() == Some("-") || self.settings.save.file.is_stream() {
+			warn!("The spritesheet metadata is not saved for a stream.");
+		} else {
+			let metadata = output.with_extension("json");
+			fs::write(
+				&metadata,
+				sheet.to_json(
+					&output.file_name().unwrap_or_default().to_string_lossy(),
+				),
+			)?;
+			info!("Spritesheet metadata saved to: {:?}", metadata);
+		}
+		Ok(sheet.image)
 	}
 
 	/**
@@ -330,6 +1273,31 @@ where
 			.map(|(w, h)| debug!("Image dimensions: {}x{}", w, h))
 	}
 
+	/**
+	 * Pick the color of the pixel under the pointer.
+	 *
+	 * @return Result
+	 */
+	fn pick_color(self) -> AppResult<()> {
+		let window = self.window.ok_or_else(|| {
+			AppError::WsError(String::from("Failed to get the window"))
+		})?;
+		let image = window.get_image().ok_or_else(|| {
+			AppError::WsError(String::from("Failed to get the image"))
+		})?;
+		self.release_window();
+		let data = image.get_data(ExtendedColorType::Rgba8);
+		let color = data.get(0..4).ok_or_else(|| {
+			AppError::WsError(String::from("Failed to get the pixel"))
+		})?;
+		let picker = ColorPicker::new(
+			Rgba([color[0], color[1], color[2], color[3]]),
+			&self.settings.pick,
+		);
+		println!("{}", picker.get_output());
+		picker.copy_to_clipboard()
+	}
+
 	/**
 	 * Return the updated frames after decoding the animation.
 	 *
@@ -338,27 +1306,42 @@ where
 	 * @return Frames (Result)
 	 */
 	fn edit_anim<Input: Read>(self, input: Input, path: &Path) -> AppResult<Frames> {
-		let format = Reader::open(path)?.with_guessed_format()?.format();
-		let frames =
-			AnimDecoder::new(self.settings.edit.get_imageops(), &self.settings.anim)
-				.update_frames(match format {
-					Some(ImageFormat::Gif) => {
-						GifDecoder::new(input)?.into_frames().collect_frames()
-					}
-					Some(ImageFormat::Png) => PngDecoder::new(input)?
-						.apng()
-						.into_frames()
-						.collect_frames(),
-					_ => Err(ImageError::Unsupported(
-						UnsupportedError::from_format_and_kind(
-							ImageFormatHint::Unknown,
-							UnsupportedErrorKind::Format(ImageFormatHint::Unknown),
-						),
-					)),
-				}?)?;
+		let frames = AnimDecoder::new(
+			self.settings.edit.get_imageops()?,
+			&self.settings.anim,
+		)
+		.update_frames(self.decode_anim(input, path)?.collect_frames()?)?;
 		Ok(frames)
 	}
 
+	/**
+	 * Create a lazy frame iterator for the animation.
+	 *
+	 * @param  input
+	 * @param  path
+	 * @return Frames (Result)
+	 */
+	fn decode_anim<'b, Input: Read + 'b>(
+		self,
+		input: Input,
+		path: &Path,
+	) -> AppResult<ImageFrames<'b>> {
+		let format = Reader::open(path)?.with_guessed_format()?.format();
+		Ok(match format {
+			Some(ImageFormat::Gif) => GifDecoder::new(input)?.into_frames(),
+			Some(ImageFormat::Png) => PngDecoder::new(input)?.apng().into_frames(),
+			_ => {
+				return Err(ImageError::Unsupported(
+					UnsupportedError::from_format_and_kind(
+						ImageFormatHint::Unknown,
+						UnsupportedErrorKind::Format(ImageFormatHint::Unknown),
+					),
+				)
+				.into())
+			}
+		})
+	}
+
 	/**
 	 * Split animation into frames.
 	 *
@@ -372,11 +1355,15 @@ where
 		for i in 0..frames.len() {
 			let path = FileUtil::get_path_with_extension(
 				self.settings.split.dir.join(format!("frame_{i}",)),
-				&self.settings.save.file.format,
+				&self.settings.split.format,
 			);
 			debug!("Saving to {:?}\r", path);
 			io::stdout().flush()?;
-			self.save_output((frames.get(i).cloned(), None), File::create(path)?)?;
+			self.save_output(
+				(frames.get(i).cloned(), None),
+				&self.settings.split.format,
+				File::create(path)?,
+			)?;
 		}
 		debug!("\n");
 		Ok(())
@@ -386,68 +1373,178 @@ where
 	 * Save the application output.
 	 *
 	 * @param   app_output
+	 * @param   format
 	 * @param   output
-	 * @return  Result
+	 * @return  OutputInfo (Result)
 	 */
 	fn save_output<Output: Write + Seek>(
 		&self,
 		app_output: AppOutput,
+		format: &FileFormat,
 		mut output: Output,
-	) -> AppResult<()> {
+	) -> AppResult<OutputInfo> {
 		let (image, frames) = app_output;
-		match self.settings.save.file.format {
+		let alpha = self.settings.save.alpha;
+		let image = match alpha {
+			AlphaChannel::Remove(background) => image.map(|mut image| {
+				debug!("Removing the alpha channel... ({:?})", background);
+				image.fill_background(background);
+				image
+			}),
+			AlphaChannel::Force
+				if image.is_some()
+					&& !format.has_alpha()
+					&& format != &FileFormat::WebP =>
+			{
+				warn!(
+					"{} does not support transparency, saving without alpha channel.",
+					format.as_extension().to_uppercase()
+				);
+				image
+			}
+			_ => image,
+		};
+		let mut info = match (&image, &frames) {
+			(_, Some((images, _))) if format.is_animation() => {
+				OutputInfo::from_images(images)
+			}
+			(Some(image), _) => OutputInfo::from_images(slice::from_ref(image)),
+			_ => OutputInfo::default(),
+		};
+		match format {
 			FileFormat::Gif => {
 				debug!("{:?}", self.settings.anim);
-				self.save_gif(frames, output)
+				let frames = frames.map(|frames| self.apply_max_dimension(frames));
+				let frames = match self.settings.anim.output_fps {
+					Some(fps) => {
+						frames.map(|frames| Resampler::new(fps).apply(frames))
+					}
+					None => frames,
+				};
+				let frames = if self.settings.anim.boomerang {
+					frames.map(|frames| self.apply_boomerang(frames))
+				} else {
+					frames
+				};
+				let frames = match self.settings.anim.interpolate {
+					Some(fps) => {
+						frames.map(|frames| Interpolator::new(fps).apply(frames))
+					}
+					None => frames,
+				};
+				if let Some((images, _)) = &frames {
+					info = OutputInfo::from_images(images);
+				}
+				match &self.settings.anim.comment {
+					Some(comment) => {
+						let mut data = Vec::new();
+						self.encode_gif(frames, &mut data)?;
+						output.write_all(&GifComment::new(comment).insert(data)?)?;
+						Ok(())
+					}
+					None => self.encode_gif(frames, output),
+				}
 			}
 			FileFormat::Apng => {
 				debug!("{:?}", self.settings.anim);
 				self.save_apng(frames, output)
 			}
-			FileFormat::Png => self.save_image(
-				image,
-				PngEncoder::new_with_quality(
+			FileFormat::Png => {
+				let mut data = Vec::new();
+				let color_type = alpha.get_color_type(
+					if image.as_ref().is_some_and(Image::is_deep) {
+						ExtendedColorType::Rgba16
+					} else {
+						ExtendedColorType::Rgba8
+					},
+				);
+				self.save_image(
+					image,
+					PngEncoder::new_with_quality(
+						&mut data,
+						self.settings.png.compression,
+						self.settings.png.filter,
+					),
+					color_type,
+				)?;
+				let data = match self.settings.png.density {
+					Some(density) => {
+						info!("Setting the pixel density... ({} DPI)", density.dpi);
+						density.embed_png(&data)
+					}
+					None => data,
+				};
+				self.write_with_profile(
+					data,
+					format,
+					self.settings.png.profile,
 					output,
-					self.settings.png.compression,
-					self.settings.png.filter,
-				),
-				ExtendedColorType::Rgba8,
-			),
-			FileFormat::Jpg => self.save_image(
-				image,
-				JpegEncoder::new_with_quality(
-					&mut output,
+				)
+			}
+			FileFormat::Jpg if self.settings.jpg.cmyk => {
+				let mut data = Vec::new();
+				if let Some(density) = self.settings.jpg.density {
+					info!("Setting the pixel density... ({} DPI)", density.dpi);
+				}
+				self.save_image(
+					image,
+					CmykEncoder::new(
+						&mut data,
+						self.settings.jpg.quality,
+						self.settings.jpg.density,
+					),
+					ExtendedColorType::Rgb8,
+				)?;
+				let profile = match self.settings.jpg.profile {
+					Some(EmbedProfile::Srgb) => {
+						warn!("sRGB profile cannot be embedded into a CMYK image.");
+						None
+					}
+					profile => profile,
+				};
+				self.write_with_profile(data, format, profile, output)
+			}
+			FileFormat::Jpg => {
+				let mut data = Vec::new();
+				let mut encoder = JpegEncoder::new_with_quality(
+					&mut data,
 					self.settings.jpg.quality,
-				),
-				ExtendedColorType::Rgb8,
-			),
+				);
+				if let Some(density) = self.settings.jpg.density {
+					info!("Setting the pixel density... ({} DPI)", density.dpi);
+					encoder.set_pixel_density(PixelDensity::dpi(density.dpi));
+				}
+				self.save_image(image, encoder, ExtendedColorType::Rgb8)?;
+				self.write_with_profile(
+					data,
+					format,
+					self.settings.jpg.profile,
+					output,
+				)
+			}
 			FileFormat::WebP => self.save_image(
 				image,
 				WebPEncoder::new_with_quality(
 					&mut output,
 					self.settings.webp.get_quality(),
 				),
-				ExtendedColorType::Rgb8,
+				alpha.get_color_type(ExtendedColorType::Rgb8),
 			),
 			FileFormat::Bmp => self.save_image(
 				image,
 				BmpEncoder::new(&mut output),
-				ExtendedColorType::Rgba8,
+				alpha.get_color_type(ExtendedColorType::Rgba8),
 			),
 			FileFormat::Ico => self.save_image(
 				image,
 				IcoEncoder::new(output),
 				ExtendedColorType::Rgba8,
 			),
-			FileFormat::Tiff => self.save_image(
-				image,
-				TiffEncoder::new(File::create(&self.settings.save.file.path)?),
-				ExtendedColorType::Rgba8,
-			),
+			FileFormat::Tiff => self.save_tiff(image, output),
 			FileFormat::Tga => self.save_image(
 				image,
 				TgaEncoder::new(output),
-				ExtendedColorType::Rgba8,
+				alpha.get_color_type(ExtendedColorType::Rgba8),
 			),
 			FileFormat::Pnm(_) => self.save_image(
 				image,
@@ -470,7 +1567,8 @@ where
 				ExtendedColorType::Rgba32F,
 			),
 			_ => Ok(()),
-		}
+		}?;
+		Ok(info)
 	}
 
 	/**
@@ -508,6 +1606,7 @@ where
 			match color_type {
 				ExtendedColorType::L1 | ExtendedColorType::L8 => ColorType::L8,
 				ExtendedColorType::Rgb8 => ColorType::Rgb8,
+				ExtendedColorType::Rgb16 => ColorType::Rgb16,
 				ExtendedColorType::Rgba16 => ColorType::Rgba16,
 				ExtendedColorType::Rgba32F => ColorType::Rgba32F,
 				_ => ColorType::Rgba8,
@@ -517,13 +1616,159 @@ where
 	}
 
 	/**
-	 * Save frames to a GIF file.
+	 * Write the encoded image with the color profile embedded.
+	 *
+	 * @param  data
+	 * @param  format
+	 * @param  profile (Option)
+	 * @param  output
+	 * @return Result
+	 */
+	fn write_with_profile<Output: Write>(
+		self,
+		data: Vec<u8>,
+		format: &FileFormat,
+		profile: Option<EmbedProfile>,
+		mut output: Output,
+	) -> AppResult<()> {
+		let data = match (profile, format) {
+			(None, _) => data,
+			(Some(EmbedProfile::Srgb), FileFormat::Png) => {
+				info!("Marking the image as sRGB...");
+				ColorProfile::embed_png_srgb(&data)
+			}
+			(Some(profile), _) => {
+				let profile = match profile {
+					EmbedProfile::Srgb => ColorProfile::srgb(),
+					EmbedProfile::Icc(path) => ColorProfile::load(Path::new(path))?,
+				};
+				info!("Embedding the color profile ({} bytes)...", profile.size());
+				if format == &FileFormat::Png {
+					profile.embed_png(&data)
+				} else {
+					profile.embed_jpeg(&data)
+				}
+			}
+		};
+		output.write_all(&data)?;
+		Ok(())
+	}
+
+	/**
+	 * Save the image to a TIFF file.
+	 *
+	 * @param  image (Option)
+	 * @param  output
+	 * @return Result
+	 */
+	fn save_tiff<Output: Write + Seek>(
+		self,
+		image: Option<Image>,
+		output: Output,
+	) -> AppResult<()> {
+		let image = image.ok_or_else(|| {
+			AppError::WsError(String::from("Failed to get the image"))
+		})?;
+		debug!("{:?}", self.settings.tiff);
+		let (width, height) = (image.geometry.width, image.geometry.height);
+		let color_type = self
+			.settings
+			.save
+			.alpha
+			.get_color_type(ExtendedColorType::Rgba8);
+		let data = image.get_data(color_type);
+		let mut encoder = TiffEncoder::new(output)?;
+		let image = (width, height, data.as_slice());
+		let rgb = color_type == ExtendedColorType::Rgb8;
+		match self.settings.tiff.compression {
+			TiffCompression::None if rgb => {
+				self.write_tiff::<_, RGB8, _>(&mut encoder, image, Uncompressed)
+			}
+			TiffCompression::None => {
+				self.write_tiff::<_, RGBA8, _>(&mut encoder, image, Uncompressed)
+			}
+			TiffCompression::Lzw if rgb => {
+				self.write_tiff::<_, RGB8, _>(&mut encoder, image, Lzw)
+			}
+			TiffCompression::Lzw => {
+				self.write_tiff::<_, RGBA8, _>(&mut encoder, image, Lzw)
+			}
+			TiffCompression::Deflate if rgb => self.write_tiff::<_, RGB8, _>(
+				&mut encoder,
+				image,
+				Deflate::default(),
+			),
+			TiffCompression::Deflate => self.write_tiff::<_, RGBA8, _>(
+				&mut encoder,
+				image,
+				Deflate::default(),
+			),
+		}
+	}
+
+	/**
+	 * Write the image data with the given compression to the TIFF encoder.
+	 *
+	 * @param  encoder
+	 * @param  image
+	 * @param  compression
+	 * @return Result
+	 */
+	fn write_tiff<
+		Output: Write + Seek,
+		Color: TiffColorType<Inner = u8>,
+		C: Compression,
+	>(
+		self,
+		encoder: &mut TiffEncoder<Output>,
+		(width, height, data): (u32, u32, &[u8]),
+		compression: C,
+	) -> AppResult<()> {
+		let mut image = encoder.new_image_with_compression::<Color, _>(
+			width,
+			height,
+			compression,
+		)?;
+		if let Some(density) = self.settings.tiff.density {
+			info!("Setting the pixel density... ({} DPI)", density.dpi);
+			image.resolution(
+				ResolutionUnit::Inch,
+				Rational {
+					n: density.dpi.into(),
+					d: 1,
+				},
+			);
+		}
+		image.write_data(data)?;
+		Ok(())
+	}
+
+	/**
+	 * Save frames to a GIF file with or without lossy compression.
+	 *
+	 * @param   frames (Option)
+	 * @param   output
+	 * @return  Result
+	 */
+	fn encode_gif<Output: Write>(
+		self,
+		frames: Option<Frames>,
+		output: Output,
+	) -> AppResult<()> {
+		if self.settings.anim.lossy > 0 {
+			self.save_lossy_gif(frames, output)
+		} else {
+			self.save_gif(frames, output)
+		}
+	}
+
+	/**
+	 * Save frames to a GIF file using the selected encoder.
 	 *
 	 * @param   frames (Option)
 	 * @param   output
 	 * @return  Result
 	 */
-	#[cfg(feature = "ski")]
 	fn save_gif<Output: Write>(
 		self,
 		frames: Option<Frames>,
@@ -539,43 +1784,53 @@ where
 			})?
 			.geometry;
 		let config = EncoderConfig::new(fps, geometry, output, &self.settings.anim);
-		if self.settings.anim.gifski.0 {
-			GifskiEncoder::new(config)?.save(images, self.settings.input_state)?;
-		} else {
-			GifEncoder::new(config)?.save(images, self.settings.input_state)?;
+		if self.settings.anim.gifski.0 && self.settings.anim.palette_from.is_some() {
+			warn!("The gifski encoder does not support custom palettes, using the image encoder.");
+		} else if self.settings.anim.gifski.0 {
+			#[cfg(feature = "ski")]
+			return GifskiEncoder::new(config)?
+				.save(images, self.settings.input_state);
+			#[cfg(not(feature = "ski"))]
+			warn!("The gifski encoder is not available, using the image encoder.");
 		}
-		Ok(())
+		GifEncoder::new(config)?.save(images, self.settings.input_state)
 	}
 
 	/**
-	 * Save frames to a GIF file.
+	 * Save frames to a GIF file with lossy compression.
 	 *
 	 * @param   frames (Option)
 	 * @param   output
 	 * @return  Result
 	 */
-	#[cfg(not(feature = "ski"))]
-	fn save_gif<Output: Write>(
+	fn save_lossy_gif<Output: Write>(
 		self,
 		frames: Option<Frames>,
-		output: Output,
+		mut output: Output,
 	) -> AppResult<()> {
 		let (images, fps) = frames.ok_or_else(|| {
 			AppError::FrameError(String::from("Failed to get the frames"))
 		})?;
-		let geometry = images
-			.first()
-			.ok_or_else(|| {
-				AppError::FrameError(String::from("No frames found to save"))
-			})?
-			.geometry;
-		GifEncoder::new(EncoderConfig::new(
-			fps,
-			geometry,
-			output,
-			&self.settings.anim,
-		))?
-		.save(images, self.settings.input_state)?;
+		let filter = LossyFilter::new(self.settings.anim.lossy);
+		let mut data = Vec::new();
+		self.save_gif(Some((images.clone(), fps)), &mut data)?;
+		let size = data.len();
+		let data = match filter.compress(data) {
+			Some(data) => data,
+			None => {
+				warn!("gifsicle is not available, using the built-in lossy filter.");
+				let mut data = Vec::new();
+				self.save_gif(Some((filter.apply(images), fps)), &mut data)?;
+				data
+			}
+		};
+		info!(
+			"Lossy compression: {} -> {} ({:.1}% smaller)",
+			ByteSize(size as u64),
+			ByteSize(data.len() as u64),
+			(1. - data.len() as f64 / size.max(1) as f64) * 100.
+		);
+		output.write_all(&data)?;
 		Ok(())
 	}
 
@@ -591,11 +1846,9 @@ where
 		frames: Option<Frames>,
 		output: Output,
 	) -> AppResult<()> {
-		let images = frames
-			.ok_or_else(|| {
-				AppError::FrameError(String::from("Failed to get the frames"))
-			})?
-			.0;
+		let (images, fps) = frames.ok_or_else(|| {
+			AppError::FrameError(String::from("Failed to get the frames"))
+		})?;
 		let geometry = images
 			.first()
 			.ok_or_else(|| {
@@ -604,6 +1857,7 @@ where
 			.geometry;
 		ApngEncoder::new(
 			images.len().try_into().unwrap_or_default(),
+			fps,
 			geometry,
 			output,
 			&self.settings.anim,
@@ -617,6 +1871,7 @@ where
 mod tests {
 	use super::*;
 	use crate::args::matches::ArgMatches;
+	use crate::image::geometry::Geometry;
 	use crate::window::test::TestWindow;
 	use clap::ArgMatches as Args;
 	use std::env;
@@ -644,17 +1899,63 @@ mod tests {
 			settings.save.file.path = path.clone();
 			settings.analyze.file = path.clone();
 			let app = App::new(Some(window), &settings);
-			app.save_output((app.get_image().ok(), None), File::create(&path)?)?;
+			app.save_output(
+				(app.get_image().ok(), None),
+				&format,
+				File::create(&path)?,
+			)?;
 			app.edit_image(&path)?;
 			app.analyze_image()?;
 			fs::remove_file(path)?;
 		}
 		settings.save.file.path = PathBuf::from("test");
+		settings.record.flag.preview = true;
 		App::new(Some(window), &settings).start()?;
 		fs::remove_file(settings.save.file.path)?;
 		Ok(())
 	}
 	#[test]
+	fn test_alpha_channel() -> AppResult<()> {
+		let args = Args::default();
+		let matches = ArgMatches::new(&args);
+		let mut settings = AppSettings::new(&matches);
+		let image = Image::new(
+			vec![Rgba([255, 0, 0, 255]), Rgba([0, 0, 255, 0])],
+			true,
+			Geometry::new(0, 0, 2, 1),
+		);
+		let save = |settings: &AppSettings<'_>, format: FileFormat| {
+			let mut data = Vec::new();
+			App::<TestWindow>::new(None, settings).save_output(
+				(Some(image.clone()), None),
+				&format,
+				io::Cursor::new(&mut data),
+			)?;
+			Ok::<_, AppError>(data)
+		};
+		let load = |data: Vec<u8>| image::load_from_memory(&data);
+		assert_eq!(
+			ColorType::Rgba8,
+			load(save(&settings, FileFormat::Png)?)?.color()
+		);
+		assert_eq!(
+			ColorType::Rgb8,
+			load(save(&settings, FileFormat::WebP)?)?.color()
+		);
+		settings.save.alpha = AlphaChannel::Remove(Rgba([0, 255, 0, 255]));
+		for format in [FileFormat::Png, FileFormat::Bmp, FileFormat::Tiff] {
+			let output = load(save(&settings, format)?)?;
+			assert_eq!(ColorType::Rgb8, output.color());
+			assert_eq!([0, 255, 0], output.to_rgb8().get_pixel(1, 0).0);
+		}
+		settings.save.alpha = AlphaChannel::Force;
+		/* Check the alpha flag of the extended WebP header */
+		let data = save(&settings, FileFormat::WebP)?;
+		assert_eq!(b"VP8X", &data[12..16]);
+		assert_eq!(0x10, data[20] & 0x10);
+		Ok(())
+	}
+	#[test]
 	fn test_app_anim() -> AppResult<()> {
 		let args = Args::default();
 		let matches = ArgMatches::new(&args);
@@ -665,7 +1966,19 @@ mod tests {
 		let window = TestWindow::default();
 		let app = App::new(Some(window), &settings);
 		let images = app.get_frames()?.0;
-		app.save_gif(Some((images.clone(), 10)), File::create("test.gif")?)?;
+		let output = (None, Some((images.clone(), 10.)));
+		assert_eq!(
+			images.len().min(2),
+			App::<TestWindow>::get_preview_images(&output).len()
+		);
+		let boomerang = app
+			.apply_boomerang((
+				images.iter().take(1).cycle().take(4).cloned().collect(),
+				10.,
+			))
+			.0;
+		assert_eq!(images.len().min(1) * 6, boomerang.len());
+		app.save_gif(Some((images.clone(), 10.)), File::create("test.gif")?)?;
 		app.edit_anim(File::open("test.gif")?, Path::new("test.gif"))?;
 		let dir = env::current_dir()?;
 		settings.split.dir = PathBuf::from(dir.to_str().unwrap_or_default());
@@ -674,7 +1987,7 @@ mod tests {
 		let app = App::new(Some(window), &settings);
 		app.split_anim(File::open("test.gif")?)?;
 		fs::remove_file("test.gif")?;
-		app.save_apng(Some((images.clone(), 20)), File::create("test.apng")?)?;
+		app.save_apng(Some((images.clone(), 20.)), File::create("test.apng")?)?;
 		fs::remove_file("test.apng")?;
 		for i in 0..images.len() {
 			let path = PathBuf::from(format!("frame_{i}.png"));
@@ -684,4 +1997,19 @@ mod tests {
 		}
 		Ok(())
 	}
+	#[test]
+	fn test_app_error() {
+		assert_eq!(
+			r#"{"error": "Frame error: `\"x\"\n`", "stage": "capture"}"#,
+			AppError::FrameError(String::from("\"x\"\n")).to_json("capture")
+		);
+		let error = AppError::EditError(String::from("x"))
+			.with_stage(AppStage::Encode)
+			.with_stage(AppStage::Save);
+		assert_eq!("Edit error: `x`", error.to_string());
+		assert_eq!(
+			r#"{"error": "Edit error: `x`", "stage": "encode"}"#,
+			error.to_json("record")
+		);
+	}
 }
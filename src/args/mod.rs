@@ -2,6 +2,7 @@ pub mod matches;
 pub mod parser;
 use crate::anim::{AnimFormat, AnimMode};
 use crate::file::format::FileFormat;
+use crate::record::settings::RecordWindow;
 use clap::{App, AppSettings, Arg, ArgMatches, Shell, SubCommand};
 use std::io::Write;
 use std::str::FromStr;
@@ -31,10 +32,14 @@ pub struct Args<'a, 'b> {
 	record: App<'a, 'b>,
 	split: App<'a, 'b>,
 	make: App<'a, 'b>,
+	encode: App<'a, 'b>,
 	capture: App<'a, 'b>,
 	edit: App<'a, 'b>,
+	montage: App<'a, 'b>,
+	spritesheet: App<'a, 'b>,
 	analyze: App<'a, 'b>,
 	view: App<'a, 'b>,
+	pick: App<'a, 'b>,
 	misc: App<'a, 'b>,
 }
 
@@ -77,10 +82,14 @@ where
 			record: Self::get_record_args(false),
 			split: Self::get_split_args(),
 			make: Self::get_anim_args(AnimMode::Make),
+			encode: Self::get_encode_args(),
 			capture: Self::get_record_args(true),
 			edit: Self::get_edit_args(),
+			montage: Self::get_montage_args(),
+			spritesheet: Self::get_spritesheet_args(),
 			analyze: Self::get_analyze_args(),
 			view: Self::get_view_args(),
+			pick: Self::get_pick_args(),
 			misc: Self::get_misc_args(),
 		}
 	}
@@ -126,6 +135,12 @@ where
 					.help("Do not show output")
 					.display_order(1001),
 			)
+			.arg(
+				Arg::with_name("json-errors")
+					.long("json-errors")
+					.help("Print the errors in JSON format")
+					.display_order(1002),
+			)
 			.arg(
 				Arg::with_name("config")
 					.short("c")
@@ -142,6 +157,13 @@ where
 					.help("Set the main color")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("quality")
+					.long("quality")
+					.value_name("QUALITY")
+					.help("Set the quality of the output format (1-100)")
+					.takes_value(true),
+			)
 			.subcommand(
 				args.record
 					.subcommand(
@@ -156,6 +178,18 @@ where
 			)
 			.subcommand(Self::get_image_args(args.split, false))
 			.subcommand(args.make.subcommand(Self::get_save_args(FileFormat::Gif)))
+			.subcommand(
+				args.encode
+					.subcommand(
+						Self::get_anim_args(AnimMode::Encode(AnimFormat::Gif))
+							.subcommand(Self::get_save_args(FileFormat::Gif)),
+					)
+					.subcommand(
+						Self::get_anim_args(AnimMode::Encode(AnimFormat::Apng))
+							.subcommand(Self::get_save_args(FileFormat::Apng)),
+					)
+					.subcommand(Self::get_save_args(FileFormat::Gif)),
+			)
 			.subcommand(Self::get_image_args(args.capture, true))
 			.subcommand(Self::get_image_args(
 				args.edit
@@ -169,11 +203,14 @@ where
 					),
 				true,
 			))
+			.subcommand(Self::get_image_args(args.montage, true))
+			.subcommand(Self::get_image_args(args.spritesheet, true))
 			.subcommand(
 				args.analyze
 					.subcommand(Self::get_save_args(FileFormat::Txt)),
 			)
 			.subcommand(args.view)
+			.subcommand(args.pick)
 			.subcommand(args.misc)
 	}
 
@@ -218,11 +255,20 @@ where
 						"Record the focused window"
 					}),
 			)
-			.arg(Arg::with_name("select").long("select").help(if capture {
-				"Select the window to capture"
-			} else {
-				"Select the window to record"
-			}))
+			.arg(
+				Arg::with_name("select")
+					.long("select")
+					.value_name("RATIO")
+					.help(if capture {
+						"Select the window to capture (with optional aspect ratio)"
+					} else {
+						"Select the window to record (with optional aspect ratio)"
+					})
+					.min_values(0)
+					.max_values(1)
+					.require_equals(true)
+					.takes_value(true),
+			)
 			.arg(Arg::with_name("parent").long("parent").help(if capture {
 				"Capture the parent of the window"
 			} else {
@@ -237,6 +283,42 @@ where
 						"Record with the alpha channel"
 					}),
 			)
+			.arg(
+				Arg::with_name("opaque")
+					.long("opaque")
+					.help("Force the alpha channel to be fully opaque"),
+			)
+			.arg(
+				Arg::with_name("scale-monitors")
+					.long("scale-monitors")
+					.help("Scale the monitors to the same pixel density on root"),
+			)
+			.arg(
+				Arg::with_name("with-shadow")
+					.long("with-shadow")
+					.help("Include the window frame and shadow from the compositor")
+					.hidden(!capture),
+			)
+			.arg(
+				Arg::with_name("all-matching")
+					.long("all-matching")
+					.help("Capture all windows that match the class into separate files")
+					.requires("window-class")
+					.hidden(!capture),
+			)
+			.arg(
+				Arg::with_name("no-sanity-check")
+					.long("no-sanity-check")
+					.help("Do not warn about the captured image being a single color")
+					.hidden(!capture),
+			)
+			.arg(
+				Arg::with_name("stitch")
+					.long("stitch")
+					.help("Stitch the captures of a scrolled window into one image")
+					.conflicts_with_all(&["command", "all-matching"])
+					.hidden(!capture),
+			)
 			.arg(
 				Arg::with_name("no-keys")
 					.long("no-keys")
@@ -266,6 +348,51 @@ where
 					.long("mouse")
 					.help("Select the window with mouse click"),
 			)
+			.arg(Arg::with_name("preview").long("preview").help(if capture {
+				"Preview the image before saving"
+			} else {
+				"Preview the first and last frames before saving"
+			}))
+			.arg(
+				Arg::with_name("corner-markers")
+					.long("corner-markers")
+					.help("Draw markers on the corners of the border"),
+			)
+			.arg(
+				Arg::with_name("dim")
+					.long("dim")
+					.value_name("ALPHA")
+					.help("Dim the area around the selection (0-255)")
+					.min_values(0)
+					.max_values(1)
+					.require_equals(true)
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("loupe").long("loupe").help(
+				"Show the magnified pixels around the pointer while selecting",
+			))
+			.arg(
+				Arg::with_name("snap")
+					.long("snap")
+					.value_name("THRESHOLD")
+					.help("Snap the selection to the nearby window edges")
+					.min_values(0)
+					.max_values(1)
+					.require_equals(true)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("on-keypress")
+					.long("on-keypress")
+					.help("Capture a new frame only when a key is pressed")
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("show-keys")
+					.long("show-keys")
+					.help("Show the pressed keys on the recorded frames")
+					.hidden(capture),
+			)
 			.arg(
 				Arg::with_name("border")
 					.short("b")
@@ -275,6 +402,15 @@ where
 					.help("Set the border width")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("border-style")
+					.long("border-style")
+					.value_name("STYLE")
+					.possible_values(&["solid", "dashed"])
+					.default_value("solid")
+					.help("Set the border style")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("padding")
 					.short("p")
@@ -300,6 +436,20 @@ where
 					.empty_values(true)
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("save-region")
+					.long("save-region")
+					.value_name("NAME")
+					.help("Save the selected area as a named region")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("region-name")
+					.long("region-name")
+					.value_name("NAME")
+					.help("Use a saved region instead of selecting (or list)")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("duration")
 					.short("d")
@@ -310,6 +460,50 @@ where
 					.takes_value(true)
 					.hidden(capture),
 			)
+			.arg(
+				Arg::with_name("replay-buffer")
+					.long("replay-buffer")
+					.value_name("S")
+					.help("Keep only the last seconds of the recording")
+					.takes_value(true)
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("max-frames")
+					.long("max-frames")
+					.value_name("N")
+					.default_value("1000")
+					.help("Set the maximum number of frames to record (0 for unlimited)")
+					.takes_value(true)
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("min-free")
+					.long("min-free")
+					.value_name("SIZE")
+					.help(
+						"Stop recording when the free disk space is below the size",
+					)
+					.takes_value(true)
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("cursor-trail")
+					.long("cursor-trail")
+					.value_name("N")
+					.help("Draw a trail of the last pointer positions")
+					.takes_value(true)
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("trail-fade")
+					.long("trail-fade")
+					.value_name("F")
+					.default_value("1.0")
+					.help("Set the fading of the cursor trail (0.0-1.0)")
+					.takes_value(true)
+					.hidden(capture),
+			)
 			.arg(
 				Arg::with_name("countdown")
 					.short("c")
@@ -342,6 +536,14 @@ where
 					.help("Set the refresh interval for window selection")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("wait-for-display")
+					.long("wait-for-display")
+					.value_name("S")
+					.default_value("0")
+					.help("Set the timeout for waiting the display to open")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("font")
 					.long("font")
@@ -350,6 +552,24 @@ where
 					.allow_hyphen_values(true)
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("font-size")
+					.long("font-size")
+					.value_name("PX")
+					.help("Set the size of the font in pixels")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("text-pos")
+					.long("text-pos")
+					.value_name("POS")
+					.possible_values(&[
+						"tl", "top", "tr", "left", "center", "right", "bl",
+						"bottom", "br",
+					])
+					.help("Set the position of the selection and countdown text")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("monitor")
 					.long("monitor")
@@ -361,6 +581,97 @@ where
 					})
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("window-id")
+					.long("window-id")
+					.value_name("ID")
+					.help(if capture {
+						"Set the ID of the window to capture"
+					} else {
+						"Set the ID of the window to record"
+					})
+					.validator(RecordWindow::validate_id)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("window-class")
+					.long("window-class")
+					.value_name("CLASS")
+					.help(if capture {
+						"Set the class name of the window to capture"
+					} else {
+						"Set the class name of the window to record"
+					})
+					.conflicts_with("window-id")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("relative-to")
+					.long("relative-to")
+					.value_name("WINDOW")
+					.help("Set the window that the area position is relative to (focus, root or ID)")
+					.requires("size")
+					.conflicts_with_all(&["root", "focus", "monitor", "window-id", "window-class"])
+					.validator(RecordWindow::validate_relative)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("synthetic")
+					.long("synthetic")
+					.value_name("WxH")
+					.help("Use a generated test source instead of the display")
+					.conflicts_with_all(&["command", "window-id", "window-class"])
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("exclude-window-id")
+					.long("exclude-window-id")
+					.value_name("ID")
+					.help("Set the ID of a window to exclude")
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("exclude-mode")
+					.long("exclude-mode")
+					.value_name("MODE")
+					.possible_values(&["black", "blur"])
+					.default_value("black")
+					.help("Set the treatment of the excluded windows")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("canvas")
+					.long("canvas")
+					.value_name("WxH")
+					.help("Center the output on a canvas with the given size")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("background")
+					.long("background")
+					.value_name("HEX")
+					.default_value("000000")
+					.help("Set the background color of the canvas")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("raw-dump")
+					.long("raw-dump")
+					.value_name("FILE")
+					.help("Dump the raw frames to a file for encoding later")
+					.takes_value(true)
+					.hidden(capture),
+			)
+			.arg(
+				Arg::with_name("histogram")
+					.long("histogram")
+					.value_name("FILE")
+					.help("Save the histogram of the capture (or print as CSV)")
+					.takes_value(true)
+					.hidden(!capture),
+			)
 	}
 
 	/**
@@ -385,9 +696,21 @@ where
 					.value_name("FPS")
 					.default_value("20")
 					.help("Set the FPS")
-					.hidden(mode.is_edit())
+					.hidden(
+						mode.is_edit()
+							|| mode == AnimMode::Encode(AnimFormat::Gif)
+							|| mode == AnimMode::Encode(AnimFormat::Apng),
+					)
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("delay-cs")
+					.long("delay-cs")
+					.value_name("N")
+					.help("Set the frame delay in centiseconds (overrides FPS)")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
 			.arg(
 				Arg::with_name("quality")
 					.short("q")
@@ -398,6 +721,61 @@ where
 					.takes_value(true)
 					.hidden(mode.has_format(AnimFormat::Apng)),
 			)
+			.arg(
+				Arg::with_name("lossy")
+					.long("lossy")
+					.value_name("LEVEL")
+					.default_value("0")
+					.help("Set the lossy compression level (0-200)")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("colors")
+					.long("colors")
+					.value_name("NUM")
+					.help("Set the number of colors in the palette (2-256 or auto)")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("palette-from")
+					.long("palette-from")
+					.value_name("FILE")
+					.help("Use the palette extracted from the given image")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("dither")
+					.long("dither")
+					.help("Dither the frames while mapping them to the palette")
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("interpolate")
+					.long("interpolate")
+					.value_name("FPS")
+					.help("Interpolate the frames to reach the target FPS")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("output-fps")
+					.long("output-fps")
+					.value_name("FPS")
+					.help("Downsample the frames to the target FPS")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("max-dimension")
+					.long("max-dimension")
+					.value_name("PX")
+					.help("Downscale the frames to fit the maximum dimension")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
 			.arg(
 				Arg::with_name("repeat")
 					.short("r")
@@ -407,6 +785,11 @@ where
 					.help("Set the number of repetitions")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("loop-once")
+					.long("loop-once")
+					.help("Play the animation once without looping"),
+			)
 			.arg(
 				Arg::with_name("gifski")
 					.long("gifski")
@@ -425,6 +808,48 @@ where
 							|| cfg!(not(feature = "ski")),
 					),
 			)
+			.arg(
+				Arg::with_name("gif-encoder")
+					.long("gif-encoder")
+					.value_name("ENCODER")
+					.possible_values(&["ski", "image"])
+					.help("Select the GIF encoder at runtime")
+					.takes_value(true)
+					.hidden(
+						mode.has_format(AnimFormat::Apng)
+							|| cfg!(not(feature = "ski")),
+					),
+			)
+			.arg(
+				Arg::with_name("gif-comment")
+					.long("gif-comment")
+					.value_name("TEXT")
+					.help("Write a comment into the GIF file")
+					.takes_value(true)
+					.hidden(mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("boomerang")
+					.long("boomerang")
+					.help("Play the recording forwards and then backwards")
+					.hidden(!mode.is_record() || mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("stream")
+					.long("stream")
+					.help("Write the frames to the file while recording")
+					.hidden(!mode.is_record() || !mode.has_format(AnimFormat::Apng)),
+			)
+			.arg(
+				Arg::with_name("estimate")
+					.long("estimate")
+					.help("Estimate the output size before encoding"),
+			)
+			.arg(
+				Arg::with_name("yes")
+					.long("yes")
+					.help("Skip the confirmation of the estimated size"),
+			)
 			.arg(
 				Arg::with_name("speed")
 					.short("s")
@@ -432,16 +857,32 @@ where
 					.value_name("SPEED")
 					.default_value("1.0")
 					.help("Set the animation speed")
-					.hidden(!mode.is_edit())
+					.hidden(mode == AnimMode::Make)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("speed-curve")
+					.long("speed-curve")
+					.value_name("CURVE")
+					.possible_values(&[
+						"linear",
+						"ease-in",
+						"ease-out",
+						"ease-in-out",
+					])
+					.default_value("linear")
+					.help("Set the curve of the speed along the animation")
+					.hidden(mode.has_format(AnimFormat::Apng))
 					.takes_value(true),
 			)
 			.arg(
 				Arg::with_name("cut-beginning")
 					.long("cut-beginning")
+					.visible_alias("cut-start")
 					.value_name("S")
 					.default_value("0.0")
 					.help("Cut the beginning of the animation")
-					.hidden(!mode.is_edit())
+					.hidden(!mode.is_edit() && !mode.is_record())
 					.takes_value(true),
 			)
 			.arg(
@@ -450,13 +891,25 @@ where
 					.value_name("S")
 					.default_value("0.0")
 					.help("Cut the end of the animation")
+					.hidden(!mode.is_edit() && !mode.is_record())
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("deletterbox")
+					.long("deletterbox")
+					.visible_alias("letterbox-detect")
+					.value_name("THRESHOLD")
+					.help("Crop the dark bars around the frames (0-255)")
+					.min_values(0)
+					.max_values(1)
+					.require_equals(true)
 					.hidden(!mode.is_edit())
 					.takes_value(true),
 			)
 			.arg(
 				Arg::with_name("frames")
 					.value_name("FRAMES")
-					.help("Set the animation frames")
+					.help("Set the animation frames or a video file")
 					.min_values(1)
 					.hidden(mode != AnimMode::Make)
 					.default_value_if("dir", None, "-")
@@ -481,6 +934,22 @@ where
 					.hidden(mode != AnimMode::Make)
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("from")
+					.long("from")
+					.value_name("TIME")
+					.help("Set the start time of the video frames")
+					.hidden(mode != AnimMode::Make)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("to")
+					.long("to")
+					.value_name("TIME")
+					.help("Set the end time of the video frames")
+					.hidden(mode != AnimMode::Make)
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("format")
 					.long("format")
@@ -513,6 +982,74 @@ where
 					.long("convert")
 					.help("Convert image using the given encoder"),
 			)
+			.arg(
+				Arg::with_name("strip")
+					.long("strip")
+					.visible_alias("strip-metadata")
+					.help("Remove the metadata and color profile from the image"),
+			)
+			.arg(
+				Arg::with_name("no-auto-orient")
+					.long("no-auto-orient")
+					.help("Do not rotate the image using its EXIF orientation"),
+			)
+			.arg(
+				Arg::with_name("watch")
+					.long("watch")
+					.help("Edit the image again whenever the file changes"),
+			)
+			.arg(
+				Arg::with_name("frame")
+					.long("frame")
+					.value_name("N")
+					.help("Extract a single frame from the animation")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("flatten")
+					.long("flatten")
+					.value_name("MODE")
+					.possible_values(&["first", "last", "average", "max"])
+					.conflicts_with("frame")
+					.help("Flatten the animation frames into a single image")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("contact-sheet")
+					.long("contact-sheet")
+					.conflicts_with_all(&["frame", "flatten"])
+					.help("Tile the animation frames into a single image"),
+			)
+			.arg(
+				Arg::with_name("cols")
+					.long("cols")
+					.value_name("N")
+					.default_value("5")
+					.help("Set the number of columns in the contact sheet")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("every")
+					.long("every")
+					.value_name("N")
+					.default_value("1")
+					.help("Use every Nth frame in the contact sheet")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("thumb-size")
+					.long("thumb-size")
+					.value_name("PX")
+					.help(
+						"Downscale the frames in the contact sheet to fit the size",
+					)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("labels")
+					.long("labels")
+					.help("Show the frame numbers on the contact sheet"),
+			)
 			.arg(
 				Arg::with_name("grayscale")
 					.long("grayscale")
@@ -530,11 +1067,45 @@ where
 					.help("Apply padding to crop the image")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("crop-center")
+					.long("crop-center")
+					.value_name("WxH")
+					.help("Crop the image to the given size from its center")
+					.conflicts_with("crop-at")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("crop-at")
+					.long("crop-at")
+					.value_name("X,Y,W,H")
+					.help("Crop the given rectangle of the image")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("ops")
+					.long("ops")
+					.value_name("OPS")
+					.help("Apply a chain of operations in the given order")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("preset")
+					.long("preset")
+					.value_name("NAME")
+					.help("Apply the operations of a preset before the others")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("trim-transparent")
+					.long("trim-transparent")
+					.help("Crop away the transparent borders of the image"),
+			)
 			.arg(
 				Arg::with_name("resize")
 					.long("resize")
-					.value_name("WxH")
-					.help("Resize the image without keeping the aspect ratio")
+					.value_name("SIZE")
+					.help("Resize the image (WxH, N% or L<N> for the long edge)")
 					.takes_value(true),
 			)
 			.arg(
@@ -569,6 +1140,30 @@ where
 					.help("Blur the image")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("blur-region")
+					.long("blur-region")
+					.value_name("X,Y,W,H:SIGMA")
+					.help("Blur the given area of the image (SIGMA defaults to 8)")
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("median")
+					.long("median")
+					.value_name("RADIUS")
+					.default_value("0")
+					.help("Remove the noise with a median filter (1-3)")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("edge-detect")
+					.long("edge-detect")
+					.value_name("METHOD")
+					.help("Detect the edges as grayscale (sobel, canny[:LOW:HIGH])")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("hue")
 					.long("hue")
@@ -596,9 +1191,81 @@ where
 					.allow_hyphen_values(true)
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("noise")
+					.long("noise")
+					.value_name("AMOUNT")
+					.default_value("0")
+					.help("Add noise to the image (0-255)")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("noise-mono")
+					.long("noise-mono")
+					.help("Use monochrome noise"),
+			)
+			.arg(
+				Arg::with_name("seed")
+					.long("seed")
+					.value_name("SEED")
+					.help("Set the seed of the random noise")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("chroma-key")
+					.long("chroma-key")
+					.value_name("HEX:TOLERANCE")
+					.help(
+						"Make the given color transparent (with optional :FEATHER)",
+					)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("replace-color")
+					.long("replace-color")
+					.value_name("FROM:TO:TOLERANCE")
+					.help("Replace the colors within the tolerance of FROM with TO")
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("mask")
+					.long("mask")
+					.value_name("FILE")
+					.help("Use the luminance of the image as the alpha mask")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("badge")
+					.long("badge")
+					.value_name("X,Y")
+					.help("Draw a numbered badge at the given position")
+					.allow_hyphen_values(true)
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("badge-size")
+					.long("badge-size")
+					.value_name("PX")
+					.default_value("24")
+					.help("Set the diameter of the badges")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("badge-color")
+					.long("badge-color")
+					.value_name("HEX")
+					.default_value("ff0000")
+					.help("Set the color of the badges")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("filter")
 					.long("filter")
+					.visible_alias("resize-filter")
 					.value_name("FILTER")
 					.possible_values(&[
 						"nearest",
@@ -611,6 +1278,108 @@ where
 					.help("Set the sampling filter for scaling")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("color-space")
+					.long("color-space")
+					.value_name("SPACE")
+					.possible_values(&["srgb", "linear"])
+					.default_value("srgb")
+					.help("Set the color space for blurring and resizing")
+					.takes_value(true),
+			)
+	}
+
+	/**
+	 * Get the raw dump encode arguments.
+	 *
+	 * @return App
+	 */
+	fn get_encode_args() -> App<'a, 'b> {
+		SubCommand::with_name("encode")
+			.about("Encode the frames from a raw dump")
+			.help_message("Print help information")
+			.arg(
+				Arg::with_name("file")
+					.value_name("FILE")
+					.help("Set the raw dump file")
+					.required(true),
+			)
+	}
+
+	/**
+	 * Get the montage arguments.
+	 *
+	 * @return App
+	 */
+	fn get_montage_args() -> App<'a, 'b> {
+		SubCommand::with_name("montage")
+			.about("Combine images into a grid")
+			.help_message("Print help information")
+			.arg(
+				Arg::with_name("files")
+					.value_name("FILES")
+					.help("Set the input files")
+					.min_values(1)
+					.required(true),
+			)
+			.arg(
+				Arg::with_name("cols")
+					.long("cols")
+					.value_name("N")
+					.default_value("2")
+					.help("Set the number of columns")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("gap")
+					.long("gap")
+					.value_name("PX")
+					.default_value("0")
+					.help("Set the gap between the images")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("background")
+					.long("background")
+					.value_name("HEX")
+					.default_value("00000000")
+					.help("Set the background color")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("cell-fit")
+					.long("cell-fit")
+					.value_name("FIT")
+					.possible_values(&["pad", "crop", "scale"])
+					.default_value("pad")
+					.help("Set the method for fitting images into the cells")
+					.takes_value(true),
+			)
+	}
+
+	/**
+	 * Get the spritesheet arguments.
+	 *
+	 * @return App
+	 */
+	fn get_spritesheet_args() -> App<'a, 'b> {
+		SubCommand::with_name("spritesheet")
+			.about("Tile the animation frames into a spritesheet")
+			.help_message("Print help information")
+			.arg(
+				Arg::with_name("file")
+					.value_name("FILE")
+					.help("Set the animation file")
+					.required(true),
+			)
+			.arg(
+				Arg::with_name("cols")
+					.long("cols")
+					.value_name("N")
+					.default_value("0")
+					.help("Set the number of columns (0 for a single row)")
+					.takes_value(true),
+			)
 	}
 
 	/**
@@ -695,6 +1464,33 @@ where
 			)
 	}
 
+	/**
+	 * Get the color picker arguments.
+	 *
+	 * @return App
+	 */
+	fn get_pick_args() -> App<'a, 'b> {
+		SubCommand::with_name("pick")
+			.help_message("Print help information")
+			.about("Pick a color from the screen")
+			.arg(
+				Arg::with_name("format")
+					.short("f")
+					.long("format")
+					.value_name("FORMAT")
+					.possible_values(&["hex", "rgb", "hsl", "all"])
+					.default_value("all")
+					.help("Set the output format of the color")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("clipboard")
+					.short("c")
+					.long("clipboard")
+					.help("Copy the color to clipboard (via xclip)"),
+			)
+	}
+
 	/**
 	 * Add image related subcommands to the given arguments.
 	 *
@@ -731,6 +1527,8 @@ where
 						.help("Set the filter algorithm")
 						.takes_value(true),
 				)
+				.args(&Self::get_profile_args())
+				.args(&Self::get_density_args())
 				.subcommand(
 					Self::get_save_args(FileFormat::Png).settings(&save_settings),
 				),
@@ -748,6 +1546,13 @@ where
 						.help("Set the image quality (1-100)")
 						.takes_value(true),
 				)
+				.args(&Self::get_profile_args())
+				.args(&Self::get_density_args())
+				.arg(
+					Arg::with_name("cmyk")
+						.long("cmyk")
+						.help("Encode the image in CMYK for printing"),
+				)
 				.subcommand(
 					Self::get_save_args(FileFormat::Jpg).settings(&save_settings),
 				),
@@ -795,6 +1600,17 @@ where
 			SubCommand::with_name("tiff")
 				.about("Use the TIFF encoder")
 				.help_message("Print help information")
+				.arg(
+					Arg::with_name("compression")
+						.short("c")
+						.long("compression")
+						.value_name("COMPRESSION")
+						.possible_values(&["none", "lzw", "deflate"])
+						.default_value("none")
+						.help("Set the compression method")
+						.takes_value(true),
+				)
+				.args(&Self::get_density_args())
 				.subcommand(
 					Self::get_save_args(FileFormat::Tiff).settings(&save_settings),
 				),
@@ -879,7 +1695,7 @@ where
 							.to_str()
 							.unwrap_or_default(),
 					)
-					.help("Set the output file"),
+					.help("Set the output file or directory ({n} is the next free number)"),
 			)
 			.arg(
 				Arg::with_name("with-extension")
@@ -902,6 +1718,96 @@ where
 					.help("Add formatted date/time to the file name")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("output-dir")
+					.long("output-dir")
+					.value_name("DIR")
+					.help("Set the output directory (with dated file names)")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("mkdir").long("mkdir").help(
+					"Create the directory of the output file if it is missing",
+				),
+			)
+			.arg(
+				Arg::with_name("verify")
+					.long("verify")
+					.help("Decode the output file after saving to verify it"),
+			)
+			.arg(
+				Arg::with_name("open")
+					.long("open")
+					.value_name("CMD")
+					.help("Open the output file after saving [default: xdg-open]")
+					.min_values(0)
+					.max_values(1)
+					.require_equals(true),
+			)
+			.arg(
+				Arg::with_name("post")
+					.long("post")
+					.value_name("CMD")
+					.help("Run a command on the output file after saving ({} is the path)")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("no-alpha")
+					.long("no-alpha")
+					.help("Save the image without the alpha channel"),
+			)
+			.arg(
+				Arg::with_name("force-alpha")
+					.long("force-alpha")
+					.conflicts_with("no-alpha")
+					.help("Save the image with the alpha channel if supported"),
+			)
+			.arg(
+				Arg::with_name("background")
+					.long("background")
+					.value_name("HEX")
+					.requires("no-alpha")
+					.help("Set the background color for removing the alpha channel [default: ffffff]")
+					.takes_value(true),
+			)
+	}
+
+	/**
+	 * Get the arguments for embedding a color profile.
+	 *
+	 * @return Vector of Arg
+	 */
+	fn get_profile_args() -> Vec<Arg<'a, 'b>> {
+		vec![
+			Arg::with_name("srgb")
+				.long("srgb")
+				.help("Embed the sRGB color profile"),
+			Arg::with_name("icc")
+				.long("icc")
+				.value_name("FILE")
+				.conflicts_with("srgb")
+				.help("Embed the ICC profile from the given file")
+				.takes_value(true),
+		]
+	}
+
+	/**
+	 * Get the pixel density arguments.
+	 *
+	 * @return Vector of Arg
+	 */
+	fn get_density_args() -> Vec<Arg<'a, 'b>> {
+		vec![
+			Arg::with_name("retina")
+				.long("retina")
+				.help("Mark the image as a 2x asset (144 DPI)"),
+			Arg::with_name("density")
+				.long("density")
+				.value_name("DPI")
+				.conflicts_with("retina")
+				.help("Set the pixel density of the image")
+				.takes_value(true),
+		]
 	}
 
 	/**
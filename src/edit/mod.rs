@@ -1,11 +1,32 @@
+pub mod op;
+pub mod orient;
+pub mod preset;
+pub mod profile;
 pub mod settings;
+#[cfg(feature = "svg")]
+pub mod svg;
+pub mod watch;
 
-use crate::edit::settings::ColorSettings;
-use crate::edit::settings::{EditSettings, Flip};
+use crate::app::{AppError, AppResult};
+use crate::edit::op::Op;
+use crate::edit::profile::ColorProfile;
+use crate::edit::settings::{
+	ChromaKey, ColorReplace, EdgeDetect, EditSettings, Flip,
+};
+use crate::image::font;
 use crate::image::geometry::Geometry;
 use crate::image::Image;
+use crate::util::rng::Rng;
 use image::imageops::{self, colorops};
-use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use image::{
+	DynamicImage, GrayImage, ImageBuffer, Luma, Pixel, Rgba, Rgba32FImage, RgbaImage,
+};
+use imageproc::rect::Rect;
+use imageproc::{drawing, edges, filter, gradients};
+use std::array;
+
+/* Maximum alpha value of the pixels to trim */
+const TRIM_ALPHA_THRESHOLD: u8 = 8;
 
 /* Image processor */
 #[derive(Debug)]
@@ -13,6 +34,10 @@ pub struct ImageOps<'a> {
 	pub image: RgbaImage,
 	pub geometry: Geometry,
 	settings: &'a EditSettings,
+	ops: Vec<Op>,
+	rng: Rng,
+	badges: u32,
+	mask: Option<GrayImage>,
 }
 
 impl<'a> ImageOps<'a> {
@@ -20,46 +45,56 @@ impl<'a> ImageOps<'a> {
 	 * Create a new ImageOps object.
 	 *
 	 * @param  settings
-	 * @return ImageOps
+	 * @return ImageOps (Result)
 	 */
-	pub fn new(settings: &'a EditSettings) -> Self {
-		Self {
+	pub fn new(settings: &'a EditSettings) -> AppResult<Self> {
+		Ok(Self {
 			image: ImageBuffer::new(0, 0),
 			geometry: Geometry::default(),
 			settings,
-		}
+			ops: settings.get_ops()?,
+			rng: Rng::new(settings.color.seed),
+			badges: 0,
+			mask: settings.mask.as_ref().and_then(|path| {
+				image::open(path)
+					.map(|mask| mask.to_luma8())
+					.map_err(|e| warn!("Failed to load the mask: {}", e))
+					.ok()
+			}),
+		})
 	}
 
 	/**
 	 * Set the geometry to use while processing.
 	 *
-	 * @param size
+	 * @param  size
+	 * @return ImageOps (Result)
 	 */
-	pub fn init(&mut self, size: (u32, u32)) -> &mut Self {
-		let (mut width, mut height) = if !self.settings.image.resize.is_zero() {
-			(
-				self.settings.image.resize.width,
-				self.settings.image.resize.height,
-			)
-		} else {
-			size
-		};
-		if self.settings.image.ratio > 0.
-			&& (self.settings.image.ratio - 1.).abs() > f32::EPSILON
-		{
-			let (w, h) = (width, height);
-			width = (w as f32 * self.settings.image.ratio) as u32;
-			height = (h as f32 * self.settings.image.ratio) as u32;
+	pub fn init(&mut self, size: (u32, u32)) -> AppResult<&mut Self> {
+		if let (Some(path), None) = (&self.settings.mask, &self.mask) {
+			return Err(AppError::EditError(format!(
+				"Failed to load the mask from {:?}",
+				path
+			)));
 		}
-		if self.settings.image.rotate == 90 || self.settings.image.rotate == 270 {
-			let (w, h) = (width, height);
-			width = h;
-			height = w;
+		let mut geometry = Geometry::new(0, 0, size.0, size.1);
+		for op in &self.ops {
+			if let Op::CropArea(crop_area) = op {
+				if crop_area
+					.get_area(geometry.width, geometry.height)
+					.is_none()
+				{
+					return Err(AppError::EditError(format!(
+						"Crop area {} does not fit in the image ({})",
+						crop_area, geometry
+					)));
+				}
+			}
+			geometry = op.get_geometry(geometry);
 		}
-		self.geometry = Geometry::new(0, 0, width, height)
-			.with_padding(self.settings.image.crop);
+		self.geometry = geometry;
 		debug!("{:?} -> {:?}", size, self.geometry);
-		self
+		Ok(self)
 	}
 
 	/**
@@ -69,16 +104,41 @@ impl<'a> ImageOps<'a> {
 	 */
 	pub fn process(&mut self, image: RgbaImage) -> &mut Self {
 		self.image = image;
-		self.crop().flip().rotate().resize().blur().update_colors();
+		self.badges = 0;
+		for op in self.ops.clone() {
+			self.apply(op);
+		}
 		self
 	}
 
+	/**
+	 * Process the image and get the Image object.
+	 *
+	 * Image is converted directly if there are no operations to apply.
+	 *
+	 * @param  image
+	 * @return Image (Result)
+	 */
+	pub fn edit(&mut self, image: RgbaImage) -> AppResult<Image> {
+		if self.ops.is_empty() {
+			debug!("No operations to apply, skipping the processing.");
+			let (width, height) = image.dimensions();
+			return Ok(Image::new(
+				image.pixels().copied().collect(),
+				true,
+				Geometry::new(0, 0, width, height),
+			));
+		}
+		Ok(self.init(image.dimensions())?.process(image).get_image())
+	}
+
 	/**
 	 * Get Image object from the processed buffer.
 	 *
 	 * @return Image
 	 */
 	pub fn get_image(&self) -> Image {
+		let (width, height) = self.image.dimensions();
 		Image::new(
 			self.image
 				.clone()
@@ -87,128 +147,491 @@ impl<'a> ImageOps<'a> {
 				.map(|rgba| Rgba::from([rgba[0], rgba[1], rgba[2], rgba[3]]))
 				.collect(),
 			true,
-			self.geometry,
+			Geometry {
+				width,
+				height,
+				..self.geometry
+			},
 		)
 	}
 
-	/* Resize the image */
-	fn resize(&mut self) -> &mut Self {
-		if !self.settings.image.resize.is_zero()
-			|| (self.settings.image.ratio > 0.
-				&& (self.settings.image.ratio - 1.).abs() > f32::EPSILON)
-		{
-			info!(
-				"Resizing image... ({}x{})",
-				self.geometry.width, self.geometry.height
-			);
-			self.image = imageops::resize(
-				&self.image,
-				self.geometry.width,
-				self.geometry.height,
-				self.settings.image.filter,
-			);
-		}
-		self
-	}
-
-	/* Crop the image */
-	fn crop(&mut self) -> &mut Self {
-		if !self.settings.image.crop.is_zero() {
-			info!(
-				"Cropping the image... ({}x{})",
-				self.geometry.width, self.geometry.height
-			);
-			self.image = imageops::crop(
-				&mut self.image,
-				self.geometry.x.try_into().unwrap_or_default(),
-				self.geometry.y.try_into().unwrap_or_default(),
-				self.geometry.width,
-				self.geometry.height,
-			)
-			.to_image();
-		}
-		self
-	}
-
-	/* Flip the image */
-	fn flip(&mut self) -> &mut Self {
-		match self.settings.image.flip {
-			Some(Flip::Horizontal) => {
+	/**
+	 * Apply the given operation to the image.
+	 *
+	 * @param op
+	 */
+	fn apply(&mut self, op: Op) {
+		match op {
+			Op::Crop(padding) => {
+				let (width, height) = self.image.dimensions();
+				let area = Geometry::new(0, 0, width, height).with_padding(padding);
+				info!("Cropping the image... ({}x{})", area.width, area.height);
+				self.image = imageops::crop(
+					&mut self.image,
+					area.x.try_into().unwrap_or_default(),
+					area.y.try_into().unwrap_or_default(),
+					area.width,
+					area.height,
+				)
+				.to_image();
+			}
+			Op::CropArea(crop_area) => {
+				let (width, height) = self.image.dimensions();
+				match crop_area.get_area(width, height) {
+					Some(area) => {
+						info!(
+							"Cropping the image... ({}x{})",
+							area.width, area.height
+						);
+						self.image = imageops::crop(
+							&mut self.image,
+							area.x.try_into().unwrap_or_default(),
+							area.y.try_into().unwrap_or_default(),
+							area.width,
+							area.height,
+						)
+						.to_image();
+					}
+					None => {
+						warn!("Crop area {} does not fit in the image.", crop_area)
+					}
+				}
+			}
+			Op::Resize(_) | Op::Ratio(_) => {
+				let (width, height) = self.image.dimensions();
+				let size = op.get_geometry(Geometry::new(0, 0, width, height));
+				info!("Resizing image... ({}x{})", size.width, size.height);
+				self.resize(size);
+			}
+			Op::Flip(Flip::Horizontal) => {
 				info!("Flipping the image horizontally...");
 				imageops::flip_horizontal_in_place(&mut self.image)
 			}
-			Some(Flip::Vertical) => {
+			Op::Flip(Flip::Vertical) => {
 				info!("Flipping the image vertically...");
 				imageops::flip_vertical_in_place(&mut self.image)
 			}
-			_ => {}
+			Op::Rotate(degrees) => {
+				info!("Rotating the image {} degrees...", degrees);
+				self.image = match degrees {
+					90 => imageops::rotate90(&self.image),
+					180 => imageops::rotate180(&self.image),
+					_ => imageops::rotate270(&self.image),
+				};
+			}
+			Op::Blur(sigma) => {
+				info!("Blurring the image... (\u{03C3}={})", sigma);
+				self.image = self.blur(&self.image, sigma);
+			}
+			Op::BlurRegion(region) => {
+				let (width, height) = self.image.dimensions();
+				match region.get_area(width, height) {
+					Some(area) => {
+						info!(
+							"Blurring the region... ({} at {},{}, \u{03C3}={})",
+							area, area.x, area.y, region.sigma
+						);
+						let (x, y) = (
+							u32::try_from(area.x).unwrap_or_default(),
+							u32::try_from(area.y).unwrap_or_default(),
+						);
+						let blurred = self.blur(
+							&imageops::crop_imm(
+								&self.image,
+								x,
+								y,
+								area.width,
+								area.height,
+							)
+							.to_image(),
+							region.sigma,
+						);
+						imageops::replace(
+							&mut self.image,
+							&blurred,
+							i64::from(x),
+							i64::from(y),
+						);
+					}
+					None => warn!("Blur region is outside of the image."),
+				}
+			}
+			Op::Median(radius) => {
+				info!("Applying the median filter... (r={})", radius);
+				self.image = filter::median_filter(&self.image, radius, radius);
+			}
+			Op::Sharpen(sigma) => {
+				info!("Sharpening the image... (\u{03C3}={})", sigma);
+				self.image = imageops::unsharpen(&self.image, sigma, 0);
+			}
+			Op::EdgeDetect(method) => self.detect_edges(method),
+			Op::Grayscale => {
+				info!("Converting the image to grayscale...");
+				self.image =
+					DynamicImage::ImageLuma8(colorops::grayscale(&self.image))
+						.to_rgba8();
+			}
+			Op::Invert => {
+				info!("Inverting the colors...");
+				colorops::invert(&mut self.image);
+			}
+			Op::Brightness(value) => {
+				info!("Adjusting the brightness... ({})", value);
+				self.image = colorops::brighten(&self.image, value);
+			}
+			Op::Hue(value) => {
+				info!("Rotating the hue... ({})", value);
+				self.image = colorops::huerotate(&self.image, value);
+			}
+			Op::Contrast(value) => {
+				info!("Adjusting the contrast... ({})", value);
+				self.image = colorops::contrast(&self.image, value);
+			}
+			Op::Noise(amount, mono) => self.add_noise(amount, mono),
+			Op::ChromaKey(chroma_key) => self.apply_chroma_key(chroma_key),
+			Op::ReplaceColor(replace) => self.replace_color(replace),
+			Op::Mask => self.apply_mask(),
+			Op::Trim => self.trim_transparent(),
+			Op::Badge(position) => self.draw_badge(position),
 		}
-		self
 	}
 
-	/* Rotate the image */
-	fn rotate(&mut self) -> &mut Self {
-		if self.settings.image.rotate == 90 {
-			info!("Rotating the image 90 degrees...");
-			self.image = imageops::rotate90(&self.image);
-		} else if self.settings.image.rotate == 180 {
-			info!("Rotating the image 180 degrees...");
-			self.image = imageops::rotate180(&self.image);
-		} else if self.settings.image.rotate == 270 {
-			info!("Rotating the image 270 degrees...");
-			self.image = imageops::rotate270(&self.image);
+	/**
+	 * Blur the given image (in linear light if enabled).
+	 *
+	 * @param  image
+	 * @param  sigma
+	 * @return RgbaImage
+	 */
+	fn blur(&self, image: &RgbaImage, sigma: f32) -> RgbaImage {
+		if self.settings.image.linear {
+			Self::from_linear(&imageops::blur(&Self::to_linear(image), sigma))
+		} else {
+			imageops::blur(image, sigma)
 		}
-		self
 	}
 
-	/* Blur the image */
-	fn blur(&mut self) -> &mut Self {
-		if self.settings.image.blur > 0. {
-			info!(
-				"Blurring the image... (\u{03C3}={})",
-				self.settings.image.blur
+	/**
+	 * Draw the next numbered badge centered at the given position.
+	 *
+	 * @param position
+	 */
+	fn draw_badge(&mut self, position: (i32, i32)) {
+		self.badges += 1;
+		let (size, color) = (self.settings.badges.size, self.settings.badges.color);
+		info!(
+			"Drawing the badge #{}... ({},{})",
+			self.badges, position.0, position.1
+		);
+		drawing::draw_filled_circle_mut(
+			&mut self.image,
+			position,
+			i32::try_from(size / 2).unwrap_or_default(),
+			color,
+		);
+		let text_color = if color.to_luma()[0] > 128 {
+			Rgba([0, 0, 0, 255])
+		} else {
+			Rgba([255, 255, 255, 255])
+		};
+		let text = self.badges.to_string();
+		let scale = (size / (font::GLYPH_HEIGHT * 2)).max(1);
+		let advance = (font::GLYPH_WIDTH + font::GLYPH_SPACING) * scale;
+		let width = advance * text.len() as u32 - font::GLYPH_SPACING * scale;
+		let left = position.0 - (width / 2) as i32;
+		let top = position.1 - (font::GLYPH_HEIGHT * scale / 2) as i32;
+		for (i, c) in (0..).zip(text.chars()) {
+			for (row, bits) in (0..).zip(font::get_glyph(c)) {
+				for column in 0..font::GLYPH_WIDTH {
+					if (bits >> (font::GLYPH_WIDTH - 1 - column)) & 1 == 0 {
+						continue;
+					}
+					drawing::draw_filled_rect_mut(
+						&mut self.image,
+						Rect::at(
+							left + (i * advance + column * scale) as i32,
+							top + (row * scale) as i32,
+						)
+						.of_size(scale, scale),
+						text_color,
+					);
+				}
+			}
+		}
+	}
+
+	/**
+	 * Resize the image with premultiplied alpha.
+	 *
+	 * Color channels are multiplied by alpha before resampling so that
+	 * the colors of the transparent pixels do not bleed into the edges.
+	 *
+	 * @param size
+	 */
+	fn resize(&mut self, size: Geometry) {
+		let linear = self.settings.image.linear;
+		if !linear && self.image.pixels().all(|pixel| pixel[3] == u8::MAX) {
+			self.image = imageops::resize(
+				&self.image,
+				size.width,
+				size.height,
+				self.settings.image.filter,
 			);
-			self.image = imageops::blur(&self.image, self.settings.image.blur);
+			return;
 		}
-		self
+		let values = Self::get_value_table(linear);
+		let premultiplied = Rgba32FImage::from_fn(
+			self.image.width(),
+			self.image.height(),
+			|x, y| {
+				let pixel = self.image.get_pixel(x, y);
+				let alpha = f32::from(pixel[3]) / 255.;
+				Rgba([
+					values[usize::from(pixel[0])] * alpha,
+					values[usize::from(pixel[1])] * alpha,
+					values[usize::from(pixel[2])] * alpha,
+					alpha,
+				])
+			},
+		);
+		let resized = imageops::resize(
+			&premultiplied,
+			size.width,
+			size.height,
+			self.settings.image.filter,
+		);
+		self.image = RgbaImage::from_fn(size.width, size.height, |x, y| {
+			let pixel = resized.get_pixel(x, y);
+			let alpha = pixel[3].clamp(0., 1.);
+			if alpha == 0. {
+				return Rgba([0, 0, 0, 0]);
+			}
+			Rgba([
+				Self::get_color_value(pixel[0] / alpha, linear),
+				Self::get_color_value(pixel[1] / alpha, linear),
+				Self::get_color_value(pixel[2] / alpha, linear),
+				(alpha * 255.).round() as u8,
+			])
+		});
 	}
 
-	/* Update the colors of the image */
-	fn update_colors(&mut self) -> &mut Self {
-		if format!("{:?}", self.settings.color)
-			!= format!("{:?}", ColorSettings::default())
-		{
-			info!("Updating the colors...");
+	/**
+	 * Get the table for converting the color values to floating point.
+	 *
+	 * @param  linear
+	 * @return Array of f32
+	 */
+	fn get_value_table(linear: bool) -> [f32; 256] {
+		array::from_fn(|value| {
+			let value = value as f64 / 255.;
+			(if linear {
+				ColorProfile::to_linear(value)
+			} else {
+				value
+			}) as f32
+		})
+	}
+
+	/**
+	 * Convert the floating point value back to a color value.
+	 *
+	 * @param  value
+	 * @param  linear
+	 * @return u8
+	 */
+	fn get_color_value(value: f32, linear: bool) -> u8 {
+		let value = if linear {
+			ColorProfile::to_srgb(f64::from(value.clamp(0., 1.))) as f32
+		} else {
+			value
+		};
+		(value * 255.).round().clamp(0., 255.) as u8
+	}
+
+	/**
+	 * Convert the image to linear light.
+	 *
+	 * @param  image
+	 * @return Rgba32FImage
+	 */
+	fn to_linear(image: &RgbaImage) -> Rgba32FImage {
+		let values = Self::get_value_table(true);
+		Rgba32FImage::from_fn(image.width(), image.height(), |x, y| {
+			let pixel = image.get_pixel(x, y);
+			Rgba([
+				values[usize::from(pixel[0])],
+				values[usize::from(pixel[1])],
+				values[usize::from(pixel[2])],
+				f32::from(pixel[3]) / 255.,
+			])
+		})
+	}
+
+	/**
+	 * Convert the image from linear light back to sRGB.
+	 *
+	 * @param  image
+	 * @return RgbaImage
+	 */
+	fn from_linear(image: &Rgba32FImage) -> RgbaImage {
+		RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+			let pixel = image.get_pixel(x, y);
+			Rgba([
+				Self::get_color_value(pixel[0], true),
+				Self::get_color_value(pixel[1], true),
+				Self::get_color_value(pixel[2], true),
+				Self::get_color_value(pixel[3], false),
+			])
+		})
+	}
+
+	/**
+	 * Add noise to the image.
+	 *
+	 * @param amount
+	 * @param mono
+	 */
+	fn add_noise(&mut self, amount: u8, mono: bool) {
+		info!(
+			"Adding {}noise to the image... ({})",
+			if mono { "monochrome " } else { "" },
+			amount
+		);
+		for pixel in self.image.pixels_mut() {
+			let mut offset = self.rng.next_offset(amount);
+			for channel in pixel.0.iter_mut().take(3) {
+				if !mono {
+					offset = self.rng.next_offset(amount);
+				}
+				*channel = (i32::from(*channel) + offset).clamp(0, 255) as u8;
+			}
 		}
-		if self.settings.color.grayscale {
-			self.image = DynamicImage::ImageLuma8(colorops::grayscale(&self.image))
-				.to_rgba8();
+	}
+
+	/**
+	 * Make the pixels that match the key color transparent.
+	 *
+	 * @param chroma_key
+	 */
+	fn apply_chroma_key(&mut self, chroma_key: ChromaKey) {
+		info!(
+			"Keying out the color... (#{}, {}%)",
+			hex::encode_upper(&chroma_key.color.0[..3]),
+			chroma_key.tolerance
+		);
+		for pixel in self.image.pixels_mut() {
+			let opacity = chroma_key.get_opacity(pixel);
+			pixel[3] = (f32::from(pixel[3]) * opacity).round() as u8;
 		}
-		if self.settings.color.invert {
-			colorops::invert(&mut self.image);
+	}
+
+	/**
+	 * Replace the pixels that match the color with another color.
+	 *
+	 * @param replace
+	 */
+	fn replace_color(&mut self, replace: ColorReplace) {
+		info!(
+			"Replacing the color... (#{} -> #{}, {}%)",
+			hex::encode_upper(&replace.from.0[..3]),
+			hex::encode_upper(&replace.to.0[..3]),
+			replace.tolerance
+		);
+		for pixel in self.image.pixels_mut() {
+			replace.apply(pixel);
 		}
-		if self.settings.color.brightness != 0 {
-			self.image =
-				colorops::brighten(&self.image, self.settings.color.brightness);
+	}
+
+	/* Multiply the alpha channel with the luminance of the mask */
+	fn apply_mask(&mut self) {
+		let mask = match &self.mask {
+			Some(mask) => mask,
+			None => {
+				warn!("No mask is loaded, skipping the mask.");
+				return;
+			}
+		};
+		let (width, height) = self.image.dimensions();
+		info!("Applying the mask... ({}x{})", width, height);
+		let mask = if mask.dimensions() == (width, height) {
+			mask.clone()
+		} else {
+			imageops::resize(mask, width, height, self.settings.image.filter)
+		};
+		for (pixel, value) in self.image.pixels_mut().zip(mask.pixels()) {
+			pixel[3] =
+				((u16::from(pixel[3]) * u16::from(value[0]) + 127) / 255) as u8;
 		}
-		if self.settings.color.hue != 0 {
-			self.image = colorops::huerotate(&self.image, self.settings.color.hue);
+	}
+
+	/**
+	 * Replace the image with its (grayscale) edge map.
+	 *
+	 * @param method
+	 */
+	fn detect_edges(&mut self, method: EdgeDetect) {
+		let image = colorops::grayscale(&self.image);
+		let edges = match method {
+			EdgeDetect::Sobel => {
+				info!("Detecting the edges... (Sobel)");
+				let gradients = gradients::sobel_gradients(&image);
+				ImageBuffer::from_fn(image.width(), image.height(), |x, y| {
+					Luma([gradients.get_pixel(x, y)[0].min(u8::MAX.into()) as u8])
+				})
+			}
+			EdgeDetect::Canny(low, high) => {
+				info!("Detecting the edges... (Canny, {}-{})", low, high);
+				edges::canny(&image, low, high)
+			}
+		};
+		self.image = DynamicImage::ImageLuma8(edges).to_rgba8();
+	}
+
+	/* Trim the transparent borders of the image */
+	fn trim_transparent(&mut self) {
+		let (width, height) = self.image.dimensions();
+		let (mut left, mut top, mut right, mut bottom) = (width, height, 0, 0);
+		for (x, y, pixel) in self.image.enumerate_pixels() {
+			if pixel[3] > TRIM_ALPHA_THRESHOLD {
+				left = left.min(x);
+				top = top.min(y);
+				right = right.max(x + 1);
+				bottom = bottom.max(y + 1);
+			}
 		}
-		if self.settings.color.contrast != 0. {
-			self.image =
-				colorops::contrast(&self.image, self.settings.color.contrast);
+		if right > left
+			&& bottom > top
+			&& (right - left, bottom - top) != (width, height)
+		{
+			info!(
+				"Trimming the transparent borders... ({}x{})",
+				right - left,
+				bottom - top
+			);
+			self.image = imageops::crop(
+				&mut self.image,
+				left,
+				top,
+				right - left,
+				bottom - top,
+			)
+			.to_image();
 		}
-		self
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::edit::settings::{CropArea, Resize};
+	use image::imageops::FilterType;
 	use image::{ExtendedColorType, Rgba, RgbaImage};
-	use pretty_assertions::assert_eq;
+	use pretty_assertions::{assert_eq, assert_ne};
+	use std::env;
+	use std::fs;
+	use std::path::PathBuf;
 	#[test]
-	fn test_edit() {
+	fn test_edit() -> AppResult<()> {
 		let mut image = RgbaImage::new(32, 32);
 		for x in 15..=17 {
 			for y in 8..24 {
@@ -219,7 +642,7 @@ mod tests {
 		let mut settings = EditSettings::default();
 		settings.image.crop.top = 10;
 		settings.image.ratio = 2.;
-		settings.image.resize = Geometry::new(0, 0, 32, 42);
+		settings.image.resize = Some(Resize::Size(Geometry::new(0, 0, 32, 42)));
 		settings.image.flip = Some(Flip::Vertical);
 		settings.image.rotate = 270;
 		settings.image.blur = 1.5;
@@ -228,8 +651,14 @@ mod tests {
 		settings.color.brightness = -2;
 		settings.color.hue = 15;
 		settings.color.contrast = -5.;
-		let mut imageops = ImageOps::new(&settings);
-		let image = imageops.init(image.dimensions()).process(image).get_image();
+		settings.color.noise = (10, true);
+		settings.color.seed = Some(1);
+		let mut imageops = ImageOps::new(&settings)?;
+		let image = imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image)
+			.get_image();
 		let (width, height) = imageops.image.dimensions();
 		assert_eq!(
 			format!("{image:?}"),
@@ -240,5 +669,394 @@ mod tests {
 			width * height * 4,
 			image.get_data(ExtendedColorType::Rgba8).len() as u32
 		);
+		let image =
+			RgbaImage::from_fn(3, 2, |x, y| Rgba([x as u8, y as u8, 0, 128]));
+		let settings = EditSettings::default();
+		let edited = ImageOps::new(&settings)?
+			.edit(image.clone())
+			.expect("Failed to edit");
+		let processed = ImageOps::new(&settings)?
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image)
+			.get_image();
+		assert_eq!(processed.geometry, edited.geometry);
+		assert_eq!(
+			processed.get_data(ExtendedColorType::Rgba8),
+			edited.get_data(ExtendedColorType::Rgba8)
+		);
+		Ok(())
+	}
+	#[test]
+	fn test_crop_area() -> AppResult<()> {
+		let image =
+			RgbaImage::from_fn(8, 6, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+		let mut settings = EditSettings::default();
+		settings.image.crop_area = CropArea::parse_center("4x2");
+		let mut imageops = ImageOps::new(&settings)?;
+		let cropped = imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image.clone())
+			.get_image();
+		assert_eq!(Geometry::new(2, 2, 4, 2), cropped.geometry);
+		assert_eq!(Rgba([2, 2, 0, 255]), *imageops.image.get_pixel(0, 0));
+		settings.image.crop_area = CropArea::parse_at("5,1,3,5");
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize");
+		assert_eq!(Geometry::new(5, 1, 3, 5), imageops.geometry);
+		settings.image.crop_area = CropArea::parse_at("6,1,3,5");
+		assert!(ImageOps::new(&settings)?.init(image.dimensions()).is_err());
+		settings.image.crop_area = CropArea::parse_center("10x2");
+		let mut imageops = ImageOps::new(&settings)?;
+		assert!(imageops.init(image.dimensions()).is_err());
+		assert_eq!((8, 6), imageops.process(image).image.dimensions());
+		Ok(())
+	}
+	#[test]
+	fn test_resize_filter() -> AppResult<()> {
+		let image = RgbaImage::from_fn(8, 8, |x, y| {
+			if (x + y) % 2 == 0 {
+				Rgba([255, 255, 255, 255])
+			} else {
+				Rgba([0, 0, 0, 255])
+			}
+		});
+		let mut settings = EditSettings::default();
+		settings.image.ratio = 0.5;
+		let mut resized = Vec::new();
+		for filter in [
+			FilterType::Nearest,
+			FilterType::Triangle,
+			FilterType::Lanczos3,
+		] {
+			settings.image.filter = filter;
+			resized.push(
+				ImageOps::new(&settings)?
+					.process(image.clone())
+					.image
+					.clone(),
+			);
+		}
+		assert_eq!((4, 4), resized[0].dimensions());
+		assert!(resized[0]
+			.pixels()
+			.all(|pixel| pixel[0] == 0 || pixel[0] == 255));
+		assert_ne!(resized[0], resized[1]);
+		assert_ne!(resized[1], resized[2]);
+		Ok(())
+	}
+	#[test]
+	fn test_resize_alpha() -> AppResult<()> {
+		let image = RgbaImage::from_fn(8, 1, |x, _| {
+			if x < 4 {
+				Rgba([0, 0, 0, 0])
+			} else {
+				Rgba([255, 0, 0, 255])
+			}
+		});
+		let mut settings = EditSettings::default();
+		settings.image.resize = Some(Resize::Size(Geometry::new(0, 0, 4, 1)));
+		settings.image.filter = FilterType::Triangle;
+		let resized = ImageOps::new(&settings)?.process(image).image.clone();
+		assert_eq!((4, 1), resized.dimensions());
+		assert!(resized
+			.pixels()
+			.filter(|pixel| pixel[3] != 0)
+			.all(|pixel| pixel.0[..3] == [255, 0, 0]));
+		assert!(resized.get_pixel(1, 0)[3] < resized.get_pixel(2, 0)[3]);
+		Ok(())
+	}
+	#[test]
+	fn test_linear() -> AppResult<()> {
+		let image = RgbaImage::from_fn(2, 1, |x, _| {
+			Rgba([if x == 0 { 0 } else { 255 }, 0, 0, 255])
+		});
+		let mut settings = EditSettings::default();
+		settings.image.resize = Some(Resize::Size(Geometry::new(0, 0, 1, 1)));
+		settings.image.filter = FilterType::Triangle;
+		let srgb = ImageOps::new(&settings)?
+			.process(image.clone())
+			.image
+			.clone();
+		settings.image.linear = true;
+		let linear = ImageOps::new(&settings)?
+			.process(image.clone())
+			.image
+			.clone();
+		assert_eq!(128, srgb.get_pixel(0, 0)[0]);
+		assert_eq!(188, linear.get_pixel(0, 0)[0]);
+		assert_eq!(255, linear.get_pixel(0, 0)[3]);
+		let image = RgbaImage::from_fn(256, 1, |x, _| {
+			Rgba([x as u8, 255 - x as u8, 0, x as u8])
+		});
+		assert_eq!(image, ImageOps::from_linear(&ImageOps::to_linear(&image)));
+		let image = RgbaImage::from_fn(8, 8, |x, _| {
+			Rgba([if x % 2 == 0 { 0 } else { 255 }, 0, 0, 255])
+		});
+		settings.image.resize = None;
+		settings.image.blur = 2.;
+		let blurred = ImageOps::new(&settings)?
+			.process(image.clone())
+			.image
+			.clone();
+		assert!(
+			blurred.get_pixel(4, 4)[0]
+				> imageops::blur(&image, 2.).get_pixel(4, 4)[0]
+		);
+		Ok(())
+	}
+	#[test]
+	fn test_noise() -> AppResult<()> {
+		let image = RgbaImage::from_pixel(8, 8, Rgba([128, 128, 128, 100]));
+		let mut settings = EditSettings::default();
+		settings.color.noise = (30, false);
+		settings.color.seed = Some(42);
+		let noisy = ImageOps::new(&settings)?
+			.process(image.clone())
+			.image
+			.clone();
+		assert_eq!(
+			noisy,
+			ImageOps::new(&settings)?.process(image.clone()).image
+		);
+		assert_ne!(image, noisy);
+		assert!(noisy.pixels().all(|pixel| pixel[3] == 100
+			&& pixel.0[..3].iter().all(|v| (98..=158).contains(v))));
+		settings.color.noise = (30, true);
+		let noisy = ImageOps::new(&settings)?.process(image).image.clone();
+		assert!(noisy
+			.pixels()
+			.all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]));
+		Ok(())
+	}
+	#[test]
+	fn test_median() -> AppResult<()> {
+		let mut image = RgbaImage::from_pixel(8, 8, Rgba([128, 128, 128, 255]));
+		for (x, y) in [(1, 1), (4, 2), (6, 6)] {
+			image.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+		}
+		for (x, y) in [(2, 5), (5, 4)] {
+			image.put_pixel(x, y, Rgba([0, 0, 0, 255]));
+		}
+		let mut settings = EditSettings::default();
+		settings.image.median = 1;
+		let filtered = ImageOps::new(&settings)?.process(image).image.clone();
+		assert_eq!((8, 8), filtered.dimensions());
+		assert!(filtered
+			.pixels()
+			.all(|pixel| *pixel == Rgba([128, 128, 128, 255])));
+		Ok(())
+	}
+	#[test]
+	fn test_trim_transparent() -> AppResult<()> {
+		let mut image = RgbaImage::new(10, 8);
+		for x in 2..7 {
+			image.put_pixel(x, 3, Rgba([255, 0, 0, 255]));
+		}
+		image.put_pixel(4, 5, Rgba([0, 255, 0, 128]));
+		image.put_pixel(9, 7, Rgba([0, 0, 255, TRIM_ALPHA_THRESHOLD]));
+		let mut settings = EditSettings::default();
+		settings.image.trim = true;
+		let mut imageops = ImageOps::new(&settings)?;
+		let trimmed = imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image)
+			.get_image();
+		assert_eq!(Geometry::new(0, 0, 5, 3), trimmed.geometry);
+		assert_eq!((5, 3), imageops.image.dimensions());
+		assert_eq!(Rgba([0, 255, 0, 128]), *imageops.image.get_pixel(2, 2));
+		let opaque = RgbaImage::from_pixel(4, 4, Rgba([0, 0, 0, 255]));
+		assert_eq!((4, 4), imageops.process(opaque).image.dimensions());
+		let transparent = RgbaImage::new(4, 4);
+		assert_eq!((4, 4), imageops.process(transparent).image.dimensions());
+		Ok(())
+	}
+	#[test]
+	fn test_chroma_key() -> AppResult<()> {
+		let mut image = RgbaImage::from_pixel(8, 4, Rgba([0, 255, 0, 255]));
+		for x in 4..8 {
+			for y in 0..4 {
+				image.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+			}
+		}
+		image.put_pixel(0, 0, Rgba([10, 250, 10, 255]));
+		image.put_pixel(1, 0, Rgba([0, 200, 0, 255]));
+		let mut settings = EditSettings::default();
+		settings.color.chroma_key = "#00ff00:10:20".parse().ok();
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image);
+		assert_eq!(0, imageops.image.get_pixel(0, 0)[3]);
+		assert_eq!(31, imageops.image.get_pixel(1, 0)[3]);
+		assert_eq!(0, imageops.image.get_pixel(3, 3)[3]);
+		assert_eq!(Rgba([255, 0, 0, 255]), *imageops.image.get_pixel(4, 0));
+		assert_eq!(
+			Ok(ChromaKey {
+				color: Rgba([0, 0, 255, 255]),
+				tolerance: 10.,
+				feather: 0.,
+			}),
+			"0000FF".parse()
+		);
+		assert!("0000FF:101".parse::<ChromaKey>().is_err());
+		assert!("0000FF:1:2:3".parse::<ChromaKey>().is_err());
+		assert!("blue".parse::<ChromaKey>().is_err());
+		Ok(())
+	}
+	#[test]
+	fn test_replace_color() -> AppResult<()> {
+		let mut image = RgbaImage::from_pixel(8, 8, Rgba([255, 255, 255, 255]));
+		for x in 2..6 {
+			for y in 2..6 {
+				image.put_pixel(x, y, Rgba([200, 30, 30, 128]));
+			}
+		}
+		image.put_pixel(2, 2, Rgba([205, 25, 30, 255]));
+		image.put_pixel(0, 0, Rgba([180, 30, 30, 255]));
+		let settings = EditSettings {
+			color_replaces: ["c81e1e:00ff00:3", "00ff00:0000ff"]
+				.iter()
+				.map(|v| v.parse().unwrap())
+				.collect(),
+			..EditSettings::default()
+		};
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image);
+		assert_eq!(Rgba([0, 0, 255, 128]), *imageops.image.get_pixel(3, 4));
+		assert_eq!(Rgba([0, 0, 255, 255]), *imageops.image.get_pixel(2, 2));
+		assert_eq!(Rgba([180, 30, 30, 255]), *imageops.image.get_pixel(0, 0));
+		assert_eq!(Rgba([255, 255, 255, 255]), *imageops.image.get_pixel(1, 6));
+		assert_eq!(Rgba([255, 255, 255, 255]), *imageops.image.get_pixel(6, 2));
+		assert_eq!(
+			Ok(ColorReplace {
+				from: Rgba([0, 0, 255, 255]),
+				to: Rgba([255, 0, 0, 255]),
+				tolerance: 0.,
+			}),
+			"0000FF:FF0000".parse()
+		);
+		assert!("0000FF".parse::<ColorReplace>().is_err());
+		assert!("0000FF:FF0000:1:2".parse::<ColorReplace>().is_err());
+		Ok(())
+	}
+	#[test]
+	fn test_badge() -> AppResult<()> {
+		let image = RgbaImage::from_pixel(60, 30, Rgba([255, 255, 255, 255]));
+		let mut settings = EditSettings::default();
+		settings.badges.positions = vec![(15, 15), (45, 15)];
+		settings.badges.size = 28;
+		settings.badges.color = Rgba([0, 0, 255, 255]);
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image.clone());
+		assert_eq!(2, imageops.badges);
+		assert_eq!(Rgba([0, 0, 255, 255]), *imageops.image.get_pixel(5, 15));
+		assert_eq!(Rgba([255, 255, 255, 255]), *imageops.image.get_pixel(0, 0));
+		let get_text = |imageops: &ImageOps<'_>, x: u32| {
+			(x - 7..x + 7)
+				.flat_map(|x| (8..22).map(move |y| (x, y)))
+				.filter(|(x, y)| {
+					*imageops.image.get_pixel(*x, *y) == Rgba([255, 255, 255, 255])
+				})
+				.count()
+		};
+		assert_ne!(0, get_text(&imageops, 15));
+		assert_ne!(get_text(&imageops, 15), get_text(&imageops, 45));
+		imageops.process(image);
+		assert_eq!(2, imageops.badges);
+		Ok(())
+	}
+	#[test]
+	fn test_blur_region() -> AppResult<()> {
+		let image = RgbaImage::from_fn(16, 8, |x, y| {
+			if (x + y) % 2 == 0 {
+				Rgba([0, 0, 0, 255])
+			} else {
+				Rgba([255, 255, 255, 255])
+			}
+		});
+		let settings = EditSettings {
+			blur_regions: ["0,0,6,8:2", "12,2,10,10", "20,20,4,4"]
+				.iter()
+				.map(|v| v.parse().unwrap())
+				.collect(),
+			..EditSettings::default()
+		};
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops
+			.init(image.dimensions())
+			.expect("Failed to initialize")
+			.process(image.clone());
+		assert_eq!((16, 8), imageops.image.dimensions());
+		for (x, y) in [(2, 3), (14, 5)] {
+			let value = imageops.image.get_pixel(x, y)[0];
+			assert!(value > 32 && value < 224);
+		}
+		for (x, y) in [(6, 3), (9, 4), (14, 1)] {
+			assert_eq!(image.get_pixel(x, y), imageops.image.get_pixel(x, y));
+		}
+		Ok(())
+	}
+	#[test]
+	fn test_mask() -> AppResult<()> {
+		let path = env::temp_dir().join("menyoki_test_mask.png");
+		GrayImage::from_fn(4, 2, |x, _| Luma([if x < 2 { 0 } else { 255 }]))
+			.save(&path)?;
+		let image = RgbaImage::from_pixel(8, 4, Rgba([255, 0, 0, 200]));
+		let mut settings = EditSettings {
+			mask: Some(path.clone()),
+			..EditSettings::default()
+		};
+		settings.image.filter = FilterType::Nearest;
+		let mut imageops = ImageOps::new(&settings)?;
+		imageops.init(image.dimensions())?.process(image.clone());
+		fs::remove_file(path)?;
+		for (x, _, pixel) in imageops.image.enumerate_pixels() {
+			assert_eq!(if x < 4 { 0 } else { 200 }, pixel[3]);
+			assert_eq!([255, 0, 0], pixel.0[..3]);
+		}
+		settings.mask = Some(PathBuf::from("menyoki_test_missing_mask.png"));
+		assert!(ImageOps::new(&settings)?.init(image.dimensions()).is_err());
+		Ok(())
+	}
+	#[test]
+	fn test_edge_detect() -> AppResult<()> {
+		let image = RgbaImage::from_fn(16, 8, |x, _| {
+			if x < 8 {
+				Rgba([0, 0, 0, 255])
+			} else {
+				Rgba([255, 255, 255, 255])
+			}
+		});
+		let mut settings = EditSettings::default();
+		for method in [EdgeDetect::Sobel, EdgeDetect::Canny(50., 100.)] {
+			settings.image.edge = Some(method);
+			let edges = ImageOps::new(&settings)?
+				.process(image.clone())
+				.image
+				.clone();
+			assert_eq!((16, 8), edges.dimensions());
+			for (x, y, pixel) in edges.enumerate_pixels() {
+				assert_eq!(pixel[0], pixel[1]);
+				assert_eq!(pixel[1], pixel[2]);
+				assert_eq!(255, pixel[3]);
+				if !(6..=9).contains(&x) {
+					assert_eq!(0, pixel[0], "({x}, {y})");
+				}
+			}
+			assert!((1..7).all(|y| (7..=8).any(|x| edges.get_pixel(x, y)[0] == 255)));
+		}
+		Ok(())
 	}
 }
@@ -0,0 +1,308 @@
+use crate::app::{AppError, AppResult};
+use crate::edit::settings::{
+	Badges, BlurRegion, ChromaKey, ColorReplace, CropArea, EdgeDetect, EditSettings,
+	Flip, Resize,
+};
+use crate::image::geometry::Geometry;
+use crate::image::padding::Padding;
+use std::str::FromStr;
+
+/* Image editing operation */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+	Crop(Padding),
+	CropArea(CropArea),
+	Resize(Resize),
+	Ratio(f32),
+	Flip(Flip),
+	Rotate(u32),
+	Blur(f32),
+	BlurRegion(BlurRegion),
+	Median(u32),
+	Sharpen(f32),
+	EdgeDetect(EdgeDetect),
+	Grayscale,
+	Invert,
+	Brightness(i32),
+	Hue(i32),
+	Contrast(f32),
+	Noise(u8, bool),
+	ChromaKey(ChromaKey),
+	ReplaceColor(ColorReplace),
+	Mask,
+	Trim,
+	Badge((i32, i32)),
+}
+
+/* Implementation for parsing Op from a string */
+impl FromStr for Op {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = s.trim().splitn(2, ':');
+		let name = values.next().unwrap_or_default().to_lowercase();
+		let value = values.next().unwrap_or_default().trim();
+		match (name.as_str(), value) {
+			("crop", value) if !value.is_empty() => {
+				Ok(Self::Crop(Padding::parse(value)))
+			}
+			("crop-center", value) => {
+				CropArea::parse_center(value).map(Self::CropArea).ok_or(())
+			}
+			("crop-at", value) => {
+				CropArea::parse_at(value).map(Self::CropArea).ok_or(())
+			}
+			("resize", value) => value.parse().map(Self::Resize),
+			("ratio", value) => match value.parse() {
+				Ok(ratio) if ratio > 0. => Ok(Self::Ratio(ratio)),
+				_ => Err(()),
+			},
+			("flip", "horizontal") => Ok(Self::Flip(Flip::Horizontal)),
+			("flip", "vertical") => Ok(Self::Flip(Flip::Vertical)),
+			("rotate", "90" | "180" | "270") => {
+				Ok(Self::Rotate(value.parse().map_err(|_| ())?))
+			}
+			("blur", value) => value.parse().map(Self::Blur).map_err(|_| ()),
+			("blur-region", value) => value.parse().map(Self::BlurRegion),
+			("median", value) => match value.parse() {
+				Ok(radius) if radius > 0 => Ok(Self::Median(radius)),
+				_ => Err(()),
+			},
+			("sharpen", value) => value.parse().map(Self::Sharpen).map_err(|_| ()),
+			("edge", value) => value.parse().map(Self::EdgeDetect),
+			("grayscale", "") => Ok(Self::Grayscale),
+			("invert", "") => Ok(Self::Invert),
+			("brightness", value) => {
+				value.parse().map(Self::Brightness).map_err(|_| ())
+			}
+			("hue", value) => value.parse().map(Self::Hue).map_err(|_| ()),
+			("contrast", value) => value.parse().map(Self::Contrast).map_err(|_| ()),
+			("noise", value) => {
+				let mut values = value.split(':');
+				let amount = values.next().unwrap_or_default().parse();
+				match (amount, values.next(), values.next()) {
+					(Ok(amount), None, None) => Ok(Self::Noise(amount, false)),
+					(Ok(amount), Some("mono"), None) => {
+						Ok(Self::Noise(amount, true))
+					}
+					_ => Err(()),
+				}
+			}
+			("chroma-key", value) => value.parse().map(Self::ChromaKey),
+			("replace-color", value) => value.parse().map(Self::ReplaceColor),
+			("mask", "") => Ok(Self::Mask),
+			("trim", "") => Ok(Self::Trim),
+			("badge", value) => {
+				Badges::parse_position(value).map(Self::Badge).ok_or(())
+			}
+			_ => Err(()),
+		}
+	}
+}
+
+impl Op {
+	/**
+	 * Parse a semicolon-separated chain of operations.
+	 *
+	 * @param  spec
+	 * @return Vector of Op (Result)
+	 */
+	pub fn parse_chain(spec: &str) -> AppResult<Vec<Self>> {
+		spec.split(';')
+			.filter(|op| !op.trim().is_empty())
+			.map(|op| {
+				Self::from_str(op).map_err(|_| {
+					AppError::EditError(format!(
+						"Invalid edit operation: {:?}",
+						op.trim()
+					))
+				})
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the chain of operations from the individual edit flags.
+	 *
+	 * @param  settings
+	 * @return Vector of Op
+	 */
+	pub fn from_settings(settings: &EditSettings) -> Vec<Self> {
+		let (image, color) = (settings.image, settings.color);
+		let mut ops = settings
+			.color_replaces
+			.iter()
+			.copied()
+			.map(Self::ReplaceColor)
+			.collect::<Vec<Self>>();
+		if let Some(chroma_key) = color.chroma_key {
+			ops.push(Self::ChromaKey(chroma_key));
+		}
+		if let Some(resize) = image.resize {
+			ops.push(Self::Resize(resize));
+		}
+		if image.ratio > 0. && (image.ratio - 1.).abs() > f32::EPSILON {
+			ops.push(Self::Ratio(image.ratio));
+		}
+		if let Some(flip) = image.flip {
+			ops.push(Self::Flip(flip));
+		}
+		if [90, 180, 270].contains(&image.rotate) {
+			ops.push(Self::Rotate(image.rotate));
+		}
+		if !image.crop.is_zero() {
+			ops.push(Self::Crop(image.crop));
+		}
+		if let Some(crop_area) = image.crop_area {
+			ops.push(Self::CropArea(crop_area));
+		}
+		if image.blur > 0. {
+			ops.push(Self::Blur(image.blur));
+		}
+		ops.extend(settings.blur_regions.iter().copied().map(Self::BlurRegion));
+		if image.median > 0 {
+			ops.push(Self::Median(image.median));
+		}
+		if let Some(edge) = image.edge {
+			ops.push(Self::EdgeDetect(edge));
+		}
+		if color.grayscale {
+			ops.push(Self::Grayscale);
+		}
+		if color.invert {
+			ops.push(Self::Invert);
+		}
+		if color.brightness != 0 {
+			ops.push(Self::Brightness(color.brightness));
+		}
+		if color.hue != 0 {
+			ops.push(Self::Hue(color.hue));
+		}
+		if color.contrast != 0. {
+			ops.push(Self::Contrast(color.contrast));
+		}
+		if color.noise.0 != 0 {
+			ops.push(Self::Noise(color.noise.0, color.noise.1));
+		}
+		if settings.mask.is_some() {
+			ops.push(Self::Mask);
+		}
+		if image.trim {
+			ops.push(Self::Trim);
+		}
+		ops.extend(settings.badges.positions.iter().copied().map(Self::Badge));
+		ops
+	}
+
+	/**
+	 * Get the geometry after applying the operation.
+	 *
+	 * @param  geometry
+	 * @return Geometry
+	 */
+	pub fn get_geometry(&self, mut geometry: Geometry) -> Geometry {
+		match self {
+			Self::Crop(padding) => geometry.with_padding(*padding),
+			Self::CropArea(crop_area) => crop_area
+				.get_area(geometry.width, geometry.height)
+				.map(|area| Geometry {
+					x: geometry.x + area.x,
+					y: geometry.y + area.y,
+					..area
+				})
+				.unwrap_or(geometry),
+			Self::Resize(resize) => resize.get_geometry(geometry),
+			Self::Ratio(ratio) => Geometry {
+				width: (geometry.width as f32 * ratio) as u32,
+				height: (geometry.height as f32 * ratio) as u32,
+				..geometry
+			},
+			Self::Rotate(90 | 270) => Geometry {
+				width: geometry.height,
+				height: geometry.width,
+				..geometry
+			},
+			_ => geometry,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::Rgba;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_op() {
+		assert_eq!(
+			vec![
+				Op::Blur(2.),
+				Op::Crop(Padding::new(10, 10, 10, 10)),
+				Op::Grayscale,
+				Op::Sharpen(1.5),
+				Op::Median(2),
+				Op::Noise(20, true),
+				Op::Rotate(90),
+				Op::EdgeDetect(EdgeDetect::Canny(5., 10.)),
+				Op::CropArea(CropArea::Center(Geometry::new(0, 0, 4, 2))),
+				Op::CropArea(CropArea::At(Geometry::new(1, 2, 3, 4))),
+				Op::Resize(Resize::Percent(50.)),
+				Op::Resize(Resize::LongEdge(800)),
+				Op::Badge((10, -5)),
+				Op::BlurRegion(BlurRegion {
+					area: Geometry::new(1, 2, 3, 4),
+					sigma: 2.5,
+				}),
+				Op::Mask,
+				Op::ReplaceColor(ColorReplace {
+					from: Rgba([255, 0, 0, 255]),
+					to: Rgba([0, 0, 255, 255]),
+					tolerance: 5.,
+				}),
+			],
+			Op::parse_chain(
+				"blur:2;crop:10:10:10:10; grayscale;sharpen:1.5;;median:2;noise:20:mono;\
+				rotate:90;edge:canny:5:10;crop-center:4x2;crop-at:1,2,3,4;resize:50%;\
+				resize:L800;badge:10,-5;blur-region:1,2,3,4:2.5;mask;\
+				replace-color:ff0000:#0000ff:5"
+			)
+			.unwrap_or_default()
+		);
+		for op in [
+			"median:0",
+			"rotate:45",
+			"blur",
+			"unknown",
+			"edge:x",
+			"crop-at:1,2,3",
+			"crop-center:0x5",
+			"resize:0x0",
+			"badge:10",
+			"blur-region:1,2,3,4:0",
+			"mask:x",
+			"replace-color:ff0000",
+			"replace-color:ff0000:0000ff:101",
+		] {
+			assert!(Op::parse_chain(&format!("blur:2;{op}")).is_err(), "{op}");
+		}
+		let mut settings = EditSettings::default();
+		settings.image.crop.top = 10;
+		settings.image.rotate = 270;
+		settings.image.resize = Some(Resize::Size(Geometry::new(0, 0, 32, 42)));
+		settings.color.invert = true;
+		let ops = Op::from_settings(&settings);
+		assert_eq!(
+			vec![
+				Op::Resize(Resize::Size(Geometry::new(0, 0, 32, 42))),
+				Op::Rotate(270),
+				Op::Crop(settings.image.crop),
+				Op::Invert,
+			],
+			ops
+		);
+		assert_eq!(
+			Geometry::new(0, 10, 42, 22),
+			ops.iter().fold(Geometry::new(0, 0, 8, 8), |geometry, op| op
+				.get_geometry(geometry))
+		);
+	}
+}
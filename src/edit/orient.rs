@@ -0,0 +1,94 @@
+use exif::{In, Reader as ExifReader, Tag};
+use image::imageops;
+use image::RgbaImage;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/* EXIF orientation of an image */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orientation {
+	value: u32,
+}
+
+impl Orientation {
+	/**
+	 * Create a new Orientation object.
+	 *
+	 * @param  value
+	 * @return Orientation
+	 */
+	pub fn new(value: u32) -> Self {
+		Self { value }
+	}
+
+	/**
+	 * Read the orientation from the EXIF data of the given file.
+	 *
+	 * @param  path
+	 * @return Orientation (Option)
+	 */
+	pub fn read(path: &Path) -> Option<Self> {
+		let exif = ExifReader::new()
+			.read_from_container(&mut BufReader::new(File::open(path).ok()?))
+			.ok()?;
+		exif.get_field(Tag::Orientation, In::PRIMARY)
+			.and_then(|field| field.value.get_uint(0))
+			.map(Self::new)
+	}
+
+	/**
+	 * Check if the image needs to be transformed.
+	 *
+	 * @return bool
+	 */
+	pub fn is_transformed(&self) -> bool {
+		(2..=8).contains(&self.value)
+	}
+
+	/**
+	 * Flip and rotate the image to undo the orientation.
+	 *
+	 * @param  image
+	 * @return RgbaImage
+	 */
+	pub fn apply(&self, image: RgbaImage) -> RgbaImage {
+		match self.value {
+			2 => imageops::flip_horizontal(&image),
+			3 => imageops::rotate180(&image),
+			4 => imageops::flip_vertical(&image),
+			5 => imageops::flip_horizontal(&imageops::rotate90(&image)),
+			6 => imageops::rotate90(&image),
+			7 => imageops::flip_horizontal(&imageops::rotate270(&image)),
+			8 => imageops::rotate270(&image),
+			_ => image,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::Rgba;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_orientation() {
+		let image =
+			RgbaImage::from_fn(3, 2, |x, y| Rgba([x as u8, y as u8, 0, 255]));
+		assert_eq!(image, Orientation::new(1).apply(image.clone()));
+		assert!(!Orientation::new(1).is_transformed());
+		assert!(Orientation::new(6).is_transformed());
+		for value in 2..=8 {
+			let oriented = Orientation::new(value).apply(image.clone());
+			let dimensions = if value >= 5 { (2, 3) } else { (3, 2) };
+			assert_eq!(dimensions, oriented.dimensions());
+		}
+		let transposed = Orientation::new(5).apply(image.clone());
+		assert_eq!(Rgba([2, 1, 0, 255]), *transposed.get_pixel(1, 2));
+		let rotated = Orientation::new(6).apply(image.clone());
+		assert_eq!(Rgba([0, 1, 0, 255]), *rotated.get_pixel(0, 0));
+		let transversed = Orientation::new(7).apply(image);
+		assert_eq!(Rgba([2, 1, 0, 255]), *transversed.get_pixel(0, 0));
+		assert_eq!(None, Orientation::read(Path::new("Cargo.toml")));
+	}
+}
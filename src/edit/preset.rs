@@ -0,0 +1,111 @@
+use crate::app::{AppError, AppResult};
+use std::fs;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/* Name of the file for storing the presets */
+const PRESETS_FILE: &str = "presets.toml";
+
+/* Named chains of editing operations */
+#[derive(Debug)]
+pub struct PresetStore {
+	path: PathBuf,
+}
+
+/* Default initialization values for PresetStore */
+impl Default for PresetStore {
+	fn default() -> Self {
+		Self::new(
+			dirs::config_dir()
+				.unwrap_or_else(|| {
+					dirs::home_dir()
+						.expect("Failed to access the home directory")
+						.join(".config")
+				})
+				.join(env!("CARGO_PKG_NAME"))
+				.join(PRESETS_FILE),
+		)
+	}
+}
+
+impl PresetStore {
+	/**
+	 * Create a new PresetStore object.
+	 *
+	 * @param  path
+	 * @return PresetStore
+	 */
+	pub fn new(path: PathBuf) -> Self {
+		Self { path }
+	}
+
+	/**
+	 * Get the chain of operations of the preset with the given name.
+	 *
+	 * The operations are given either as a single chain
+	 * or as an array of operations.
+	 *
+	 * @param  name
+	 * @return String (Result)
+	 */
+	pub fn get(&self, name: &str) -> AppResult<String> {
+		let error = |message: String| AppError::EditError(message);
+		let presets = fs::read_to_string(&self.path)
+			.map_err(|e| {
+				error(format!(
+					"Failed to read the presets from {:?}: {}",
+					self.path, e
+				))
+			})?
+			.parse::<Table>()
+			.map_err(|e| {
+				error(format!(
+					"Failed to parse the presets from {:?}: {}",
+					self.path, e
+				))
+			})?;
+		let preset = presets
+			.get(name)
+			.ok_or_else(|| error(format!("Preset not found: {name:?}")))?;
+		match preset.get("ops") {
+			Some(Value::String(ops)) => Ok(ops.to_string()),
+			Some(Value::Array(ops)) => ops
+				.iter()
+				.map(|op| op.as_str())
+				.collect::<Option<Vec<&str>>>()
+				.map(|ops| ops.join(";"))
+				.ok_or_else(|| {
+					error(format!("Invalid operations in preset: {name:?}"))
+				}),
+			_ => Err(error(format!("No operations in preset: {name:?}"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	use std::fs;
+	#[test]
+	fn test_preset_store() -> AppResult<()> {
+		let path = env::temp_dir().join("menyoki_test_presets.toml");
+		fs::write(
+			&path,
+			"[blog]\nops = \"resize:800x0;sharpen:0.5\"\n\n\
+			[thumbnail]\nops = [\"ratio:0.25\", \"trim\"]\n\n\
+			[empty]\n",
+		)?;
+		let store = PresetStore::new(path.clone());
+		assert_eq!("resize:800x0;sharpen:0.5", store.get("blog")?);
+		assert_eq!("ratio:0.25;trim", store.get("thumbnail")?);
+		assert!(store.get("empty").is_err());
+		assert!(store.get("x").is_err());
+		fs::write(&path, "[blog\n")?;
+		assert!(store.get("blog").is_err());
+		fs::remove_file(path)?;
+		assert!(store.get("blog").is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,409 @@
+use std::fs;
+use std::io::{Error, ErrorKind, Result as IoResult};
+use std::path::Path;
+
+/* Signature of the PNG files */
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+/* Start of image marker of the JPEG files */
+const JPEG_SOI: &[u8] = &[0xFF, 0xD8];
+/* Identifier of the APP2 segments that contain ICC profile data */
+const JPEG_ICC_MARKER: &[u8] = b"ICC_PROFILE\0";
+/* Maximum size of the profile data in a single APP2 segment */
+const JPEG_ICC_CHUNK_SIZE: usize = 65519;
+/* Signature of the ICC profiles */
+const ICC_SIGNATURE: &[u8] = b"acsp";
+/* Description of the built-in sRGB profile */
+const SRGB_DESCRIPTION: &str = "sRGB IEC61966-2.1";
+/* Number of entries in the tone curve of the built-in sRGB profile */
+const SRGB_CURVE_SIZE: u16 = 1024;
+/* D50 white point of the profile connection space */
+const D50: [f64; 3] = [0.9642, 1.0, 0.8249];
+/* Colorants of the sRGB primaries (adapted to D50) */
+const SRGB_COLORANTS: [[f64; 3]; 3] = [
+	[0.436_074_7, 0.222_504_5, 0.013_932_2],
+	[0.385_064_9, 0.716_878_6, 0.097_104_5],
+	[0.143_080_4, 0.060_616_9, 0.714_173_3],
+];
+/* Gamma value of the sRGB chunk (scaled by 100000) */
+const SRGB_GAMMA: u32 = 45455;
+
+/* Embedded color profile of an image */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorProfile {
+	data: Vec<u8>,
+}
+
+impl ColorProfile {
+	/**
+	 * Read the embedded color profile from the given file.
+	 *
+	 * @param  path
+	 * @return ColorProfile (Option)
+	 */
+	pub fn from_file(path: &Path) -> Option<Self> {
+		Self::from_bytes(&fs::read(path).ok()?)
+	}
+
+	/**
+	 * Read the embedded color profile from the given image data.
+	 *
+	 * @param  bytes
+	 * @return ColorProfile (Option)
+	 */
+	pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		let data = if bytes.starts_with(PNG_SIGNATURE) {
+			Self::read_png(bytes)
+		} else if bytes.starts_with(JPEG_SOI) {
+			Self::read_jpeg(bytes)
+		} else {
+			None
+		}?;
+		if data.is_empty() {
+			None
+		} else {
+			Some(Self { data })
+		}
+	}
+
+	/**
+	 * Load the ICC profile from the given file.
+	 *
+	 * @param  path
+	 * @return ColorProfile (Result)
+	 */
+	pub fn load(path: &Path) -> IoResult<Self> {
+		let data = fs::read(path)?;
+		if data.get(36..40) == Some(ICC_SIGNATURE) {
+			Ok(Self { data })
+		} else {
+			Err(Error::new(ErrorKind::InvalidData, "Invalid ICC profile"))
+		}
+	}
+
+	/**
+	 * Create the built-in sRGB profile.
+	 *
+	 * @return ColorProfile
+	 */
+	pub fn srgb() -> Self {
+		let xyz = |xyz: [f64; 3]| {
+			let mut data = b"XYZ \0\0\0\0".to_vec();
+			for value in xyz {
+				data.extend_from_slice(
+					&((value * 65536.).round() as i32).to_be_bytes(),
+				);
+			}
+			data
+		};
+		let mut description = b"desc\0\0\0\0".to_vec();
+		description
+			.extend_from_slice(&(SRGB_DESCRIPTION.len() as u32 + 1).to_be_bytes());
+		description.extend_from_slice(SRGB_DESCRIPTION.as_bytes());
+		description.extend_from_slice(&[0; 1 + 4 + 4 + 2 + 1 + 67]);
+		let mut curve = b"curv\0\0\0\0".to_vec();
+		curve.extend_from_slice(&u32::from(SRGB_CURVE_SIZE).to_be_bytes());
+		for i in 0..SRGB_CURVE_SIZE {
+			let linear =
+				Self::to_linear(f64::from(i) / f64::from(SRGB_CURVE_SIZE - 1));
+			curve.extend_from_slice(
+				&((linear * 65535.).round() as u16).to_be_bytes(),
+			);
+		}
+		let tags: [(&[u8], Vec<u8>); 9] = [
+			(b"desc", description),
+			(b"cprt", b"text\0\0\0\0No copyright, use freely\0".to_vec()),
+			(b"wtpt", xyz(D50)),
+			(b"rXYZ", xyz(SRGB_COLORANTS[0])),
+			(b"gXYZ", xyz(SRGB_COLORANTS[1])),
+			(b"bXYZ", xyz(SRGB_COLORANTS[2])),
+			(b"rTRC", curve.clone()),
+			(b"gTRC", curve.clone()),
+			(b"bTRC", curve),
+		];
+		let mut table = (tags.len() as u32).to_be_bytes().to_vec();
+		let mut elements = Vec::new();
+		let offset = 128 + table.len() + tags.len() * 12;
+		for (signature, mut element) in tags {
+			element.resize((element.len() + 3) & !3, 0);
+			table.extend_from_slice(signature);
+			table.extend_from_slice(
+				&((offset + elements.len()) as u32).to_be_bytes(),
+			);
+			table.extend_from_slice(&(element.len() as u32).to_be_bytes());
+			elements.extend(element);
+		}
+		let mut data = Vec::new();
+		data.extend_from_slice(&((offset + elements.len()) as u32).to_be_bytes());
+		data.extend_from_slice(&[0; 4]);
+		data.extend_from_slice(&[2, 0x10, 0, 0]);
+		data.extend_from_slice(b"mntrRGB XYZ ");
+		data.extend_from_slice(&[0; 12]);
+		data.extend_from_slice(ICC_SIGNATURE);
+		data.extend_from_slice(&[0; 24]);
+		data.extend_from_slice(&[0; 4]);
+		data.extend_from_slice(&xyz(D50)[8..]);
+		data.resize(128, 0);
+		data.extend(table);
+		data.extend(elements);
+		Self { data }
+	}
+
+	/**
+	 * Embed the profile into the given PNG data as an iCCP chunk.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8
+	 */
+	pub fn embed_png(&self, bytes: &[u8]) -> Vec<u8> {
+		let mut chunk = b"ICC Profile\0\0".to_vec();
+		chunk.extend(miniz_oxide::deflate::compress_to_vec_zlib(&self.data, 6));
+		Self::insert_png_chunk(bytes, b"iCCP", &chunk)
+	}
+
+	/**
+	 * Mark the given PNG data as sRGB with the sRGB and gAMA chunks.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8
+	 */
+	pub fn embed_png_srgb(bytes: &[u8]) -> Vec<u8> {
+		let bytes =
+			Self::insert_png_chunk(bytes, b"gAMA", &SRGB_GAMMA.to_be_bytes());
+		/* Use the perceptual rendering intent */
+		Self::insert_png_chunk(&bytes, b"sRGB", &[0])
+	}
+
+	/**
+	 * Insert a chunk after the header of the given PNG data.
+	 *
+	 * @param  bytes
+	 * @param  chunk_type
+	 * @param  data
+	 * @return Vector of u8
+	 */
+	pub fn insert_png_chunk(
+		bytes: &[u8],
+		chunk_type: &[u8; 4],
+		data: &[u8],
+	) -> Vec<u8> {
+		/* Signature + IHDR (length, type, 13 bytes of data, CRC) */
+		let index = (PNG_SIGNATURE.len() + 4 + 4 + 13 + 4).min(bytes.len());
+		let mut hasher = crc32fast::Hasher::new();
+		hasher.update(chunk_type);
+		hasher.update(data);
+		let mut output = bytes[..index].to_vec();
+		output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+		output.extend_from_slice(chunk_type);
+		output.extend_from_slice(data);
+		output.extend_from_slice(&hasher.finalize().to_be_bytes());
+		output.extend_from_slice(&bytes[index..]);
+		output
+	}
+
+	/**
+	 * Embed the profile into the given JPEG data as APP2 segments.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8
+	 */
+	pub fn embed_jpeg(&self, bytes: &[u8]) -> Vec<u8> {
+		let mut index = JPEG_SOI.len();
+		/* Keep the JFIF segment first */
+		if bytes.get(index..index + 2) == Some(&[0xFF, 0xE0]) {
+			index += 2 + bytes
+				.get(index + 2..index + 4)
+				.map_or(0, |len| usize::from(u16::from_be_bytes([len[0], len[1]])));
+		}
+		let index = index.min(bytes.len());
+		let chunks = self.data.chunks(JPEG_ICC_CHUNK_SIZE);
+		let count = chunks.len() as u8;
+		let mut output = bytes[..index].to_vec();
+		for (i, chunk) in chunks.enumerate() {
+			output.extend_from_slice(&[0xFF, 0xE2]);
+			output.extend_from_slice(
+				&((2 + JPEG_ICC_MARKER.len() + 2 + chunk.len()) as u16)
+					.to_be_bytes(),
+			);
+			output.extend_from_slice(JPEG_ICC_MARKER);
+			output.extend_from_slice(&[i as u8 + 1, count]);
+			output.extend_from_slice(chunk);
+		}
+		output.extend_from_slice(&bytes[index..]);
+		output
+	}
+
+	/**
+	 * Read the ICC profile from the iCCP chunk of a PNG file.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8 (Option)
+	 */
+	fn read_png(bytes: &[u8]) -> Option<Vec<u8>> {
+		let mut i = PNG_SIGNATURE.len();
+		while i + 8 <= bytes.len() {
+			let len = u32::from_be_bytes(bytes[i..i + 4].try_into().ok()?) as usize;
+			let chunk_type = &bytes[i + 4..i + 8];
+			if chunk_type == b"IDAT" || chunk_type == b"IEND" {
+				break;
+			}
+			if chunk_type == b"iCCP" {
+				let chunk = bytes.get(i + 8..i + 8 + len)?;
+				/* Skip the profile name and the compression method */
+				let data = chunk.get(chunk.iter().position(|&b| b == 0)? + 2..)?;
+				return miniz_oxide::inflate::decompress_to_vec_zlib(data).ok();
+			}
+			i += 12 + len;
+		}
+		None
+	}
+
+	/**
+	 * Read the ICC profile from the APP2 segments of a JPEG file.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8 (Option)
+	 */
+	fn read_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+		let mut data = Vec::new();
+		let mut i = JPEG_SOI.len();
+		while i + 4 <= bytes.len() && bytes[i] == 0xFF {
+			let marker = bytes[i + 1];
+			if marker == 0xDA || marker == 0xD9 {
+				break;
+			}
+			let len = usize::from(u16::from_be_bytes([bytes[i + 2], bytes[i + 3]]));
+			let segment = bytes.get(i + 4..i + 2 + len)?;
+			if marker == 0xE2 && segment.starts_with(JPEG_ICC_MARKER) {
+				/* Skip the sequence number and the total count of chunks */
+				data.extend_from_slice(segment.get(JPEG_ICC_MARKER.len() + 2..)?);
+			}
+			i += 2 + len;
+		}
+		Some(data)
+	}
+
+	/**
+	 * Convert the sRGB-encoded value to linear light.
+	 *
+	 * @param  value
+	 * @return f64
+	 */
+	pub fn to_linear(value: f64) -> f64 {
+		if value <= 0.04045 {
+			value / 12.92
+		} else {
+			((value + 0.055) / 1.055).powf(2.4)
+		}
+	}
+
+	/**
+	 * Convert the linear light value to sRGB encoding.
+	 *
+	 * @param  value
+	 * @return f64
+	 */
+	pub fn to_srgb(value: f64) -> f64 {
+		if value <= 0.003_130_8 {
+			value * 12.92
+		} else {
+			1.055 * value.powf(1. / 2.4) - 0.055
+		}
+	}
+
+	/**
+	 * Check if the profile describes the sRGB color space.
+	 *
+	 * @return bool
+	 */
+	pub fn is_srgb(&self) -> bool {
+		self.data.windows(4).any(|w| w == b"sRGB")
+	}
+
+	/**
+	 * Get the size of the profile.
+	 *
+	 * @return usize
+	 */
+	pub fn size(&self) -> usize {
+		self.data.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::codecs::jpeg::JpegEncoder;
+	use image::codecs::png::PngEncoder;
+	use image::{ColorType, ImageEncoder, RgbaImage};
+	use pretty_assertions::assert_eq;
+	use std::io::Cursor;
+	fn jpeg_with_profile(profile: &[u8]) -> Vec<u8> {
+		let mut bytes = JPEG_SOI.to_vec();
+		let len = (2 + JPEG_ICC_MARKER.len() + 2 + profile.len()) as u16;
+		bytes.extend_from_slice(&[0xFF, 0xE2]);
+		bytes.extend_from_slice(&len.to_be_bytes());
+		bytes.extend_from_slice(JPEG_ICC_MARKER);
+		bytes.extend_from_slice(&[1, 1]);
+		bytes.extend_from_slice(profile);
+		bytes.extend_from_slice(&[0xFF, 0xD9]);
+		bytes
+	}
+	#[test]
+	fn test_color_profile() {
+		let profile =
+			ColorProfile::from_bytes(&jpeg_with_profile(b"desc sRGB IEC61966-2.1"))
+				.unwrap();
+		assert_eq!(22, profile.size());
+		assert!(profile.is_srgb());
+		let profile =
+			ColorProfile::from_bytes(&jpeg_with_profile(b"desc Display P3"))
+				.unwrap();
+		assert!(!profile.is_srgb());
+		assert_eq!(None, ColorProfile::from_bytes(&jpeg_with_profile(b"")));
+		assert_eq!(None, ColorProfile::from_bytes(PNG_SIGNATURE));
+		assert_eq!(None, ColorProfile::from_bytes(b"test"));
+	}
+	#[test]
+	fn test_embed_profile() -> IoResult<()> {
+		let srgb = ColorProfile::srgb();
+		assert!(srgb.is_srgb());
+		assert_eq!(srgb.size(), srgb.data.len());
+		assert_eq!(
+			srgb.size(),
+			u32::from_be_bytes([
+				srgb.data[0],
+				srgb.data[1],
+				srgb.data[2],
+				srgb.data[3]
+			]) as usize
+		);
+		let path = std::env::temp_dir().join("test_profile.icc");
+		fs::write(&path, &srgb.data)?;
+		assert_eq!(srgb, ColorProfile::load(&path)?);
+		fs::write(&path, b"test")?;
+		assert!(ColorProfile::load(&path).is_err());
+		fs::remove_file(path)?;
+		let image = RgbaImage::new(2, 2);
+		let mut png = Vec::new();
+		PngEncoder::new(&mut png)
+			.write_image(&image, 2, 2, ColorType::Rgba8)
+			.unwrap();
+		assert_eq!(
+			Some(srgb.clone()),
+			ColorProfile::from_bytes(&srgb.embed_png(&png))
+		);
+		let reader =
+			png::Decoder::new(Cursor::new(ColorProfile::embed_png_srgb(&png)))
+				.read_info()
+				.unwrap();
+		assert!(reader.info().srgb.is_some());
+		let mut jpeg = Vec::new();
+		JpegEncoder::new(&mut jpeg)
+			.write_image(&image, 2, 2, ColorType::Rgba8)
+			.unwrap();
+		let jpeg = srgb.embed_jpeg(&jpeg);
+		assert_eq!(Some(srgb), ColorProfile::from_bytes(&jpeg));
+		assert!(image::load_from_memory(&jpeg).is_ok());
+		Ok(())
+	}
+}
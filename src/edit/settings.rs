@@ -1,21 +1,46 @@
+use crate::app::{AppError, AppResult};
 use crate::args::matches::ArgMatches;
 use crate::args::parser::ArgParser;
+use crate::edit::op::Op;
+use crate::edit::preset::PresetStore;
 use crate::edit::ImageOps;
 use crate::image::geometry::Geometry;
 use crate::image::padding::Padding;
+use crate::record::settings::RecordCanvas;
 use image::imageops::FilterType;
+use image::Rgba;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+/* Default low and high thresholds of the Canny edge detector */
+const CANNY_THRESHOLDS: (f32, f32) = (50., 100.);
+/* Maximum radius of the median filter */
+const MEDIAN_RADIUS_LIMIT: u32 = 3;
+/* Default tolerance of the chroma key (in percent) */
+const CHROMA_KEY_TOLERANCE: f32 = 10.;
+/* Maximum distance between two RGB colors */
+const MAX_COLOR_DISTANCE: f32 = 441.673;
+/* Default diameter of the numbered badges */
+const BADGE_SIZE: u32 = 24;
+/* Default sigma of the blurred regions */
+const BLUR_REGION_SIGMA: f32 = 8.;
 
 /* Image settings */
 #[derive(Clone, Copy, Debug)]
 pub struct ImageSettings {
 	pub crop: Padding,
-	pub resize: Geometry,
+	pub resize: Option<Resize>,
 	pub ratio: f32,
 	pub flip: Option<Flip>,
 	pub rotate: u32,
 	pub blur: f32,
+	pub median: u32,
+	pub edge: Option<EdgeDetect>,
 	pub filter: FilterType,
+	pub trim: bool,
+	pub crop_area: Option<CropArea>,
+	pub linear: bool,
 }
 
 /* Default initialization values for ImageSettings */
@@ -23,12 +48,17 @@ impl Default for ImageSettings {
 	fn default() -> Self {
 		Self {
 			crop: Padding::default(),
-			resize: Geometry::default(),
+			resize: None,
 			ratio: 1.,
 			flip: None,
 			rotate: 0,
 			blur: 0.,
+			median: 0,
+			edge: None,
 			filter: FilterType::Lanczos3,
+			trim: false,
+			crop_area: None,
+			linear: false,
 		}
 	}
 }
@@ -38,21 +68,32 @@ impl ImageSettings {
 	 * Create a new ImageSettings object.
 	 *
 	 * @param  crop
-	 * @param  resize
+	 * @param  resize (Option)
 	 * @param  ratio
 	 * @param  flip (Option)
 	 * @param  rotate
 	 * @param  blur
+	 * @param  median
+	 * @param  edge (Option)
 	 * @param  filter
+	 * @param  trim
+	 * @param  crop_area (Option)
+	 * @param  linear
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		crop: Padding,
-		resize: Geometry,
+		resize: Option<Resize>,
 		ratio: f32,
 		flip: Option<Flip>,
 		rotate: u32,
 		blur: f32,
+		median: u32,
+		edge: Option<EdgeDetect>,
 		filter: FilterType,
+		trim: bool,
+		crop_area: Option<CropArea>,
+		linear: bool,
 	) -> Self {
 		Self {
 			crop,
@@ -61,7 +102,238 @@ impl ImageSettings {
 			flip,
 			rotate,
 			blur,
+			median,
+			edge,
 			filter,
+			trim,
+			crop_area,
+			linear,
+		}
+	}
+}
+
+/* Target size of the resize operation */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Resize {
+	Size(Geometry),
+	Percent(f32),
+	LongEdge(u32),
+}
+
+/* Implementation for parsing Resize from a string (WxH, N% or LN) */
+impl FromStr for Resize {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if let Some(percent) = s.strip_suffix('%') {
+			match percent.trim().parse() {
+				Ok(percent) if percent > 0. => Ok(Self::Percent(percent)),
+				_ => Err(()),
+			}
+		} else if let Some(edge) = s.strip_prefix(['L', 'l']) {
+			match edge.trim().parse() {
+				Ok(edge) if edge > 0 => Ok(Self::LongEdge(edge)),
+				_ => Err(()),
+			}
+		} else {
+			let values = s
+				.split(['x', ':'])
+				.map(|v| v.trim().parse::<u32>().ok())
+				.collect::<Option<Vec<u32>>>()
+				.ok_or(())?;
+			match values[..] {
+				[width, height] if width > 0 || height > 0 => {
+					Ok(Self::Size(Geometry::new(0, 0, width, height)))
+				}
+				_ => Err(()),
+			}
+		}
+	}
+}
+
+impl Resize {
+	/**
+	 * Get the target size for the given geometry.
+	 *
+	 * Aspect ratio is kept for the percentage, long edge
+	 * and the size with a zero width or height.
+	 *
+	 * @param  geometry
+	 * @return Geometry
+	 */
+	pub fn get_geometry(&self, geometry: Geometry) -> Geometry {
+		let scale = |ratio: f32| {
+			(
+				((geometry.width as f32 * ratio).round() as u32).max(1),
+				((geometry.height as f32 * ratio).round() as u32).max(1),
+			)
+		};
+		let (width, height) = match *self {
+			Self::Size(size) => match (size.width, size.height) {
+				(0, height) if geometry.height != 0 => {
+					(scale(height as f32 / geometry.height as f32).0, height)
+				}
+				(width, 0) if geometry.width != 0 => {
+					(width, scale(width as f32 / geometry.width as f32).1)
+				}
+				size => size,
+			},
+			Self::Percent(percent) => scale(percent / 100.),
+			Self::LongEdge(edge) => {
+				let long_edge = geometry.width.max(geometry.height);
+				if long_edge > edge {
+					scale(edge as f32 / long_edge as f32)
+				} else {
+					(geometry.width, geometry.height)
+				}
+			}
+		};
+		Geometry {
+			width,
+			height,
+			..geometry
+		}
+	}
+}
+
+/* Rectangle to crop from the image */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CropArea {
+	Center(Geometry),
+	At(Geometry),
+}
+
+/* Display implementation for user-facing output */
+impl fmt::Display for CropArea {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Center(size) => write!(f, "{size} (centered)"),
+			Self::At(area) => {
+				write!(f, "{size} at {x},{y}", size = area, x = area.x, y = area.y)
+			}
+		}
+	}
+}
+
+impl CropArea {
+	/**
+	 * Parse a centered crop area from a string (WxH).
+	 *
+	 * @param  size
+	 * @return CropArea (Option)
+	 */
+	pub fn parse_center(size: &str) -> Option<Self> {
+		let size = Geometry::parse(size);
+		if size.width > 0 && size.height > 0 {
+			Some(Self::Center(size))
+		} else {
+			None
+		}
+	}
+
+	/**
+	 * Parse an explicit crop area from a string (X,Y,W,H).
+	 *
+	 * @param  area
+	 * @return CropArea (Option)
+	 */
+	pub fn parse_at(area: &str) -> Option<Self> {
+		let values = area
+			.split(',')
+			.map(|v| v.trim().parse::<u32>().ok())
+			.collect::<Option<Vec<u32>>>()?;
+		match values[..] {
+			[x, y, width, height] if width > 0 && height > 0 => Some(Self::At(
+				Geometry::new(x.try_into().ok()?, y.try_into().ok()?, width, height),
+			)),
+			_ => None,
+		}
+	}
+
+	/**
+	 * Resolve the crop area against the size of the image.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @return Geometry (Option)
+	 */
+	pub fn get_area(&self, width: u32, height: u32) -> Option<Geometry> {
+		let area = match self {
+			Self::Center(size) => Geometry::new(
+				((i64::from(width) - i64::from(size.width)) / 2)
+					.try_into()
+					.ok()?,
+				((i64::from(height) - i64::from(size.height)) / 2)
+					.try_into()
+					.ok()?,
+				size.width,
+				size.height,
+			),
+			Self::At(area) => *area,
+		};
+		if area.x >= 0
+			&& area.y >= 0
+			&& i64::from(area.x) + i64::from(area.width) <= i64::from(width)
+			&& i64::from(area.y) + i64::from(area.height) <= i64::from(height)
+		{
+			Some(area)
+		} else {
+			None
+		}
+	}
+}
+
+/* Rectangle to blur on the image */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlurRegion {
+	pub area: Geometry,
+	pub sigma: f32,
+}
+
+/* Implementation for parsing BlurRegion from a string (X,Y,W,H:SIGMA) */
+impl FromStr for BlurRegion {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = s.trim().splitn(2, ':');
+		let area = match CropArea::parse_at(values.next().unwrap_or_default()) {
+			Some(CropArea::At(area)) => area,
+			_ => return Err(()),
+		};
+		match values.next().map(|v| v.trim().parse()) {
+			None => Ok(Self {
+				area,
+				sigma: BLUR_REGION_SIGMA,
+			}),
+			Some(Ok(sigma)) if sigma > 0. => Ok(Self { area, sigma }),
+			_ => Err(()),
+		}
+	}
+}
+
+impl BlurRegion {
+	/**
+	 * Get the part of the region inside the image.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @return Geometry (Option)
+	 */
+	pub fn get_area(&self, width: u32, height: u32) -> Option<Geometry> {
+		let (x, y) = (
+			u32::try_from(self.area.x).ok()?,
+			u32::try_from(self.area.y).ok()?,
+		);
+		let right = x.saturating_add(self.area.width).min(width);
+		let bottom = y.saturating_add(self.area.height).min(height);
+		if right > x && bottom > y {
+			Some(Geometry::new(
+				self.area.x,
+				self.area.y,
+				right - x,
+				bottom - y,
+			))
+		} else {
+			None
 		}
 	}
 }
@@ -74,6 +346,9 @@ pub struct ColorSettings {
 	pub hue: i32,
 	pub contrast: f32,
 	pub brightness: i32,
+	pub noise: (u8, bool),
+	pub seed: Option<u64>,
+	pub chroma_key: Option<ChromaKey>,
 }
 
 /* Default initialization values for ColorSettings */
@@ -85,6 +360,9 @@ impl Default for ColorSettings {
 			hue: 0,
 			contrast: 0.,
 			brightness: 0,
+			noise: (0, false),
+			seed: None,
+			chroma_key: None,
 		}
 	}
 }
@@ -98,14 +376,21 @@ impl ColorSettings {
 	 * @param  hue
 	 * @param  contrast
 	 * @param  brightness
+	 * @param  noise
+	 * @param  seed (Option)
+	 * @param  chroma_key (Option)
 	 * @return ColorSettings
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		grayscale: bool,
 		invert: bool,
 		hue: i32,
 		contrast: f32,
 		brightness: i32,
+		noise: (u8, bool),
+		seed: Option<u64>,
+		chroma_key: Option<ChromaKey>,
 	) -> Self {
 		Self {
 			grayscale,
@@ -113,6 +398,131 @@ impl ColorSettings {
 			hue,
 			contrast,
 			brightness,
+			noise,
+			seed,
+			chroma_key,
+		}
+	}
+}
+
+/* Color to replace with transparency */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChromaKey {
+	pub color: Rgba<u8>,
+	pub tolerance: f32,
+	pub feather: f32,
+}
+
+/* Implementation for parsing ChromaKey from a string */
+impl FromStr for ChromaKey {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = s.trim().split(':');
+		let color =
+			RecordCanvas::parse_color(values.next().unwrap_or_default()).ok_or(())?;
+		let mut next_percentage = |default: f32| match values.next() {
+			Some(value) => value
+				.parse()
+				.ok()
+				.filter(|value| (0. ..=100.).contains(value))
+				.ok_or(()),
+			None => Ok(default),
+		};
+		let tolerance = next_percentage(CHROMA_KEY_TOLERANCE)?;
+		let feather = next_percentage(0.)?;
+		match values.next() {
+			Some(_) => Err(()),
+			None => Ok(Self {
+				color,
+				tolerance,
+				feather,
+			}),
+		}
+	}
+}
+
+impl ChromaKey {
+	/**
+	 * Get the distance between the RGB values of two colors (in percent).
+	 *
+	 * @param  first
+	 * @param  second
+	 * @return f32
+	 */
+	pub fn get_distance(first: &Rgba<u8>, second: &Rgba<u8>) -> f32 {
+		(0..3)
+			.map(|c| (f32::from(first[c]) - f32::from(second[c])).powi(2))
+			.sum::<f32>()
+			.sqrt() / MAX_COLOR_DISTANCE
+			* 100.
+	}
+
+	/**
+	 * Get the opacity to apply on the given color.
+	 *
+	 * Colors within the tolerance are fully transparent and
+	 * the ones within the feathering range fade in linearly.
+	 *
+	 * @param  rgba
+	 * @return f32
+	 */
+	pub fn get_opacity(&self, rgba: &Rgba<u8>) -> f32 {
+		let distance = Self::get_distance(rgba, &self.color);
+		if distance <= self.tolerance {
+			0.
+		} else if distance < self.tolerance + self.feather {
+			(distance - self.tolerance) / self.feather
+		} else {
+			1.
+		}
+	}
+}
+
+/* Color to replace with another color */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorReplace {
+	pub from: Rgba<u8>,
+	pub to: Rgba<u8>,
+	pub tolerance: f32,
+}
+
+/* Implementation for parsing ColorReplace from a string (FROM:TO:TOLERANCE) */
+impl FromStr for ColorReplace {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = s.trim().split(':');
+		let mut next_color = || {
+			RecordCanvas::parse_color(values.next().unwrap_or_default()).ok_or(())
+		};
+		let (from, to) = (next_color()?, next_color()?);
+		let tolerance = match values.next() {
+			Some(value) => value
+				.parse()
+				.ok()
+				.filter(|value| (0. ..=100.).contains(value))
+				.ok_or(())?,
+			None => 0.,
+		};
+		match values.next() {
+			Some(_) => Err(()),
+			None => Ok(Self {
+				from,
+				to,
+				tolerance,
+			}),
+		}
+	}
+}
+
+impl ColorReplace {
+	/**
+	 * Replace the color if it is within the tolerance, preserving the alpha.
+	 *
+	 * @param  rgba
+	 */
+	pub fn apply(&self, rgba: &mut Rgba<u8>) {
+		if ChromaKey::get_distance(rgba, &self.from) <= self.tolerance {
+			rgba.0[..3].copy_from_slice(&self.to.0[..3]);
 		}
 	}
 }
@@ -124,6 +534,212 @@ pub enum Flip {
 	Vertical,
 }
 
+/* Edge detection method */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeDetect {
+	Sobel,
+	Canny(f32, f32),
+}
+
+/* Implementation for parsing EdgeDetect from a string */
+impl FromStr for EdgeDetect {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = s.trim().split(':');
+		match (values.next(), values.next(), values.next(), values.next()) {
+			(Some("sobel"), None, None, None) => Ok(Self::Sobel),
+			(Some("canny"), None, None, None) => {
+				Ok(Self::Canny(CANNY_THRESHOLDS.0, CANNY_THRESHOLDS.1))
+			}
+			(Some("canny"), Some(low), Some(high), None) => {
+				match (low.parse(), high.parse()) {
+					(Ok(low), Ok(high)) if low <= high => Ok(Self::Canny(low, high)),
+					_ => Err(()),
+				}
+			}
+			_ => Err(()),
+		}
+	}
+}
+
+/* Strategy for flattening the frames into a single image */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flatten {
+	First,
+	Last,
+	Average,
+	Max,
+}
+
+/* Implementation for parsing Flatten from a string */
+impl FromStr for Flatten {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.trim() {
+			"first" => Ok(Self::First),
+			"last" => Ok(Self::Last),
+			"average" => Ok(Self::Average),
+			"max" => Ok(Self::Max),
+			_ => Err(()),
+		}
+	}
+}
+
+/* Layout of the contact sheet of the animation frames */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContactSheet {
+	pub cols: u32,
+	pub every: usize,
+	pub size: Option<u32>,
+	pub labels: bool,
+}
+
+/* Default initialization values for ContactSheet */
+impl Default for ContactSheet {
+	fn default() -> Self {
+		Self {
+			cols: 5,
+			every: 1,
+			size: None,
+			labels: false,
+		}
+	}
+}
+
+impl ContactSheet {
+	/**
+	 * Create a new ContactSheet object.
+	 *
+	 * @param  cols
+	 * @param  every
+	 * @param  size (Option)
+	 * @param  labels
+	 * @return ContactSheet
+	 */
+	pub fn new(cols: u32, every: usize, size: Option<u32>, labels: bool) -> Self {
+		Self {
+			cols: cols.max(1),
+			every: every.max(1),
+			size,
+			labels,
+		}
+	}
+
+	/**
+	 * Create a ContactSheet object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return ContactSheet (Option)
+	 */
+	fn from_parser(parser: &ArgParser<'_>) -> Option<Self> {
+		let matches = parser.args.as_ref()?;
+		if !matches.is_present("contact-sheet") {
+			return None;
+		}
+		Some(Self::new(
+			parser.parse("cols", Self::default().cols),
+			parser.parse("every", Self::default().every),
+			matches
+				.value_of("thumb-size")
+				.and_then(|v| v.parse().ok())
+				.filter(|size| *size > 0),
+			matches.is_present("labels"),
+		))
+	}
+}
+
+/* Numbered badges to draw on the image */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Badges {
+	pub positions: Vec<(i32, i32)>,
+	pub size: u32,
+	pub color: Rgba<u8>,
+}
+
+/* Default initialization values for Badges */
+impl Default for Badges {
+	fn default() -> Self {
+		Self {
+			positions: Vec::new(),
+			size: BADGE_SIZE,
+			color: Rgba([255, 0, 0, 255]),
+		}
+	}
+}
+
+impl Badges {
+	/**
+	 * Create a new Badges object.
+	 *
+	 * @param  positions
+	 * @param  size
+	 * @param  color
+	 * @return Badges
+	 */
+	pub fn new(positions: Vec<(i32, i32)>, size: u32, color: Rgba<u8>) -> Self {
+		Self {
+			positions,
+			size: size.max(1),
+			color,
+		}
+	}
+
+	/**
+	 * Parse the position of a badge.
+	 *
+	 * @param  position
+	 * @return Tuple (Option)
+	 */
+	pub fn parse_position(position: &str) -> Option<(i32, i32)> {
+		match position
+			.split(',')
+			.map(|v| v.trim().parse().ok())
+			.collect::<Option<Vec<i32>>>()?
+			.as_slice()
+		{
+			[x, y] => Some((*x, *y)),
+			_ => None,
+		}
+	}
+
+	/**
+	 * Create a Badges object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return Badges
+	 */
+	fn from_parser(parser: &ArgParser<'_>) -> Self {
+		let matches = match parser.args.as_ref() {
+			Some(matches) => matches,
+			None => return Self::default(),
+		};
+		let values = match matches.values_of("badge") {
+			Some(values) => values.collect(),
+			None => matches
+				.value_of("badge")
+				.map(|v| vec![v])
+				.unwrap_or_default(),
+		};
+		Self::new(
+			values
+				.into_iter()
+				.filter_map(|v| {
+					let position = Self::parse_position(v);
+					if position.is_none() {
+						warn!("Invalid badge position: {:?}", v);
+					}
+					position
+				})
+				.collect(),
+			parser.parse("badge-size", Self::default().size),
+			matches
+				.value_of("badge-color")
+				.and_then(RecordCanvas::parse_color)
+				.unwrap_or(Self::default().color),
+		)
+	}
+}
+
 /* Image editing settings */
 #[derive(Debug)]
 pub struct EditSettings {
@@ -131,6 +747,19 @@ pub struct EditSettings {
 	pub convert: bool,
 	pub image: ImageSettings,
 	pub color: ColorSettings,
+	pub ops: Option<String>,
+	pub strip: bool,
+	pub frame: Option<usize>,
+	pub flatten: Option<Flatten>,
+	pub preset: Option<String>,
+	pub presets: PresetStore,
+	pub auto_orient: bool,
+	pub watch: bool,
+	pub contact_sheet: Option<ContactSheet>,
+	pub badges: Badges,
+	pub blur_regions: Vec<BlurRegion>,
+	pub mask: Option<PathBuf>,
+	pub color_replaces: Vec<ColorReplace>,
 }
 
 /* Default initialization values for EditSettings */
@@ -141,6 +770,19 @@ impl Default for EditSettings {
 			convert: false,
 			image: ImageSettings::default(),
 			color: ColorSettings::default(),
+			ops: None,
+			strip: false,
+			frame: None,
+			flatten: None,
+			preset: None,
+			presets: PresetStore::default(),
+			auto_orient: true,
+			watch: false,
+			contact_sheet: None,
+			badges: Badges::default(),
+			blur_regions: Vec::new(),
+			mask: None,
+			color_replaces: Vec::new(),
 		}
 	}
 }
@@ -153,19 +795,57 @@ impl EditSettings {
 	 * @param  convert
 	 * @param  image
 	 * @param  color
+	 * @param  ops (Option)
+	 * @param  strip
+	 * @param  frame (Option)
+	 * @param  flatten (Option)
+	 * @param  preset (Option)
+	 * @param  auto_orient
+	 * @param  watch
+	 * @param  contact_sheet (Option)
+	 * @param  badges
+	 * @param  blur_regions
+	 * @param  mask (Option)
+	 * @param  color_replaces
 	 * @return EditSettings
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		path: PathBuf,
 		convert: bool,
 		image: ImageSettings,
 		color: ColorSettings,
+		ops: Option<String>,
+		strip: bool,
+		frame: Option<usize>,
+		flatten: Option<Flatten>,
+		preset: Option<String>,
+		auto_orient: bool,
+		watch: bool,
+		contact_sheet: Option<ContactSheet>,
+		badges: Badges,
+		blur_regions: Vec<BlurRegion>,
+		mask: Option<PathBuf>,
+		color_replaces: Vec<ColorReplace>,
 	) -> Self {
 		Self {
 			path,
 			convert,
 			image,
 			color,
+			ops,
+			strip,
+			frame,
+			flatten,
+			preset,
+			presets: PresetStore::default(),
+			auto_orient,
+			watch,
+			contact_sheet,
+			badges,
+			blur_regions,
+			mask,
+			color_replaces,
 		}
 	}
 
@@ -197,9 +877,11 @@ impl EditSettings {
 					matches.is_present("convert"),
 					ImageSettings::new(
 						Padding::parse(matches.value_of("crop").unwrap_or_default()),
-						Geometry::parse(
-							matches.value_of("resize").unwrap_or_default(),
-						),
+						matches.value_of("resize").and_then(|v| {
+							Resize::from_str(v)
+								.map_err(|_| warn!("Invalid resize: {:?}", v))
+								.ok()
+						}),
 						parser.parse("ratio", ImageSettings::default().ratio),
 						match matches.value_of("flip") {
 							Some("horizontal") => Some(Flip::Horizontal),
@@ -208,6 +890,24 @@ impl EditSettings {
 						},
 						parser.parse("rotate", ImageSettings::default().rotate),
 						parser.parse("blur", ImageSettings::default().blur),
+						match parser.parse("median", ImageSettings::default().median)
+						{
+							median if median > MEDIAN_RADIUS_LIMIT => {
+								warn!(
+									"Median radius is limited to {}.",
+									MEDIAN_RADIUS_LIMIT
+								);
+								MEDIAN_RADIUS_LIMIT
+							}
+							median => median,
+						},
+						matches.value_of("edge-detect").and_then(|v| {
+							EdgeDetect::from_str(v)
+								.map_err(|_| {
+									warn!("Invalid edge detection: {:?}", v)
+								})
+								.ok()
+						}),
 						match matches.value_of("filter") {
 							Some("nearest") => FilterType::Nearest,
 							Some("triangle") => FilterType::Triangle,
@@ -215,6 +915,9 @@ impl EditSettings {
 							Some("gaussian") => FilterType::Gaussian,
 							_ => FilterType::Lanczos3,
 						},
+						matches.is_present("trim-transparent"),
+						Self::get_crop_area(matches),
+						matches.value_of("color-space") == Some("linear"),
 					),
 					ColorSettings::new(
 						matches.is_present("grayscale"),
@@ -225,19 +928,145 @@ impl EditSettings {
 							"brightness",
 							ColorSettings::default().brightness,
 						),
+						(
+							parser.parse("noise", ColorSettings::default().noise.0),
+							matches.is_present("noise-mono"),
+						),
+						matches.value_of("seed").and_then(|v| v.parse().ok()),
+						matches.value_of("chroma-key").and_then(|v| {
+							ChromaKey::from_str(v)
+								.map_err(|_| warn!("Invalid chroma key: {:?}", v))
+								.ok()
+						}),
 					),
+					matches.value_of("ops").map(String::from),
+					matches.is_present("strip"),
+					matches.value_of("frame").and_then(|v| v.parse().ok()),
+					matches
+						.value_of("flatten")
+						.and_then(|v| Flatten::from_str(v).ok()),
+					matches.value_of("preset").map(String::from),
+					!matches.is_present("no-auto-orient"),
+					matches.is_present("watch"),
+					ContactSheet::from_parser(&parser),
+					Badges::from_parser(&parser),
+					Self::get_blur_regions(matches),
+					matches.value_of("mask").map(|mask| {
+						PathBuf::from(
+							shellexpand::full(mask)
+								.map(|s| s.to_string())
+								.unwrap_or(mask.to_string()),
+						)
+					}),
+					Self::get_color_replaces(matches),
 				)
 			}
 			None => Self::default(),
 		}
 	}
 
+	/**
+	 * Get the crop area from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return CropArea (Option)
+	 */
+	fn get_crop_area(matches: &ArgMatches<'_>) -> Option<CropArea> {
+		let (value, crop_area) = if let Some(size) = matches.value_of("crop-center")
+		{
+			(size, CropArea::parse_center(size))
+		} else {
+			let area = matches.value_of("crop-at")?;
+			(area, CropArea::parse_at(area))
+		};
+		if crop_area.is_none() {
+			warn!("Invalid crop area: {:?}", value);
+		}
+		crop_area
+	}
+
+	/**
+	 * Get the regions to blur from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return Vector of BlurRegion
+	 */
+	fn get_blur_regions(matches: &ArgMatches<'_>) -> Vec<BlurRegion> {
+		let values = match matches.values_of("blur-region") {
+			Some(values) => values.collect(),
+			None => matches
+				.value_of("blur-region")
+				.map(|v| vec![v])
+				.unwrap_or_default(),
+		};
+		values
+			.into_iter()
+			.filter_map(|v| {
+				BlurRegion::from_str(v)
+					.map_err(|_| warn!("Invalid blur region: {:?}", v))
+					.ok()
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the color replacements from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return Vector of ColorReplace
+	 */
+	fn get_color_replaces(matches: &ArgMatches<'_>) -> Vec<ColorReplace> {
+		let values = match matches.values_of("replace-color") {
+			Some(values) => values.collect(),
+			None => matches
+				.value_of("replace-color")
+				.map(|v| vec![v])
+				.unwrap_or_default(),
+		};
+		values
+			.into_iter()
+			.filter_map(|v| {
+				ColorReplace::from_str(v)
+					.map_err(|_| warn!("Invalid color replacement: {:?}", v))
+					.ok()
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the ordered chain of editing operations.
+	 *
+	 * The chain that is given with --ops cannot be combined
+	 * with the individual flags since their order is unknown.
+	 *
+	 * @return Vector of Op (Result)
+	 */
+	pub fn get_ops(&self) -> AppResult<Vec<Op>> {
+		let mut ops = match self.preset.as_deref() {
+			Some(preset) => Op::parse_chain(&self.presets.get(preset)?)?,
+			None => Vec::new(),
+		};
+		let flag_ops = Op::from_settings(self);
+		match &self.ops {
+			Some(spec) => {
+				if flag_ops.iter().any(|op| *op != Op::Mask) {
+					return Err(AppError::EditError(String::from(
+						"--ops cannot be combined with the individual edit flags",
+					)));
+				}
+				ops.extend(Op::parse_chain(spec)?);
+			}
+			None => ops.extend(flag_ops),
+		}
+		Ok(ops)
+	}
+
 	/**
 	 * Get ImageOps object from EditSettings.
 	 *
-	 * @return ImageOps
+	 * @return ImageOps (Result)
 	 */
-	pub fn get_imageops(&self) -> ImageOps<'_> {
+	pub fn get_imageops(&self) -> AppResult<ImageOps<'_>> {
 		ImageOps::new(self)
 	}
 }
@@ -247,19 +1076,35 @@ mod tests {
 	use super::*;
 	use clap::{App, Arg};
 	use pretty_assertions::assert_eq;
+	use std::env;
+	use std::fs;
 	#[test]
-	fn test_edit_settings() {
+	fn test_edit_settings() -> AppResult<()> {
 		let args = App::new("test")
 			.arg(Arg::with_name("file"))
 			.arg(Arg::with_name("convert").long("convert"))
+			.arg(Arg::with_name("strip").long("strip"))
+			.arg(Arg::with_name("no-auto-orient").long("no-auto-orient"))
 			.arg(Arg::with_name("grayscale").long("grayscale"))
 			.arg(Arg::with_name("invert").long("invert"))
 			.arg(Arg::with_name("crop").long("crop").takes_value(true))
+			.arg(
+				Arg::with_name("crop-center")
+					.long("crop-center")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("trim-transparent").long("trim-transparent"))
 			.arg(Arg::with_name("resize").long("resize").takes_value(true))
 			.arg(Arg::with_name("ratio").long("ratio").takes_value(true))
 			.arg(Arg::with_name("flip").long("flip").takes_value(true))
 			.arg(Arg::with_name("rotate").long("rotate").takes_value(true))
 			.arg(Arg::with_name("blur").long("blur").takes_value(true))
+			.arg(Arg::with_name("median").long("median").takes_value(true))
+			.arg(
+				Arg::with_name("edge-detect")
+					.long("edge-detect")
+					.takes_value(true),
+			)
 			.arg(Arg::with_name("hue").long("hue").takes_value(true))
 			.arg(
 				Arg::with_name("contrast")
@@ -273,12 +1118,47 @@ mod tests {
 					.takes_value(true),
 			)
 			.arg(Arg::with_name("filter").long("filter").takes_value(true))
+			.arg(Arg::with_name("noise").long("noise").takes_value(true))
+			.arg(Arg::with_name("noise-mono").long("noise-mono"))
+			.arg(Arg::with_name("seed").long("seed").takes_value(true))
+			.arg(Arg::with_name("ops").long("ops").takes_value(true))
+			.arg(Arg::with_name("contact-sheet").long("contact-sheet"))
+			.arg(Arg::with_name("cols").long("cols").takes_value(true))
+			.arg(Arg::with_name("every").long("every").takes_value(true))
+			.arg(
+				Arg::with_name("thumb-size")
+					.long("thumb-size")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("labels").long("labels"))
+			.arg(
+				Arg::with_name("badge")
+					.long("badge")
+					.allow_hyphen_values(true)
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("badge-size")
+					.long("badge-size")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("badge-color")
+					.long("badge-color")
+					.takes_value(true),
+			)
 			.get_matches_from(vec![
 				"test",
 				"x",
 				"--convert",
+				"--strip",
 				"--crop",
 				"10",
+				"--crop-center",
+				"80x60",
+				"--trim-transparent",
 				"--resize",
 				"100:100",
 				"--ratio",
@@ -289,6 +1169,10 @@ mod tests {
 				"90",
 				"--blur",
 				"1.5",
+				"--median",
+				"5",
+				"--edge-detect",
+				"canny:10:20",
 				"--grayscale",
 				"--invert",
 				"--hue",
@@ -299,20 +1183,120 @@ mod tests {
 				"2",
 				"--filter",
 				"triangle",
+				"--noise",
+				"20",
+				"--noise-mono",
+				"--seed",
+				"42",
+				"--ops",
+				"blur:2;invert",
+				"--contact-sheet",
+				"--cols",
+				"4",
+				"--every",
+				"0",
+				"--thumb-size",
+				"160",
+				"--labels",
+				"--badge",
+				"10,20",
+				"--badge",
+				"x,20",
+				"--badge",
+				"-5, 30",
+				"--badge-size",
+				"32",
+				"--badge-color",
+				"#0000ff",
 			]);
 		let edit_settings = EditSettings::from_parser(ArgParser::from_args(&args));
 		assert_eq!(PathBuf::from("x"), edit_settings.path);
 		assert_eq!(true, edit_settings.convert);
+		assert_eq!(true, edit_settings.strip);
+		assert_eq!(true, edit_settings.auto_orient);
 		assert_eq!(10, edit_settings.image.crop.top);
+		assert_eq!(
+			Some(CropArea::Center(Geometry::new(0, 0, 80, 60))),
+			edit_settings.image.crop_area
+		);
+		assert_eq!(true, edit_settings.image.trim);
+		assert_eq!(
+			Some(Resize::Size(Geometry::new(0, 0, 100, 100))),
+			edit_settings.image.resize
+		);
+		let geometry = Geometry::new(0, 0, 1600, 900);
+		for (resize, size) in [
+			("300x200", (300, 200)),
+			("300:0", (300, 169)),
+			("50%", (800, 450)),
+			("12.5%", (200, 113)),
+			("L1200", (1200, 675)),
+			("l2000", (1600, 900)),
+		] {
+			let resize = Resize::from_str(resize).map(|v| v.get_geometry(geometry));
+			assert_eq!(Ok(size), resize.map(|v| (v.width, v.height)));
+		}
+		for resize in ["0x0", "300", "300x200x100", "0%", "-50%", "L0", "Lx", "x"] {
+			assert_eq!(Err(()), Resize::from_str(resize));
+		}
 		assert_eq!(0.5, edit_settings.image.ratio);
 		assert_eq!(Some(Flip::Horizontal), edit_settings.image.flip);
 		assert_eq!(90, edit_settings.image.rotate);
 		assert_eq!(1.5, edit_settings.image.blur);
+		assert_eq!(MEDIAN_RADIUS_LIMIT, edit_settings.image.median);
+		assert_eq!(Some(EdgeDetect::Canny(10., 20.)), edit_settings.image.edge);
+		assert_eq!(Ok(EdgeDetect::Sobel), EdgeDetect::from_str("sobel"));
+		assert_eq!(
+			Ok(EdgeDetect::Canny(50., 100.)),
+			EdgeDetect::from_str("canny")
+		);
+		assert_eq!(Err(()), EdgeDetect::from_str("canny:20:10"));
 		assert_eq!(true, edit_settings.color.grayscale);
 		assert_eq!(true, edit_settings.color.invert);
 		assert_eq!(2, edit_settings.color.brightness);
 		assert_eq!(3, edit_settings.color.hue);
 		assert_eq!(-5., edit_settings.color.contrast);
+		assert_eq!((20, true), edit_settings.color.noise);
+		assert_eq!(Some(42), edit_settings.color.seed);
+		assert_eq!(
+			Some(ContactSheet::new(4, 1, Some(160), true)),
+			edit_settings.contact_sheet
+		);
+		assert_eq!(
+			None,
+			ContactSheet::from_parser(&ArgParser::from_args(
+				&App::new("test").get_matches_from(vec!["test"])
+			))
+		);
+		assert_eq!(
+			Badges::new(vec![(10, 20), (-5, 30)], 32, Rgba([0, 0, 255, 255])),
+			edit_settings.badges
+		);
+		assert_eq!(None, Badges::parse_position("10,20,30"));
 		assert_eq!("Triangle", format!("{:?}", edit_settings.image.filter));
+		assert!(edit_settings.get_ops().is_err());
+		let edit_settings = EditSettings {
+			ops: edit_settings.ops,
+			..EditSettings::default()
+		};
+		assert_eq!(vec![Op::Blur(2.), Op::Invert], edit_settings.get_ops()?);
+		let path = env::temp_dir().join("menyoki_test_edit_presets.toml");
+		fs::write(&path, "[test]\nops = \"ratio:0.5;grayscale\"\n")?;
+		let mut edit_settings = EditSettings {
+			preset: Some(String::from("test")),
+			presets: PresetStore::new(path.clone()),
+			..edit_settings
+		};
+		assert_eq!(
+			vec![Op::Ratio(0.5), Op::Grayscale, Op::Blur(2.), Op::Invert],
+			edit_settings.get_ops()?
+		);
+		edit_settings.preset = Some(String::from("tset"));
+		assert!(edit_settings.get_ops().is_err());
+		edit_settings.preset = Some(String::from("test"));
+		edit_settings.ops = Some(String::from("blur:2;rotate:45"));
+		assert!(edit_settings.get_ops().is_err());
+		fs::remove_file(path)?;
+		Ok(())
 	}
 }
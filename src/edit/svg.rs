@@ -0,0 +1,129 @@
+use crate::app::{AppError, AppResult};
+use crate::edit::settings::Resize;
+use crate::image::geometry::Geometry;
+use image::{Rgba, RgbaImage};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+use std::fs;
+use std::path::Path;
+
+/* Rasterizer for SVG files */
+#[derive(Debug)]
+pub struct SvgRasterizer {
+	tree: Tree,
+}
+
+impl SvgRasterizer {
+	/**
+	 * Check if the given file is an SVG file.
+	 *
+	 * @param  path
+	 * @return bool
+	 */
+	pub fn is_svg(path: &Path) -> bool {
+		path.extension()
+			.and_then(|extension| extension.to_str())
+			.map(|extension| {
+				extension.eq_ignore_ascii_case("svg")
+					|| extension.eq_ignore_ascii_case("svgz")
+			})
+			.unwrap_or(false)
+	}
+
+	/**
+	 * Open and parse the given SVG file.
+	 *
+	 * @param  path
+	 * @return SvgRasterizer (Result)
+	 */
+	pub fn open(path: &Path) -> AppResult<Self> {
+		Self::from_data(&fs::read(path)?)
+	}
+
+	/**
+	 * Parse the given SVG data.
+	 *
+	 * @param  data
+	 * @return SvgRasterizer (Result)
+	 */
+	pub fn from_data(data: &[u8]) -> AppResult<Self> {
+		Tree::from_data(data, &Options::default())
+			.map(|tree| Self { tree })
+			.map_err(|e| AppError::Svg(e.to_string()))
+	}
+
+	/**
+	 * Get the natural size of the SVG.
+	 *
+	 * @return Geometry
+	 */
+	fn get_geometry(&self) -> Geometry {
+		Geometry::new(
+			0,
+			0,
+			self.tree.size().width().ceil() as u32,
+			self.tree.size().height().ceil() as u32,
+		)
+	}
+
+	/**
+	 * Render the SVG with the given resize (or the natural size).
+	 *
+	 * @param  resize (Option)
+	 * @return RgbaImage (Result)
+	 */
+	pub fn rasterize(&self, resize: Option<Resize>) -> AppResult<RgbaImage> {
+		let geometry = self.get_geometry();
+		let Geometry { width, height, .. } =
+			resize.map_or(geometry, |resize| resize.get_geometry(geometry));
+		info!("Rasterizing the SVG... ({}x{})", width, height);
+		let mut pixmap = Pixmap::new(width, height).ok_or_else(|| {
+			AppError::Svg(format!("Invalid size: {width}x{height}"))
+		})?;
+		resvg::render(
+			&self.tree,
+			Transform::from_scale(
+				width as f32 / self.tree.size().width(),
+				height as f32 / self.tree.size().height(),
+			),
+			&mut pixmap.as_mut(),
+		);
+		Ok(RgbaImage::from_fn(width, height, |x, y| {
+			pixmap
+				.pixel(x, y)
+				.map(|pixel| {
+					let pixel = pixel.demultiply();
+					Rgba([pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()])
+				})
+				.unwrap_or(Rgba([0, 0, 0, 0]))
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_svg_rasterizer() -> AppResult<()> {
+		let rasterizer = SvgRasterizer::from_data(
+			br#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="5">
+				<rect width="5" height="5" fill="red"/>
+			</svg>"#,
+		)?;
+		let image = rasterizer.rasterize(None)?;
+		assert_eq!((10, 5), image.dimensions());
+		assert_eq!(Rgba([255, 0, 0, 255]), *image.get_pixel(2, 2));
+		assert_eq!(Rgba([0, 0, 0, 0]), *image.get_pixel(7, 2));
+		let image =
+			rasterizer.rasterize(Some(Resize::Size(Geometry::new(0, 0, 20, 0))))?;
+		assert_eq!((20, 10), image.dimensions());
+		let image = rasterizer.rasterize(Some(Resize::Percent(200.)))?;
+		assert_eq!((20, 10), image.dimensions());
+		assert_eq!(Rgba([255, 0, 0, 255]), *image.get_pixel(5, 5));
+		assert!(SvgRasterizer::is_svg(Path::new("test.SVG")));
+		assert!(!SvgRasterizer::is_svg(Path::new("test.png")));
+		assert!(SvgRasterizer::from_data(b"<svg").is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,116 @@
+use crate::app::AppResult;
+use notify::{
+	Event, EventKind, RecommendedWatcher, RecursiveMode, Result as NotifyResult,
+	Watcher,
+};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/* Time to wait for the consecutive events of a single change */
+const DEBOUNCE_TIME: Duration = Duration::from_millis(250);
+
+/* Watcher for the changes of a file */
+pub struct InputWatcher {
+	path: PathBuf,
+	receiver: Receiver<NotifyResult<Event>>,
+	_watcher: RecommendedWatcher,
+}
+
+impl InputWatcher {
+	/**
+	 * Create a new InputWatcher object.
+	 *
+	 * The parent directory is watched instead of the file itself
+	 * since the editors usually replace the file while saving.
+	 *
+	 * @param  path
+	 * @return InputWatcher (Result)
+	 */
+	pub fn new(path: &Path) -> AppResult<Self> {
+		let path = path.canonicalize()?;
+		let (sender, receiver) = mpsc::channel();
+		let mut watcher = notify::recommended_watcher(sender)?;
+		watcher.watch(
+			path.parent().unwrap_or_else(|| Path::new("/")),
+			RecursiveMode::NonRecursive,
+		)?;
+		Ok(Self {
+			path,
+			receiver,
+			_watcher: watcher,
+		})
+	}
+
+	/**
+	 * Check if the event is a modification of the watched file.
+	 *
+	 * @param  event
+	 * @return bool
+	 */
+	fn is_changed(&self, event: &NotifyResult<Event>) -> bool {
+		match event {
+			Ok(event) => {
+				matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+					&& event.paths.iter().any(|path| {
+						path == &self.path
+							|| path.file_name() == self.path.file_name()
+					})
+			}
+			Err(e) => {
+				warn!("Failed to watch {:?}: {}", self.path, e);
+				false
+			}
+		}
+	}
+
+	/**
+	 * Block until the file changes and its events are settled.
+	 *
+	 * @return bool
+	 */
+	pub fn wait(&self) -> bool {
+		loop {
+			match self.receiver.recv() {
+				Ok(event) if self.is_changed(&event) => break,
+				Ok(_) => continue,
+				Err(_) => return false,
+			}
+		}
+		loop {
+			match self.receiver.recv_timeout(DEBOUNCE_TIME) {
+				Ok(_) => continue,
+				Err(RecvTimeoutError::Timeout) => return true,
+				Err(RecvTimeoutError::Disconnected) => return false,
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::env;
+	use std::fs;
+	use std::thread;
+	#[test]
+	fn test_input_watcher() -> AppResult<()> {
+		let dir = env::temp_dir().join("menyoki_test_watch");
+		fs::create_dir_all(&dir)?;
+		let path = dir.join("input.png");
+		fs::write(&path, [0])?;
+		let watcher = InputWatcher::new(&path)?;
+		let file = path.clone();
+		let writer = thread::spawn(move || {
+			thread::sleep(Duration::from_millis(50));
+			fs::write(dir.join("other.png"), [0]).unwrap();
+			for i in 0..3 {
+				fs::write(&file, [i]).unwrap();
+			}
+		});
+		assert!(watcher.wait());
+		writer.join().expect("Failed to join the thread");
+		fs::remove_dir_all(path.parent().unwrap_or(&path))?;
+		Ok(())
+	}
+}
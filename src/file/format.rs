@@ -67,8 +67,12 @@ impl FileFormat {
 	) -> Self {
 		match args.subcommand_matches(if args.is_present("edit") {
 			"edit"
+		} else if args.is_present("montage") {
+			"montage"
 		} else if args.is_present("split") {
 			"split"
+		} else if args.is_present("spritesheet") {
+			"spritesheet"
 		} else if args.is_present("analyze") {
 			"analyze"
 		} else {
@@ -112,7 +116,10 @@ impl FileFormat {
 				}
 			}
 			None => {
-				if let Some(matches) = args.subcommand_matches("record") {
+				if let Some(matches) = args
+					.subcommand_matches("record")
+					.or_else(|| args.subcommand_matches("encode"))
+				{
 					if matches.is_present("apng") {
 						Self::Apng
 					} else {
@@ -147,7 +154,7 @@ impl FileFormat {
 	 *
 	 * @return String
 	 */
-	fn get_default_file_name(&self) -> String {
+	pub fn get_default_file_name(&self) -> String {
 		String::from(match self {
 			Self::Any => "output",
 			Self::Txt => "report",
@@ -165,6 +172,19 @@ impl FileFormat {
 		self == &Self::Gif || self == &Self::Apng
 	}
 
+	/**
+	 * Check if the format can store the transparency.
+	 *
+	 * @return bool
+	 */
+	pub fn has_alpha(&self) -> bool {
+		match self {
+			Self::Jpg | Self::WebP | Self::Txt => false,
+			Self::Pnm(v) => v == "pam",
+			_ => true,
+		}
+	}
+
 	/**
 	 * Get extension from format.
 	 *
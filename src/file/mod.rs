@@ -1,16 +1,21 @@
 pub mod format;
 pub mod info;
 pub mod settings;
+pub mod verify;
 
 use crate::file::format::FileFormat;
 use std::env;
 use std::ffi::OsStr;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io;
+use std::path::{Path, PathBuf, MAIN_SEPARATOR};
 
 /* Extension of the configuration file */
 const CONFIG_FILE_EXTENSION: &str = "conf";
 
+/* Placeholder of the counter in the file name */
+const COUNTER_PLACEHOLDER: &str = "{n";
+
 /* Representation of the output file */
 #[derive(Debug)]
 pub struct File {
@@ -28,22 +33,36 @@ impl File {
 	 * @return File
 	 */
 	pub fn new(mut path: PathBuf, format: FileFormat, with_extension: bool) -> Self {
-		Self::create_path(&path);
 		if with_extension || path.extension().and_then(OsStr::to_str) == Some("*") {
 			path = Self::get_path_with_extension(path, &format)
 		}
+		path = Self::get_path_with_counter(path);
 		Self { path, format }
 	}
 
 	/**
-	 * Create the path if it does not exist.
+	 * Check if the directory of the file exists or create it.
 	 *
-	 * @param path
+	 * @param  create
+	 * @return Result
 	 */
-	fn create_path(path: &Path) {
-		if !path.exists() && !cfg!(test) {
-			fs::create_dir_all(path.parent().expect("Failed to get the directory"))
-				.unwrap_or_else(|_| panic!("Failed to create directory: {path:?}"));
+	pub fn check_dir(&self, create: bool) -> io::Result<()> {
+		match self
+			.path
+			.parent()
+			.filter(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+		{
+			Some(dir) if create => {
+				debug!("Creating the directory: {:?}", dir);
+				fs::create_dir_all(dir)
+			}
+			Some(dir) => Err(io::Error::new(
+				io::ErrorKind::NotFound,
+				format!(
+					"Directory does not exist: {dir:?} (use --mkdir to create it)"
+				),
+			)),
+			None => Ok(()),
 		}
 	}
 
@@ -61,6 +80,112 @@ impl File {
 		}
 	}
 
+	/**
+	 * Get the path with the given suffix appended to the file name.
+	 *
+	 * Characters other than alphanumerics, dashes and underscores
+	 * are replaced in the suffix to keep it usable as a file name.
+	 *
+	 * @param  path
+	 * @param  suffix
+	 * @return PathBuf
+	 */
+	pub fn get_path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+		let suffix = suffix
+			.chars()
+			.map(|c| {
+				if c.is_alphanumeric() || c == '-' || c == '_' {
+					c
+				} else {
+					'_'
+				}
+			})
+			.collect::<String>();
+		let mut file_name = path
+			.file_stem()
+			.map(|stem| stem.to_os_string())
+			.unwrap_or_default();
+		file_name.push(format!("_{suffix}"));
+		if let Some(extension) = path.extension() {
+			file_name.push(".");
+			file_name.push(extension);
+		}
+		path.with_file_name(file_name)
+	}
+
+	/**
+	 * Get the path with the counter placeholder replaced.
+	 *
+	 * "{n}" is replaced with the number after the highest one found in
+	 * the existing file names and "{n:03}" pads the number with zeros.
+	 *
+	 * @param  path
+	 * @return PathBuf
+	 */
+	pub fn get_path_with_counter(path: PathBuf) -> PathBuf {
+		let file_name = match path.file_name().and_then(OsStr::to_str) {
+			Some(file_name) => file_name.to_string(),
+			None => return path,
+		};
+		let start = match file_name.find(COUNTER_PLACEHOLDER) {
+			Some(start) => start,
+			None => return path,
+		};
+		let end = match file_name[start..].find('}') {
+			Some(end) => start + end,
+			None => return path,
+		};
+		let width = match &file_name[start + COUNTER_PLACEHOLDER.len()..end] {
+			"" => 0,
+			spec => match spec.strip_prefix(':').map(str::parse::<usize>) {
+				Some(Ok(width)) => width,
+				_ => return path,
+			},
+		};
+		let (prefix, suffix) = (&file_name[..start], &file_name[end + 1..]);
+		let dir = match path.parent() {
+			Some(dir) if !dir.as_os_str().is_empty() => dir,
+			_ => Path::new("."),
+		};
+		let count = fs::read_dir(dir)
+			.map(|entries| {
+				entries
+					.filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+					.filter_map(|name| {
+						name.strip_prefix(prefix)?
+							.strip_suffix(suffix)
+							.filter(|n| n.chars().all(|c| c.is_ascii_digit()))?
+							.parse::<u64>()
+							.ok()
+					})
+					.max()
+					.map_or(1, |n| n + 1)
+			})
+			.unwrap_or(1);
+		path.with_file_name(format!("{prefix}{count:0width$}{suffix}"))
+	}
+
+	/**
+	 * Check if the path is an existing directory or ends with a separator.
+	 *
+	 * @param  path
+	 * @return bool
+	 */
+	pub fn is_dir_path(path: &str) -> bool {
+		path.ends_with(MAIN_SEPARATOR) || Path::new(path).is_dir()
+	}
+
+	/**
+	 * Check if the file is a stream (e.g. a named pipe) instead of a regular file.
+	 *
+	 * @return bool
+	 */
+	pub fn is_stream(&self) -> bool {
+		fs::metadata(&self.path)
+			.map(|metadata| !metadata.is_file() && !metadata.is_dir())
+			.unwrap_or(false)
+	}
+
 	/**
 	 * Get the default path for a file.
 	 *
@@ -68,14 +193,21 @@ impl File {
 	 * @param  PathBuf
 	 */
 	pub fn get_default_path(file_name: &str) -> PathBuf {
-		dirs::picture_dir()
-			.unwrap_or_else(|| {
-				dirs::home_dir()
-					.expect("Failed to access the home directory")
-					.as_path()
-					.join(env!("CARGO_PKG_NAME"))
-			})
-			.join(file_name)
+		Self::get_default_dir().join(file_name)
+	}
+
+	/**
+	 * Get the default directory for the files.
+	 *
+	 * @return PathBuf
+	 */
+	pub fn get_default_dir() -> PathBuf {
+		dirs::picture_dir().unwrap_or_else(|| {
+			dirs::home_dir()
+				.expect("Failed to access the home directory")
+				.as_path()
+				.join(env!("CARGO_PKG_NAME"))
+		})
 	}
 
 	/**
@@ -160,5 +292,56 @@ mod tests {
 				FileInfo::from_args(&ArgMatches::new(&args))
 			);
 		}
+		let file = File::new(env::temp_dir(), FileFormat::Gif, false);
+		assert!(!file.is_stream());
+		let file = File::new(PathBuf::from("/dev/null"), FileFormat::Gif, false);
+		assert_eq!(cfg!(unix), file.is_stream());
+		let dir = env::temp_dir().join("menyoki_test_mkdir");
+		let file = File::new(dir.join("x").join("test.png"), FileFormat::Png, false);
+		let error = file
+			.check_dir(false)
+			.expect_err("Directory should not exist");
+		assert_eq!(io::ErrorKind::NotFound, error.kind());
+		assert!(error.to_string().contains("menyoki_test_mkdir"));
+		assert!(error.to_string().contains("--mkdir"));
+		file.check_dir(true)
+			.expect("Failed to create the directory");
+		assert!(dir.join("x").is_dir());
+		assert!(file.check_dir(false).is_ok());
+		assert!(File::new(PathBuf::from("test.png"), FileFormat::Png, false)
+			.check_dir(false)
+			.is_ok());
+		fs::remove_dir_all(dir).expect("Failed to remove the directory");
+		assert_eq!(
+			PathBuf::from("out/cap_2_Terminal_-_bash.png"),
+			File::get_path_with_suffix(
+				Path::new("out/cap.png"),
+				"2_Terminal - bash"
+			)
+		);
+		assert_eq!(
+			PathBuf::from("cap_1"),
+			File::get_path_with_suffix(Path::new("cap"), "1")
+		);
+		let dir = env::temp_dir().join("menyoki_test_counter");
+		fs::create_dir_all(&dir).expect("Failed to create the directory");
+		let path = dir.join("shot_{n:03}.png");
+		assert_eq!(
+			dir.join("shot_001.png"),
+			File::new(path.clone(), FileFormat::Png, false).path
+		);
+		for name in ["shot_002.png", "shot_9.png", "shot_x.png", "shot_12.jpg"] {
+			fs::write(dir.join(name), []).expect("Failed to create the file");
+		}
+		assert_eq!(dir.join("shot_010.png"), File::get_path_with_counter(path));
+		assert_eq!(
+			dir.join("shot_10.png"),
+			File::new(dir.join("shot_{n}"), FileFormat::Png, true).path
+		);
+		assert_eq!(
+			dir.join("shot_{n:x}.png"),
+			File::get_path_with_counter(dir.join("shot_{n:x}.png"))
+		);
+		fs::remove_dir_all(dir).expect("Failed to remove the directory");
 	}
 }
@@ -5,13 +5,76 @@ use crate::file::format::FileFormat;
 use crate::file::info::FileInfo;
 use crate::file::File;
 use crate::image::settings::PnmSettings;
+use crate::record::settings::RecordCanvas;
+use image::{ExtendedColorType, Rgba};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+/* Default command for opening the output file */
+const DEFAULT_OPENER: &str = "xdg-open";
+
+/* Default background color for removing the alpha channel */
+const ALPHA_BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/* Presence of the alpha channel in the output */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlphaChannel {
+	Auto,
+	Remove(Rgba<u8>),
+	Force,
+}
+
+impl AlphaChannel {
+	/**
+	 * Get the alpha channel setting from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return AlphaChannel
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Self {
+		if matches.is_present("no-alpha") {
+			Self::Remove(
+				matches
+					.value_of("background")
+					.and_then(RecordCanvas::parse_color)
+					.unwrap_or(ALPHA_BACKGROUND),
+			)
+		} else if matches.is_present("force-alpha") {
+			Self::Force
+		} else {
+			Self::Auto
+		}
+	}
+
+	/**
+	 * Get the color type to encode with the alpha channel setting applied.
+	 *
+	 * @param  color_type
+	 * @return ExtendedColorType
+	 */
+	pub fn get_color_type(
+		&self,
+		color_type: ExtendedColorType,
+	) -> ExtendedColorType {
+		match (self, color_type) {
+			(Self::Remove(_), ExtendedColorType::Rgba8) => ExtendedColorType::Rgb8,
+			(Self::Remove(_), ExtendedColorType::Rgba16) => ExtendedColorType::Rgb16,
+			(Self::Force, ExtendedColorType::Rgb8) => ExtendedColorType::Rgba8,
+			(Self::Force, ExtendedColorType::Rgb16) => ExtendedColorType::Rgba16,
+			_ => color_type,
+		}
+	}
+}
+
 /* Output file settings */
 #[derive(Debug)]
 pub struct SaveSettings {
 	pub file: File,
+	pub open: Option<String>,
+	pub mkdir: bool,
+	pub verify: bool,
+	pub post: Option<String>,
+	pub alpha: AlphaChannel,
 }
 
 impl SaveSettings {
@@ -19,10 +82,29 @@ impl SaveSettings {
 	 * Create a new SaveSettings object.
 	 *
 	 * @param  file
+	 * @param  open (Option)
+	 * @param  mkdir
+	 * @param  verify
+	 * @param  post (Option)
+	 * @param  alpha
 	 * @return SaveSettings
 	 */
-	pub fn new(file: File) -> Self {
-		Self { file }
+	pub fn new(
+		file: File,
+		open: Option<String>,
+		mkdir: bool,
+		verify: bool,
+		post: Option<String>,
+		alpha: AlphaChannel,
+	) -> Self {
+		Self {
+			file,
+			open,
+			mkdir,
+			verify,
+			post,
+			alpha,
+		}
 	}
 
 	/**
@@ -45,21 +127,35 @@ impl SaveSettings {
 			} else {
 				FileFormat::from_args(matches, Some(pnm.subtype))
 			};
-		Self::from_parser(
-			ArgParser::from_subcommand(matches, "save"),
-			if edit.convert {
-				format
-			} else {
-				FileFormat::from_str(
-					edit.path
-						.extension()
-						.unwrap_or_default()
-						.to_str()
-						.unwrap_or_default(),
-				)
-				.unwrap_or(format)
-			},
-		)
+		let format = if edit.convert
+			|| edit.frame.is_some()
+			|| edit.flatten.is_some()
+			|| edit.contact_sheet.is_some()
+		{
+			format
+		} else {
+			FileFormat::from_str(
+				edit.path
+					.extension()
+					.unwrap_or_default()
+					.to_str()
+					.unwrap_or_default(),
+			)
+			.unwrap_or(format)
+		};
+		/* Keep the transparency of the chroma key and the mask */
+		let format = if (edit.color.chroma_key.is_some() || edit.mask.is_some())
+			&& !format.has_alpha()
+		{
+			warn!(
+				"{} does not support transparency, saving as PNG.",
+				format.as_extension().to_uppercase()
+			);
+			FileFormat::Png
+		} else {
+			format
+		};
+		Self::from_parser(ArgParser::from_subcommand(matches, "save"), format)
 	}
 
 	/**
@@ -76,8 +172,30 @@ impl SaveSettings {
 				let file = shellexpand::full(file)
 					.map(|s| s.to_string())
 					.unwrap_or(file.to_string());
-				let mut path = PathBuf::from(file);
-				if let Some(info) = FileInfo::from_args(&matches) {
+				let mut path = PathBuf::from(&file);
+				/* Generate the file name if a directory is given */
+				let in_dir = File::is_dir_path(&file);
+				if in_dir {
+					path = path.join(format!(
+						"{}.{}",
+						file_format.get_default_file_name(),
+						file_format.as_extension()
+					));
+				}
+				let output_dir = matches.value_of("output-dir").map(|dir| {
+					shellexpand::full(dir)
+						.map(|s| s.to_string())
+						.unwrap_or(dir.to_string())
+				});
+				if let Some(dir) = &output_dir {
+					path = PathBuf::from(dir)
+						.join(path.file_name().unwrap_or_default());
+				}
+				if let Some(info) = FileInfo::from_args(&matches).or_else(|| {
+					(output_dir.is_some() || in_dir).then(|| {
+						FileInfo::Date(matches.value_of("date").unwrap_or_default())
+					})
+				}) {
 					path.set_file_name(format!(
 						"{}_{}{}",
 						path.file_stem()
@@ -91,13 +209,41 @@ impl SaveSettings {
 						))
 					));
 				}
-				Self::new(File::new(
-					path,
-					file_format,
-					matches.is_present("with-extension"),
-				))
+				/* The default and the given output directories are always created */
+				let mkdir = matches.is_present("mkdir")
+					|| output_dir.is_some()
+					|| in_dir || path.parent()
+					== Some(File::get_default_dir().as_path());
+				Self::new(
+					File::new(
+						path,
+						file_format,
+						matches.is_present("with-extension"),
+					),
+					if matches.is_present("open") {
+						Some(
+							matches
+								.value_of("open")
+								.unwrap_or(DEFAULT_OPENER)
+								.to_string(),
+						)
+					} else {
+						None
+					},
+					mkdir,
+					matches.is_present("verify"),
+					matches.value_of("post").map(String::from),
+					AlphaChannel::from_args(&matches),
+				)
 			}
-			None => Self::new(file_format.into_file()),
+			None => Self::new(
+				file_format.into_file(),
+				None,
+				true,
+				false,
+				None,
+				AlphaChannel::Auto,
+			),
 		}
 	}
 }
@@ -108,6 +254,7 @@ mod tests {
 	use crate::args::matches::ArgMatches;
 	use clap::{App, Arg, SubCommand};
 	use pretty_assertions::assert_eq;
+	use std::env;
 	#[test]
 	fn test_save_settings() {
 		let args = App::new("test")
@@ -134,5 +281,79 @@ mod tests {
 		);
 		assert!(save_settings.file.path.to_str().unwrap().contains("test_"));
 		assert_eq!(FileFormat::Jpg, save_settings.file.format);
+		assert_eq!(None, save_settings.open);
+		assert!(!save_settings.mkdir);
+		let args = App::new("test")
+			.subcommand(
+				SubCommand::with_name("save")
+					.arg(Arg::with_name("file").long("file").takes_value(true))
+					.arg(
+						Arg::with_name("output-dir")
+							.long("output-dir")
+							.takes_value(true),
+					)
+					.arg(
+						Arg::with_name("date")
+							.long("date")
+							.default_value("%Y")
+							.takes_value(true),
+					)
+					.arg(
+						Arg::with_name("open")
+							.long("open")
+							.min_values(0)
+							.max_values(1)
+							.require_equals(true),
+					),
+			)
+			.get_matches_from(vec![
+				"test",
+				"save",
+				"--file",
+				"x/test.png",
+				"--output-dir",
+				"shots",
+				"--open",
+			]);
+		let matches = ArgMatches::new(&args);
+		let save_settings = SaveSettings::from_parser(
+			ArgParser::from_subcommand(&matches, "save"),
+			FileFormat::Png,
+		);
+		assert_eq!(
+			PathBuf::from("shots")
+				.join(format!("test_{}.png", chrono::Local::now().format("%Y"))),
+			save_settings.file.path
+		);
+		assert_eq!(Some(String::from(DEFAULT_OPENER)), save_settings.open);
+		assert!(save_settings.mkdir);
+		for dir in [
+			env::temp_dir().to_string_lossy().to_string(),
+			String::from("new/"),
+		] {
+			let args = App::new("test")
+				.subcommand(
+					SubCommand::with_name("save")
+						.arg(Arg::with_name("file").takes_value(true))
+						.arg(
+							Arg::with_name("date")
+								.long("date")
+								.default_value("%Y")
+								.takes_value(true),
+						),
+				)
+				.get_matches_from(vec!["test", "save", &dir]);
+			let matches = ArgMatches::new(&args);
+			let save_settings = SaveSettings::from_parser(
+				ArgParser::from_subcommand(&matches, "save"),
+				FileFormat::Jpg,
+			);
+			assert_eq!(
+				PathBuf::from(&dir)
+					.join(format!("cap_{}.jpg", chrono::Local::now().format("%Y"))),
+				save_settings.file.path
+			);
+			assert!(save_settings.mkdir);
+		}
 	}
 }
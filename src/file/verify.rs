@@ -0,0 +1,167 @@
+use crate::app::AppResult;
+use crate::file::format::FileFormat;
+use crate::image::Image;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::io::Reader;
+use image::{AnimationDecoder, Frame, ImageFormat};
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/* Dimensions and frame count of the encoded output */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutputInfo {
+	pub width: u32,
+	pub height: u32,
+	pub frames: usize,
+}
+
+/* Display implementation for user-facing output */
+impl fmt::Display for OutputInfo {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}x{} ({} frames)", self.width, self.height, self.frames)
+	}
+}
+
+impl OutputInfo {
+	/**
+	 * Create a new OutputInfo object.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @param  frames
+	 * @return OutputInfo
+	 */
+	pub fn new(width: u32, height: u32, frames: usize) -> Self {
+		Self {
+			width,
+			height,
+			frames,
+		}
+	}
+
+	/**
+	 * Create an OutputInfo object from the images to encode.
+	 *
+	 * @param  images
+	 * @return OutputInfo
+	 */
+	pub fn from_images(images: &[Image]) -> Self {
+		images.first().map_or_else(Self::default, |image| {
+			Self::new(image.geometry.width, image.geometry.height, images.len())
+		})
+	}
+
+	/**
+	 * Create an OutputInfo object from the decoded frames.
+	 *
+	 * @param  frames
+	 * @return OutputInfo
+	 */
+	fn from_frames(frames: &[Frame]) -> Self {
+		frames.first().map_or_else(Self::default, |frame| {
+			let (width, height) = frame.buffer().dimensions();
+			Self::new(width, height, frames.len())
+		})
+	}
+
+	/**
+	 * Read the info back from the encoded file.
+	 *
+	 * The format is given explicitly since the path
+	 * might not have an extension.
+	 *
+	 * @param  path
+	 * @param  format
+	 * @return OutputInfo (Result)
+	 */
+	pub fn read(path: &Path, format: &FileFormat) -> AppResult<Self> {
+		let input = BufReader::new(File::open(path)?);
+		Ok(match format {
+			FileFormat::Gif => Self::from_frames(
+				&GifDecoder::new(input)?.into_frames().collect_frames()?,
+			),
+			FileFormat::Apng => Self::from_frames(
+				&PngDecoder::new(input)?
+					.apng()
+					.into_frames()
+					.collect_frames()?,
+			),
+			_ => {
+				let mut reader = Reader::new(input);
+				match Self::get_image_format(format) {
+					Some(image_format) => reader.set_format(image_format),
+					None => reader = reader.with_guessed_format()?,
+				}
+				let image = reader.decode()?;
+				Self::new(image.width(), image.height(), 1)
+			}
+		})
+	}
+
+	/**
+	 * Get the image format to decode the file with.
+	 *
+	 * @param  format
+	 * @return ImageFormat (Option)
+	 */
+	fn get_image_format(format: &FileFormat) -> Option<ImageFormat> {
+		match format {
+			FileFormat::Gif => Some(ImageFormat::Gif),
+			FileFormat::Apng | FileFormat::Png => Some(ImageFormat::Png),
+			FileFormat::Jpg => Some(ImageFormat::Jpeg),
+			FileFormat::WebP => Some(ImageFormat::WebP),
+			FileFormat::Bmp => Some(ImageFormat::Bmp),
+			FileFormat::Ico => Some(ImageFormat::Ico),
+			FileFormat::Tiff => Some(ImageFormat::Tiff),
+			FileFormat::Tga => Some(ImageFormat::Tga),
+			FileFormat::Pnm(_) => Some(ImageFormat::Pnm),
+			FileFormat::Ff => Some(ImageFormat::Farbfeld),
+			FileFormat::Exr => Some(ImageFormat::OpenExr),
+			FileFormat::Any | FileFormat::Txt => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use image::codecs::gif::GifEncoder;
+	use image::{Rgba, RgbaImage};
+	use pretty_assertions::assert_eq;
+	use std::env;
+	use std::fs;
+	#[test]
+	fn test_output_info() -> AppResult<()> {
+		let image = Image::new(
+			vec![Rgba([0, 0, 0, 255]); 6],
+			false,
+			Geometry::new(0, 0, 3, 2),
+		);
+		let info = OutputInfo::from_images(&[image.clone(), image]);
+		assert_eq!(OutputInfo::new(3, 2, 2), info);
+		assert_eq!("3x2 (2 frames)", info.to_string());
+		assert_eq!(OutputInfo::default(), OutputInfo::from_images(&[]));
+		let dir = env::temp_dir().join("menyoki_test_verify");
+		fs::create_dir_all(&dir)?;
+		let path = dir.join("output");
+		RgbaImage::new(3, 2).save_with_format(&path, ImageFormat::Png)?;
+		assert_eq!(
+			OutputInfo::new(3, 2, 1),
+			OutputInfo::read(&path, &FileFormat::Png)?
+		);
+		assert_eq!(
+			OutputInfo::new(3, 2, 1),
+			OutputInfo::read(&path, &FileFormat::Any)?
+		);
+		assert!(OutputInfo::read(&path, &FileFormat::Jpg).is_err());
+		GifEncoder::new(File::create(&path)?)
+			.encode_frames((0..2).map(|_| Frame::new(RgbaImage::new(3, 2))))?;
+		assert_eq!(info, OutputInfo::read(&path, &FileFormat::Gif)?);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,186 @@
+use crate::app::{AppError, AppResult};
+
+/* Introducer of the extension blocks */
+const EXTENSION_INTRODUCER: u8 = 0x21;
+/* Label of the comment extension */
+const COMMENT_LABEL: u8 = 0xFE;
+/* Separator of the image descriptors */
+const IMAGE_SEPARATOR: u8 = 0x2C;
+/* Last byte of the GIF data stream */
+const TRAILER: u8 = 0x3B;
+/* Size of the header and logical screen descriptor */
+const HEADER_SIZE: usize = 13;
+/* Maximum size of a data sub-block */
+const SUB_BLOCK_SIZE: usize = 255;
+
+/* Comment extension of the GIF files */
+#[derive(Clone, Debug)]
+pub struct GifComment<'a> {
+	text: &'a str,
+}
+
+impl<'a> GifComment<'a> {
+	/**
+	 * Create a new GifComment object.
+	 *
+	 * @param  text
+	 * @return GifComment
+	 */
+	pub fn new(text: &'a str) -> Self {
+		Self { text }
+	}
+
+	/**
+	 * Insert the comment extension after the header of the GIF data.
+	 *
+	 * @param  data
+	 * @return Vector of u8 (Result)
+	 */
+	pub fn insert(&self, mut data: Vec<u8>) -> AppResult<Vec<u8>> {
+		if !self.text.is_ascii() {
+			warn!("GIF comments should only contain ASCII characters.");
+		}
+		let offset = Self::get_header_size(&data).ok_or_else(Self::invalid)?;
+		let mut block = vec![EXTENSION_INTRODUCER, COMMENT_LABEL];
+		for chunk in self.text.as_bytes().chunks(SUB_BLOCK_SIZE) {
+			block.push(u8::try_from(chunk.len()).unwrap_or_default());
+			block.extend_from_slice(chunk);
+		}
+		block.push(0);
+		data.splice(offset..offset, block);
+		Ok(data)
+	}
+
+	/**
+	 * Read the comments from the GIF data.
+	 *
+	 * @param  data
+	 * @return Vector of String (Result)
+	 */
+	pub fn read(data: &[u8]) -> AppResult<Vec<String>> {
+		let mut offset = Self::get_header_size(data).ok_or_else(Self::invalid)?;
+		let mut comments = Vec::new();
+		loop {
+			match data.get(offset).copied() {
+				Some(EXTENSION_INTRODUCER) => {
+					let label = *data.get(offset + 1).ok_or_else(Self::invalid)?;
+					let (text, next) = Self::read_sub_blocks(data, offset + 2)
+						.ok_or_else(Self::invalid)?;
+					if label == COMMENT_LABEL {
+						comments.push(String::from_utf8_lossy(&text).to_string());
+					}
+					offset = next;
+				}
+				Some(IMAGE_SEPARATOR) => {
+					let flags = *data.get(offset + 9).ok_or_else(Self::invalid)?;
+					offset += 10 + Self::get_color_table_size(flags);
+					/* Skip the minimum code size of the image data */
+					offset = Self::read_sub_blocks(data, offset + 1)
+						.ok_or_else(Self::invalid)?
+						.1;
+				}
+				Some(TRAILER) => return Ok(comments),
+				_ => return Err(Self::invalid()),
+			}
+		}
+	}
+
+	/**
+	 * Get the size of the header including the global color table.
+	 *
+	 * @param  data
+	 * @return usize (Option)
+	 */
+	fn get_header_size(data: &[u8]) -> Option<usize> {
+		if !data.starts_with(b"GIF") {
+			return None;
+		}
+		let size = HEADER_SIZE + Self::get_color_table_size(*data.get(10)?);
+		(data.len() >= size).then_some(size)
+	}
+
+	/**
+	 * Get the size of the color table from the packed fields.
+	 *
+	 * @param  flags
+	 * @return usize
+	 */
+	fn get_color_table_size(flags: u8) -> usize {
+		if flags & 0x80 != 0 {
+			3 * (1 << ((flags & 0x07) + 1))
+		} else {
+			0
+		}
+	}
+
+	/**
+	 * Read the data sub-blocks starting from the given offset.
+	 *
+	 * @param  data
+	 * @param  offset
+	 * @return Tuple (Option)
+	 */
+	fn read_sub_blocks(data: &[u8], mut offset: usize) -> Option<(Vec<u8>, usize)> {
+		let mut content = Vec::new();
+		loop {
+			let size = usize::from(*data.get(offset)?);
+			offset += 1;
+			if size == 0 {
+				return Some((content, offset));
+			}
+			content.extend_from_slice(data.get(offset..offset + size)?);
+			offset += size;
+		}
+	}
+
+	/**
+	 * Get the error of the invalid GIF data.
+	 *
+	 * @return AppError
+	 */
+	fn invalid() -> AppError {
+		AppError::FrameError(String::from("Invalid GIF data"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::anim::settings::AnimSettings;
+	use crate::gif::encoder::{Encoder, EncoderConfig};
+	use crate::gif::GifEncoder;
+	use crate::image::geometry::Geometry;
+	use crate::image::Image;
+	use image::codecs::gif::GifDecoder;
+	use image::{AnimationDecoder, Rgba};
+	use pretty_assertions::assert_eq;
+	use std::io::Cursor;
+	#[test]
+	fn test_gif_comment() -> AppResult<()> {
+		let geometry = Geometry::new(0, 0, 2, 2);
+		let images = [[255, 0, 0, 255], [0, 0, 255, 255]]
+			.into_iter()
+			.map(|color| Image::new(vec![Rgba(color); 4], false, geometry))
+			.collect::<Vec<Image>>();
+		let settings = AnimSettings::default();
+		let mut data = Vec::new();
+		GifEncoder::new(EncoderConfig::new(10., geometry, &mut data, &settings))?
+			.save(images, None)?;
+		assert_eq!(Vec::<String>::new(), GifComment::read(&data)?);
+		let text = "menyoki ".repeat(40);
+		let data = GifComment::new("source: test").insert(data)?;
+		let data = GifComment::new(&text).insert(data)?;
+		assert_eq!(
+			vec![text, String::from("source: test")],
+			GifComment::read(&data)?
+		);
+		let frames = GifDecoder::new(Cursor::new(data))?
+			.into_frames()
+			.collect_frames()?;
+		assert_eq!(2, frames.len());
+		assert_eq!(&Rgba([0, 0, 255, 255]), frames[1].buffer().get_pixel(1, 1));
+		assert!(GifComment::new("x").insert(b"PNG".to_vec()).is_err());
+		assert!(GifComment::read(b"GIF89a").is_err());
+		Ok(())
+	}
+}
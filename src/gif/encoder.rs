@@ -8,7 +8,7 @@ use std::io::Write;
 /* GIF encoder configuration */
 #[derive(Clone, Copy, Debug)]
 pub struct EncoderConfig<'a, Output: Write> {
-	pub fps: u32,
+	pub fps: f32,
 	pub geometry: Geometry,
 	pub output: Output,
 	pub settings: &'a AnimSettings,
@@ -25,7 +25,7 @@ impl<'a, Output: Write> EncoderConfig<'a, Output> {
 	 * @return EncoderConfig
 	 */
 	pub fn new(
-		fps: u32,
+		fps: f32,
 		geometry: Geometry,
 		output: Output,
 		settings: &'a AnimSettings,
@@ -60,6 +60,7 @@ mod tests {
 	use crate::gif::GifEncoder;
 	use image::Rgba;
 	const GIF_HEADER: &[u8] = &[0x47, 0x49, 0x46, 0x38, 0x39, 0x61];
+	const LOOP_EXTENSION: &[u8] = b"NETSCAPE2.0";
 	fn get_config<Output: Write>(
 		output: Output,
 		settings: &AnimSettings,
@@ -70,7 +71,14 @@ mod tests {
 			Image::new(data.clone(), false, geometry),
 			Image::new(data.into_iter().rev().collect(), false, geometry),
 		];
-		(EncoderConfig::new(10, geometry, output, settings), images)
+		(EncoderConfig::new(10., geometry, output, settings), images)
+	}
+	fn get_loop_count(output: &[u8]) -> Option<u16> {
+		let index = output
+			.windows(LOOP_EXTENSION.len())
+			.position(|window| window == LOOP_EXTENSION)?
+			+ LOOP_EXTENSION.len();
+		Some(u16::from_le_bytes([output[index + 2], output[index + 3]]))
 	}
 	#[test]
 	fn test_gif_encoder() {
@@ -78,9 +86,23 @@ mod tests {
 		let settings = AnimSettings::default();
 		let (config, images) = get_config(&mut output, &settings);
 		GifEncoder::new(config).unwrap().save(images, None).unwrap();
+		assert_eq!(Some(0), get_loop_count(&output));
 		output.truncate(6);
 		assert_eq!(GIF_HEADER, output);
 		output.clear();
+		for (repeat, loop_count) in [(0, None), (2, Some(2)), (-2, Some(0))] {
+			let settings = AnimSettings {
+				repeat,
+				..AnimSettings::default()
+			};
+			let (config, images) = get_config(&mut output, &settings);
+			GifEncoder::new(config).unwrap().save(images, None).unwrap();
+			assert_eq!(loop_count, get_loop_count(&output));
+			assert!(gif::DecodeOptions::new()
+				.read_info(output.as_slice())
+				.is_ok());
+			output.clear();
+		}
 	}
 	#[cfg(feature = "ski")]
 	#[test]
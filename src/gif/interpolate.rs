@@ -0,0 +1,127 @@
+use crate::anim::Frames;
+use crate::image::Image;
+use image::{ExtendedColorType, Rgba};
+
+/* Frame interpolation for increasing the FPS of animations */
+#[derive(Clone, Copy, Debug)]
+pub struct Interpolator {
+	fps: f32,
+}
+
+impl Interpolator {
+	/**
+	 * Create a new Interpolator object.
+	 *
+	 * @param  fps
+	 * @return Interpolator
+	 */
+	pub fn new(fps: f32) -> Self {
+		Self { fps }
+	}
+
+	/**
+	 * Generate the intermediate frames to reach the target FPS.
+	 *
+	 * Frames are cross-faded with their next frame based on
+	 * their position in time.
+	 *
+	 * @param  frames
+	 * @return Frames
+	 */
+	pub fn apply(&self, frames: Frames) -> Frames {
+		let (images, fps) = frames;
+		if self.fps <= fps || images.len() < 2 {
+			if self.fps < fps {
+				warn!("Target FPS is lower than the source FPS, skipping.");
+			}
+			return (images, fps);
+		}
+		let count = (images.len() as f32 * self.fps / fps).ceil() as usize;
+		info!(
+			"Interpolating {} frames to {} frames... ({} FPS -> {} FPS)",
+			images.len(),
+			count,
+			fps,
+			self.fps
+		);
+		let interpolated = (0..count)
+			.map(|i| {
+				let position = i as f32 * fps / self.fps;
+				let index = position as usize;
+				match (images.get(index), images.get(index + 1)) {
+					(Some(image), Some(next)) => {
+						Self::blend(image, next, position.fract())
+					}
+					_ => images[images.len() - 1].clone(),
+				}
+			})
+			.collect();
+		(interpolated, self.fps)
+	}
+
+	/**
+	 * Blend two images with the given weight of the second one.
+	 *
+	 * @param  image
+	 * @param  next
+	 * @param  weight
+	 * @return Image
+	 */
+	fn blend(image: &Image, next: &Image, weight: f32) -> Image {
+		if weight == 0. || image.geometry != next.geometry {
+			return image.clone();
+		}
+		let next_data = next.get_data(ExtendedColorType::Rgba8);
+		Image::new(
+			image
+				.get_data(ExtendedColorType::Rgba8)
+				.chunks_exact(4)
+				.zip(next_data.chunks_exact(4))
+				.map(|(rgba, next)| {
+					let mut pixel = [0; 4];
+					for c in 0..4 {
+						pixel[c] = (f32::from(rgba[c]) * (1. - weight)
+							+ f32::from(next[c]) * weight)
+							.round() as u8;
+					}
+					Rgba(pixel)
+				})
+				.collect(),
+			true,
+			image.geometry,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_interpolator() {
+		let geometry = Geometry::new(0, 0, 1, 1);
+		let images = vec![
+			Image::new(vec![Rgba([0, 0, 0, 255])], true, geometry),
+			Image::new(vec![Rgba([100, 200, 50, 255])], true, geometry),
+		];
+		let (frames, fps) = Interpolator::new(20.).apply((images.clone(), 10.));
+		assert_eq!(20., fps);
+		assert_eq!(4, frames.len());
+		assert_eq!(
+			vec![0, 0, 0, 255],
+			frames[0].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(
+			vec![50, 100, 25, 255],
+			frames[1].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(
+			vec![100, 200, 50, 255],
+			frames[3].get_data(ExtendedColorType::Rgba8)
+		);
+		let (frames, fps) = Interpolator::new(5.).apply((images, 10.));
+		assert_eq!(10., fps);
+		assert_eq!(2, frames.len());
+	}
+}
@@ -0,0 +1,135 @@
+use crate::image::Image;
+use image::{ExtendedColorType, Rgba};
+use std::io::Write;
+use std::process::{Command as OsCommand, Stdio};
+use std::thread;
+
+/* Lossy compression for reducing the size of GIF files */
+#[derive(Clone, Copy, Debug)]
+pub struct LossyFilter {
+	level: u8,
+}
+
+impl LossyFilter {
+	/**
+	 * Create a new LossyFilter object.
+	 *
+	 * @param  level
+	 * @return LossyFilter
+	 */
+	pub fn new(level: u8) -> Self {
+		Self { level }
+	}
+
+	/**
+	 * Compress the GIF data using gifsicle.
+	 *
+	 * @param  data
+	 * @return Vector of u8 (Option)
+	 */
+	pub fn compress(&self, data: Vec<u8>) -> Option<Vec<u8>> {
+		let mut child = OsCommand::new("gifsicle")
+			.args(["-O3", &format!("--lossy={}", self.level)])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::null())
+			.spawn()
+			.ok()?;
+		let mut stdin = child.stdin.take()?;
+		let writer = thread::spawn(move || stdin.write_all(&data));
+		let output = child.wait_with_output().ok()?;
+		match writer.join() {
+			Ok(Ok(())) if output.status.success() => Some(output.stdout),
+			_ => None,
+		}
+	}
+
+	/**
+	 * Apply the lossy filter to the frames.
+	 *
+	 * Colors are quantized and the pixels that are close enough
+	 * to the previous frame are reused to get longer LZW runs.
+	 *
+	 * @param  images
+	 * @return Vector of Image
+	 */
+	pub fn apply(&self, images: Vec<Image>) -> Vec<Image> {
+		let step = 1 + u16::from(self.level) / 25;
+		let threshold = i16::from(self.level / 10);
+		let mut previous: Option<Vec<Rgba<u8>>> = None;
+		images
+			.into_iter()
+			.map(|image| {
+				let data = image
+					.get_data(ExtendedColorType::Rgba8)
+					.chunks_exact(4)
+					.enumerate()
+					.map(|(i, rgba)| {
+						match previous.as_ref().and_then(|prev| prev.get(i)) {
+							Some(prev)
+								if (0..3).all(|c| {
+									(i16::from(rgba[c]) - i16::from(prev[c])).abs()
+										<= threshold
+								}) && rgba[3] == prev[3] =>
+							{
+								*prev
+							}
+							_ => Rgba([
+								Self::quantize(rgba[0], step),
+								Self::quantize(rgba[1], step),
+								Self::quantize(rgba[2], step),
+								rgba[3],
+							]),
+						}
+					})
+					.collect::<Vec<Rgba<u8>>>();
+				previous = Some(data.clone());
+				Image::new(data, true, image.geometry)
+			})
+			.collect()
+	}
+
+	/**
+	 * Quantize the color value with the given step.
+	 *
+	 * @param  value
+	 * @param  step
+	 * @return u8
+	 */
+	fn quantize(value: u8, step: u16) -> u8 {
+		((u16::from(value) / step) * step + step / 2).min(255) as u8
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_lossy_filter() {
+		let geometry = Geometry::new(0, 0, 2, 1);
+		let images = vec![
+			Image::new(
+				vec![Rgba([100, 100, 100, 255]), Rgba([0, 0, 0, 255])],
+				true,
+				geometry,
+			),
+			Image::new(
+				vec![Rgba([103, 98, 101, 255]), Rgba([200, 0, 0, 255])],
+				true,
+				geometry,
+			),
+		];
+		let images = LossyFilter::new(100).apply(images);
+		assert_eq!(
+			vec![102, 102, 102, 255, 2, 2, 2, 255],
+			images[0].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(
+			vec![102, 102, 102, 255, 202, 2, 2, 255],
+			images[1].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(255, LossyFilter::quantize(255, 5));
+	}
+}
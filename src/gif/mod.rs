@@ -1,21 +1,28 @@
+pub mod comment;
 pub mod encoder;
+pub mod interpolate;
+pub mod lossy;
+pub mod palette;
+pub mod resample;
 #[cfg(feature = "ski")]
 pub mod ski;
 
-use crate::anim::settings::AnimSettings;
+use crate::anim::settings::{AnimSettings, SpeedCurve};
 use crate::app::AppResult;
 use crate::gif::encoder::{Encoder, EncoderConfig};
+use crate::gif::palette::{PaletteSize, ReferencePalette};
 use crate::image::Image;
 use crate::util::state::InputState;
-use gif::{Encoder as BaseEncoder, Frame, Repeat};
+use gif::{Encoder as BaseEncoder, Repeat};
 use image::ExtendedColorType;
 use std::io::{self, Write};
 
 /* GIF encoder and settings */
 pub struct GifEncoder<'a, Output: Write> {
-	fps: u32,
+	fps: f32,
 	encoder: BaseEncoder<Output>,
 	settings: &'a AnimSettings,
+	palette: Option<ReferencePalette>,
 }
 
 impl<'a, Output: Write> Encoder<'a, Output> for GifEncoder<'a, Output> {
@@ -26,20 +33,34 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifEncoder<'a, Output> {
 	 * @return GifEncoder (Result)
 	 */
 	fn new(config: EncoderConfig<'a, Output>) -> AppResult<Self> {
+		let palette = match &config.settings.palette_from {
+			Some(path) => {
+				Some(ReferencePalette::open(path, config.settings.dither)?)
+			}
+			None => None,
+		};
 		let mut encoder = BaseEncoder::new(
 			config.output,
 			config.geometry.width.try_into().unwrap_or_default(),
 			config.geometry.height.try_into().unwrap_or_default(),
-			&[],
+			&palette
+				.as_ref()
+				.map(ReferencePalette::get_colors)
+				.unwrap_or_default(),
 		)?;
-		encoder.set_repeat(match config.settings.repeat {
-			n if n >= 0 => Repeat::Finite(n.try_into().unwrap_or_default()),
-			_ => Repeat::Infinite,
-		})?;
+		/* Loop count of 0 means infinite, so the extension is omitted for playing once */
+		match config.settings.get_play_count() {
+			Some(1) => {}
+			Some(plays) => encoder.set_repeat(Repeat::Finite(
+				(plays - 1).try_into().unwrap_or(u16::MAX),
+			))?,
+			None => encoder.set_repeat(Repeat::Infinite)?,
+		}
 		Ok(Self {
 			fps: config.fps,
 			encoder,
 			settings: config.settings,
+			palette,
 		})
 	}
 
@@ -61,6 +82,7 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifEncoder<'a, Output> {
 				(1., 100.),
 				(0., 29.),
 			) as i32;
+		let mut palette_colors = 0;
 		for (i, image) in images.iter().enumerate() {
 			let percentage = ((i + 1) as f64 / images.len() as f64) * 100.;
 			info!("Saving... ({:.1}%)\r", percentage);
@@ -78,17 +100,48 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifEncoder<'a, Output> {
 					panic!("Failed to write the frames")
 				}
 			}
-			let mut frame = Frame::from_rgba_speed(
+			let mut data = image.get_data(ExtendedColorType::Rgba8);
+			let (width, height) = (
 				image.geometry.width.try_into().unwrap_or_default(),
 				image.geometry.height.try_into().unwrap_or_default(),
-				&mut image.get_data(ExtendedColorType::Rgba8),
-				speed,
 			);
-			frame.delay = (1e2 / self.fps as f32) as u16;
+			let mut frame = match &self.palette {
+				Some(palette) => palette.get_frame(width, height, data),
+				None => {
+					let colors = self.settings.colors.get_colors(&data);
+					palette_colors += colors;
+					PaletteSize::get_frame(width, height, &mut data, colors, speed)
+				}
+			};
+			frame.delay = match (image.get_delay(), self.settings.speed_curve) {
+				(Some(delay), _) => (delay.as_secs_f32() * 1e2)
+					.round()
+					.clamp(1., u16::MAX.into()) as u16,
+				(None, SpeedCurve::Linear) => {
+					self.settings.delay.unwrap_or((1e2 / self.fps) as u16)
+				}
+				(None, curve) => curve
+					.get_delay(
+						self.settings.delay.map_or(1e2 / self.fps, f32::from),
+						i,
+						images.len(),
+					)
+					.round()
+					.max(1.) as u16,
+			};
 			frame.make_lzw_pre_encoded();
 			self.encoder.write_lzw_pre_encoded_frame(&frame)?;
 		}
 		info!("\n");
+		if self.settings.colors == PaletteSize::Auto
+			&& self.palette.is_none()
+			&& !images.is_empty()
+		{
+			debug!(
+				"Average palette size: {:.1} colors",
+				palette_colors as f64 / images.len() as f64
+			);
+		}
 		Ok(())
 	}
 }
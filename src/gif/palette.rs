@@ -0,0 +1,324 @@
+use crate::app::AppResult;
+use color_quant::NeuQuant;
+use gif::Frame;
+use image::imageops::{self, ColorMap};
+use image::{Rgba, RgbaImage};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+
+/* Maximum number of colors in a GIF palette */
+const MAX_COLORS: usize = 256;
+/* Minimum number of colors in a GIF palette */
+const MIN_COLORS: usize = 2;
+/* Number of bits to keep per channel while counting the distinct colors */
+const VISIBLE_BITS: u8 = 5;
+/* Sampling factor for extracting the colors of a reference image */
+const REFERENCE_SPEED: i32 = 10;
+/* Number of bits per channel in the lookup table of a reference palette */
+const LOOKUP_BITS: usize = 5;
+
+/* Number of colors in the palette of frames */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteSize {
+	Fixed(usize),
+	Auto,
+}
+
+/* Default initialization values for PaletteSize */
+impl Default for PaletteSize {
+	fn default() -> Self {
+		Self::Fixed(MAX_COLORS)
+	}
+}
+
+/* Implementation for parsing PaletteSize from a string */
+impl FromStr for PaletteSize {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"auto" => Ok(Self::Auto),
+			_ => s
+				.parse::<usize>()
+				.map(|colors| Self::Fixed(colors.clamp(MIN_COLORS, MAX_COLORS)))
+				.map_err(|_| ()),
+		}
+	}
+}
+
+impl PaletteSize {
+	/**
+	 * Get the number of colors to use for the given RGBA data.
+	 *
+	 * In auto mode, colors are counted with reduced precision
+	 * so that visually identical shades are not counted twice.
+	 *
+	 * @param  data
+	 * @return usize
+	 */
+	pub fn get_colors(&self, data: &[u8]) -> usize {
+		match self {
+			Self::Fixed(colors) => *colors,
+			Self::Auto => {
+				let shift = 8 - VISIBLE_BITS;
+				let mut colors = HashSet::new();
+				for rgba in data.chunks_exact(4) {
+					colors.insert(if rgba[3] == 0 {
+						[0, 0, 0, 0]
+					} else {
+						[rgba[0] >> shift, rgba[1] >> shift, rgba[2] >> shift, 1]
+					});
+					if colors.len() > MAX_COLORS / 2 {
+						return MAX_COLORS;
+					}
+				}
+				colors.len().next_power_of_two().max(MIN_COLORS)
+			}
+		}
+	}
+
+	/**
+	 * Create a GIF frame with the given number of colors.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @param  data
+	 * @param  colors
+	 * @param  speed
+	 * @return Frame
+	 */
+	pub fn get_frame(
+		width: u16,
+		height: u16,
+		data: &mut [u8],
+		colors: usize,
+		speed: i32,
+	) -> Frame<'static> {
+		if colors >= MAX_COLORS {
+			return Frame::from_rgba_speed(width, height, data, speed);
+		}
+		let mut transparent = None;
+		let mut distinct = HashSet::new();
+		for rgba in data.chunks_exact_mut(4) {
+			if rgba[3] != 0 {
+				rgba[3] = u8::MAX;
+			} else {
+				transparent = Some([rgba[0], rgba[1], rgba[2], rgba[3]]);
+			}
+			if distinct.len() <= colors {
+				distinct.insert([rgba[0], rgba[1], rgba[2], rgba[3]]);
+			}
+		}
+		if distinct.len() <= colors {
+			return Frame::from_rgba_speed(width, height, data, speed);
+		}
+		let quantizer = NeuQuant::new(speed, colors, data);
+		let pixels = data
+			.chunks_exact(4)
+			.map(|rgba| quantizer.index_of(rgba) as u8)
+			.collect::<Vec<u8>>();
+		Frame::from_palette_pixels(
+			width,
+			height,
+			&pixels,
+			&quantizer.color_map_rgb(),
+			transparent.map(|rgba| quantizer.index_of(&rgba) as u8),
+		)
+	}
+}
+
+/* Global palette extracted from a reference image */
+pub struct ReferencePalette {
+	colors: Vec<[u8; 3]>,
+	lookup: Vec<u8>,
+	dither: bool,
+}
+
+/* Color map implementation for mapping the pixels to the palette */
+impl ColorMap for ReferencePalette {
+	type Color = Rgba<u8>;
+	fn index_of(&self, color: &Rgba<u8>) -> usize {
+		let [r, g, b, _] = color.0.map(|v| usize::from(v >> (8 - LOOKUP_BITS)));
+		usize::from(self.lookup[(r << (LOOKUP_BITS * 2)) | (g << LOOKUP_BITS) | b])
+	}
+	fn map_color(&self, color: &mut Rgba<u8>) {
+		let [r, g, b] = self.colors[self.index_of(color)];
+		color.0 = [r, g, b, color.0[3]];
+	}
+}
+
+impl ReferencePalette {
+	/**
+	 * Create a new ReferencePalette object.
+	 *
+	 * The colors are used as is if there are not more than
+	 * the maximum number of colors, otherwise they are quantized.
+	 *
+	 * @param  image
+	 * @param  dither
+	 * @return ReferencePalette
+	 */
+	pub fn new(image: &RgbaImage, dither: bool) -> Self {
+		let mut distinct = HashSet::new();
+		let mut colors = Vec::new();
+		for rgba in image.pixels() {
+			let rgb = [rgba[0], rgba[1], rgba[2]];
+			if distinct.insert(rgb) {
+				colors.push(rgb);
+			}
+			if colors.len() > MAX_COLORS {
+				colors = NeuQuant::new(REFERENCE_SPEED, MAX_COLORS, image.as_raw())
+					.color_map_rgb()
+					.chunks_exact(3)
+					.map(|rgb| [rgb[0], rgb[1], rgb[2]])
+					.collect();
+				break;
+			}
+		}
+		if colors.is_empty() {
+			colors.push([0, 0, 0]);
+		}
+		let lookup = (0..1 << (LOOKUP_BITS * 3))
+			.map(|i| Self::get_nearest(&colors, Self::get_bucket_color(i)))
+			.collect();
+		Self {
+			colors,
+			lookup,
+			dither,
+		}
+	}
+
+	/**
+	 * Get the color at the center of a bucket in the lookup table.
+	 *
+	 * @param  bucket
+	 * @return Array of i32
+	 */
+	fn get_bucket_color(bucket: usize) -> [i32; 3] {
+		let shift = 8 - LOOKUP_BITS;
+		[2, 1, 0].map(|channel| {
+			let value =
+				(bucket >> (LOOKUP_BITS * channel)) & ((1 << LOOKUP_BITS) - 1);
+			i32::try_from((value << shift) | (1 << (shift - 1))).unwrap_or_default()
+		})
+	}
+
+	/**
+	 * Get the index of the nearest color in the palette.
+	 *
+	 * @param  colors
+	 * @param  color
+	 * @return u8
+	 */
+	fn get_nearest(colors: &[[u8; 3]], color: [i32; 3]) -> u8 {
+		colors
+			.iter()
+			.enumerate()
+			.min_by_key(|(_, rgb)| {
+				rgb.iter()
+					.zip(color)
+					.map(|(v, c)| (i32::from(*v) - c).pow(2))
+					.sum::<i32>()
+			})
+			.map(|(index, _)| index as u8)
+			.unwrap_or_default()
+	}
+
+	/**
+	 * Extract the palette from the given image file.
+	 *
+	 * @param  path
+	 * @param  dither
+	 * @return ReferencePalette (Result)
+	 */
+	pub fn open(path: &Path, dither: bool) -> AppResult<Self> {
+		let palette = Self::new(&image::open(path)?.to_rgba8(), dither);
+		debug!("Extracted {} colors from {:?}", palette.colors.len(), path);
+		Ok(palette)
+	}
+
+	/**
+	 * Get the colors of the palette.
+	 *
+	 * @return Vector of u8
+	 */
+	pub fn get_colors(&self) -> Vec<u8> {
+		self.colors.concat()
+	}
+
+	/**
+	 * Create a GIF frame that uses the global palette.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @param  data
+	 * @return Frame
+	 */
+	pub fn get_frame(
+		&self,
+		width: u16,
+		height: u16,
+		data: Vec<u8>,
+	) -> Frame<'static> {
+		let mut image =
+			RgbaImage::from_raw(u32::from(width), u32::from(height), data)
+				.unwrap_or_else(|| RgbaImage::new(width.into(), height.into()));
+		if self.dither {
+			imageops::dither(&mut image, self);
+		}
+		Frame {
+			width,
+			height,
+			buffer: Cow::Owned(imageops::index_colors(&image, self).into_raw()),
+			..Frame::default()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_palette_size() {
+		assert_eq!(Ok(PaletteSize::Auto), PaletteSize::from_str("auto"));
+		assert_eq!(Ok(PaletteSize::Fixed(16)), PaletteSize::from_str("16"));
+		assert_eq!(Ok(PaletteSize::Fixed(256)), PaletteSize::from_str("1000"));
+		assert_eq!(Err(()), PaletteSize::from_str("x"));
+		let mut text = (0..64u8)
+			.flat_map(|i| [i % 2 * 200 + i % 4, 0, 0, 255])
+			.collect::<Vec<u8>>();
+		assert_eq!(2, PaletteSize::Auto.get_colors(&text));
+		let photo = (0..=255u8)
+			.flat_map(|i| [i, i.wrapping_mul(7), 255 - i, 255])
+			.collect::<Vec<u8>>();
+		assert_eq!(256, PaletteSize::Auto.get_colors(&photo));
+		let frame = PaletteSize::get_frame(8, 8, &mut text, 2, 10);
+		assert_eq!(Some(2 * 3), frame.palette.map(|v| v.len()));
+		let frame = PaletteSize::get_frame(16, 16, &mut photo.clone(), 16, 10);
+		assert_eq!(Some(16 * 3), frame.palette.map(|v| v.len()));
+		assert!(frame.buffer.iter().all(|index| *index < 16));
+		let reference = RgbaImage::from_fn(16, 16, |x, _| {
+			Rgba(if x < 8 {
+				[255, 0, 0, 255]
+			} else {
+				[0, 0, 255, 255]
+			})
+		});
+		for dither in [false, true] {
+			let palette = ReferencePalette::new(&reference, dither);
+			assert_eq!(vec![255, 0, 0, 0, 0, 255], palette.get_colors());
+			let frame =
+				palette.get_frame(2, 1, vec![250, 5, 5, 255, 5, 5, 250, 255]);
+			assert_eq!(None, frame.palette);
+			assert_eq!(vec![0, 1], frame.buffer.to_vec());
+		}
+		let photo = RgbaImage::from_raw(16, 16, photo).unwrap_or_default();
+		let palette = ReferencePalette::new(&photo, false);
+		assert_eq!(MAX_COLORS * 3, palette.get_colors().len());
+		let mut color = Rgba([12, 84, 243, 255]);
+		palette.map_color(&mut color);
+		assert_eq!(255, color[3]);
+	}
+}
@@ -0,0 +1,132 @@
+use crate::anim::Frames;
+use crate::image::Image;
+use image::ExtendedColorType;
+
+/* Frame resampling for decreasing the FPS of animations */
+#[derive(Clone, Copy, Debug)]
+pub struct Resampler {
+	fps: f32,
+}
+
+impl Resampler {
+	/**
+	 * Create a new Resampler object.
+	 *
+	 * @param  fps
+	 * @return Resampler
+	 */
+	pub fn new(fps: f32) -> Self {
+		Self { fps }
+	}
+
+	/**
+	 * Select the frames to keep for reaching the target FPS.
+	 *
+	 * Frames are grouped into the time slots of the target FPS
+	 * and the frame that differs the most from the previously
+	 * kept frame is selected from each slot.
+	 *
+	 * @param  frames
+	 * @return Frames
+	 */
+	pub fn apply(&self, frames: Frames) -> Frames {
+		let (images, fps) = frames;
+		if self.fps >= fps || images.len() < 2 {
+			if self.fps > fps {
+				warn!("Target FPS is higher than the source FPS, skipping.");
+			}
+			return (images, fps);
+		}
+		let count = ((images.len() as f32 * self.fps / fps).ceil() as usize).max(1);
+		info!(
+			"Resampling {} frames to {} frames... ({} FPS -> {} FPS)",
+			images.len(),
+			count,
+			fps,
+			self.fps
+		);
+		let mut selected: Vec<usize> = Vec::with_capacity(count);
+		for i in 0..count {
+			let start = ((i as f32 * fps / self.fps) as usize).min(images.len() - 1);
+			let end = (((i + 1) as f32 * fps / self.fps) as usize)
+				.clamp(start + 1, images.len());
+			let index = match selected.last() {
+				Some(&previous) => (start..end)
+					.max_by_key(|&index| {
+						Self::get_difference(&images[previous], &images[index])
+					})
+					.unwrap_or(start),
+				None => start,
+			};
+			selected.push(index);
+		}
+		let mut images: Vec<Option<Image>> = images.into_iter().map(Some).collect();
+		(
+			selected
+				.into_iter()
+				.filter_map(|index| images[index].take())
+				.collect(),
+			self.fps,
+		)
+	}
+
+	/**
+	 * Get the total difference of the pixels between two images.
+	 *
+	 * @param  image
+	 * @param  other
+	 * @return u64
+	 */
+	fn get_difference(image: &Image, other: &Image) -> u64 {
+		if image.geometry != other.geometry {
+			return u64::MAX;
+		}
+		image
+			.get_data(ExtendedColorType::Rgba8)
+			.iter()
+			.zip(other.get_data(ExtendedColorType::Rgba8))
+			.map(|(value, other)| u64::from(value.abs_diff(other)))
+			.sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use image::Rgba;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_resampler() {
+		let geometry = Geometry::new(0, 0, 1, 1);
+		let images = [0, 10, 200, 30, 40, 50]
+			.into_iter()
+			.map(|value| {
+				Image::new(vec![Rgba([value, value, value, 255])], true, geometry)
+			})
+			.collect::<Vec<Image>>();
+		let (frames, fps) = Resampler::new(10.).apply((images.clone(), 30.));
+		assert_eq!(10., fps);
+		assert_eq!(2, frames.len());
+		assert_eq!(
+			vec![0, 0, 0, 255],
+			frames[0].get_data(ExtendedColorType::Rgba8)
+		);
+		assert_eq!(
+			vec![50, 50, 50, 255],
+			frames[1].get_data(ExtendedColorType::Rgba8)
+		);
+		let (frames, fps) = Resampler::new(15.).apply((images.clone(), 30.));
+		assert_eq!(15., fps);
+		assert_eq!(
+			vec![0, 200, 40],
+			frames
+				.iter()
+				.map(|frame| frame.get_data(ExtendedColorType::Rgba8)[0])
+				.collect::<Vec<u8>>()
+		);
+		let (frames, fps) = Resampler::new(60.).apply((images, 30.));
+		assert_eq!(30., fps);
+		assert_eq!(6, frames.len());
+	}
+}
@@ -1,3 +1,4 @@
+use crate::anim::settings::SpeedCurve;
 use crate::app::AppResult;
 use crate::gif::encoder::{Encoder, EncoderConfig};
 use crate::image::Image;
@@ -8,7 +9,8 @@ use std::thread;
 
 /* GIF encoder and settings */
 pub struct GifskiEncoder<Output: Write> {
-	fps: u32,
+	fps: f32,
+	speed_curve: SpeedCurve,
 	collector: Collector,
 	writer: Writer,
 	output: Output,
@@ -22,18 +24,27 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifskiEncoder<Output> {
 	 * @return GifskiEncoder (Result)
 	 */
 	fn new(config: EncoderConfig<'a, Output>) -> AppResult<Self> {
+		if config.settings.get_play_count() == Some(1) {
+			warn!("Playing once is not supported by gifski, looping forever.");
+		}
 		let (collector, writer) = gifski::new(gifski::Settings {
 			width: Some(config.geometry.width),
 			height: Some(config.geometry.height),
 			quality: config.settings.quality,
 			fast: config.settings.gifski.1,
-			repeat: match config.settings.repeat {
-				n if n >= 0 => Repeat::Finite(n.try_into().unwrap_or_default()),
-				_ => Repeat::Infinite,
+			repeat: match config.settings.get_play_count() {
+				Some(plays) => {
+					Repeat::Finite((plays - 1).try_into().unwrap_or(u16::MAX))
+				}
+				None => Repeat::Infinite,
 			},
 		})?;
 		Ok(Self {
-			fps: config.fps,
+			fps: match config.settings.delay {
+				Some(delay) => 1e2 / f32::from(delay),
+				None => config.fps,
+			},
+			speed_curve: config.settings.speed_curve,
 			collector,
 			writer,
 			output: config.output,
@@ -52,7 +63,22 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifskiEncoder<Output> {
 		images: Vec<Image>,
 		input_state: Option<&'static InputState>,
 	) -> AppResult<()> {
-		let fps = self.fps;
+		let timestamps = images
+			.iter()
+			.enumerate()
+			.scan(0., |timestamp, (i, image)| {
+				let frame_timestamp = *timestamp;
+				*timestamp += match image.get_delay() {
+					Some(delay) => delay.as_secs_f64(),
+					None => f64::from(self.speed_curve.get_delay(
+						1. / self.fps,
+						i,
+						images.len(),
+					)),
+				};
+				Some(frame_timestamp)
+			})
+			.collect::<Vec<f64>>();
 		let collector_thread = thread::spawn(move || {
 			for (i, image) in images.iter().enumerate() {
 				let percentage = ((i + 1) as f64 / images.len() as f64) * 100.;
@@ -72,7 +98,7 @@ impl<'a, Output: Write> Encoder<'a, Output> for GifskiEncoder<Output> {
 					}
 				}
 				self.collector
-					.add_frame_rgba(i, image.get_img_vec(), i as f64 / fps as f64)
+					.add_frame_rgba(i, image.get_img_vec(), timestamps[i])
 					.expect("Failed to collect a frame");
 			}
 			info!("\n");
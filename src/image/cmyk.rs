@@ -0,0 +1,235 @@
+use crate::image::settings::Density;
+use image::codecs::jpeg::{JpegEncoder, PixelDensity};
+use image::error::{
+	EncodingError, ImageFormatHint, UnsupportedError, UnsupportedErrorKind,
+};
+use image::{ColorType, ImageEncoder, ImageError, ImageResult};
+use std::io::Write;
+
+/* Start of image marker */
+const JPEG_SOI: &[u8] = &[0xFF, 0xD8];
+/* End of image marker */
+const JPEG_EOI: &[u8] = &[0xFF, 0xD9];
+/* JFIF segment marker */
+const APP0_MARKER: u8 = 0xE0;
+/* Markers of the table segments that are copied from the encoded planes */
+const TABLE_MARKERS: &[u8] = &[0xDB, 0xC4];
+/* Start of scan marker */
+const SOS_MARKER: u8 = 0xDA;
+/* Adobe segment for marking the components as (inverted) CMYK */
+const ADOBE_SEGMENT: &[u8] = &[
+	0xFF, 0xEE, 0x00, 0x0E, b'A', b'd', b'o', b'b', b'e', 0x00, 0x64, 0x00, 0x00,
+	0x00, 0x00, 0x00,
+];
+
+/* JPEG encoder for the CMYK color space */
+pub struct CmykEncoder<Output: Write> {
+	output: Output,
+	quality: u8,
+	density: Option<Density>,
+}
+
+impl<Output: Write> CmykEncoder<Output> {
+	/**
+	 * Create a new CmykEncoder object.
+	 *
+	 * @param  output
+	 * @param  quality
+	 * @param  density (Option)
+	 * @return CmykEncoder
+	 */
+	pub fn new(output: Output, quality: u8, density: Option<Density>) -> Self {
+		Self {
+			output,
+			quality,
+			density,
+		}
+	}
+
+	/**
+	 * Convert the RGB data to inverted CMYK planes.
+	 *
+	 * Inverted values are used in the file as Adobe applications do.
+	 *
+	 * @param  data
+	 * @return Array of Vector
+	 */
+	fn get_planes(data: &[u8]) -> [Vec<u8>; 4] {
+		let mut planes: [Vec<u8>; 4] = Default::default();
+		for rgb in data.chunks_exact(3) {
+			let max = rgb[0].max(rgb[1]).max(rgb[2]);
+			for (plane, value) in planes.iter_mut().zip(rgb) {
+				plane.push(if max == 0 {
+					u8::MAX
+				} else {
+					((u16::from(*value) * 255 + u16::from(max) / 2) / u16::from(max))
+						as u8
+				});
+			}
+			planes[3].push(max);
+		}
+		planes
+	}
+
+	/**
+	 * Get the JFIF segment, tables and the scan data of an encoded plane.
+	 *
+	 * @param  data
+	 * @return Tuple (Option)
+	 */
+	fn read_segments(data: &[u8]) -> Option<(Vec<u8>, Vec<u8>, &[u8])> {
+		let mut header = Vec::new();
+		let mut tables = Vec::new();
+		let mut index = JPEG_SOI.len();
+		loop {
+			let marker = *data.get(index + 1)?;
+			let length = usize::from(u16::from_be_bytes([
+				*data.get(index + 2)?,
+				*data.get(index + 3)?,
+			]));
+			let segment = data.get(index..index + 2 + length)?;
+			index += segment.len();
+			if marker == SOS_MARKER {
+				return Some((
+					header,
+					tables,
+					data.get(index..data.len().checked_sub(JPEG_EOI.len())?)?,
+				));
+			} else if marker == APP0_MARKER {
+				header.extend_from_slice(segment);
+			} else if TABLE_MARKERS.contains(&marker) {
+				tables.extend_from_slice(segment);
+			}
+		}
+	}
+}
+
+/* Encoder implementation for CmykEncoder */
+impl<Output: Write> ImageEncoder for CmykEncoder<Output> {
+	/**
+	 * Encode the RGB data as a CMYK JPEG.
+	 *
+	 * Planes are encoded separately and written as non-interleaved scans.
+	 *
+	 * @param  buf
+	 * @param  width
+	 * @param  height
+	 * @param  color_type
+	 * @return Result
+	 */
+	fn write_image(
+		mut self,
+		buf: &[u8],
+		width: u32,
+		height: u32,
+		color_type: ColorType,
+	) -> ImageResult<()> {
+		if color_type != ColorType::Rgb8 {
+			return Err(ImageError::Unsupported(
+				UnsupportedError::from_format_and_kind(
+					ImageFormatHint::Name(String::from("CMYK JPEG")),
+					UnsupportedErrorKind::Color(color_type.into()),
+				),
+			));
+		}
+		let mut scans = Vec::new();
+		let (mut header, mut tables) = (Vec::new(), Vec::new());
+		for (i, plane) in Self::get_planes(buf).iter().enumerate() {
+			let mut data = Vec::new();
+			let mut encoder = JpegEncoder::new_with_quality(&mut data, self.quality);
+			if let Some(density) = self.density {
+				encoder.set_pixel_density(PixelDensity::dpi(density.dpi));
+			}
+			encoder.write_image(plane, width, height, ColorType::L8)?;
+			let (plane_header, plane_tables, scan) = Self::read_segments(&data)
+				.ok_or_else(|| {
+					ImageError::Encoding(EncodingError::new(
+						ImageFormatHint::Name(String::from("CMYK JPEG")),
+						"Invalid JPEG data",
+					))
+				})?;
+			if i == 0 {
+				header = plane_header;
+				tables = plane_tables;
+			}
+			scans.extend_from_slice(&[
+				0xFF,
+				SOS_MARKER,
+				0x00,
+				0x08,
+				0x01,
+				i as u8 + 1,
+				0x00,
+				0x00,
+				0x3F,
+				0x00,
+			]);
+			scans.extend_from_slice(scan);
+		}
+		let mut frame = vec![0xFF, 0xC0, 0x00, 0x14, 0x08];
+		frame.extend_from_slice(&(height as u16).to_be_bytes());
+		frame.extend_from_slice(&(width as u16).to_be_bytes());
+		frame.push(4);
+		for id in 1..=4 {
+			frame.extend_from_slice(&[id, 0x11, 0x00]);
+		}
+		for data in [
+			JPEG_SOI,
+			&header,
+			ADOBE_SEGMENT,
+			&tables,
+			&frame,
+			&scans,
+			JPEG_EOI,
+		] {
+			self.output.write_all(data)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_cmyk_encoder() -> ImageResult<()> {
+		let data = [
+			[255, 0, 0],
+			[0, 0, 255],
+			[0, 0, 0],
+			[255, 255, 255],
+			[128, 64, 32],
+			[0, 128, 0],
+		]
+		.repeat(16)
+		.concat();
+		assert_eq!(
+			[vec![255], vec![128], vec![64], vec![128]],
+			CmykEncoder::<Vec<u8>>::get_planes(&[128, 64, 32])
+		);
+		let mut output = Vec::new();
+		CmykEncoder::new(&mut output, 100, Some(Density::new(300))).write_image(
+			&data,
+			12,
+			8,
+			ColorType::Rgb8,
+		)?;
+		assert!(output
+			.windows(ADOBE_SEGMENT.len())
+			.any(|segment| segment == ADOBE_SEGMENT));
+		assert_eq!(Some(&[0xFF, APP0_MARKER][..]), output.get(2..4));
+		let image = image::load_from_memory(&output)?;
+		assert_eq!((12, 8), (image.width(), image.height()));
+		let image = image.to_rgb8();
+		for (pixel, rgb) in image.pixels().zip(data.chunks(3)) {
+			for (value, expected) in pixel.0.iter().zip(rgb) {
+				assert!(value.abs_diff(*expected) < 16);
+			}
+		}
+		assert!(CmykEncoder::new(Vec::new(), 90, None)
+			.write_image(&[0; 4], 1, 1, ColorType::Rgba8)
+			.is_err());
+		Ok(())
+	}
+}
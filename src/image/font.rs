@@ -0,0 +1,87 @@
+/* Width of a glyph in pixels */
+pub const GLYPH_WIDTH: u32 = 5;
+/* Height of a glyph in pixels */
+pub const GLYPH_HEIGHT: u32 = 7;
+/* Space between the glyphs in pixels */
+pub const GLYPH_SPACING: u32 = 1;
+
+/**
+ * Get the rows of the glyph for the given character.
+ *
+ * Only uppercase letters, digits and some symbols are included,
+ * other characters are shown as question marks.
+ *
+ * @param  c
+ * @return Array of u8
+ */
+pub fn get_glyph(c: char) -> [u8; GLYPH_HEIGHT as usize] {
+	match c.to_ascii_uppercase() {
+		'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+		'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+		'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+		'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+		'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+		'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+		'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+		'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+		'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+		'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+		'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+		'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+		'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+		'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+		'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+		'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+		'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+		'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+		'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+		'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+		'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+		'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+		'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+		'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+		'Y' => [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04],
+		'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+		'0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+		'1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+		'2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+		'3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+		'4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+		'5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+		'6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+		'7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+		'8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+		'9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+		' ' => [0x00; GLYPH_HEIGHT as usize],
+		'+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+		'-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+		'*' => [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00],
+		'=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+		'.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+		',' => [0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08],
+		';' => [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08],
+		'/' => [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00],
+		'\\' => [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00],
+		'[' => [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E],
+		']' => [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E],
+		'\'' => [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+		'`' => [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00],
+		_ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+	}
+}
+
+/**
+ * Get the size of the rendered text.
+ *
+ * @param  text
+ * @param  scale
+ * @return Tuple
+ */
+pub fn get_text_size(text: &str, scale: u32) -> (u32, u32) {
+	let count = u32::try_from(text.chars().count()).unwrap_or_default();
+	(
+		(count * (GLYPH_WIDTH + GLYPH_SPACING)).saturating_sub(GLYPH_SPACING)
+			* scale,
+		GLYPH_HEIGHT * scale,
+	)
+}
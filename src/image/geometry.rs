@@ -1,5 +1,6 @@
 use crate::image::padding::Padding;
 use std::fmt;
+use std::io::{self, Read, Write};
 
 /* Position and size in 2D */
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -85,16 +86,67 @@ impl Geometry {
 			.y
 			.checked_add(i32::try_from(padding.top).unwrap_or_default())
 			.unwrap_or_default();
-		self.width = self
-			.width
-			.checked_sub(padding.right + padding.left)
-			.unwrap_or_default();
-		self.height = self
-			.height
-			.checked_sub(padding.top + padding.bottom)
-			.unwrap_or_default();
+		self.width = self.width.saturating_sub(padding.right + padding.left);
+		self.height = self.height.saturating_sub(padding.top + padding.bottom);
 		*self
 	}
+
+	/**
+	 * Get the intersection with the given geometry.
+	 *
+	 * @param  geometry
+	 * @return Geometry (Option)
+	 */
+	pub fn intersect(&self, geometry: Geometry) -> Option<Self> {
+		let x = self.x.max(geometry.x);
+		let y = self.y.max(geometry.y);
+		let right = (i64::from(self.x) + i64::from(self.width))
+			.min(i64::from(geometry.x) + i64::from(geometry.width));
+		let bottom = (i64::from(self.y) + i64::from(self.height))
+			.min(i64::from(geometry.y) + i64::from(geometry.height));
+		if right > i64::from(x) && bottom > i64::from(y) {
+			Some(Self::new(
+				x,
+				y,
+				u32::try_from(right - i64::from(x)).unwrap_or_default(),
+				u32::try_from(bottom - i64::from(y)).unwrap_or_default(),
+			))
+		} else {
+			None
+		}
+	}
+
+	/**
+	 * Write the raw (little-endian) representation of Geometry.
+	 *
+	 * @param  output
+	 * @return Result
+	 */
+	pub fn write_raw<Output: Write>(&self, output: &mut Output) -> io::Result<()> {
+		output.write_all(&self.x.to_le_bytes())?;
+		output.write_all(&self.y.to_le_bytes())?;
+		output.write_all(&self.width.to_le_bytes())?;
+		output.write_all(&self.height.to_le_bytes())
+	}
+
+	/**
+	 * Read Geometry from its raw representation.
+	 *
+	 * @param  input
+	 * @return Geometry (Result)
+	 */
+	pub fn read_raw<Input: Read>(input: &mut Input) -> io::Result<Self> {
+		let mut values = [[0; 4]; 4];
+		for value in values.iter_mut() {
+			input.read_exact(value)?;
+		}
+		Ok(Self::new(
+			i32::from_le_bytes(values[0]),
+			i32::from_le_bytes(values[1]),
+			u32::from_le_bytes(values[2]),
+			u32::from_le_bytes(values[3]),
+		))
+	}
 }
 
 #[cfg(test)]
@@ -112,5 +164,16 @@ mod tests {
 		let values = "45x28";
 		let geometry = Geometry::parse(values);
 		assert_eq!(values, geometry.to_string());
+		let geometry = Geometry::new(0, 0, 100, 100);
+		assert_eq!(
+			Some(Geometry::new(50, 0, 50, 20)),
+			geometry.intersect(Geometry::new(50, -10, 100, 30))
+		);
+		assert_eq!(None, geometry.intersect(Geometry::new(100, 0, 10, 10)));
+		let geometry = Geometry::new(-5, 10, 640, 480);
+		let mut raw = Vec::new();
+		geometry.write_raw(&mut raw).unwrap();
+		assert_eq!(16, raw.len());
+		assert_eq!(geometry, Geometry::read_raw(&mut raw.as_slice()).unwrap());
 	}
 }
@@ -1,10 +1,17 @@
+pub mod cmyk;
+pub mod font;
 pub mod geometry;
+pub mod monitor;
 pub mod padding;
+pub mod ratio;
 pub mod settings;
 use std::fmt;
 
 use crate::image::geometry::Geometry;
-use image::{ExtendedColorType, Rgba};
+use image::imageops::{self, FilterType};
+use image::{ExtendedColorType, Rgba, RgbaImage};
+use std::io::{self, Read, Write};
+use std::time::Duration;
 #[cfg(feature = "ski")]
 use {
 	imgref::{Img, ImgVec},
@@ -18,7 +25,9 @@ const SRGB_LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
 #[derive(Clone)]
 pub struct Image {
 	data: Vec<Rgba<u8>>,
+	deep_data: Option<Vec<Rgba<u16>>>,
 	alpha_channel: bool,
+	delay: Option<Duration>,
 	pub geometry: Geometry,
 }
 
@@ -49,11 +58,109 @@ impl Image {
 	) -> Self {
 		Self {
 			data,
+			deep_data: None,
 			alpha_channel,
+			delay: None,
 			geometry,
 		}
 	}
 
+	/**
+	 * Set the time to show the image as a frame of an animation.
+	 *
+	 * @param  delay
+	 * @return Image
+	 */
+	pub fn with_delay(mut self, delay: Duration) -> Self {
+		self.delay = Some(delay);
+		self
+	}
+
+	/**
+	 * Get the frame delay of the image (if any).
+	 *
+	 * @return Duration (Option)
+	 */
+	pub fn get_delay(&self) -> Option<Duration> {
+		self.delay
+	}
+
+	/**
+	 * Keep the data with more than 8 bits per channel along with the image.
+	 *
+	 * @param  deep_data
+	 * @return Image
+	 */
+	pub fn with_deep_data(mut self, deep_data: Vec<Rgba<u16>>) -> Self {
+		if deep_data.len() == self.data.len() {
+			self.deep_data = Some(deep_data);
+		}
+		self
+	}
+
+	/**
+	 * Check if the image has data with more than 8 bits per channel.
+	 *
+	 * @return bool
+	 */
+	pub fn is_deep(&self) -> bool {
+		self.deep_data.is_some()
+	}
+
+	/**
+	 * Get the color of the image if all the pixels are the same.
+	 *
+	 * @return Rgba (Option)
+	 */
+	pub fn get_uniform_color(&self) -> Option<Rgba<u8>> {
+		let color = *self.data.first()?;
+		self.data.iter().all(|rgba| *rgba == color).then_some(color)
+	}
+
+	/* Set the alpha value of all pixels to the maximum. */
+	pub fn set_opaque(&mut self) {
+		self.data.iter_mut().for_each(|rgba| rgba[3] = u8::MAX);
+		if let Some(deep_data) = self.deep_data.as_mut() {
+			deep_data.iter_mut().for_each(|rgba| rgba[3] = u16::MAX);
+		}
+	}
+
+	/**
+	 * Composite the transparent pixels over the background color.
+	 *
+	 * @param background
+	 */
+	pub fn fill_background(&mut self, background: Rgba<u8>) {
+		if !self.alpha_channel {
+			return;
+		}
+		let blend = |value: u32, background: u32, alpha: u32, max: u32| {
+			(value * alpha + background * (max - alpha) + max / 2) / max
+		};
+		self.data.iter_mut().for_each(|rgba| {
+			let alpha = u32::from(rgba[3]);
+			for i in 0..3 {
+				rgba[i] =
+					blend(rgba[i].into(), background[i].into(), alpha, 255) as u8;
+			}
+			rgba[3] = u8::MAX;
+		});
+		if let Some(deep_data) = self.deep_data.as_mut() {
+			deep_data.iter_mut().for_each(|rgba| {
+				let alpha = u32::from(rgba[3]);
+				for i in 0..3 {
+					rgba[i] = blend(
+						rgba[i].into(),
+						u32::from(background[i]) * 257,
+						alpha,
+						65535,
+					) as u16;
+				}
+				rgba[3] = u16::MAX;
+			});
+		}
+	}
+
 	/**
 	 * Get image data in the given color type.
 	 *
@@ -61,6 +168,28 @@ impl Image {
 	 * @return Vector of u8
 	 */
 	pub fn get_data(&self, color_type: ExtendedColorType) -> Vec<u8> {
+		if let (
+			ExtendedColorType::Rgb16 | ExtendedColorType::Rgba16,
+			Some(deep_data),
+		) = (color_type, &self.deep_data)
+		{
+			return deep_data
+				.iter()
+				.flat_map(|rgba| {
+					let alpha = if self.alpha_channel {
+						rgba[3]
+					} else {
+						u16::MAX
+					};
+					if color_type == ExtendedColorType::Rgb16 {
+						vec![rgba[0], rgba[1], rgba[2]]
+					} else {
+						vec![rgba[0], rgba[1], rgba[2], alpha]
+					}
+				})
+				.flat_map(u16::to_ne_bytes)
+				.collect();
+		}
 		self.data.iter().fold(Vec::<u8>::new(), |mut data, rgba| {
 			let alpha = if self.alpha_channel { rgba[3] } else { 255 };
 			data.extend(&match color_type {
@@ -75,6 +204,9 @@ impl Image {
 					}
 				}],
 				ExtendedColorType::Rgb8 => vec![rgba[0], rgba[1], rgba[2]],
+				ExtendedColorType::Rgb16 => {
+					vec![rgba[0], rgba[0], rgba[1], rgba[1], rgba[2], rgba[2]]
+				}
 				ExtendedColorType::Rgba16 => vec![
 					rgba[0], rgba[0], rgba[1], rgba[1], rgba[2], rgba[2], alpha,
 					alpha,
@@ -93,6 +225,307 @@ impl Image {
 		})
 	}
 
+	/**
+	 * Get the given area of the image that is within the bounds.
+	 *
+	 * @param  area
+	 * @return Geometry (Option)
+	 */
+	fn get_area(&self, area: Geometry) -> Option<Geometry> {
+		Geometry::new(0, 0, self.geometry.width, self.geometry.height)
+			.intersect(area)
+	}
+
+	/**
+	 * Fill the given area of the image with a color.
+	 *
+	 * @param area
+	 * @param color
+	 */
+	pub fn fill_area(&mut self, area: Geometry, color: Rgba<u8>) {
+		if let Some(area) = self.get_area(area) {
+			let width = self.geometry.width as usize;
+			for y in area.y as usize..(area.y as usize + area.height as usize) {
+				let start = y * width + area.x as usize;
+				if let Some(row) =
+					self.data.get_mut(start..start + area.width as usize)
+				{
+					row.fill(color);
+				}
+				if let Some(row) = self.deep_data.as_mut().and_then(|deep_data| {
+					deep_data.get_mut(start..start + area.width as usize)
+				}) {
+					row.fill(Rgba(color.0.map(|v| u16::from(v) * 257)));
+				}
+			}
+		}
+	}
+
+	/**
+	 * Blend a filled circle with the given color and opacity.
+	 *
+	 * @param center
+	 * @param radius
+	 * @param color
+	 * @param opacity
+	 */
+	pub fn draw_dot(
+		&mut self,
+		center: (i32, i32),
+		radius: u32,
+		color: Rgba<u8>,
+		opacity: f32,
+	) {
+		let r = i32::try_from(radius).unwrap_or_default();
+		let area = Geometry::new(
+			center.0 - r,
+			center.1 - r,
+			radius * 2 + 1,
+			radius * 2 + 1,
+		);
+		if let Some(area) = self.get_area(area) {
+			self.deep_data = None;
+			for y in area.y..area.y + area.height as i32 {
+				for x in area.x..area.x + area.width as i32 {
+					let (dx, dy) = (x - center.0, y - center.1);
+					if dx * dx + dy * dy <= r * r {
+						self.blend_pixel(x, y, color, opacity);
+					}
+				}
+			}
+		}
+	}
+
+	/**
+	 * Blend the given area with a color and opacity.
+	 *
+	 * @param area
+	 * @param color
+	 * @param opacity
+	 */
+	pub fn shade_area(&mut self, area: Geometry, color: Rgba<u8>, opacity: f32) {
+		if let Some(area) = self.get_area(area) {
+			self.deep_data = None;
+			for y in area.y..area.y + area.height as i32 {
+				for x in area.x..area.x + area.width as i32 {
+					self.blend_pixel(x, y, color, opacity);
+				}
+			}
+		}
+	}
+
+	/**
+	 * Blend a text with the given color and opacity.
+	 *
+	 * @param text
+	 * @param position
+	 * @param scale
+	 * @param color
+	 * @param opacity
+	 */
+	pub fn draw_text(
+		&mut self,
+		text: &str,
+		position: (i32, i32),
+		scale: u32,
+		color: Rgba<u8>,
+		opacity: f32,
+	) {
+		self.deep_data = None;
+		let scale = i32::try_from(scale).unwrap_or(1);
+		let advance = i32::try_from(font::GLYPH_WIDTH + font::GLYPH_SPACING)
+			.unwrap_or_default()
+			* scale;
+		for (i, c) in text.chars().enumerate() {
+			let left = position.0 + i32::try_from(i).unwrap_or_default() * advance;
+			for (row, bits) in (0..).zip(font::get_glyph(c)) {
+				for column in 0..font::GLYPH_WIDTH {
+					if (bits >> (font::GLYPH_WIDTH - 1 - column)) & 1 == 0 {
+						continue;
+					}
+					let x = left + i32::try_from(column).unwrap_or_default() * scale;
+					let y = position.1 + row * scale;
+					for (dx, dy) in
+						(0..scale).flat_map(|dx| (0..scale).map(move |dy| (dx, dy)))
+					{
+						self.blend_pixel(x + dx, y + dy, color, opacity);
+					}
+				}
+			}
+		}
+	}
+
+	/**
+	 * Blend the pixel at the given position with a color.
+	 *
+	 * @param x
+	 * @param y
+	 * @param color
+	 * @param opacity
+	 */
+	fn blend_pixel(&mut self, x: i32, y: i32, color: Rgba<u8>, opacity: f32) {
+		let (width, height) =
+			(self.geometry.width as i32, self.geometry.height as i32);
+		if !(0..width).contains(&x) || !(0..height).contains(&y) {
+			return;
+		}
+		if let Some(pixel) = self.data.get_mut((y * width + x) as usize) {
+			for c in 0..3 {
+				pixel[c] = (f32::from(pixel[c]) * (1. - opacity)
+					+ f32::from(color[c]) * opacity)
+					.round() as u8;
+			}
+			pixel[3] = pixel[3].max((opacity * 255.).round() as u8);
+		}
+	}
+
+	/**
+	 * Blur the given area of the image.
+	 *
+	 * @param area
+	 * @param sigma
+	 */
+	pub fn blur_area(&mut self, area: Geometry, sigma: f32) {
+		if let Some(area) = self.get_area(area) {
+			self.deep_data = None;
+			let width = self.geometry.width as usize;
+			let buffer = RgbaImage::from_fn(area.width, area.height, |x, y| {
+				self.data
+					.get(
+						(area.y as usize + y as usize) * width
+							+ area.x as usize + x as usize,
+					)
+					.copied()
+					.unwrap_or(Rgba([0, 0, 0, 0]))
+			});
+			let buffer = imageops::blur(&buffer, sigma);
+			for (x, y, pixel) in buffer.enumerate_pixels() {
+				if let Some(rgba) = self.data.get_mut(
+					(area.y as usize + y as usize) * width
+						+ area.x as usize + x as usize,
+				) {
+					*rgba = *pixel;
+				}
+			}
+		}
+	}
+
+	/**
+	 * Get the image centered on a canvas with the given size.
+	 *
+	 * @param  size
+	 * @param  background
+	 * @return Image
+	 */
+	pub fn with_canvas(&self, size: Geometry, background: Rgba<u8>) -> Self {
+		let mut data = vec![background; size.width as usize * size.height as usize];
+		let offset_x = (size.width as i64 - self.geometry.width as i64) / 2;
+		let offset_y = (size.height as i64 - self.geometry.height as i64) / 2;
+		for (i, rgba) in self.data.iter().enumerate() {
+			let x = (i % self.geometry.width as usize) as i64 + offset_x;
+			let y = (i / self.geometry.width as usize) as i64 + offset_y;
+			if (0..size.width as i64).contains(&x)
+				&& (0..size.height as i64).contains(&y)
+			{
+				data[y as usize * size.width as usize + x as usize] = *rgba;
+			}
+		}
+		Self {
+			delay: self.delay,
+			..Self::new(
+				data,
+				self.alpha_channel,
+				Geometry {
+					width: size.width,
+					height: size.height,
+					..self.geometry
+				},
+			)
+		}
+	}
+
+	/**
+	 * Get the image resized to the given size.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @param  filter
+	 * @return Image
+	 */
+	pub fn resize(&self, width: u32, height: u32, filter: FilterType) -> Self {
+		let buffer =
+			RgbaImage::from_fn(self.geometry.width, self.geometry.height, |x, y| {
+				self.data
+					.get((y * self.geometry.width + x) as usize)
+					.copied()
+					.unwrap_or(Rgba([0, 0, 0, 0]))
+			});
+		Self {
+			delay: self.delay,
+			..Self::new(
+				imageops::resize(&buffer, width, height, filter)
+					.pixels()
+					.copied()
+					.collect(),
+				self.alpha_channel,
+				Geometry {
+					width,
+					height,
+					..self.geometry
+				},
+			)
+		}
+	}
+
+	/**
+	 * Write the raw representation of the image.
+	 *
+	 * @param  output
+	 * @return Result
+	 */
+	pub fn write_raw<Output: Write>(&self, output: &mut Output) -> io::Result<()> {
+		self.geometry.write_raw(output)?;
+		output.write_all(&[u8::from(self.alpha_channel)])?;
+		output.write_all(&(self.data.len() as u64).to_le_bytes())?;
+		output.write_all(
+			&self
+				.data
+				.iter()
+				.flat_map(|rgba| rgba.0)
+				.collect::<Vec<u8>>(),
+		)
+	}
+
+	/**
+	 * Read the image from its raw representation.
+	 *
+	 * @param  input
+	 * @return Image (Result)
+	 */
+	pub fn read_raw<Input: Read>(input: &mut Input) -> io::Result<Self> {
+		let geometry = Geometry::read_raw(input)?;
+		let mut alpha_channel = [0; 1];
+		input.read_exact(&mut alpha_channel)?;
+		let mut data_len = [0; 8];
+		input.read_exact(&mut data_len)?;
+		let data_len = u64::from_le_bytes(data_len).saturating_mul(4);
+		let mut data = Vec::new();
+		input.take(data_len).read_to_end(&mut data)?;
+		if data.len() as u64 != data_len {
+			return Err(io::Error::new(
+				io::ErrorKind::UnexpectedEof,
+				"Incomplete image data",
+			));
+		}
+		Ok(Self::new(
+			data.chunks_exact(4)
+				.map(|rgba| Rgba::from([rgba[0], rgba[1], rgba[2], rgba[3]]))
+				.collect(),
+			alpha_channel[0] != 0,
+			geometry,
+		))
+	}
+
 	/**
 	 * Get an Img Vector from the image data.
 	 *
@@ -146,5 +579,71 @@ mod tests {
 		assert_eq!(255, image.get_data(ExtendedColorType::Rgb8)[4]);
 		assert_eq!(255, image.get_data(ExtendedColorType::Rgba8)[5]);
 		assert_eq!(128, image.get_data(ExtendedColorType::Rgba16)[5]);
+		let resized = Image::new(
+			vec![Rgba::from([255, 0, 0, 255]); 16],
+			false,
+			Geometry::new(0, 0, 4, 4),
+		)
+		.resize(2, 1, FilterType::Triangle);
+		assert_eq!(Geometry::new(0, 0, 2, 1), resized.geometry);
+		assert_eq!(
+			vec![255, 0, 0],
+			resized.get_data(ExtendedColorType::Rgb8)[..3]
+		);
+		assert_eq!(None, image.get_uniform_color());
+		let mut image = Image::new(data.to_vec(), true, geometry);
+		assert_eq!(0, image.get_data(ExtendedColorType::Rgba8)[3]);
+		image.set_opaque();
+		assert!(image
+			.get_data(ExtendedColorType::Rgba8)
+			.chunks(4)
+			.all(|rgba| rgba[3] == 255));
+		let mut image = Image::new(
+			vec![Rgba::from([255, 255, 255, 255]); 16],
+			true,
+			Geometry::new(0, 0, 4, 4),
+		);
+		assert_eq!(
+			Some(Rgba::from([255, 255, 255, 255])),
+			image.get_uniform_color()
+		);
+		image.fill_area(Geometry::new(2, 2, 10, 10), Rgba::from([0, 0, 0, 255]));
+		let data = image.get_data(ExtendedColorType::L8);
+		assert_eq!(4, data.iter().filter(|v| **v == 0).count());
+		assert_eq!(0, data[15]);
+		let canvas = image
+			.with_canvas(Geometry::new(0, 0, 6, 2), Rgba::from([0, 0, 255, 255]));
+		assert_eq!(Geometry::new(0, 0, 6, 2), canvas.geometry);
+		let data = canvas.get_data(ExtendedColorType::Rgba8);
+		assert_eq!(vec![0, 0, 255, 255], data[0..4]);
+		assert_eq!(vec![255, 255, 255, 255], data[4..8]);
+		assert_eq!(vec![0, 0, 0, 255], data[40..44]);
+		image.blur_area(Geometry::new(0, 0, 4, 4), 1.);
+		assert!(image.get_data(ExtendedColorType::L8)[15] > 0);
+		let mut raw = Vec::new();
+		image.write_raw(&mut raw).unwrap();
+		let raw_image = Image::read_raw(&mut raw.as_slice()).unwrap();
+		assert_eq!(image.geometry, raw_image.geometry);
+		assert_eq!(
+			image.get_data(ExtendedColorType::Rgba8),
+			raw_image.get_data(ExtendedColorType::Rgba8)
+		);
+		assert!(Image::read_raw(&mut &raw[..raw.len() - 1]).is_err());
+		let mut image = Image::new(
+			vec![Rgba::from([255, 0, 0, 0]); 4],
+			false,
+			Geometry::new(0, 0, 2, 2),
+		)
+		.with_deep_data(vec![Rgba::from([0xFFC0, 0x0100, 0, 0]); 4]);
+		assert!(image.is_deep());
+		let data = image.get_data(ExtendedColorType::Rgba16);
+		assert_eq!(32, data.len());
+		assert_eq!(0x0100, u16::from_ne_bytes([data[2], data[3]]));
+		assert_eq!(u16::MAX, u16::from_ne_bytes([data[6], data[7]]));
+		image.fill_area(Geometry::new(0, 0, 1, 1), Rgba::from([0, 0, 255, 255]));
+		let data = image.get_data(ExtendedColorType::Rgba16);
+		assert_eq!(u16::MAX, u16::from_ne_bytes([data[4], data[5]]));
+		image.blur_area(Geometry::new(0, 0, 2, 2), 1.);
+		assert!(!image.is_deep());
 	}
 }
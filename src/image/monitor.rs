@@ -0,0 +1,242 @@
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+
+/* Maximum ratio between the DPI values of the uniform monitors */
+const DPI_TOLERANCE: f32 = 1.1;
+/* Background color of the areas that are not covered by a monitor */
+const LAYOUT_BACKGROUND: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+/* Monitor area with its pixel density */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Monitor {
+	pub geometry: Geometry,
+	pub dpi: f32,
+}
+
+impl Monitor {
+	/**
+	 * Create a new Monitor object.
+	 *
+	 * @param  geometry
+	 * @param  dpi
+	 * @return Monitor
+	 */
+	pub fn new(geometry: Geometry, dpi: f32) -> Self {
+		Self { geometry, dpi }
+	}
+}
+
+/* Layout of the monitors scaled to the same pixel density */
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorLayout {
+	areas: Vec<(Geometry, Geometry)>,
+	size: (u32, u32),
+}
+
+impl MonitorLayout {
+	/**
+	 * Create a new MonitorLayout object if the monitors have different densities.
+	 *
+	 * @param  monitors
+	 * @return MonitorLayout (Option)
+	 */
+	pub fn new(monitors: &[Monitor]) -> Option<Self> {
+		let max_dpi = monitors
+			.iter()
+			.map(|monitor| monitor.dpi)
+			.reduce(f32::max)?;
+		let min_dpi = monitors
+			.iter()
+			.map(|monitor| monitor.dpi)
+			.reduce(f32::min)?;
+		if min_dpi <= 0. || max_dpi / min_dpi <= DPI_TOLERANCE {
+			return None;
+		}
+		let sizes = monitors
+			.iter()
+			.map(|monitor| {
+				let scale = max_dpi / monitor.dpi;
+				(
+					(monitor.geometry.width as f32 * scale).round() as u32,
+					(monitor.geometry.height as f32 * scale).round() as u32,
+				)
+			})
+			.collect::<Vec<(u32, u32)>>();
+		let geometries = monitors
+			.iter()
+			.map(|monitor| monitor.geometry)
+			.collect::<Vec<Geometry>>();
+		let x = Self::get_positions(
+			&geometries
+				.iter()
+				.zip(&sizes)
+				.map(|(geometry, size)| (geometry.x, geometry.width, size.0))
+				.collect::<Vec<(i32, u32, u32)>>(),
+		);
+		let y = Self::get_positions(
+			&geometries
+				.iter()
+				.zip(&sizes)
+				.map(|(geometry, size)| (geometry.y, geometry.height, size.1))
+				.collect::<Vec<(i32, u32, u32)>>(),
+		);
+		let areas = geometries
+			.iter()
+			.enumerate()
+			.map(|(i, geometry)| {
+				(*geometry, Geometry::new(x[i], y[i], sizes[i].0, sizes[i].1))
+			})
+			.collect::<Vec<(Geometry, Geometry)>>();
+		let size = areas.iter().fold((0, 0), |size, (_, area)| {
+			(
+				size.0
+					.max(u32::try_from(area.x).unwrap_or_default() + area.width),
+				size.1
+					.max(u32::try_from(area.y).unwrap_or_default() + area.height),
+			)
+		});
+		Some(Self { areas, size })
+	}
+
+	/**
+	 * Get the positions of the scaled monitors on one axis.
+	 *
+	 * Monitors are placed after the scaled monitors that end
+	 * before them so that the adjacent monitors stay adjacent.
+	 *
+	 * @param  monitors (position, length, scaled length)
+	 * @return Vector of i32
+	 */
+	fn get_positions(monitors: &[(i32, u32, u32)]) -> Vec<i32> {
+		let mut order = (0..monitors.len()).collect::<Vec<usize>>();
+		order.sort_by_key(|i| monitors[*i].0);
+		let mut positions = vec![0; monitors.len()];
+		for (index, i) in order.iter().enumerate() {
+			let (position, _, _) = monitors[*i];
+			positions[*i] = order[..index]
+				.iter()
+				.filter_map(|j| {
+					let (start, length, scaled) = monitors[*j];
+					let end = start + i32::try_from(length).unwrap_or_default();
+					(end <= position).then(|| {
+						positions[*j]
+							+ i32::try_from(scaled).unwrap_or_default()
+							+ (position - end)
+					})
+				})
+				.max()
+				.unwrap_or(position);
+		}
+		positions
+	}
+
+	/**
+	 * Get the image with the monitors scaled into the layout.
+	 *
+	 * @param  image
+	 * @return Image
+	 */
+	pub fn get_image(&self, image: &Image) -> Image {
+		let (width, height) = (image.geometry.width, image.geometry.height);
+		let source = RgbaImage::from_fn(width, height, |x, y| {
+			image.data[(y * width + x) as usize]
+		});
+		let mut canvas =
+			RgbaImage::from_pixel(self.size.0, self.size.1, LAYOUT_BACKGROUND);
+		for (geometry, area) in &self.areas {
+			let geometry =
+				match Geometry::new(0, 0, width, height).intersect(*geometry) {
+					Some(geometry) => geometry,
+					None => continue,
+				};
+			let monitor = imageops::crop_imm(
+				&source,
+				u32::try_from(geometry.x).unwrap_or_default(),
+				u32::try_from(geometry.y).unwrap_or_default(),
+				geometry.width,
+				geometry.height,
+			)
+			.to_image();
+			/* Monitors with the highest density are copied without resampling */
+			let monitor = if monitor.dimensions() == (area.width, area.height) {
+				monitor
+			} else {
+				imageops::resize(
+					&monitor,
+					area.width,
+					area.height,
+					FilterType::CatmullRom,
+				)
+			};
+			imageops::replace(
+				&mut canvas,
+				&monitor,
+				i64::from(area.x),
+				i64::from(area.y),
+			);
+		}
+		Image::new(
+			canvas.pixels().copied().collect(),
+			image.alpha_channel,
+			Geometry {
+				width: self.size.0,
+				height: self.size.1,
+				..image.geometry
+			},
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_monitor_layout() {
+		let monitors = [
+			Monitor::new(Geometry::new(0, 0, 4, 4), 192.),
+			Monitor::new(Geometry::new(4, 0, 2, 2), 96.),
+		];
+		assert_eq!(
+			None,
+			MonitorLayout::new(&[
+				Monitor::new(Geometry::new(0, 0, 4, 4), 96.),
+				Monitor::new(Geometry::new(4, 0, 4, 4), 100.),
+			])
+		);
+		assert_eq!(None, MonitorLayout::new(&[]));
+		let layout = MonitorLayout::new(&monitors).unwrap();
+		assert_eq!((8, 4), layout.size);
+		assert_eq!(Geometry::new(4, 0, 4, 4), layout.areas[1].1);
+		let data = (0..24)
+			.map(|i| {
+				if i == 7 {
+					Rgba([0, 255, 0, 255])
+				} else if i % 6 < 4 {
+					Rgba([255, 0, 0, 255])
+				} else {
+					Rgba([0, 0, 255, 255])
+				}
+			})
+			.collect();
+		let image =
+			layout.get_image(&Image::new(data, false, Geometry::new(0, 0, 6, 4)));
+		assert_eq!(Geometry::new(0, 0, 8, 4), image.geometry);
+		assert_eq!(Rgba([255, 0, 0, 255]), image.data[0]);
+		assert_eq!(Rgba([255, 0, 0, 255]), image.data[8]);
+		assert_eq!(Rgba([0, 255, 0, 255]), image.data[8 + 1]);
+		assert_eq!(Rgba([0, 0, 255, 255]), image.data[8 + 5]);
+		assert_eq!(Rgba([0, 0, 255, 255]), image.data[3 * 8 + 7]);
+		assert_eq!(
+			vec![0, 100, 100, 310],
+			MonitorLayout::get_positions(&[
+				(0, 50, 100),
+				(50, 20, 200),
+				(50, 20, 20),
+				(80, 10, 10)
+			])
+		);
+	}
+}
@@ -0,0 +1,152 @@
+use crate::image::geometry::Geometry;
+use std::fmt;
+
+/* Named aspect ratio presets */
+const RATIO_PRESETS: &[(&str, u32, u32)] = &[
+	("square", 1, 1),
+	("a4", 1000, 1414),
+	("letter", 17, 22),
+	("legal", 17, 28),
+];
+
+/* Proportional relationship between width and height */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AspectRatio {
+	pub width: u32,
+	pub height: u32,
+}
+
+/* Display implementation for user-facing output */
+impl fmt::Display for AspectRatio {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}", self.width, self.height)
+	}
+}
+
+impl AspectRatio {
+	/**
+	 * Create a new AspectRatio object.
+	 *
+	 * @param  width
+	 * @param  height
+	 * @return AspectRatio
+	 */
+	pub fn new(width: u32, height: u32) -> Self {
+		Self { width, height }
+	}
+
+	/**
+	 * Parse AspectRatio from a string.
+	 *
+	 * @param  ratio
+	 * @return AspectRatio (Option)
+	 */
+	pub fn parse(ratio: &str) -> Option<Self> {
+		let ratio = ratio.trim().to_lowercase();
+		if let Some((_, width, height)) =
+			RATIO_PRESETS.iter().find(|(name, _, _)| name == &ratio)
+		{
+			return Some(Self::new(*width, *height));
+		}
+		let mut values = ratio.split(':').map(|v| v.parse::<u32>().ok());
+		match (values.next(), values.next(), values.next()) {
+			(Some(Some(width)), Some(Some(height)), None)
+				if width != 0 && height != 0 =>
+			{
+				Some(Self::new(width, height))
+			}
+			_ => None,
+		}
+	}
+
+	/**
+	 * Parse a selection preset in the form of "RATIO[@HEIGHT]".
+	 *
+	 * @param  preset
+	 * @return Tuple (AspectRatio, Geometry) (Option)
+	 */
+	pub fn parse_preset(preset: &str) -> Option<(Self, Geometry)> {
+		let mut values = preset.split('@');
+		let ratio = Self::parse(values.next().unwrap_or_default())?;
+		let size = match values.next() {
+			Some(height) => {
+				let height = height.parse::<u32>().ok()?;
+				Geometry::new(0, 0, ratio.get_width(height), height)
+			}
+			None => Geometry::default(),
+		};
+		Some((ratio, size))
+	}
+
+	/**
+	 * Get the width that corresponds to the given height.
+	 *
+	 * @param  height
+	 * @return u32
+	 */
+	pub fn get_width(&self, height: u32) -> u32 {
+		(u64::from(height) * u64::from(self.width) / u64::from(self.height))
+			.try_into()
+			.unwrap_or(u32::MAX)
+	}
+
+	/**
+	 * Get the height that corresponds to the given width.
+	 *
+	 * @param  width
+	 * @return u32
+	 */
+	pub fn get_height(&self, width: u32) -> u32 {
+		(u64::from(width) * u64::from(self.height) / u64::from(self.width))
+			.try_into()
+			.unwrap_or(u32::MAX)
+	}
+
+	/**
+	 * Get the largest size that fits into the given geometry.
+	 *
+	 * @param  geometry
+	 * @return Geometry
+	 */
+	pub fn fit(&self, geometry: Geometry) -> Geometry {
+		let height = self.get_height(geometry.width);
+		if height <= geometry.height {
+			Geometry::new(0, 0, geometry.width, height)
+		} else {
+			Geometry::new(0, 0, self.get_width(geometry.height), geometry.height)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_aspect_ratio() {
+		let ratio = AspectRatio::parse("16:9").unwrap();
+		assert_eq!("16:9", ratio.to_string());
+		assert_eq!(1280, ratio.get_width(720));
+		assert_eq!(1080, ratio.get_height(1920));
+		assert_eq!(
+			Geometry::new(0, 0, 1280, 720),
+			ratio.fit(Geometry::new(0, 0, 1280, 1024))
+		);
+		assert_eq!(
+			Geometry::new(0, 0, 640, 360),
+			ratio.fit(Geometry::new(0, 0, 800, 360))
+		);
+		assert_eq!(Some(AspectRatio::new(1, 1)), AspectRatio::parse("Square"));
+		assert_eq!(None, AspectRatio::parse("16:0"));
+		assert_eq!(None, AspectRatio::parse("true"));
+		assert_eq!(
+			Some((ratio, Geometry::new(0, 0, 1280, 720))),
+			AspectRatio::parse_preset("16:9@720")
+		);
+		assert_eq!(
+			Some((AspectRatio::new(1000, 1414), Geometry::default())),
+			AspectRatio::parse_preset("a4")
+		);
+		assert_eq!(None, AspectRatio::parse_preset("4:3@x"));
+	}
+}
@@ -1,14 +1,111 @@
 use crate::args::matches::ArgMatches;
 use crate::args::parser::ArgParser;
+use crate::edit::profile::ColorProfile;
 use image::codecs::png::{CompressionType, FilterType};
 use image::codecs::pnm::{PnmSubtype, SampleEncoding};
 use image::codecs::webp::WebPQuality;
 
+/* Color profile to embed into the output */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmbedProfile {
+	Srgb,
+	Icc(&'static str),
+}
+
+impl EmbedProfile {
+	/**
+	 * Get the color profile to embed from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return EmbedProfile (Option)
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Option<Self> {
+		if let Some(path) = matches.value_of("icc") {
+			Some(Self::Icc(Box::leak(path.to_string().into_boxed_str())))
+		} else if matches.is_present("srgb") {
+			Some(Self::Srgb)
+		} else {
+			None
+		}
+	}
+}
+
+/* Pixel density of the 2x (retina) assets */
+const RETINA_DENSITY: u16 = 144;
+/* Number of inches in a meter */
+const INCHES_PER_METER: f64 = 39.3701;
+
+/* Pixel density to write into the output */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Density {
+	pub dpi: u16,
+}
+
+impl Density {
+	/**
+	 * Create a new Density object.
+	 *
+	 * @param  dpi
+	 * @return Density
+	 */
+	pub fn new(dpi: u16) -> Self {
+		Self { dpi }
+	}
+
+	/**
+	 * Get the pixel density from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return Density (Option)
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Option<Self> {
+		if matches.is_present("retina") {
+			Some(Self::new(RETINA_DENSITY))
+		} else {
+			match matches.value_of("density").map(str::parse) {
+				Some(Ok(dpi)) if dpi > 0 => Some(Self::new(dpi)),
+				Some(_) => {
+					warn!("Invalid density value, the pixel density is not set.");
+					None
+				}
+				None => None,
+			}
+		}
+	}
+
+	/**
+	 * Get the number of pixels per meter.
+	 *
+	 * @return u32
+	 */
+	pub fn get_ppm(&self) -> u32 {
+		(f64::from(self.dpi) * INCHES_PER_METER).round() as u32
+	}
+
+	/**
+	 * Write the pixel density into the given PNG data as a pHYs chunk.
+	 *
+	 * @param  bytes
+	 * @return Vector of u8
+	 */
+	pub fn embed_png(&self, bytes: &[u8]) -> Vec<u8> {
+		let ppm = self.get_ppm().to_be_bytes();
+		let mut data = Vec::with_capacity(9);
+		data.extend_from_slice(&ppm);
+		data.extend_from_slice(&ppm);
+		/* Unit is the meter */
+		data.push(1);
+		ColorProfile::insert_png_chunk(bytes, b"pHYs", &data)
+	}
+}
+
 /* PNG compression and filter settings */
 #[derive(Clone, Copy, Debug)]
 pub struct PngSettings {
 	pub compression: CompressionType,
 	pub filter: FilterType,
+	pub profile: Option<EmbedProfile>,
+	pub density: Option<Density>,
 }
 
 /* Default initialization values for PngSettings */
@@ -17,6 +114,8 @@ impl Default for PngSettings {
 		Self {
 			compression: CompressionType::Fast,
 			filter: FilterType::Sub,
+			profile: None,
+			density: None,
 		}
 	}
 }
@@ -27,12 +126,21 @@ impl PngSettings {
 	 *
 	 * @param  compression
 	 * @param  filter
+	 * @param  profile (Option)
+	 * @param  density (Option)
 	 * @return PngSettings
 	 */
-	pub fn new(compression: CompressionType, filter: FilterType) -> Self {
+	pub fn new(
+		compression: CompressionType,
+		filter: FilterType,
+		profile: Option<EmbedProfile>,
+		density: Option<Density>,
+	) -> Self {
 		Self {
 			compression,
 			filter,
+			profile,
+			density,
 		}
 	}
 
@@ -69,6 +177,8 @@ impl PngSettings {
 					Some("paeth") => FilterType::Paeth,
 					_ => FilterType::Sub,
 				},
+				EmbedProfile::from_args(&matches),
+				Density::from_args(&matches),
 			),
 			None => Self::default(),
 		}
@@ -79,12 +189,20 @@ impl PngSettings {
 #[derive(Clone, Copy, Debug)]
 pub struct JpgSettings {
 	pub quality: u8,
+	pub profile: Option<EmbedProfile>,
+	pub density: Option<Density>,
+	pub cmyk: bool,
 }
 
 /* Default initialization values for JpgSettings */
 impl Default for JpgSettings {
 	fn default() -> Self {
-		Self { quality: 90 }
+		Self {
+			quality: 90,
+			profile: None,
+			density: None,
+			cmyk: false,
+		}
 	}
 }
 
@@ -93,10 +211,23 @@ impl JpgSettings {
 	 * Create a new JpgSettings object.
 	 *
 	 * @param  quality
+	 * @param  profile (Option)
+	 * @param  density (Option)
+	 * @param  cmyk
 	 * @return JpgSettings
 	 */
-	pub fn new(quality: u8) -> Self {
-		Self { quality }
+	pub fn new(
+		quality: u8,
+		profile: Option<EmbedProfile>,
+		density: Option<Density>,
+		cmyk: bool,
+	) -> Self {
+		Self {
+			quality,
+			profile,
+			density,
+			cmyk,
+		}
 	}
 
 	/**
@@ -117,7 +248,12 @@ impl JpgSettings {
 	 */
 	fn from_parser(parser: ArgParser<'_>) -> Self {
 		match parser.args {
-			Some(_) => Self::new(parser.parse("quality", Self::default().quality)),
+			Some(ref matches) => Self::new(
+				parser.parse("quality", Self::default().quality),
+				EmbedProfile::from_args(matches),
+				Density::from_args(matches),
+				matches.is_present("cmyk"),
+			),
 			None => Self::default(),
 		}
 	}
@@ -149,6 +285,16 @@ impl WebPSettings {
 		Self { quality }
 	}
 
+	/**
+	 * Set the lossy encoding quality (unless lossless).
+	 *
+	 * @param  quality
+	 * @return WebPSettings
+	 */
+	pub fn with_quality(self, quality: u8) -> Self {
+		Self::new(self.quality.map(|_| quality))
+	}
+
 	/**
 	 * Create a WebPQuality object from WebPSettings.
 	 *
@@ -191,6 +337,77 @@ impl WebPSettings {
 	}
 }
 
+/* TIFF compression methods */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TiffCompression {
+	None,
+	Lzw,
+	Deflate,
+}
+
+/* TIFF compression setting */
+#[derive(Clone, Copy, Debug)]
+pub struct TiffSettings {
+	pub compression: TiffCompression,
+	pub density: Option<Density>,
+}
+
+/* Default initialization values for TiffSettings */
+impl Default for TiffSettings {
+	fn default() -> Self {
+		Self {
+			compression: TiffCompression::None,
+			density: None,
+		}
+	}
+}
+
+impl TiffSettings {
+	/**
+	 * Create a new TiffSettings object.
+	 *
+	 * @param  compression
+	 * @param  density (Option)
+	 * @return TiffSettings
+	 */
+	pub fn new(compression: TiffCompression, density: Option<Density>) -> Self {
+		Self {
+			compression,
+			density,
+		}
+	}
+
+	/**
+	 * Create a new TiffSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return TiffSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::from_parser(ArgParser::from_subcommand(matches, "tiff"))
+	}
+
+	/**
+	 * Create a TiffSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return TiffSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>) -> Self {
+		match parser.args {
+			Some(matches) => Self::new(
+				match matches.value_of("compression") {
+					Some("lzw") => TiffCompression::Lzw,
+					Some("deflate") => TiffCompression::Deflate,
+					_ => TiffCompression::None,
+				},
+				Density::from_args(&matches),
+			),
+			None => Self::default(),
+		}
+	}
+}
+
 /* PNM subtype settings */
 #[derive(Clone, Copy, Debug)]
 pub struct PnmSettings {
@@ -297,6 +514,39 @@ mod tests {
 		}
 	}
 	#[test]
+	fn test_density() {
+		let get_density = |values: Vec<&str>| {
+			let args = App::new("test")
+				.arg(Arg::with_name("retina").long("retina"))
+				.arg(Arg::with_name("density").long("density").takes_value(true))
+				.get_matches_from(values);
+			Density::from_args(&ArgMatches::new(&args))
+		};
+		assert_eq!(
+			Some(Density::new(144)),
+			get_density(vec!["test", "--retina"])
+		);
+		assert_eq!(
+			Some(Density::new(300)),
+			get_density(vec!["test", "--density", "300"])
+		);
+		assert_eq!(None, get_density(vec!["test", "--density", "0"]));
+		assert_eq!(None, get_density(vec!["test"]));
+		let mut data = Vec::new();
+		let mut encoder = png::Encoder::new(&mut data, 1, 1);
+		encoder.set_color(png::ColorType::Rgba);
+		encoder
+			.write_header()
+			.unwrap()
+			.write_image_data(&[0, 0, 0, 255])
+			.unwrap();
+		let data = Density::new(144).embed_png(&data);
+		let reader = png::Decoder::new(data.as_slice()).read_info().unwrap();
+		let dims = reader.info().pixel_dims.unwrap();
+		assert_eq!((5669, 5669), (dims.xppu, dims.yppu));
+		assert_eq!(png::Unit::Meter, dims.unit);
+	}
+	#[test]
 	fn test_jpg_settings() {
 		let args = App::new("test")
 			.arg(Arg::with_name("quality").long("quality").takes_value(true))
@@ -306,6 +556,23 @@ mod tests {
 			JpgSettings::from_parser(ArgParser::from_args(&args)).quality
 		);
 		assert_eq!(90, JpgSettings::from_parser(ArgParser::new(None)).quality);
+		let args = App::new("test")
+			.arg(Arg::with_name("srgb").long("srgb"))
+			.arg(Arg::with_name("icc").long("icc").takes_value(true))
+			.get_matches_from(vec!["test", "--srgb"]);
+		assert_eq!(
+			Some(EmbedProfile::Srgb),
+			JpgSettings::from_parser(ArgParser::from_args(&args)).profile
+		);
+		let args = App::new("test")
+			.arg(Arg::with_name("srgb").long("srgb"))
+			.arg(Arg::with_name("icc").long("icc").takes_value(true))
+			.get_matches_from(vec!["test", "--icc", "test.icc"]);
+		assert_eq!(
+			Some(EmbedProfile::Icc("test.icc")),
+			JpgSettings::from_parser(ArgParser::from_args(&args)).profile
+		);
+		assert_eq!(None, JpgSettings::from_parser(ArgParser::new(None)).profile);
 	}
 	#[test]
 	fn test_webp_settings() {
@@ -329,6 +596,24 @@ mod tests {
 		);
 	}
 	#[test]
+	fn test_tiff_settings() {
+		let args = App::new("test")
+			.arg(
+				Arg::with_name("compression")
+					.long("compression")
+					.takes_value(true),
+			)
+			.get_matches_from(vec!["test", "--compression", "deflate"]);
+		assert_eq!(
+			TiffCompression::Deflate,
+			TiffSettings::from_parser(ArgParser::from_args(&args)).compression
+		);
+		assert_eq!(
+			TiffCompression::None,
+			TiffSettings::from_parser(ArgParser::new(None)).compression
+		);
+	}
+	#[test]
 	fn test_pnm_settings() {
 		let args = App::new("test")
 			.arg(Arg::with_name("format").long("format").takes_value(true))
@@ -14,6 +14,8 @@ mod edit;
 mod file;
 mod gif;
 mod image;
+mod montage;
+mod pick;
 mod record;
 mod settings;
 mod util;
@@ -23,16 +25,91 @@ mod window;
 mod ws;
 #[cfg(all(unix, not(target_os = "macos")))]
 mod x11;
-use self::app::App;
+use self::app::{App, AppError, AppResult};
 use self::args::matches::ArgMatches;
 use self::args::Args;
+use self::file::File;
+use self::record::region::RegionStore;
+use self::record::settings::RecordWindow;
 use self::settings::AppSettings;
 use self::util::logger::Logger;
-use self::window::Access;
+use self::window::synthetic::SyntheticWindow;
+use self::window::{Access, Capture};
 #[cfg(not(all(unix, not(target_os = "macos"))))]
 use self::ws::WindowSystem;
 #[cfg(all(unix, not(target_os = "macos")))]
 use self::x11::WindowSystem;
+use std::fmt::Debug;
+use std::panic;
+
+/**
+ * Report the error and exit the process.
+ *
+ * @param  settings
+ * @param  stage
+ * @param  error
+ */
+fn exit_with_error(settings: &AppSettings<'_>, stage: &str, error: AppError) -> ! {
+	if settings.args.is_present("json-errors") {
+		eprintln!("{}", error.to_json(stage));
+	} else {
+		error!("{}", error);
+	}
+	std::process::exit(1);
+}
+
+/**
+ * Report the panics in JSON format.
+ *
+ * @param  stage
+ */
+fn set_json_panic_hook(stage: String) {
+	panic::set_hook(Box::new(move |info| {
+		let payload = info.payload();
+		let message = payload
+			.downcast_ref::<&str>()
+			.map(|message| message.to_string())
+			.or_else(|| payload.downcast_ref::<String>().cloned())
+			.unwrap_or_else(|| String::from("Unknown panic"));
+		let message = match info.location() {
+			Some(location) => format!("{message} ({location})"),
+			None => message,
+		};
+		eprintln!("{}", AppError::Panic(message).to_json(&stage));
+	}));
+}
+
+/**
+ * Capture each of the windows into a separate file.
+ *
+ * The windows share the same display so it is released
+ * once after all of them are captured.
+ *
+ * @param  windows
+ * @param  settings
+ * @return Result
+ */
+fn capture_windows<Window: Capture + Send + Sync + Copy + Debug + 'static>(
+	windows: &[Window],
+	settings: &mut AppSettings<'_>,
+) -> AppResult<()> {
+	let path = settings.save.file.path.clone();
+	info!("Capturing {} matching windows...", windows.len());
+	let result = (1..).zip(windows).try_for_each(|(i, window)| {
+		let suffix = match window.get_title() {
+			Some(title) if !title.is_empty() => format!("{i}_{title}"),
+			_ => i.to_string(),
+		};
+		settings.save.file.path = File::get_path_with_suffix(&path, &suffix);
+		App::new(Some(*window), settings)
+			.with_release(false)
+			.start()
+	});
+	if let Some(window) = windows.first() {
+		window.release();
+	}
+	result
+}
 
 fn main() {
 	let args = Args::parse();
@@ -42,25 +119,121 @@ fn main() {
 		.init()
 		.expect("Failed to initialize the logger");
 	settings.check();
-	let window = if settings.window_required {
+	if settings.args.is_present("json-errors") {
+		set_json_panic_hook(settings.args.subcommand().0.to_string());
+	}
+	if settings.record.region.is_list() {
+		for (name, region) in RegionStore::default().load() {
+			println!("{}\t{}", name, RegionStore::format(&region));
+		}
+		return;
+	}
+	let windows = if settings.window_required {
+		let ws_error = |message: &str| AppError::WsError(String::from(message));
+		if !WindowSystem::wait_for_display(&settings.record) {
+			exit_with_error(
+				&settings,
+				"window",
+				ws_error("Failed to access the window system"),
+			);
+		}
+		settings.init_input_state();
 		match WindowSystem::init(&settings) {
-			Some(mut ws) => match ws.get_window() {
-				Some(window) => Some(window),
-				None => {
-					error!("Failed to retrieve the window.");
-					std::process::exit(1);
-				}
+			Some(mut ws) => match ws.get_windows() {
+				windows if !windows.is_empty() => windows,
+				_ => exit_with_error(
+					&settings,
+					"window",
+					ws_error("Failed to retrieve the window"),
+				),
 			},
-			None => {
-				error!("Failed to access the window system.");
-				std::process::exit(1);
-			}
+			None => exit_with_error(
+				&settings,
+				"window",
+				ws_error("Failed to access the window system"),
+			),
 		}
 	} else {
-		None
+		Vec::new()
 	};
-	if let Err(e) = App::new(window, &settings).start() {
-		error!("{}", e);
-		std::process::exit(1);
+	if let RecordWindow::Synthetic(geometry) = settings.record.window {
+		if let Err(e) =
+			App::new(Some(SyntheticWindow::new(geometry)), &settings).start()
+		{
+			exit_with_error(&settings, settings.args.subcommand().0, e);
+		}
+	} else if let RecordWindow::Class(_, true) = settings.record.window {
+		if let Err(e) = capture_windows(&windows, &mut settings) {
+			exit_with_error(&settings, settings.args.subcommand().0, e);
+		}
+	} else if let Err(e) = App::new(windows.first().copied(), &settings).start() {
+		exit_with_error(&settings, settings.args.subcommand().0, e);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::file::format::FileFormat;
+	use crate::image::geometry::Geometry;
+	use crate::image::Image;
+	use ::image::Rgba;
+	use clap::ArgMatches as Args;
+	use std::env;
+	use std::fs;
+	use std::sync::atomic::{AtomicBool, Ordering};
+	/* Window that shares the closed state of its display */
+	#[derive(Clone, Copy, Debug)]
+	struct SharedWindow {
+		closed: &'static AtomicBool,
+	}
+	impl Capture for SharedWindow {
+		fn get_image(&self) -> Option<Image> {
+			if self.closed.load(Ordering::SeqCst) {
+				None
+			} else {
+				Some(Image::new(
+					vec![Rgba::from([255, 255, 255, 255])],
+					false,
+					Geometry::new(0, 0, 1, 1),
+				))
+			}
+		}
+		fn show_countdown(&self) {}
+		fn preview(&self, _images: &[&Image]) -> bool {
+			true
+		}
+		fn release(&self) {
+			assert!(!self.closed.swap(true, Ordering::SeqCst));
+		}
+		fn get_title(&self) -> Option<String> {
+			None
+		}
+		fn get_pointer(&self) -> Option<(i32, i32)> {
+			None
+		}
+		fn exists(&self) -> bool {
+			!self.closed.load(Ordering::SeqCst)
+		}
+	}
+	#[test]
+	fn test_capture_windows() -> AppResult<()> {
+		let args = Args::default();
+		let matches = ArgMatches::new(&args);
+		let mut settings = AppSettings::new(&matches);
+		let path = env::temp_dir().join("menyoki_windows.png");
+		settings.save.file.path = path.clone();
+		settings.save.file.format = FileFormat::Png;
+		let window = SharedWindow {
+			closed: Box::leak(Box::new(AtomicBool::new(false))),
+		};
+		capture_windows(&[window, window], &mut settings)?;
+		assert!(window.closed.load(Ordering::SeqCst));
+		for i in 1..=2 {
+			let path = File::get_path_with_suffix(&path, &i.to_string());
+			assert!(path.exists());
+			fs::remove_file(path)?;
+		}
+		Ok(())
 	}
 }
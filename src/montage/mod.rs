@@ -0,0 +1,164 @@
+pub mod settings;
+
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use crate::montage::settings::{CellFit, MontageSettings};
+use image::imageops::{self, FilterType};
+use image::RgbaImage;
+
+/* Grid of images to compose into a single image */
+#[derive(Debug)]
+pub struct Montage<'a> {
+	images: Vec<RgbaImage>,
+	settings: &'a MontageSettings,
+}
+
+impl<'a> Montage<'a> {
+	/**
+	 * Create a new Montage object.
+	 *
+	 * @param  images
+	 * @param  settings
+	 * @return Montage
+	 */
+	pub fn new(images: Vec<RgbaImage>, settings: &'a MontageSettings) -> Self {
+		Self { images, settings }
+	}
+
+	/**
+	 * Get the size of a grid cell.
+	 *
+	 * @return tuple
+	 */
+	fn get_cell_size(&self) -> (u32, u32) {
+		let sizes = self.images.iter().map(|image| image.dimensions());
+		if self.settings.fit == CellFit::Crop {
+			(
+				sizes.clone().map(|(w, _)| w).min().unwrap_or_default(),
+				sizes.map(|(_, h)| h).min().unwrap_or_default(),
+			)
+		} else {
+			(
+				sizes.clone().map(|(w, _)| w).max().unwrap_or_default(),
+				sizes.map(|(_, h)| h).max().unwrap_or_default(),
+			)
+		}
+	}
+
+	/**
+	 * Fit the image into a cell.
+	 *
+	 * @param  image
+	 * @param  cell
+	 * @return RgbaImage
+	 */
+	fn fit_image(
+		&self,
+		image: &RgbaImage,
+		(width, height): (u32, u32),
+	) -> RgbaImage {
+		let image = match self.settings.fit {
+			CellFit::Scale => {
+				let ratio = (width as f32 / image.width() as f32)
+					.min(height as f32 / image.height() as f32);
+				imageops::resize(
+					image,
+					((image.width() as f32 * ratio).round() as u32).max(1),
+					((image.height() as f32 * ratio).round() as u32).max(1),
+					FilterType::Lanczos3,
+				)
+			}
+			CellFit::Crop => imageops::crop_imm(
+				image,
+				(image.width() - width) / 2,
+				(image.height() - height) / 2,
+				width,
+				height,
+			)
+			.to_image(),
+			CellFit::Pad => image.clone(),
+		};
+		let mut cell =
+			RgbaImage::from_pixel(width, height, self.settings.background);
+		imageops::overlay(
+			&mut cell,
+			&image,
+			((width - image.width()) / 2).into(),
+			((height - image.height()) / 2).into(),
+		);
+		cell
+	}
+
+	/**
+	 * Compose the images into a grid.
+	 *
+	 * @return Image
+	 */
+	pub fn get_image(&self) -> Image {
+		let (cell_width, cell_height) = self.get_cell_size();
+		let count = self.images.len() as u32;
+		let cols = self.settings.cols.min(count).max(1);
+		let rows = count.div_ceil(cols);
+		let gap = self.settings.gap;
+		let width = cols * cell_width + cols.saturating_sub(1) * gap;
+		let height = rows * cell_height + rows.saturating_sub(1) * gap;
+		info!(
+			"Composing {} images into a {}x{} grid... ({}x{})",
+			count, cols, rows, width, height
+		);
+		let mut canvas =
+			RgbaImage::from_pixel(width, height, self.settings.background);
+		for (i, image) in self.images.iter().enumerate() {
+			let (col, row) = (i as u32 % cols, i as u32 / cols);
+			imageops::overlay(
+				&mut canvas,
+				&self.fit_image(image, (cell_width, cell_height)),
+				(col * (cell_width + gap)).into(),
+				(row * (cell_height + gap)).into(),
+			);
+		}
+		Image::new(
+			canvas.pixels().copied().collect(),
+			true,
+			Geometry::new(0, 0, width, height),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::{ExtendedColorType, Rgba};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_montage() {
+		let images = vec![
+			RgbaImage::from_pixel(4, 2, Rgba([255, 0, 0, 255])),
+			RgbaImage::from_pixel(2, 4, Rgba([0, 255, 0, 255])),
+			RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255])),
+		];
+		let mut settings = MontageSettings {
+			gap: 1,
+			background: Rgba([255, 255, 255, 255]),
+			..Default::default()
+		};
+		let image = Montage::new(images.clone(), &settings).get_image();
+		assert_eq!(Geometry::new(0, 0, 9, 9), image.geometry);
+		let data = image.get_data(ExtendedColorType::Rgba8);
+		let pixel =
+			|x: usize, y: usize| data[(y * 9 + x) * 4..(y * 9 + x) * 4 + 4].to_vec();
+		assert_eq!(vec![255, 255, 255, 255], pixel(0, 0));
+		assert_eq!(vec![255, 0, 0, 255], pixel(0, 1));
+		assert_eq!(vec![255, 255, 255, 255], pixel(4, 1));
+		assert_eq!(vec![0, 255, 0, 255], pixel(6, 0));
+		assert_eq!(vec![0, 0, 255, 255], pixel(0, 8));
+		assert_eq!(vec![255, 255, 255, 255], pixel(8, 8));
+		settings.fit = CellFit::Crop;
+		let image = Montage::new(images.clone(), &settings).get_image();
+		assert_eq!(Geometry::new(0, 0, 5, 5), image.geometry);
+		settings.fit = CellFit::Scale;
+		settings.cols = 3;
+		let image = Montage::new(images, &settings).get_image();
+		assert_eq!(Geometry::new(0, 0, 14, 4), image.geometry);
+	}
+}
@@ -0,0 +1,164 @@
+use crate::args::matches::ArgMatches;
+use crate::args::parser::ArgParser;
+use crate::record::settings::RecordCanvas;
+use image::Rgba;
+use std::path::PathBuf;
+
+/* Method for fitting the images of different sizes into the cells */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellFit {
+	Pad,
+	Crop,
+	Scale,
+}
+
+/* Montage settings */
+#[derive(Debug)]
+pub struct MontageSettings {
+	pub files: Vec<PathBuf>,
+	pub cols: u32,
+	pub gap: u32,
+	pub background: Rgba<u8>,
+	pub fit: CellFit,
+}
+
+/* Default initialization values for MontageSettings */
+impl Default for MontageSettings {
+	fn default() -> Self {
+		Self {
+			files: Vec::new(),
+			cols: 2,
+			gap: 0,
+			background: Rgba([0, 0, 0, 0]),
+			fit: CellFit::Pad,
+		}
+	}
+}
+
+impl MontageSettings {
+	/**
+	 * Create a new MontageSettings object.
+	 *
+	 * @param  files
+	 * @param  cols
+	 * @param  gap
+	 * @param  background
+	 * @param  fit
+	 * @return MontageSettings
+	 */
+	pub fn new(
+		files: Vec<PathBuf>,
+		cols: u32,
+		gap: u32,
+		background: Rgba<u8>,
+		fit: CellFit,
+	) -> Self {
+		Self {
+			files,
+			cols,
+			gap,
+			background,
+			fit,
+		}
+	}
+
+	/**
+	 * Create a new MontageSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return MontageSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::from_parser(ArgParser::from_subcommand(matches, "montage"))
+	}
+
+	/**
+	 * Create a MontageSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return MontageSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>) -> Self {
+		match parser.args {
+			Some(ref matches) => Self::new(
+				matches
+					.values_of("files")
+					.map(|values| {
+						values
+							.map(|file| {
+								PathBuf::from(
+									shellexpand::full(file)
+										.map(|s| s.to_string())
+										.unwrap_or(file.to_string()),
+								)
+							})
+							.collect()
+					})
+					.unwrap_or_default(),
+				match parser.parse("cols", Self::default().cols) {
+					0 => Self::default().cols,
+					cols => cols,
+				},
+				parser.parse("gap", Self::default().gap),
+				RecordCanvas::parse_color(
+					matches.value_of("background").unwrap_or_default(),
+				)
+				.unwrap_or(Self::default().background),
+				match matches.value_of("cell-fit") {
+					Some("crop") => CellFit::Crop,
+					Some("scale") => CellFit::Scale,
+					_ => CellFit::Pad,
+				},
+			),
+			None => Self::default(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::{App, Arg};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_montage_settings() {
+		let args = App::new("test")
+			.arg(Arg::with_name("files").multiple(true).required(true))
+			.arg(Arg::with_name("cols").long("cols").takes_value(true))
+			.arg(Arg::with_name("gap").long("gap").takes_value(true))
+			.arg(
+				Arg::with_name("background")
+					.long("background")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("cell-fit")
+					.long("cell-fit")
+					.takes_value(true),
+			)
+			.get_matches_from(vec![
+				"test",
+				"a.png",
+				"b.png",
+				"c.png",
+				"--cols",
+				"3",
+				"--gap",
+				"4",
+				"--background",
+				"FFFFFF",
+				"--cell-fit",
+				"scale",
+			]);
+		let settings = MontageSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(3, settings.files.len());
+		assert_eq!(Some("b.png"), settings.files[1].to_str());
+		assert_eq!(3, settings.cols);
+		assert_eq!(4, settings.gap);
+		assert_eq!(Rgba([255, 255, 255, 255]), settings.background);
+		assert_eq!(CellFit::Scale, settings.fit);
+		let settings = MontageSettings::from_parser(ArgParser::new(None));
+		assert_eq!(2, settings.cols);
+		assert_eq!(CellFit::Pad, settings.fit);
+	}
+}
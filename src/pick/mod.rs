@@ -0,0 +1,171 @@
+pub mod settings;
+
+use crate::app::{AppError, AppResult};
+use crate::pick::settings::PickSettings;
+use image::Rgba;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+/* Command for copying the color to clipboard */
+const CLIPBOARD_COMMAND: (&str, &[&str]) = ("xclip", &["-selection", "clipboard"]);
+
+/* Output format of the picked color */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormat {
+	Hex,
+	Rgb,
+	Hsl,
+	All,
+}
+
+/* Implementation for parsing ColorFormat from a string */
+impl FromStr for ColorFormat {
+	type Err = ();
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_ref() {
+			"hex" => Ok(Self::Hex),
+			"rgb" => Ok(Self::Rgb),
+			"hsl" => Ok(Self::Hsl),
+			"all" => Ok(Self::All),
+			_ => Err(()),
+		}
+	}
+}
+
+/* Color picker for a single pixel */
+pub struct ColorPicker<'a> {
+	color: Rgba<u8>,
+	settings: &'a PickSettings,
+}
+
+impl<'a> ColorPicker<'a> {
+	/**
+	 * Create a new ColorPicker object.
+	 *
+	 * @param  color
+	 * @param  settings
+	 * @return ColorPicker
+	 */
+	pub fn new(color: Rgba<u8>, settings: &'a PickSettings) -> Self {
+		debug!("{:?}", settings);
+		Self { color, settings }
+	}
+
+	/**
+	 * Get the color in hexadecimal notation.
+	 *
+	 * @return String
+	 */
+	fn get_hex(&self) -> String {
+		format!("#{}", hex::encode(&self.color.0[0..3])).to_uppercase()
+	}
+
+	/**
+	 * Get the color in RGB notation.
+	 *
+	 * @return String
+	 */
+	fn get_rgb(&self) -> String {
+		format!(
+			"rgb({}, {}, {})",
+			self.color[0], self.color[1], self.color[2]
+		)
+	}
+
+	/**
+	 * Get the color in HSL notation.
+	 *
+	 * @return String
+	 */
+	fn get_hsl(&self) -> String {
+		let [r, g, b] = [self.color[0], self.color[1], self.color[2]]
+			.map(|v| f64::from(v) / 255.);
+		let max = r.max(g).max(b);
+		let min = r.min(g).min(b);
+		let delta = max - min;
+		let lightness = (max + min) / 2.;
+		let (hue, saturation) = if delta == 0. {
+			(0., 0.)
+		} else {
+			let hue = if max == r {
+				((g - b) / delta).rem_euclid(6.)
+			} else if max == g {
+				(b - r) / delta + 2.
+			} else {
+				(r - g) / delta + 4.
+			};
+			(hue * 60., delta / (1. - (2. * lightness - 1.).abs()))
+		};
+		format!(
+			"hsl({}, {}%, {}%)",
+			hue.round(),
+			(saturation * 100.).round(),
+			(lightness * 100.).round()
+		)
+	}
+
+	/**
+	 * Get the color in the specified format.
+	 *
+	 * @return String
+	 */
+	pub fn get_output(&self) -> String {
+		match self.settings.format {
+			ColorFormat::Hex => self.get_hex(),
+			ColorFormat::Rgb => self.get_rgb(),
+			ColorFormat::Hsl => self.get_hsl(),
+			ColorFormat::All => {
+				format!("{}\n{}\n{}", self.get_hex(), self.get_rgb(), self.get_hsl())
+			}
+		}
+	}
+
+	/**
+	 * Copy the color to clipboard if enabled.
+	 *
+	 * @return Result
+	 */
+	pub fn copy_to_clipboard(&self) -> AppResult<()> {
+		if self.settings.clipboard {
+			let color = match self.settings.format {
+				ColorFormat::All => self.get_hex(),
+				_ => self.get_output(),
+			};
+			let (cmd, args) = CLIPBOARD_COMMAND;
+			let mut child = Command::new(cmd)
+				.args(args)
+				.stdin(Stdio::piped())
+				.spawn()
+				.map_err(|e| AppError::CommandError(format!("{cmd}: {e}")))?;
+			if let Some(stdin) = child.stdin.as_mut() {
+				stdin.write_all(color.as_bytes())?;
+			}
+			child.wait()?;
+			debug!("Copied {} to clipboard", color);
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_pick() {
+		let settings = PickSettings::default();
+		let picker = ColorPicker::new(Rgba([58, 164, 49, 255]), &settings);
+		assert_eq!(
+			"#3AA431\nrgb(58, 164, 49)\nhsl(115, 54%, 42%)",
+			picker.get_output()
+		);
+		assert_eq!(
+			"hsl(0, 0%, 100%)",
+			ColorPicker::new(Rgba([255, 255, 255, 255]), &settings).get_hsl()
+		);
+		assert!(picker.copy_to_clipboard().is_ok());
+		assert_eq!(Ok(ColorFormat::Rgb), ColorFormat::from_str("RGB"));
+		assert_eq!(Err(()), ColorFormat::from_str("cmyk"));
+	}
+}
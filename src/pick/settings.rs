@@ -0,0 +1,83 @@
+use crate::args::matches::ArgMatches;
+use crate::args::parser::ArgParser;
+use crate::pick::ColorFormat;
+use std::str::FromStr;
+
+/* Color picker settings */
+#[derive(Debug)]
+pub struct PickSettings {
+	pub format: ColorFormat,
+	pub clipboard: bool,
+}
+
+/* Default initialization values for PickSettings */
+impl Default for PickSettings {
+	fn default() -> Self {
+		Self {
+			format: ColorFormat::All,
+			clipboard: false,
+		}
+	}
+}
+
+impl PickSettings {
+	/**
+	 * Create a new PickSettings object.
+	 *
+	 * @param  format
+	 * @param  clipboard
+	 * @return PickSettings
+	 */
+	pub fn new(format: ColorFormat, clipboard: bool) -> Self {
+		Self { format, clipboard }
+	}
+
+	/**
+	 * Create a new PickSettings object from arguments.
+	 *
+	 * @param  matches
+	 * @return PickSettings
+	 */
+	pub fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::from_parser(ArgParser::from_subcommand(matches, "pick"))
+	}
+
+	/**
+	 * Create a PickSettings object from an argument parser.
+	 *
+	 * @param  parser
+	 * @return PickSettings
+	 */
+	fn from_parser(parser: ArgParser<'_>) -> Self {
+		match parser.args {
+			Some(matches) => Self::new(
+				ColorFormat::from_str(
+					matches.value_of("format").unwrap_or_default(),
+				)
+				.unwrap_or(Self::default().format),
+				matches.is_present("clipboard"),
+			),
+			None => Self::default(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::{App, Arg};
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_pick_settings() {
+		let args = App::new("test")
+			.arg(Arg::with_name("format").long("format").takes_value(true))
+			.arg(Arg::with_name("clipboard").long("clipboard"))
+			.get_matches_from(vec!["test", "--format", "hsl", "--clipboard"]);
+		let pick_settings = PickSettings::from_parser(ArgParser::from_args(&args));
+		assert_eq!(ColorFormat::Hsl, pick_settings.format);
+		assert!(pick_settings.clipboard);
+		let pick_settings = PickSettings::default();
+		assert_eq!(ColorFormat::All, pick_settings.format);
+		assert!(!pick_settings.clipboard);
+	}
+}
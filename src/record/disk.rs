@@ -0,0 +1,109 @@
+use bytesize::ByteSize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/* Interval between the checks of the free space */
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/* Watchdog for the free space on the output filesystem */
+#[derive(Debug)]
+pub struct DiskWatchdog {
+	path: PathBuf,
+	min_free: u64,
+	last_check: Option<Instant>,
+}
+
+impl DiskWatchdog {
+	/**
+	 * Create a new DiskWatchdog object.
+	 *
+	 * @param  path
+	 * @param  min_free
+	 * @return DiskWatchdog
+	 */
+	pub fn new(path: PathBuf, min_free: u64) -> Self {
+		Self {
+			path,
+			min_free,
+			last_check: None,
+		}
+	}
+
+	/**
+	 * Check if there is enough free space to continue.
+	 *
+	 * @return bool
+	 */
+	pub fn check(&mut self) -> bool {
+		if self
+			.last_check
+			.is_some_and(|last_check| last_check.elapsed() < CHECK_INTERVAL)
+		{
+			return true;
+		}
+		self.last_check = Some(Instant::now());
+		match Self::get_free_space(&self.path) {
+			Some(free) if free < self.min_free => {
+				debug!("\n");
+				warn!(
+					"Free space on {:?} is below {} ({}), stopping the recording.",
+					self.path,
+					ByteSize(self.min_free),
+					ByteSize(free)
+				);
+				false
+			}
+			_ => true,
+		}
+	}
+
+	/**
+	 * Get the available space on the filesystem of the given path.
+	 *
+	 * @param  path
+	 * @return u64 (Option)
+	 */
+	#[cfg(unix)]
+	pub fn get_free_space(path: &Path) -> Option<u64> {
+		use std::ffi::CString;
+		use std::mem::MaybeUninit;
+		use std::os::unix::ffi::OsStrExt;
+		let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+		let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+		if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } == 0 {
+			let stat = unsafe { stat.assume_init() };
+			#[allow(clippy::unnecessary_cast)]
+			Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+		} else {
+			None
+		}
+	}
+
+	/**
+	 * Get the available space on the filesystem of the given path.
+	 *
+	 * @param  path
+	 * @return u64 (Option)
+	 */
+	#[cfg(not(unix))]
+	pub fn get_free_space(_: &Path) -> Option<u64> {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::env;
+	#[test]
+	fn test_disk_watchdog() {
+		let path = env::temp_dir();
+		let free = DiskWatchdog::get_free_space(&path).unwrap();
+		assert!(free > 0);
+		assert!(DiskWatchdog::get_free_space(Path::new("/nonexistent")).is_none());
+		assert!(DiskWatchdog::new(path.clone(), 1).check());
+		let mut watchdog = DiskWatchdog::new(path, u64::MAX);
+		assert!(!watchdog.check());
+		assert!(watchdog.check());
+	}
+}
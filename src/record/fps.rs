@@ -4,8 +4,11 @@ use std::time::{Duration, Instant};
 /* FPS controller */
 #[derive(Clone, Copy, Debug)]
 pub struct FpsClock {
-	pub fps: u32,
+	pub fps: f32,
 	last_tick_time: Instant,
+	next_tick_time: Option<Instant>,
+	first_tick_time: Option<Instant>,
+	ticks: u32,
 }
 
 impl FpsClock {
@@ -15,43 +18,118 @@ impl FpsClock {
 	 * @param  fps
 	 * @return FpsClock
 	 */
-	pub fn new(fps: u32) -> Self {
+	pub fn new(fps: f32) -> Self {
 		Self {
 			fps,
 			last_tick_time: Instant::now(),
+			next_tick_time: None,
+			first_tick_time: None,
+			ticks: 0,
 		}
 	}
 
+	/**
+	 * Get the duration between the ticks.
+	 *
+	 * @return Duration
+	 */
+	fn get_interval(&self) -> Duration {
+		Duration::from_secs_f32(1. / self.fps)
+	}
+
+	/**
+	 * Schedule the next tick and get the time left until the current one.
+	 *
+	 * Ticks are scheduled at fixed intervals from the previous target time
+	 * so that the time spent between the ticks does not accumulate.
+	 *
+	 * @param  now
+	 * @return f32 (nanoseconds, negative if the tick is late)
+	 */
+	fn next_tick(&mut self, now: Instant) -> f32 {
+		let interval = self.get_interval();
+		let target = self
+			.next_tick_time
+			.unwrap_or(self.last_tick_time + interval);
+		/* Do not try to catch up if a whole interval is missed */
+		self.next_tick_time = Some(if now > target + interval {
+			now + interval
+		} else {
+			target + interval
+		});
+		if target > now {
+			(target - now).as_nanos() as f32
+		} else {
+			-((now - target).as_nanos() as f32)
+		}
+	}
+
+	/**
+	 * Record the time of a completed tick.
+	 *
+	 * @param  time
+	 */
+	fn record_tick(&mut self, time: Instant) {
+		self.last_tick_time = time;
+		self.first_tick_time.get_or_insert(time);
+		self.ticks += 1;
+	}
+
 	/**
 	 * Sleep the thread to run at the correct FPS.
 	 *
 	 * @return f32
 	 */
 	pub fn tick(&mut self) -> f32 {
-		let t = self.last_tick_time.elapsed();
-		let total_nanos = t.as_secs() * 1e9 as u64 + t.subsec_nanos() as u64;
-		let diff = ((1. / self.fps as f32) * 1e9) - (total_nanos as f32);
+		let diff = self.next_tick(Instant::now());
 		if diff > 0. {
-			thread::sleep(Duration::new(0, diff as u32))
+			thread::sleep(Duration::from_nanos(diff as u64));
 		}
-		self.last_tick_time = Instant::now();
+		self.record_tick(Instant::now());
 		diff
 	}
+
+	/**
+	 * Get the difference between the elapsed and the expected time.
+	 *
+	 * @return f64
+	 */
+	pub fn get_drift(&self) -> f64 {
+		match self.first_tick_time {
+			Some(first_tick_time) => {
+				(self.last_tick_time - first_tick_time).as_secs_f64()
+					- f64::from(self.ticks - 1) / f64::from(self.fps)
+			}
+			None => 0.,
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use pretty_assertions::assert_eq;
-	use std::thread;
 	#[test]
 	fn test_fps() {
-		let mut fps_clock = FpsClock::new(100);
-		let fps = (1. / fps_clock.fps as f32) * 1e9;
-		assert_eq!(1e7, fps);
-		for i in 0..2 {
-			thread::sleep(Duration::from_nanos(i));
-			assert!(fps > fps_clock.tick());
+		assert_eq!(2e9, (1. / FpsClock::new(0.5).fps) * 1e9);
+		let mut fps_clock = FpsClock::new(100.);
+		assert_eq!(0., fps_clock.get_drift());
+		let start_time = fps_clock.last_tick_time;
+		let mut now = start_time;
+		for i in 0..30 {
+			let diff = fps_clock.next_tick(now);
+			if diff > 0. {
+				now += Duration::from_nanos(diff as u64);
+			}
+			fps_clock.record_tick(now);
+			now += Duration::from_millis(i % 3 * 3);
 		}
+		let elapsed = (fps_clock.last_tick_time - start_time).as_secs_f64();
+		assert!((elapsed - 0.3).abs() < 1e-6, "elapsed: {elapsed}");
+		assert!(fps_clock.get_drift().abs() < 1e-6);
+		now += Duration::from_millis(25);
+		assert!(fps_clock.next_tick(now) < 0.);
+		fps_clock.record_tick(now);
+		assert!((fps_clock.next_tick(now) - 1e7).abs() < 1e3);
 	}
 }
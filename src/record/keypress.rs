@@ -0,0 +1,106 @@
+use crate::app::AppResult;
+use crate::image::Image;
+use device_query::Keycode;
+use std::time::Instant;
+
+/* Filter for keeping the frames only when the pressed keys change */
+#[derive(Debug, Default)]
+pub struct KeypressFilter {
+	keys: Option<Vec<Keycode>>,
+	pending: Option<(Image, Instant)>,
+}
+
+impl KeypressFilter {
+	/**
+	 * Update the pressed keys and capture a new frame if they are changed.
+	 *
+	 * The new frame is held back until the next change so that
+	 * the previous one is returned with its real elapsed time as delay.
+	 *
+	 * @param  keys
+	 * @param  now
+	 * @param  capture
+	 * @return Image (Option)
+	 */
+	pub fn update<Capture: FnOnce() -> AppResult<Image>>(
+		&mut self,
+		keys: Vec<Keycode>,
+		now: Instant,
+		capture: Capture,
+	) -> AppResult<Option<Image>> {
+		if self.keys.as_ref() == Some(&keys) {
+			return Ok(None);
+		}
+		self.keys = Some(keys);
+		let frame = capture()?;
+		Ok(self.pending.replace((frame, now)).map(|(frame, time)| {
+			frame.with_delay(now.saturating_duration_since(time))
+		}))
+	}
+
+	/**
+	 * Get the last captured frame with the time elapsed since.
+	 *
+	 * @param  now
+	 * @return Image (Option)
+	 */
+	pub fn finish(&mut self, now: Instant) -> Option<Image> {
+		self.pending.take().map(|(frame, time)| {
+			frame.with_delay(now.saturating_duration_since(time))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use image::{ExtendedColorType, Rgba};
+	use pretty_assertions::assert_eq;
+	use std::time::Duration;
+	#[test]
+	fn test_keypress_filter() -> AppResult<()> {
+		let get_image = |value: u8| {
+			Ok(Image::new(
+				vec![Rgba([value, 0, 0, 255])],
+				false,
+				Geometry::new(0, 0, 1, 1),
+			))
+		};
+		let time = Instant::now();
+		let mut filter = KeypressFilter::default();
+		assert!(filter.update(Vec::new(), time, || get_image(1))?.is_none());
+		assert!(filter
+			.update(Vec::new(), time + Duration::from_millis(100), || {
+				panic!("Captured a frame without a keypress")
+			})?
+			.is_none());
+		let frame = filter
+			.update(vec![Keycode::A], time + Duration::from_millis(250), || {
+				get_image(2)
+			})?
+			.expect("Failed to get the frame");
+		assert_eq!(Some(Duration::from_millis(250)), frame.get_delay());
+		assert_eq!(vec![1, 0, 0, 255], frame.get_data(ExtendedColorType::Rgba8));
+		assert!(filter
+			.update(vec![Keycode::A], time + Duration::from_millis(500), || {
+				get_image(3)
+			})?
+			.is_none());
+		let frame = filter
+			.update(
+				vec![Keycode::A, Keycode::LShift],
+				time + Duration::from_millis(600),
+				|| get_image(4),
+			)?
+			.expect("Failed to get the frame");
+		assert_eq!(Some(Duration::from_millis(350)), frame.get_delay());
+		assert_eq!(vec![2, 0, 0, 255], frame.get_data(ExtendedColorType::Rgba8));
+		let frame = filter
+			.finish(time + Duration::from_secs(1))
+			.expect("Failed to get the frame");
+		assert_eq!(Some(Duration::from_millis(400)), frame.get_delay());
+		assert!(filter.finish(time + Duration::from_secs(2)).is_none());
+		Ok(())
+	}
+}
@@ -0,0 +1,246 @@
+use crate::image::font;
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use device_query::{DeviceQuery, DeviceState, Keycode};
+use image::Rgba;
+
+/* Duration of showing the released keys (in seconds) */
+const CAPTION_DURATION: f32 = 1.5;
+/* Portion of the duration to fade out the caption */
+const CAPTION_FADE: f32 = 0.4;
+/* Scale factor of the caption text */
+const CAPTION_SCALE: u32 = 3;
+/* Padding around the caption text */
+const CAPTION_PADDING: u32 = 8;
+/* Distance of the caption from the bottom of the frame */
+const CAPTION_MARGIN: u32 = 16;
+/* Opacity of the caption background */
+const BACKGROUND_OPACITY: f32 = 0.7;
+/* Modifier keys in the order of display */
+const MODIFIERS: [(&str, [Keycode; 2]); 4] = [
+	("Ctrl", [Keycode::LControl, Keycode::RControl]),
+	("Shift", [Keycode::LShift, Keycode::RShift]),
+	("Alt", [Keycode::LAlt, Keycode::RAlt]),
+	("Super", [Keycode::Meta, Keycode::Meta]),
+];
+
+/* Caption of the pressed keys on the recorded frames */
+pub struct KeyCaption {
+	state: Option<DeviceState>,
+	keys: Vec<Keycode>,
+	text: Option<String>,
+	released: usize,
+	duration: usize,
+}
+
+/* Implementation for thread-safe usage */
+unsafe impl Send for KeyCaption {}
+
+impl KeyCaption {
+	/**
+	 * Create a new KeyCaption object.
+	 *
+	 * @param  fps
+	 * @return KeyCaption
+	 */
+	pub fn new(fps: f32) -> Self {
+		let duration = (fps * CAPTION_DURATION).round().max(1.) as usize;
+		Self {
+			state: None,
+			keys: Vec::new(),
+			text: None,
+			released: duration,
+			duration,
+		}
+	}
+
+	/**
+	 * Check if the key is a modifier key.
+	 *
+	 * @param  key
+	 * @return bool
+	 */
+	fn is_modifier(key: &Keycode) -> bool {
+		MODIFIERS.iter().any(|(_, keys)| keys.contains(key))
+	}
+
+	/**
+	 * Get the readable name of the key.
+	 *
+	 * @param  key
+	 * @return String
+	 */
+	fn get_key_name(key: &Keycode) -> String {
+		match key {
+			Keycode::Escape => String::from("Esc"),
+			Keycode::Grave => String::from("`"),
+			Keycode::Minus | Keycode::NumpadSubtract => String::from("-"),
+			Keycode::Equal => String::from("="),
+			Keycode::NumpadAdd => String::from("+"),
+			Keycode::NumpadMultiply => String::from("*"),
+			Keycode::NumpadDivide | Keycode::Slash => String::from("/"),
+			Keycode::LeftBracket => String::from("["),
+			Keycode::RightBracket => String::from("]"),
+			Keycode::BackSlash => String::from("\\"),
+			Keycode::Semicolon => String::from(";"),
+			Keycode::Apostrophe => String::from("'"),
+			Keycode::Comma => String::from(","),
+			Keycode::Dot => String::from("."),
+			key => {
+				let name = format!("{key:?}");
+				name.strip_prefix("Key")
+					.or_else(|| name.strip_prefix("Numpad"))
+					.map(String::from)
+					.unwrap_or(name)
+			}
+		}
+	}
+
+	/**
+	 * Get the text of the key combination.
+	 *
+	 * @param  keys
+	 * @return String
+	 */
+	fn get_text(keys: &[Keycode]) -> String {
+		MODIFIERS
+			.iter()
+			.filter(|(_, modifiers)| keys.iter().any(|key| modifiers.contains(key)))
+			.map(|(name, _)| String::from(*name))
+			.chain(
+				keys.iter()
+					.filter(|key| !Self::is_modifier(key))
+					.map(Self::get_key_name),
+			)
+			.collect::<Vec<String>>()
+			.join("+")
+	}
+
+	/**
+	 * Update the caption with the pressed keys.
+	 *
+	 * The caption only changes when a non-modifier key is pressed
+	 * and it is kept while the keys are held down.
+	 *
+	 * @param keys
+	 */
+	pub fn update(&mut self, keys: Vec<Keycode>) {
+		let pressed = keys.iter().any(|key| !Self::is_modifier(key));
+		if keys != self.keys && pressed {
+			let text = Self::get_text(&keys);
+			debug!("Pressed keys: {}", text);
+			self.text = Some(text);
+		}
+		self.keys = keys;
+		self.released = if pressed {
+			0
+		} else {
+			self.released.saturating_add(1)
+		};
+	}
+
+	/* Update the caption with the keys that are currently pressed. */
+	pub fn poll(&mut self) {
+		let keys = self.state.get_or_insert_with(DeviceState::new).get_keys();
+		self.update(keys);
+	}
+
+	/**
+	 * Get the opacity of the caption.
+	 *
+	 * @return f32
+	 */
+	fn get_opacity(&self) -> f32 {
+		let fade = (self.duration as f32 * CAPTION_FADE).max(1.);
+		let visible = self.duration as f32 - fade;
+		if self.text.is_none() || self.released >= self.duration {
+			0.
+		} else if (self.released as f32) < visible {
+			1.
+		} else {
+			1. - (self.released as f32 - visible) / fade
+		}
+	}
+
+	/**
+	 * Draw the caption on the bottom center of the image.
+	 *
+	 * @param image
+	 */
+	pub fn draw(&self, image: &mut Image) {
+		let opacity = self.get_opacity();
+		let text = match &self.text {
+			Some(text) if opacity > 0. => text.to_uppercase(),
+			_ => return,
+		};
+		let (width, height) = font::get_text_size(&text, CAPTION_SCALE);
+		let area = Geometry::new(
+			(i32::try_from(image.geometry.width).unwrap_or_default()
+				- i32::try_from(width + CAPTION_PADDING * 2).unwrap_or_default())
+				/ 2,
+			i32::try_from(image.geometry.height).unwrap_or_default()
+				- i32::try_from(height + CAPTION_PADDING * 2 + CAPTION_MARGIN)
+					.unwrap_or_default(),
+			width + CAPTION_PADDING * 2,
+			height + CAPTION_PADDING * 2,
+		);
+		image.shade_area(area, Rgba([0, 0, 0, 255]), BACKGROUND_OPACITY * opacity);
+		let padding = i32::try_from(CAPTION_PADDING).unwrap_or_default();
+		image.draw_text(
+			&text,
+			(area.x + padding, area.y + padding),
+			CAPTION_SCALE,
+			Rgba([255, 255, 255, 255]),
+			opacity,
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::ExtendedColorType;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_key_caption() {
+		assert_eq!(
+			"Ctrl+Shift+T",
+			KeyCaption::get_text(&[Keycode::T, Keycode::LShift, Keycode::RControl])
+		);
+		assert_eq!(
+			"Alt+4",
+			KeyCaption::get_text(&[Keycode::LAlt, Keycode::Key4])
+		);
+		assert_eq!(
+			"Super+/",
+			KeyCaption::get_text(&[Keycode::Meta, Keycode::Slash])
+		);
+		let mut caption = KeyCaption::new(10.);
+		assert_eq!(15, caption.duration);
+		caption.update(vec![Keycode::LControl]);
+		assert_eq!(None, caption.text);
+		caption.update(vec![Keycode::LControl, Keycode::C]);
+		assert_eq!(Some(String::from("Ctrl+C")), caption.text);
+		assert_eq!(1., caption.get_opacity());
+		caption.update(vec![Keycode::LControl]);
+		caption.update(Vec::new());
+		assert_eq!(Some(String::from("Ctrl+C")), caption.text);
+		let mut image = Image::new(
+			vec![Rgba([0, 0, 0, 255]); 200 * 60],
+			false,
+			Geometry::new(0, 0, 200, 60),
+		);
+		caption.draw(&mut image);
+		let data = image.get_data(ExtendedColorType::L8);
+		assert!(data.contains(&255));
+		assert_eq!(0, data[0]);
+		for _ in 0..10 {
+			caption.update(Vec::new());
+		}
+		assert!(caption.get_opacity() > 0. && caption.get_opacity() < 1.);
+		for _ in 0..5 {
+			caption.update(Vec::new());
+		}
+		assert_eq!(0., caption.get_opacity());
+	}
+}
@@ -1,21 +1,44 @@
+pub mod disk;
 pub mod fps;
+pub mod keypress;
+pub mod keys;
+pub mod region;
 pub mod settings;
+pub mod snap;
+pub mod stitch;
+pub mod trail;
 
 use crate::app::{AppError, AppResult};
 use crate::image::Image;
+use crate::record::disk::DiskWatchdog;
 use crate::record::fps::FpsClock;
+use crate::record::keypress::KeypressFilter;
+use crate::record::keys::KeyCaption;
 use crate::record::settings::RecordSettings;
+use crate::record::trail::CursorTrail;
 use crate::util::state::InputState;
 use crate::window::Capture;
+use device_query::{DeviceQuery, DeviceState};
+use std::collections::VecDeque;
 use std::io::{self, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{mpsc, Arc};
+use std::sync::{mpsc, Once};
 use std::thread;
+use std::time::Instant;
+
+/* Percentage of the frame limit to warn about */
+const FRAME_LIMIT_WARNING: usize = 90;
+
+/* Interrupt signal (Ctrl-C) state for stopping the recording */
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/* Installation of the interrupt signal handler */
+static INTERRUPT_HANDLER: Once = Once::new();
 
 /* Asynchronous recording result */
 #[derive(Debug)]
 pub struct RecordResult<T> {
 	sender: mpsc::Sender<()>,
+	started: mpsc::Receiver<()>,
 	thread: thread::JoinHandle<T>,
 }
 
@@ -24,11 +47,29 @@ impl<T> RecordResult<T> {
 	 * Create a new RecordResult object.
 	 *
 	 * @param  sender
+	 * @param  started
 	 * @param  thread
 	 * @return RecordResult
 	 */
-	pub fn new(sender: mpsc::Sender<()>, thread: thread::JoinHandle<T>) -> Self {
-		Self { sender, thread }
+	pub fn new(
+		sender: mpsc::Sender<()>,
+		started: mpsc::Receiver<()>,
+		thread: thread::JoinHandle<T>,
+	) -> Self {
+		Self {
+			sender,
+			started,
+			thread,
+		}
+	}
+
+	/**
+	 * Block until the first frame is captured.
+	 *
+	 * @return bool
+	 */
+	pub fn wait(&self) -> bool {
+		self.started.recv().is_ok()
 	}
 
 	/**
@@ -50,8 +91,10 @@ pub struct Recorder<Window> {
 	window: Window,
 	clock: FpsClock,
 	channel: (mpsc::Sender<()>, mpsc::Receiver<()>),
-	gifski: bool,
 	settings: RecordSettings,
+	watchdog: Option<DiskWatchdog>,
+	trail: Option<CursorTrail>,
+	caption: Option<KeyCaption>,
 }
 
 impl<Window> Recorder<Window>
@@ -63,42 +106,54 @@ where
 	 *
 	 * @param  window
 	 * @param  fps
-	 * @param  gifski
 	 * @param  settings
 	 * @return Recorder
 	 */
-	pub fn new(
-		window: Window,
-		fps: u32,
-		gifski: bool,
-		settings: RecordSettings,
-	) -> Self {
+	pub fn new(window: Window, fps: f32, settings: RecordSettings) -> Self {
 		Self {
 			window,
 			clock: FpsClock::new(fps),
 			channel: mpsc::channel(),
-			gifski,
 			settings,
+			watchdog: None,
+			trail: settings
+				.trail
+				.map(|trail| CursorTrail::new(trail, settings.color)),
+			caption: settings.flag.show_keys.then(|| KeyCaption::new(fps)),
 		}
 	}
 
+	/**
+	 * Stop the recording when the free space drops below the limit.
+	 *
+	 * @param  watchdog (Option)
+	 * @return Recorder
+	 */
+	pub fn with_watchdog(mut self, watchdog: Option<DiskWatchdog>) -> Self {
+		self.watchdog = watchdog;
+		self
+	}
+
 	/**
 	 * Get the maximum number of frames to record.
 	 *
 	 * @return usize
 	 */
 	fn get_max_frames(&self) -> usize {
+		debug!("Frame limit: {}", self.settings.max_frames);
 		if let Some(duration) = self.settings.time.duration {
 			info!(
 				"Recording {} FPS for {} seconds...",
 				self.clock.fps, duration
 			);
-			if self.gifski {
-				(duration * (self.clock.fps as f64)) as usize
-			} else {
-				(((duration * 100.) as u16) / (1e2 / self.clock.fps as f32) as u16)
-					as usize
+			let frames = (duration * f64::from(self.clock.fps)).round() as usize;
+			if frames > self.settings.max_frames {
+				warn!(
+					"Duration exceeds the frame limit ({}), recording will stop early.",
+					self.settings.max_frames
+				);
 			}
+			frames.min(self.settings.max_frames)
 		} else {
 			info!("Recording {} FPS...", self.clock.fps);
 			usize::MAX
@@ -106,43 +161,222 @@ where
 	}
 
 	/**
-	 * Record frames synchronously with blocking the current thread.
+	 * Get the number of frames to keep in the replay buffer.
+	 *
+	 * @return usize (Option)
+	 */
+	fn get_replay_size(&self) -> Option<usize> {
+		self.settings.time.replay.map(|replay| {
+			info!(
+				"Keeping the last {} seconds, press the action keys to save.",
+				replay
+			);
+			((replay * self.clock.fps as f64).ceil() as usize).max(1)
+		})
+	}
+
+	/**
+	 * Check the number of recorded frames against the frame limit.
+	 *
+	 * @param  frames
+	 * @return bool
+	 */
+	fn check_frame_limit(&self, frames: usize) -> bool {
+		let max_frames = self.settings.max_frames;
+		if frames >= max_frames {
+			debug!("\n");
+			warn!(
+				"Reached the frame limit ({}), stopping the recording.",
+				max_frames
+			);
+			false
+		} else {
+			if frames == max_frames / 100 * FRAME_LIMIT_WARNING
+				&& max_frames > FRAME_LIMIT_WARNING
+			{
+				debug!("\n");
+				warn!("Approaching the frame limit. ({}/{})", frames, max_frames);
+			}
+			true
+		}
+	}
+
+	/**
+	 * Check the free space with the watchdog (if any).
+	 *
+	 * @return bool
+	 */
+	fn check_free_space(&mut self) -> bool {
+		self.watchdog
+			.as_mut()
+			.is_none_or(|watchdog| watchdog.check())
+	}
+
+	/**
+	 * Check if the window still exists to capture.
+	 *
+	 * @return bool
+	 */
+	fn check_window(&self) -> bool {
+		let exists = self.window.exists();
+		if !exists {
+			debug!("\n");
+			warn!("Target window closed, stopping the recording.");
+		}
+		exists
+	}
+
+	/**
+	 * Get the image of the window with the cursor trail and keys (if any).
+	 *
+	 * @return Image (Option)
+	 */
+	fn get_image(&mut self) -> Option<Image> {
+		let mut image = self.window.get_image()?;
+		if let Some(trail) = self.trail.as_mut() {
+			trail.update(self.window.get_pointer());
+			trail.draw(&mut image);
+		}
+		if let Some(caption) = self.caption.as_mut() {
+			caption.poll();
+			caption.draw(&mut image);
+		}
+		Some(image)
+	}
+
+	/**
+	 * Get the next frame for the synchronous recording.
+	 *
+	 * @return Image (Result)
+	 */
+	fn get_frame(&mut self) -> AppResult<Image> {
+		self.get_image()
+			.ok_or_else(|| AppError::FrameError(String::from("Failed to get image")))
+	}
+
+	/**
+	 * Listen for the interrupt signal to stop the recording.
+	 *
+	 * The handler is installed once and shared between the recordings
+	 * so that the frames captured so far are kept on interrupt.
+	 *
+	 * @return Result
+	 */
+	fn listen_interrupt() -> AppResult<()> {
+		let mut result = Ok(());
+		INTERRUPT_HANDLER.call_once(|| {
+			result = ctrlc::set_handler(|| {
+				INTERRUPTED.store(true, Ordering::SeqCst);
+			});
+		});
+		INTERRUPTED.store(false, Ordering::SeqCst);
+		Ok(result?)
+	}
+
+	/**
+	 * Check if the recording is interrupted.
+	 *
+	 * @return bool
+	 */
+	fn is_interrupted() -> bool {
+		let interrupted = INTERRUPTED.load(Ordering::SeqCst);
+		if interrupted {
+			debug!("\n");
+			info!("Interrupted, stopping the recording...");
+		}
+		interrupted
+	}
+
+	/**
+	 * Record frames synchronously and pass them to the given function.
+	 *
+	 * The function returns the number of the kept frames
+	 * which is checked against the frame limits.
 	 *
 	 * @param  input_state (Option)
-	 * @return Vector of Image
+	 * @param  on_frame
+	 * @return bool (Result)
 	 */
-	pub fn record_sync(
+	pub fn record_frames<OnFrame: FnMut(Image) -> AppResult<usize>>(
 		&mut self,
 		input_state: Option<&InputState>,
-	) -> AppResult<Vec<Image>> {
-		let mut frames = Vec::new();
-		let recording = Arc::new(AtomicBool::new(true));
-		let rec_state = recording.clone();
-		ctrlc::set_handler(move || {
-			rec_state.store(false, Ordering::SeqCst);
-		})?;
+		mut on_frame: OnFrame,
+	) -> AppResult<bool> {
+		Self::listen_interrupt()?;
+		let mut keypress = self
+			.settings
+			.flag
+			.on_keypress
+			.then(|| (DeviceState::new(), KeypressFilter::default()));
+		let mut frame_count = 0;
 		self.window.show_countdown();
 		let max_frames = self.get_max_frames();
-		while recording.load(Ordering::SeqCst) && frames.len() < max_frames {
+		while !Self::is_interrupted()
+			&& frame_count < max_frames
+			&& self.check_frame_limit(frame_count)
+			&& self.check_free_space()
+			&& self.check_window()
+		{
 			if let Some(state) = input_state {
 				if state.check_cancel_keys() {
-					frames.clear();
 					debug!("\n");
 					warn!("User interrupt detected.");
-					break;
+					return Ok(false);
 				} else if state.check_action() {
 					break;
 				}
 			}
 			self.clock.tick();
-			frames.push(self.window.get_image().ok_or_else(|| {
-				AppError::FrameError(String::from("Failed to get image"))
-			})?);
-			debug!("Frames: {}\r", frames.len());
+			let frame = match keypress.as_mut() {
+				Some((device_state, filter)) => {
+					match filter.update(
+						device_state.get_keys(),
+						Instant::now(),
+						|| self.get_frame(),
+					)? {
+						Some(frame) => frame,
+						None => continue,
+					}
+				}
+				None => self.get_frame()?,
+			};
+			frame_count = on_frame(frame)?;
+			debug!("Frames: {}\r", frame_count);
 			io::stdout().flush()?;
 		}
+		if let Some(frame) =
+			keypress.and_then(|(_, mut filter)| filter.finish(Instant::now()))
+		{
+			on_frame(frame)?;
+		}
 		debug!("\n");
-		Ok(frames)
+		debug!("Clock drift: {:.3}s", self.clock.get_drift());
+		Ok(true)
+	}
+
+	/**
+	 * Record frames synchronously with blocking the current thread.
+	 *
+	 * @param  input_state (Option)
+	 * @return Vector of Image
+	 */
+	pub fn record_sync(
+		&mut self,
+		input_state: Option<&InputState>,
+	) -> AppResult<Vec<Image>> {
+		let mut frames: VecDeque<Image> = VecDeque::new();
+		let replay_size = self.get_replay_size();
+		let completed = self.record_frames(input_state, |frame| {
+			if replay_size.is_some_and(|size| frames.len() >= size) {
+				frames.pop_front();
+			}
+			frames.push_back(frame);
+			Ok(frames.len())
+		})?;
+		if !completed {
+			frames.clear();
+		}
+		Ok(frames.into())
 	}
 
 	/**
@@ -152,24 +386,40 @@ where
 	 */
 	pub fn record_async(mut self) -> RecordResult<Vec<Image>> {
 		let mut frames = Vec::new();
+		let (sender, receiver) = mpsc::channel();
+		if let Err(e) = Self::listen_interrupt() {
+			warn!("Failed to listen for the interrupt signal: {}", e);
+		}
 		RecordResult::new(
 			self.channel.0.clone(),
+			receiver,
 			thread::spawn(move || {
 				self.window.show_countdown();
 				let max_frames = self.get_max_frames();
+				let mut recording = true;
+				let mut started = Some(sender);
 				while self.channel.1.try_recv().is_err() {
 					self.clock.tick();
-					if frames.len() < max_frames {
+					recording = recording
+						&& !Self::is_interrupted()
+						&& frames.len() < max_frames
+						&& self.check_frame_limit(frames.len())
+						&& self.check_free_space()
+						&& self.check_window();
+					if recording {
 						frames.push(
-							self.window
-								.get_image()
-								.expect("Failed to get the image"),
+							self.get_image().expect("Failed to get the image"),
 						);
 						debug!("Frames: {}\r", frames.len());
 						io::stdout().flush().expect("Failed to flush stdout");
 					}
+					/* Notify that the capture loop is live */
+					if let Some(sender) = started.take() {
+						sender.send(()).unwrap_or_default();
+					}
 				}
 				debug!("\n");
+				debug!("Clock drift: {:.3}s", self.clock.get_drift());
 				frames
 			}),
 		)
@@ -179,21 +429,80 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::record::settings::RecordSettings;
+	use crate::record::settings::{RecordSettings, RecordTrail};
+	use crate::util::command::Command;
 	use crate::window::test::TestWindow;
-	use pretty_assertions::assert_ne;
+	use image::ExtendedColorType;
+	use pretty_assertions::{assert_eq, assert_ne};
 	use std::thread;
 	use std::time::Duration;
 	#[test]
 	fn test_record() {
 		let window = TestWindow::default();
-		let recorder = Recorder::new(window, 10, false, RecordSettings::default());
+		let recorder = Recorder::new(window, 10., RecordSettings::default());
 		let record = recorder.record_async();
 		thread::sleep(Duration::from_millis(200));
 		assert!(!record.get().unwrap().unwrap().is_empty());
-		let mut recorder =
-			Recorder::new(window, 10, false, RecordSettings::default());
+		let recorder = Recorder::new(window, 1., RecordSettings::default());
+		let record = recorder.record_async();
+		assert!(record.wait());
+		Command::new("true", Vec::new()).execute().unwrap();
+		assert!(!record.get().unwrap().unwrap().is_empty());
+		let mut recorder = Recorder::new(window, 10., RecordSettings::default());
 		recorder.settings.time.duration = Some(0.2);
+		recorder.settings.max_frames = 1;
+		assert_eq!(None, recorder.get_replay_size());
+		assert_eq!(1, recorder.record_sync(None).unwrap().len());
+		let mut recorder = Recorder::new(window, 10., RecordSettings::default());
+		let interrupt = thread::spawn(|| {
+			thread::sleep(Duration::from_millis(200));
+			INTERRUPTED.store(true, Ordering::SeqCst);
+		});
 		assert_ne!(0, recorder.record_sync(None).unwrap().len());
+		interrupt.join().unwrap();
+		INTERRUPTED.store(false, Ordering::SeqCst);
+		let mut recorder = Recorder::new(window, 10., RecordSettings::default());
+		recorder.settings.max_frames = 2;
+		recorder.settings.time.replay = Some(2.5);
+		assert_eq!(Some(25), recorder.get_replay_size());
+		let record = recorder.record_async();
+		thread::sleep(Duration::from_millis(500));
+		assert_eq!(2, record.get().unwrap().unwrap().len());
+		let settings = RecordSettings {
+			trail: Some(RecordTrail::new(4, 1.)),
+			..RecordSettings::default()
+		};
+		let mut recorder = Recorder::new(window, 10., settings);
+		recorder.get_image().unwrap();
+		assert_eq!(
+			vec![0x3a, 0xa4, 0x31, 255],
+			recorder
+				.get_image()
+				.unwrap()
+				.get_data(ExtendedColorType::Rgba8)
+		);
+	}
+	#[test]
+	fn test_max_frames() {
+		let window = TestWindow::default();
+		let settings = RecordSettings {
+			max_frames: usize::MAX,
+			..RecordSettings::default()
+		};
+		for (fps, duration, frames) in [
+			(10., 0.2, 2),
+			(0.5, 3., 2),
+			(7.5, 2., 15),
+			(144., 1., 144),
+			(240., 0.5, 120),
+			(20., 1000., 20000),
+		] {
+			let mut recorder = Recorder::new(window, fps, settings);
+			recorder.settings.time.duration = Some(duration);
+			assert_eq!(frames, recorder.get_max_frames());
+		}
+		let mut recorder = Recorder::new(window, 20., RecordSettings::default());
+		recorder.settings.time.duration = Some(1000.);
+		assert_eq!(1000, recorder.get_max_frames());
 	}
 }
@@ -0,0 +1,149 @@
+use crate::image::geometry::Geometry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Result as IoResult;
+use std::path::PathBuf;
+
+/* Name of the file for storing the regions */
+const REGIONS_FILE: &str = "regions";
+
+/* Persistent storage of the named regions */
+#[derive(Debug)]
+pub struct RegionStore {
+	path: PathBuf,
+}
+
+/* Default initialization values for RegionStore */
+impl Default for RegionStore {
+	fn default() -> Self {
+		Self::new(
+			dirs::state_dir()
+				.unwrap_or_else(|| {
+					dirs::home_dir()
+						.expect("Failed to access the home directory")
+						.join(".local")
+						.join("state")
+				})
+				.join(env!("CARGO_PKG_NAME"))
+				.join(REGIONS_FILE),
+		)
+	}
+}
+
+impl RegionStore {
+	/**
+	 * Create a new RegionStore object.
+	 *
+	 * @param  path
+	 * @return RegionStore
+	 */
+	pub fn new(path: PathBuf) -> Self {
+		Self { path }
+	}
+
+	/**
+	 * Load the saved regions.
+	 *
+	 * @return BTreeMap
+	 */
+	pub fn load(&self) -> BTreeMap<String, Geometry> {
+		fs::read_to_string(&self.path)
+			.unwrap_or_default()
+			.lines()
+			.filter_map(|line| {
+				let (name, region) = line.split_once('=')?;
+				Some((name.trim().to_string(), Self::parse(region.trim())?))
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the region with the given name.
+	 *
+	 * @param  name
+	 * @return Geometry (Option)
+	 */
+	pub fn get(&self, name: &str) -> Option<Geometry> {
+		self.load().remove(name)
+	}
+
+	/**
+	 * Save the region with the given name.
+	 *
+	 * @param  name
+	 * @param  region
+	 * @return Result
+	 */
+	pub fn save(&self, name: &str, region: Geometry) -> IoResult<()> {
+		let mut regions = self.load();
+		regions.insert(name.to_string(), region);
+		if let Some(parent) = self.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::write(
+			&self.path,
+			regions
+				.iter()
+				.map(|(name, region)| {
+					format!("{} = {}\n", name, Self::format(region))
+				})
+				.collect::<String>(),
+		)
+	}
+
+	/**
+	 * Format the region as WxH+X+Y.
+	 *
+	 * @param  region
+	 * @return String
+	 */
+	pub fn format(region: &Geometry) -> String {
+		format!("{}+{}+{}", region, region.x, region.y)
+	}
+
+	/**
+	 * Parse a region in WxH+X+Y format.
+	 *
+	 * @param  region
+	 * @return Geometry (Option)
+	 */
+	fn parse(region: &str) -> Option<Geometry> {
+		let mut values = region.split('+');
+		let size = Geometry::parse(values.next()?);
+		let x = values.next()?.parse().ok()?;
+		let y = values.next()?.parse().ok()?;
+		if size.is_zero() {
+			None
+		} else {
+			Some(Geometry::new(x, y, size.width, size.height))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn test_region_store() -> IoResult<()> {
+		let path = env::temp_dir()
+			.join(env!("CARGO_PKG_NAME"))
+			.join("test_regions");
+		let _ = fs::remove_file(&path);
+		let store = RegionStore::new(path.clone());
+		assert!(store.load().is_empty());
+		store.save("term", Geometry::new(10, 20, 640, 480))?;
+		store.save("full", Geometry::new(0, 0, 1920, 1080))?;
+		store.save("term", Geometry::new(5, 5, 800, 600))?;
+		assert_eq!(Some(Geometry::new(5, 5, 800, 600)), store.get("term"));
+		assert_eq!(None, store.get("x"));
+		assert_eq!(
+			"full = 1920x1080+0+0\nterm = 800x600+5+5\n",
+			fs::read_to_string(&path)?
+		);
+		assert_eq!(None, RegionStore::parse("0x0+1+1"));
+		assert_eq!(None, RegionStore::parse("100x100"));
+		fs::remove_file(path)
+	}
+}
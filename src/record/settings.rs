@@ -2,15 +2,21 @@ use crate::args::matches::ArgMatches;
 use crate::args::parser::ArgParser;
 use crate::image::geometry::Geometry;
 use crate::image::padding::Padding;
+use crate::image::ratio::AspectRatio;
+use crate::record::region::RegionStore;
 use crate::util::command::Command;
+use bytesize::ByteSize;
+use image::Rgba;
 
 /* Time related recording settings */
 #[derive(Clone, Copy, Debug)]
 pub struct RecordTime {
 	pub duration: Option<f64>,
+	pub replay: Option<f64>,
 	pub countdown: u64,
 	pub timeout: u64,
 	pub interval: u64,
+	pub wait: u64,
 }
 
 /* Default initialization values for RecordTime */
@@ -18,9 +24,11 @@ impl Default for RecordTime {
 	fn default() -> Self {
 		Self {
 			duration: None,
+			replay: None,
 			countdown: 3,
 			timeout: 300,
 			interval: 10,
+			wait: 0,
 		}
 	}
 }
@@ -30,22 +38,28 @@ impl RecordTime {
 	 * Create a new RecordTime object.
 	 *
 	 * @param  duration (Option)
+	 * @param  replay (Option)
 	 * @param  countdown
 	 * @param  timeout
 	 * @param  interval
+	 * @param  wait
 	 * @return RecordTime
 	 */
 	pub fn new(
 		duration: Option<f64>,
+		replay: Option<f64>,
 		countdown: u64,
 		timeout: u64,
 		interval: u64,
+		wait: u64,
 	) -> Self {
 		Self {
 			duration,
+			replay,
 			countdown,
 			timeout,
 			interval,
+			wait,
 		}
 	}
 
@@ -61,9 +75,14 @@ impl RecordTime {
 				duration if duration > 0.0 => Some(duration),
 				_ => Self::default().duration,
 			},
+			match parser.parse("replay-buffer", 0.0) {
+				replay if replay > 0.0 => Some(replay),
+				_ => Self::default().replay,
+			},
 			parser.parse("countdown", Self::default().countdown),
 			parser.parse("timeout", Self::default().timeout),
 			parser.parse("interval", Self::default().interval),
+			parser.parse("wait-for-display", Self::default().wait),
 		)
 	}
 }
@@ -72,12 +91,23 @@ impl RecordTime {
 #[derive(Clone, Copy, Debug)]
 pub struct RecordFlag {
 	pub alpha: bool,
+	pub opaque: bool,
 	pub action_keys: Option<&'static str>,
 	pub cancel_keys: Option<&'static str>,
 	pub font: Option<&'static str>,
 	pub monitor: Option<usize>,
 	pub select: bool,
 	pub mouse: bool,
+	pub preview: bool,
+	pub on_keypress: bool,
+	pub text_position: Option<TextPosition>,
+	pub font_size: Option<u32>,
+	pub shadow: bool,
+	pub loupe: bool,
+	pub show_keys: bool,
+	pub sanity_check: bool,
+	pub stitch: bool,
+	pub scale_monitors: bool,
 }
 
 /* Default initialization values for RecordFlag */
@@ -85,12 +115,23 @@ impl Default for RecordFlag {
 	fn default() -> Self {
 		Self {
 			alpha: false,
+			opaque: true,
 			action_keys: Some(""),
 			cancel_keys: Some(""),
 			font: None,
 			monitor: None,
 			select: true,
 			mouse: false,
+			preview: false,
+			on_keypress: false,
+			text_position: None,
+			font_size: None,
+			shadow: false,
+			loupe: false,
+			show_keys: false,
+			sanity_check: true,
+			stitch: false,
+			scale_monitors: false,
 		}
 	}
 }
@@ -100,25 +141,49 @@ impl RecordFlag {
 	 * Create a new RecordFlag object.
 	 *
 	 * @param  alpha
+	 * @param  opaque
 	 * @param  action_keys (Option)
 	 * @param  cancel_keys (Option)
 	 * @param  font
 	 * @param  monitor (Option)
 	 * @param  select
 	 * @param  mouse
+	 * @param  preview
+	 * @param  on_keypress
+	 * @param  text_position (Option)
+	 * @param  font_size (Option)
+	 * @param  shadow
+	 * @param  loupe
+	 * @param  show_keys
+	 * @param  sanity_check
+	 * @param  stitch
+	 * @param  scale_monitors
 	 * @return RecordFlag
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		alpha: bool,
+		opaque: bool,
 		action_keys: Option<&'static str>,
 		cancel_keys: Option<&'static str>,
 		font: &str,
 		monitor: Option<usize>,
 		select: bool,
 		mouse: bool,
+		preview: bool,
+		on_keypress: bool,
+		text_position: Option<TextPosition>,
+		font_size: Option<u32>,
+		shadow: bool,
+		loupe: bool,
+		show_keys: bool,
+		sanity_check: bool,
+		stitch: bool,
+		scale_monitors: bool,
 	) -> Self {
 		Self {
 			alpha,
+			opaque,
 			action_keys,
 			cancel_keys,
 			font: if font.is_empty() {
@@ -129,7 +194,400 @@ impl RecordFlag {
 			monitor,
 			select,
 			mouse,
+			preview,
+			on_keypress,
+			text_position,
+			font_size,
+			shadow,
+			loupe,
+			show_keys,
+			sanity_check,
+			stitch,
+			scale_monitors,
+		}
+	}
+}
+
+/* Treatment of the excluded windows */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExcludeMode {
+	Black,
+	Blur,
+}
+
+/* Windows to exclude from the recording */
+#[derive(Clone, Copy, Debug)]
+pub struct RecordExclude {
+	pub windows: &'static [u64],
+	pub mode: ExcludeMode,
+}
+
+/* Default initialization values for RecordExclude */
+impl Default for RecordExclude {
+	fn default() -> Self {
+		Self {
+			windows: &[],
+			mode: ExcludeMode::Black,
+		}
+	}
+}
+
+impl RecordExclude {
+	/**
+	 * Create a new RecordExclude object.
+	 *
+	 * @param  windows
+	 * @param  mode
+	 * @return RecordExclude
+	 */
+	pub fn new(windows: Vec<u64>, mode: ExcludeMode) -> Self {
+		Self {
+			windows: Box::leak(windows.into_boxed_slice()),
+			mode,
+		}
+	}
+
+	/**
+	 * Create a RecordExclude object from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return RecordExclude
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Self {
+		let values = match matches.values_of("exclude-window-id") {
+			Some(values) => values.collect(),
+			None => matches
+				.value_of("exclude-window-id")
+				.map(|v| vec![v])
+				.unwrap_or_default(),
+		};
+		Self::new(
+			values
+				.iter()
+				.flat_map(|v| v.split(','))
+				.filter_map(|id| {
+					let xid = RecordWindow::parse_id(id);
+					if xid.is_none() {
+						warn!("Invalid window ID: {}", id);
+					}
+					xid
+				})
+				.collect(),
+			match matches.value_of("exclude-mode") {
+				Some("blur") => ExcludeMode::Blur,
+				_ => ExcludeMode::Black,
+			},
+		)
+	}
+}
+
+/* Fixed-size canvas for centering the output */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordCanvas {
+	pub size: Geometry,
+	pub background: Rgba<u8>,
+}
+
+impl RecordCanvas {
+	/**
+	 * Create a new RecordCanvas object.
+	 *
+	 * @param  size
+	 * @param  background
+	 * @return RecordCanvas
+	 */
+	pub fn new(size: Geometry, background: Rgba<u8>) -> Self {
+		Self { size, background }
+	}
+
+	/**
+	 * Create a RecordCanvas object from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return RecordCanvas (Option)
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Option<Self> {
+		let size = Geometry::parse(matches.value_of("canvas")?);
+		if size.width == 0 || size.height == 0 {
+			warn!("Invalid canvas size, ignoring.");
+			return None;
+		}
+		Some(Self::new(
+			size,
+			Self::parse_color(matches.value_of("background").unwrap_or_default())
+				.unwrap_or_else(|| {
+					warn!("Invalid background color, using black.");
+					Rgba([0, 0, 0, 255])
+				}),
+		))
+	}
+
+	/**
+	 * Parse a color in RRGGBB or RRGGBBAA hexadecimal notation.
+	 *
+	 * @param  color
+	 * @return Rgba (Option)
+	 */
+	pub fn parse_color(color: &str) -> Option<Rgba<u8>> {
+		match hex::decode(color.trim_start_matches('#')).ok()?.as_slice() {
+			[r, g, b] => Some(Rgba([*r, *g, *b, 255])),
+			[r, g, b, a] => Some(Rgba([*r, *g, *b, *a])),
+			_ => None,
+		}
+	}
+}
+
+/* Default opacity of the oldest point of the cursor trail */
+const TRAIL_FADE: f32 = 1.;
+
+/* Trail of the pointer positions to draw on frames */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordTrail {
+	pub length: usize,
+	pub fade: f32,
+}
+
+impl RecordTrail {
+	/**
+	 * Create a new RecordTrail object.
+	 *
+	 * @param  length
+	 * @param  fade
+	 * @return RecordTrail
+	 */
+	pub fn new(length: usize, fade: f32) -> Self {
+		Self {
+			length,
+			fade: fade.clamp(0., 1.),
+		}
+	}
+
+	/**
+	 * Create a RecordTrail object from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return RecordTrail (Option)
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Option<Self> {
+		let length = matches.value_of("cursor-trail")?.parse().ok()?;
+		if length < 2 {
+			warn!("Cursor trail needs at least 2 points, ignoring.");
+			return None;
+		}
+		Some(Self::new(
+			length,
+			matches
+				.value_of("trail-fade")
+				.and_then(|v| v.parse().ok())
+				.unwrap_or(TRAIL_FADE),
+		))
+	}
+}
+
+/* Line style of the border */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineStyle {
+	Solid,
+	Dashed,
+}
+
+/* Position of the text on the window */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextPosition {
+	TopLeft,
+	Top,
+	TopRight,
+	Left,
+	Center,
+	Right,
+	BottomLeft,
+	Bottom,
+	BottomRight,
+}
+
+impl TextPosition {
+	/**
+	 * Parse the text position from the given string.
+	 *
+	 * @param  value
+	 * @return TextPosition (Option)
+	 */
+	pub fn parse(value: &str) -> Option<Self> {
+		match value {
+			"tl" | "top-left" => Some(Self::TopLeft),
+			"t" | "top" => Some(Self::Top),
+			"tr" | "top-right" => Some(Self::TopRight),
+			"l" | "left" => Some(Self::Left),
+			"c" | "center" => Some(Self::Center),
+			"r" | "right" => Some(Self::Right),
+			"bl" | "bottom-left" => Some(Self::BottomLeft),
+			"b" | "bottom" => Some(Self::Bottom),
+			"br" | "bottom-right" => Some(Self::BottomRight),
+			_ => None,
+		}
+	}
+
+	/**
+	 * Get the top-left point of the text with the given size in area.
+	 *
+	 * @param  area
+	 * @param  size
+	 * @param  margin
+	 * @return Tuple (i32, i32)
+	 */
+	pub fn get_point(
+		self,
+		area: Geometry,
+		size: (u32, u32),
+		margin: i32,
+	) -> (i32, i32) {
+		let align = |start: i32, length: u32, text: u32, index: u8| -> i32 {
+			let free =
+				i32::try_from(length.saturating_sub(text)).unwrap_or_default();
+			match index {
+				0 => start + margin.min(free),
+				1 => start + free / 2,
+				_ => start + (free - margin).max(0),
+			}
+		};
+		let (column, row) = match self {
+			Self::TopLeft => (0, 0),
+			Self::Top => (1, 0),
+			Self::TopRight => (2, 0),
+			Self::Left => (0, 1),
+			Self::Center => (1, 1),
+			Self::Right => (2, 1),
+			Self::BottomLeft => (0, 2),
+			Self::Bottom => (1, 2),
+			Self::BottomRight => (2, 2),
+		};
+		(
+			align(area.x, area.width, size.0, column),
+			align(area.y, area.height, size.1, row),
+		)
+	}
+}
+
+/* Default opacity of the dimmed area around the selection */
+const DIM_ALPHA: u8 = 128;
+
+/* Style of the selection border */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BorderStyle {
+	pub line: LineStyle,
+	pub corners: bool,
+	pub dim: Option<u8>,
+}
+
+/* Default initialization values for BorderStyle */
+impl Default for BorderStyle {
+	fn default() -> Self {
+		Self {
+			line: LineStyle::Solid,
+			corners: false,
+			dim: None,
+		}
+	}
+}
+
+impl BorderStyle {
+	/**
+	 * Create a new BorderStyle object.
+	 *
+	 * @param  line
+	 * @param  corners
+	 * @param  dim (Option)
+	 */
+	pub fn new(line: LineStyle, corners: bool, dim: Option<u8>) -> Self {
+		Self { line, corners, dim }
+	}
+
+	/**
+	 * Create a BorderStyle object from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return BorderStyle
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Self {
+		Self::new(
+			match matches.value_of("border-style") {
+				Some("dashed") => LineStyle::Dashed,
+				_ => LineStyle::Solid,
+			},
+			matches.is_present("corner-markers"),
+			if matches.is_present("dim") {
+				Some(
+					matches
+						.value_of("dim")
+						.and_then(|v| v.parse().ok())
+						.unwrap_or(DIM_ALPHA),
+				)
+			} else {
+				None
+			},
+		)
+	}
+}
+
+/* Value of the region name for listing the saved regions */
+pub const REGION_LIST: &str = "list";
+
+/* Named region to save or recall */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecordRegion {
+	pub save: Option<&'static str>,
+	pub name: Option<&'static str>,
+}
+
+impl RecordRegion {
+	/**
+	 * Create a new RecordRegion object.
+	 *
+	 * @param  save (Option)
+	 * @param  name (Option)
+	 * @return RecordRegion
+	 */
+	pub fn new(save: Option<&'static str>, name: Option<&'static str>) -> Self {
+		Self { save, name }
+	}
+
+	/**
+	 * Create a RecordRegion object from parsed arguments.
+	 *
+	 * @param  matches
+	 * @return RecordRegion
+	 */
+	fn from_args(matches: &ArgMatches<'_>) -> Self {
+		let leak =
+			|v: &str| -> &'static str { Box::leak(v.to_string().into_boxed_str()) };
+		Self::new(
+			matches.value_of("save-region").map(leak),
+			matches.value_of("region-name").map(leak),
+		)
+	}
+
+	/**
+	 * Check if the saved regions should be listed.
+	 *
+	 * @return bool
+	 */
+	pub fn is_list(&self) -> bool {
+		self.name == Some(REGION_LIST)
+	}
+
+	/**
+	 * Get the saved region to recall.
+	 *
+	 * @param  store
+	 * @return Geometry (Option)
+	 */
+	pub fn recall(&self, store: &RegionStore) -> Option<Geometry> {
+		let name = self.name.filter(|_| !self.is_list())?;
+		let region = store.get(name);
+		if region.is_none() {
+			warn!("Region not found: {:?}", name);
 		}
+		region
 	}
 }
 
@@ -138,6 +596,9 @@ impl RecordFlag {
 pub enum RecordWindow {
 	Focus(Option<Geometry>, bool),
 	Root(Option<Geometry>),
+	Id(u64, Option<Geometry>),
+	Class(&'static str, bool),
+	Synthetic(Geometry),
 }
 
 impl RecordWindow {
@@ -148,19 +609,55 @@ impl RecordWindow {
 	 * @return RecordWindow
 	 */
 	fn from_args(matches: &ArgMatches<'_>) -> Self {
+		if let Some(size) = matches.value_of("synthetic") {
+			let size = Geometry::parse(size);
+			if size.width != 0 && size.height != 0 {
+				return Self::Synthetic(size);
+			}
+			warn!("Invalid synthetic source size, ignoring.");
+		}
+		if let Some(class) = matches.value_of("window-class") {
+			return Self::Class(
+				Box::leak(class.to_string().into_boxed_str()),
+				matches.is_present("all-matching"),
+			);
+		}
+		let preset = matches
+			.value_of("select")
+			.and_then(AspectRatio::parse_preset)
+			.map(|(_, size)| size);
 		let size =
 			if matches.occurrences_of("size") != 0 || matches.is_present("select") {
-				Some(Geometry::parse(
-					matches
-						.value_of("size")
-						.unwrap_or_default()
-						.split('+')
-						.collect::<Vec<&str>>()[0],
-				))
+				Some(match preset {
+					Some(size) if matches.occurrences_of("size") == 0 => size,
+					_ => Geometry::parse(
+						matches
+							.value_of("size")
+							.unwrap_or_default()
+							.split('+')
+							.collect::<Vec<&str>>()[0],
+					),
+				})
 			} else {
 				None
 			};
-		if matches.is_present("focus") && !matches.is_present("monitor") {
+		match matches.value_of("relative-to") {
+			Some("focus") => return Self::Focus(size, matches.is_present("parent")),
+			Some("root") => return Self::Root(size),
+			Some(id) => match Self::parse_id(id) {
+				Some(xid) => return Self::Id(xid, size),
+				None => warn!("Invalid window to be relative to: {:?}", id),
+			},
+			None => {}
+		}
+		if let Some(xid) = matches.value_of("window-id").and_then(|id| {
+			Self::parse_id(id).or_else(|| {
+				warn!("Invalid window ID: {:?}", id);
+				None
+			})
+		}) {
+			Self::Id(xid, size)
+		} else if matches.is_present("focus") && !matches.is_present("monitor") {
 			Self::Focus(size, matches.is_present("parent"))
 		} else if matches.is_present("root") || matches.is_present("monitor") {
 			Self::Root(size)
@@ -168,18 +665,74 @@ impl RecordWindow {
 			Self::Focus(Some(size.unwrap_or_default()), matches.is_present("parent"))
 		}
 	}
+
+	/**
+	 * Parse a window ID in hexadecimal (0x) or decimal notation.
+	 *
+	 * @param  id
+	 * @return u64 (Option)
+	 */
+	fn parse_id(id: &str) -> Option<u64> {
+		let id = id.trim().to_lowercase();
+		match id.strip_prefix("0x") {
+			Some(hex) => u64::from_str_radix(hex, 16).ok(),
+			None => id.parse().ok(),
+		}
+	}
+
+	/**
+	 * Validate a window ID argument.
+	 *
+	 * @param  id
+	 * @return Result
+	 */
+	pub fn validate_id(id: String) -> Result<(), String> {
+		match Self::parse_id(&id) {
+			Some(_) => Ok(()),
+			None => Err(format!(
+				"{id:?} is not a window ID (use decimal or 0x-prefixed hexadecimal)"
+			)),
+		}
+	}
+
+	/**
+	 * Validate the window argument that the area is relative to.
+	 *
+	 * @param  window
+	 * @return Result
+	 */
+	pub fn validate_relative(window: String) -> Result<(), String> {
+		match window.as_str() {
+			"focus" | "root" => Ok(()),
+			_ => Self::validate_id(window.clone()).map_err(|_| {
+				format!("{window:?} is not focus, root or a window ID")
+			}),
+		}
+	}
 }
 
+/* Default distance for snapping the selection to the window edges */
+const SNAP_THRESHOLD: u32 = 10;
+
 /* Recording and window settings */
 #[derive(Clone, Copy, Debug)]
 pub struct RecordSettings {
 	pub command: Option<&'static str>,
 	pub color: u64,
 	pub border: Option<u32>,
+	pub border_style: BorderStyle,
 	pub padding: Padding,
 	pub time: RecordTime,
 	pub flag: RecordFlag,
 	pub window: RecordWindow,
+	pub ratio: Option<AspectRatio>,
+	pub max_frames: usize,
+	pub min_free: Option<u64>,
+	pub exclude: RecordExclude,
+	pub canvas: Option<RecordCanvas>,
+	pub region: RecordRegion,
+	pub trail: Option<RecordTrail>,
+	pub snap: Option<u32>,
 }
 
 /* Default initialization values for RecordSettings */
@@ -189,10 +742,19 @@ impl Default for RecordSettings {
 			command: None,
 			color: 0x003A_A431,
 			border: Some(1),
+			border_style: BorderStyle::default(),
 			padding: Padding::default(),
 			time: RecordTime::default(),
 			flag: RecordFlag::default(),
 			window: RecordWindow::Focus(Some(Geometry::default()), false),
+			ratio: None,
+			max_frames: 1000,
+			min_free: None,
+			exclude: RecordExclude::default(),
+			canvas: None,
+			region: RecordRegion::default(),
+			trail: None,
+			snap: None,
 		}
 	}
 }
@@ -204,29 +766,57 @@ impl RecordSettings {
 	 * @param  command (Option)
 	 * @param  color
 	 * @param  border (Option)
+	 * @param  border_style
 	 * @param  padding
 	 * @param  time
 	 * @param  flag
 	 * @param  window
+	 * @param  ratio (Option)
+	 * @param  max_frames
+	 * @param  min_free (Option)
+	 * @param  exclude
+	 * @param  canvas (Option)
+	 * @param  region
+	 * @param  trail (Option)
+	 * @param  snap (Option)
 	 * @return RecordSettings
 	 */
+	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		command: Option<&'static str>,
 		color: u64,
 		border: Option<u32>,
+		border_style: BorderStyle,
 		padding: Padding,
 		time: RecordTime,
 		flag: RecordFlag,
 		window: RecordWindow,
+		ratio: Option<AspectRatio>,
+		max_frames: usize,
+		min_free: Option<u64>,
+		exclude: RecordExclude,
+		canvas: Option<RecordCanvas>,
+		region: RecordRegion,
+		trail: Option<RecordTrail>,
+		snap: Option<u32>,
 	) -> Self {
 		Self {
 			command,
 			color,
 			border,
+			border_style,
 			padding,
 			time,
 			flag,
 			window,
+			ratio,
+			max_frames,
+			min_free,
+			exclude,
+			canvas,
+			region,
+			trail,
+			snap,
 		}
 	}
 
@@ -269,10 +859,13 @@ impl RecordSettings {
 					border if border > 0 => Some(border),
 					_ => None,
 				},
+				BorderStyle::from_args(matches),
 				Self::parse_padding(matches),
 				RecordTime::from_parser(&parser),
 				RecordFlag::new(
 					matches.is_present("with-alpha"),
+					matches.is_present("opaque")
+						|| !matches.is_present("with-alpha"),
 					if matches.is_present("no-keys") {
 						None
 					} else {
@@ -299,13 +892,81 @@ impl RecordSettings {
 						true
 					},
 					matches.is_present("mouse"),
+					matches.is_present("preview"),
+					matches.is_present("on-keypress"),
+					matches.value_of("text-pos").and_then(TextPosition::parse),
+					match parser.parse("font-size", 0) {
+						0 => None,
+						size => Some(size),
+					},
+					matches.is_present("with-shadow"),
+					matches.is_present("loupe"),
+					matches.is_present("show-keys"),
+					!matches.is_present("no-sanity-check"),
+					matches.is_present("stitch"),
+					matches.is_present("scale-monitors"),
 				),
 				RecordWindow::from_args(matches),
-			),
+				matches
+					.value_of("select")
+					.and_then(AspectRatio::parse_preset)
+					.map(|(ratio, _)| ratio),
+				match parser.parse("max-frames", Self::default().max_frames) {
+					0 => usize::MAX,
+					max_frames => max_frames,
+				},
+				matches.value_of("min-free").and_then(|v| {
+					v.parse::<ByteSize>()
+						.map(|size| size.as_u64())
+						.map_err(|_| warn!("Invalid free space limit: {:?}", v))
+						.ok()
+				}),
+				RecordExclude::from_args(matches),
+				RecordCanvas::from_args(matches),
+				RecordRegion::from_args(matches),
+				RecordTrail::from_args(matches),
+				if matches.is_present("snap") {
+					Some(
+						matches
+							.value_of("snap")
+							.and_then(|v| v.parse().ok())
+							.unwrap_or(SNAP_THRESHOLD),
+					)
+				} else {
+					None
+				},
+			)
+			.with_region(&RegionStore::default()),
 			None => RecordSettings::default(),
 		}
 	}
 
+	/**
+	 * Use the saved region as the area to record instead of selecting.
+	 *
+	 * @param  store
+	 * @return RecordSettings
+	 */
+	fn with_region(mut self, store: &RegionStore) -> Self {
+		if let Some(region) = self.region.recall(store) {
+			debug!("Using the saved region: {}", RegionStore::format(&region));
+			self.window = RecordWindow::Root(Some(Geometry::new(
+				0,
+				0,
+				region.width,
+				region.height,
+			)));
+			self.padding = Padding::new(
+				region.y.try_into().unwrap_or_default(),
+				0,
+				0,
+				region.x.try_into().unwrap_or_default(),
+			);
+			self.flag.select = false;
+		}
+		self
+	}
+
 	/**
 	 * Parse the padding value from arguments.
 	 *
@@ -371,6 +1032,13 @@ mod tests {
 					.takes_value(true),
 			)
 			.arg(Arg::with_name("border").long("border").takes_value(true))
+			.arg(
+				Arg::with_name("border-style")
+					.long("border-style")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("corner-markers").long("corner-markers"))
+			.arg(Arg::with_name("dim").long("dim").takes_value(true))
 			.arg(Arg::with_name("padding").long("padding").takes_value(true))
 			.arg(Arg::with_name("size").long("size").takes_value(true))
 			.arg(
@@ -378,6 +1046,11 @@ mod tests {
 					.long("duration")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("replay-buffer")
+					.long("replay-buffer")
+					.takes_value(true),
+			)
 			.arg(
 				Arg::with_name("countdown")
 					.long("countdown")
@@ -389,10 +1062,77 @@ mod tests {
 					.long("interval")
 					.takes_value(true),
 			)
+			.arg(
+				Arg::with_name("wait-for-display")
+					.long("wait-for-display")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("max-frames")
+					.long("max-frames")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("min-free")
+					.long("min-free")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("exclude-window-id")
+					.long("exclude-window-id")
+					.multiple(true)
+					.number_of_values(1)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("exclude-mode")
+					.long("exclude-mode")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("text-pos")
+					.long("text-pos")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("font-size")
+					.long("font-size")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("cursor-trail")
+					.long("cursor-trail")
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("trail-fade")
+					.long("trail-fade")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("canvas").long("canvas").takes_value(true))
+			.arg(
+				Arg::with_name("background")
+					.long("background")
+					.takes_value(true),
+			)
 			.arg(Arg::with_name("root").long("root"))
 			.arg(Arg::with_name("focus").long("focus"))
 			.arg(Arg::with_name("with-alpha").long("with-alpha"))
 			.arg(Arg::with_name("no-keys").long("no-keys"))
+			.arg(
+				Arg::with_name("select")
+					.long("select")
+					.min_values(0)
+					.require_equals(true)
+					.takes_value(true),
+			)
+			.arg(
+				Arg::with_name("snap")
+					.long("snap")
+					.min_values(0)
+					.require_equals(true)
+					.takes_value(true),
+			)
 			.get_matches_from(vec![
 				"test",
 				"--action-keys",
@@ -401,35 +1141,190 @@ mod tests {
 				"X",
 				"--border",
 				"10",
+				"--border-style",
+				"dashed",
+				"--corner-markers",
+				"--dim",
+				"200",
 				"--padding",
 				"0:0:0:0",
 				"--size",
 				"10x10+10+10",
 				"--duration",
 				"1",
+				"--replay-buffer",
+				"7.5",
 				"--countdown",
 				"2",
 				"--timeout",
 				"300",
 				"--interval",
 				"12",
+				"--wait-for-display",
+				"5",
+				"--max-frames",
+				"100",
+				"--min-free",
+				"500MB",
+				"--exclude-window-id",
+				"0x1a,0x1b",
+				"--exclude-window-id",
+				"10",
+				"--exclude-mode",
+				"blur",
+				"--text-pos",
+				"br",
+				"--font-size",
+				"24",
+				"--cursor-trail",
+				"8",
+				"--trail-fade",
+				"0.5",
+				"--canvas",
+				"640x480",
+				"--background",
+				"#FF000080",
 				"--root",
 				"--with-alpha",
+				"--select=16:9@720",
+				"--snap=20",
 			]);
 		let record_settings =
 			RecordSettings::from_parser(ArgParser::from_args(&args), "000000");
 		assert_eq!(0x0000_0000, record_settings.color);
 		assert_eq!(10, record_settings.border.unwrap());
+		assert_eq!(
+			BorderStyle::new(LineStyle::Dashed, true, Some(200)),
+			record_settings.border_style
+		);
 		assert_eq!(Padding::new(10, 0, 0, 10), record_settings.padding);
+		assert_eq!(Some(7.5), record_settings.time.replay);
 		assert_eq!(2, record_settings.time.countdown);
 		assert_eq!(300, record_settings.time.timeout);
 		assert_eq!(12, record_settings.time.interval);
+		assert_eq!(5, record_settings.time.wait);
+		assert_eq!(100, record_settings.max_frames);
+		assert_eq!(1000, RecordSettings::default().max_frames);
+		assert_eq!(Some(500_000_000), record_settings.min_free);
+		assert_eq!(&[0x1a, 0x1b, 10], record_settings.exclude.windows);
+		assert_eq!(ExcludeMode::Blur, record_settings.exclude.mode);
+		assert_eq!(
+			Some(TextPosition::BottomRight),
+			record_settings.flag.text_position
+		);
+		assert_eq!(Some(24), record_settings.flag.font_size);
+		assert_eq!(Some(RecordTrail::new(8, 0.5)), record_settings.trail);
+		assert_eq!(Some(20), record_settings.snap);
+		let area = Geometry::new(10, 10, 100, 50);
+		assert_eq!(
+			(80, 35),
+			TextPosition::BottomRight.get_point(area, (20, 15), 10)
+		);
+		assert_eq!((50, 27), TextPosition::Center.get_point(area, (20, 15), 10));
+		assert_eq!(
+			(20, 10),
+			TextPosition::TopLeft.get_point(area, (20, 50), 10)
+		);
+		assert_eq!(None, TextPosition::parse("x"));
+		assert_eq!(
+			Some(RecordCanvas::new(
+				Geometry::new(0, 0, 640, 480),
+				Rgba([255, 0, 0, 128])
+			)),
+			record_settings.canvas
+		);
+		assert_eq!(None, RecordCanvas::parse_color("FFF"));
 		assert_eq!(
 			RecordWindow::Root(Some(Geometry::new(0, 0, 10, 10))),
 			record_settings.window
 		);
 		assert!(record_settings.flag.alpha);
+		assert!(!record_settings.flag.opaque);
+		assert!(RecordSettings::default().flag.opaque);
+		let args = App::new("test")
+			.arg(Arg::with_name("with-alpha").long("with-alpha"))
+			.get_matches_from(vec!["test"]);
+		let settings = RecordSettings::from_parser(ArgParser::from_args(&args), "");
+		assert!(!settings.flag.alpha);
+		assert!(settings.flag.opaque);
 		assert_eq!("LControl-Q,S", record_settings.flag.action_keys.unwrap());
 		assert_eq!("X", record_settings.flag.cancel_keys.unwrap());
+		assert_eq!(Some(AspectRatio::new(16, 9)), record_settings.ratio);
+		assert_eq!(Some(0x1c0_0003), RecordWindow::parse_id("0x1C00003"));
+		assert_eq!(Some(42), RecordWindow::parse_id("42"));
+		assert_eq!(None, RecordWindow::parse_id("0xZZ"));
+		assert!(RecordWindow::validate_id(String::from("0x1a")).is_ok());
+		assert!(RecordWindow::validate_id(String::from("zz")).is_err());
+		assert!(RecordWindow::validate_relative(String::from("root")).is_ok());
+		assert!(RecordWindow::validate_relative(String::from("42")).is_ok());
+		assert!(RecordWindow::validate_relative(String::from("fcous")).is_err());
+		let args = App::new("test")
+			.arg(
+				Arg::with_name("window-class")
+					.long("window-class")
+					.takes_value(true),
+			)
+			.arg(Arg::with_name("all-matching").long("all-matching"))
+			.get_matches_from(vec![
+				"test",
+				"--window-class",
+				"Firefox",
+				"--all-matching",
+			]);
+		assert_eq!(
+			RecordWindow::Class("Firefox", true),
+			RecordSettings::from_parser(ArgParser::from_args(&args), "").window
+		);
+		for (relative_to, window) in [
+			(
+				"focus",
+				RecordWindow::Focus(Some(Geometry::new(0, 0, 20, 10)), false),
+			),
+			(
+				"root",
+				RecordWindow::Root(Some(Geometry::new(0, 0, 20, 10))),
+			),
+			(
+				"0x1a",
+				RecordWindow::Id(0x1a, Some(Geometry::new(0, 0, 20, 10))),
+			),
+		] {
+			let args = App::new("test")
+				.arg(Arg::with_name("size").long("size").takes_value(true))
+				.arg(
+					Arg::with_name("relative-to")
+						.long("relative-to")
+						.takes_value(true),
+				)
+				.get_matches_from(vec![
+					"test",
+					"--size",
+					"20x10+5+8",
+					"--relative-to",
+					relative_to,
+				]);
+			let settings =
+				RecordSettings::from_parser(ArgParser::from_args(&args), "");
+			assert_eq!(window, settings.window);
+			assert_eq!(Padding::new(8, 0, 0, 5), settings.padding);
+			assert!(!settings.flag.select);
+		}
+		let path = std::env::temp_dir().join("menyoki_test_record_regions");
+		let store = RegionStore::new(path.clone());
+		store
+			.save("term", Geometry::new(20, 10, 640, 480))
+			.expect("Failed to save the region");
+		let mut settings = record_settings;
+		settings.region = RecordRegion::new(None, Some("term"));
+		let settings = settings.with_region(&store);
+		assert_eq!(
+			RecordWindow::Root(Some(Geometry::new(0, 0, 640, 480))),
+			settings.window
+		);
+		assert_eq!(Padding::new(10, 0, 0, 20), settings.padding);
+		assert!(!settings.flag.select);
+		assert!(RecordRegion::new(None, Some(REGION_LIST)).is_list());
+		assert_eq!(None, RecordRegion::new(None, Some("x")).recall(&store));
+		std::fs::remove_file(path).expect("Failed to remove the regions");
 	}
 }
@@ -0,0 +1,153 @@
+use crate::image::geometry::Geometry;
+use crate::image::padding::Padding;
+use std::cmp::Ordering;
+
+/* Snapping of the selection edges to the window edges */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EdgeSnap {
+	threshold: u32,
+	size: (u32, u32),
+	columns: Vec<u32>,
+	rows: Vec<u32>,
+}
+
+impl EdgeSnap {
+	/**
+	 * Create a new EdgeSnap object.
+	 *
+	 * @param  threshold
+	 * @param  size
+	 * @param  areas
+	 * @return EdgeSnap
+	 */
+	pub fn new(threshold: u32, size: Geometry, areas: &[Geometry]) -> Self {
+		let get_edges = |length: u32, edges: &mut dyn Iterator<Item = i64>| {
+			let mut edges = edges
+				.chain([0, i64::from(length)])
+				.filter_map(|edge| u32::try_from(edge).ok())
+				.filter(|edge| *edge <= length)
+				.collect::<Vec<u32>>();
+			edges.sort_unstable();
+			edges.dedup();
+			edges
+		};
+		Self {
+			threshold,
+			size: (size.width, size.height),
+			columns: get_edges(
+				size.width,
+				&mut areas.iter().flat_map(|area| {
+					[i64::from(area.x), i64::from(area.x) + i64::from(area.width)]
+				}),
+			),
+			rows: get_edges(
+				size.height,
+				&mut areas.iter().flat_map(|area| {
+					[
+						i64::from(area.y),
+						i64::from(area.y) + i64::from(area.height),
+					]
+				}),
+			),
+		}
+	}
+
+	/**
+	 * Move the edge to the closest edge ahead within the threshold.
+	 *
+	 * Edges behind the movement are ignored so that
+	 * the selection can always be moved away from an edge.
+	 *
+	 * @param  edges
+	 * @param  prev
+	 * @param  edge
+	 * @return u32
+	 */
+	fn snap_edge(&self, edges: &[u32], prev: u32, edge: u32) -> u32 {
+		edges
+			.iter()
+			.copied()
+			.filter(|target| match edge.cmp(&prev) {
+				Ordering::Greater => {
+					*target >= edge && target - edge <= self.threshold
+				}
+				Ordering::Less => *target <= edge && edge - target <= self.threshold,
+				Ordering::Equal => false,
+			})
+			.min_by_key(|target| target.abs_diff(edge))
+			.unwrap_or(edge)
+	}
+
+	/**
+	 * Snap the moved edges of the selection.
+	 *
+	 * @param  prev
+	 * @param  padding
+	 * @return Padding
+	 */
+	pub fn apply(&self, prev: Padding, padding: Padding) -> Padding {
+		let (width, height) = self.size;
+		let left = self.snap_edge(&self.columns, prev.left, padding.left);
+		let top = self.snap_edge(&self.rows, prev.top, padding.top);
+		let right = self.snap_edge(
+			&self.columns,
+			width.saturating_sub(prev.right),
+			width.saturating_sub(padding.right),
+		);
+		let bottom = self.snap_edge(
+			&self.rows,
+			height.saturating_sub(prev.bottom),
+			height.saturating_sub(padding.bottom),
+		);
+		if left < right && top < bottom {
+			Padding::new(top, width - right, height - bottom, left)
+		} else {
+			padding
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_edge_snap() {
+		let snap = EdgeSnap::new(
+			10,
+			Geometry::new(0, 0, 200, 100),
+			&[
+				Geometry::new(40, 20, 100, 50),
+				Geometry::new(-10, 90, 300, 30),
+			],
+		);
+		assert_eq!(vec![0, 40, 140, 200], snap.columns);
+		assert_eq!(vec![0, 20, 70, 90, 100], snap.rows);
+		let prev = Padding::new(0, 0, 0, 0);
+		assert_eq!(
+			Padding::new(20, 0, 30, 40),
+			snap.apply(prev, Padding::new(15, 0, 21, 33))
+		);
+		assert_eq!(
+			Padding::new(3, 60, 0, 3),
+			snap.apply(prev, Padding::new(3, 55, 0, 3))
+		);
+		let prev = Padding::new(20, 60, 0, 40);
+		assert_eq!(
+			Padding::new(17, 57, 0, 37),
+			snap.apply(prev, Padding::new(17, 57, 0, 37))
+		);
+		assert_eq!(
+			Padding::new(20, 60, 0, 60),
+			snap.apply(prev, Padding::new(20, 60, 0, 60))
+		);
+		assert_eq!(
+			Padding::new(20, 60, 0, 139),
+			snap.apply(prev, Padding::new(20, 60, 0, 139))
+		);
+		assert_eq!(
+			prev,
+			EdgeSnap::new(0, Geometry::default(), &[]).apply(prev, prev)
+		);
+	}
+}
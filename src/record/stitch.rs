@@ -0,0 +1,247 @@
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use crate::util::state::InputState;
+use crate::window::Capture;
+use image::{ExtendedColorType, Rgba};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/* Minimum ratio of the matching rows for accepting an overlap */
+const MIN_MATCH_RATIO: f32 = 0.9;
+/* Divisor of the frame height for the minimum overlapping rows */
+const MIN_OVERLAP_DIVISOR: usize = 8;
+/* Interval of checking the input state */
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/* Delay for the window to redraw after scrolling */
+const SCROLL_DELAY: Duration = Duration::from_millis(100);
+/* Maximum interval between the captures */
+const CAPTURE_INTERVAL: Duration = Duration::from_millis(500);
+
+/* Vertical stitcher for the frames of a scrolled window */
+#[derive(Debug, Default)]
+pub struct Stitcher {
+	data: Vec<u8>,
+	rows: Vec<Option<u64>>,
+	width: u32,
+	height: u32,
+	alpha_channel: bool,
+}
+
+impl Stitcher {
+	/**
+	 * Create a new Stitcher object.
+	 *
+	 * @param  alpha_channel
+	 * @return Stitcher
+	 */
+	pub fn new(alpha_channel: bool) -> Self {
+		Self {
+			alpha_channel,
+			..Self::default()
+		}
+	}
+
+	/**
+	 * Get the hashes of the rows, None for the rows with a single color.
+	 *
+	 * @param  data
+	 * @param  width
+	 * @return Vector of u64 (Option)
+	 */
+	fn get_rows(data: &[u8], width: u32) -> Vec<Option<u64>> {
+		data.chunks_exact(width as usize * 4)
+			.map(|row| {
+				if row.chunks_exact(4).all(|rgba| rgba == &row[..4]) {
+					None
+				} else {
+					let mut hasher = DefaultHasher::new();
+					row.hash(&mut hasher);
+					Some(hasher.finish())
+				}
+			})
+			.collect()
+	}
+
+	/**
+	 * Find the number of rows that the next frame is scrolled by.
+	 *
+	 * Offsets are checked from the largest overlap and
+	 * rows with a single color are not taken into account.
+	 *
+	 * @param  prev
+	 * @param  next
+	 * @return usize (Option)
+	 */
+	fn find_offset(prev: &[Option<u64>], next: &[Option<u64>]) -> Option<usize> {
+		let height = prev.len().min(next.len());
+		let min_overlap = (height / MIN_OVERLAP_DIVISOR).max(1);
+		let mut best: Option<(usize, f32)> = None;
+		for offset in 0..=height.saturating_sub(min_overlap) {
+			let (matches, total) = prev[offset..height].iter().zip(next).fold(
+				(0, 0),
+				|(matches, total), (prev, next)| match (prev, next) {
+					(None, None) => (matches, total),
+					(prev, next) => (matches + usize::from(prev == next), total + 1),
+				},
+			);
+			if total == 0 {
+				continue;
+			}
+			let ratio = matches as f32 / total as f32;
+			if ratio >= MIN_MATCH_RATIO
+				&& best.is_none_or(|(_, best_ratio)| ratio > best_ratio)
+			{
+				best = Some((offset, ratio));
+			}
+		}
+		best.map(|(offset, _)| offset)
+	}
+
+	/**
+	 * Add a frame to the bottom of the stitched image.
+	 *
+	 * @param  frame
+	 * @return Number of the added rows (Option)
+	 */
+	pub fn add(&mut self, frame: &Image) -> Option<u32> {
+		let data = frame.get_data(ExtendedColorType::Rgba8);
+		let rows = Self::get_rows(&data, frame.geometry.width);
+		if self.rows.is_empty() {
+			self.width = frame.geometry.width;
+			self.height = frame.geometry.height;
+			self.data = data;
+			self.rows = rows;
+			return Some(self.height);
+		} else if frame.geometry.width != self.width || rows.len() != self.rows.len()
+		{
+			return None;
+		}
+		let offset = Self::find_offset(&self.rows, &rows)?;
+		if offset != 0 {
+			let row_size = self.width as usize * 4;
+			self.data
+				.extend_from_slice(&data[(rows.len() - offset) * row_size..]);
+			self.height += offset as u32;
+			self.rows = rows;
+		}
+		Some(offset as u32)
+	}
+
+	/**
+	 * Capture the window while it is scrolled until the action keys are pressed.
+	 *
+	 * Frames are captured after the mouse wheel is scrolled
+	 * and periodically since the wheel events are short-lived.
+	 *
+	 * @param  window
+	 * @param  input_state
+	 * @return bool
+	 */
+	pub fn capture<Window: Capture>(
+		&mut self,
+		window: &Window,
+		input_state: &InputState,
+	) -> bool {
+		info!("Scroll the window and press the action keys to finish stitching.");
+		while input_state.check_action() {
+			thread::sleep(POLL_INTERVAL);
+		}
+		let mut last_capture = Instant::now();
+		let mut last_scroll = None;
+		while window.exists() {
+			if input_state.check_cancel_keys() {
+				warn!("User interrupt detected.");
+				return false;
+			} else if input_state.check_action() {
+				break;
+			} else if input_state.check_scroll() {
+				last_scroll = Some(Instant::now());
+			}
+			if last_scroll.is_some_and(|time| time.elapsed() >= SCROLL_DELAY)
+				|| last_capture.elapsed() >= CAPTURE_INTERVAL
+			{
+				match window.get_image().map(|frame| self.add(&frame)) {
+					Some(Some(0)) => {}
+					Some(Some(rows)) => {
+						debug!("Stitched {} rows ({}px)", rows, self.height)
+					}
+					_ => warn!("No overlap found with the previous capture."),
+				}
+				last_capture = Instant::now();
+				last_scroll = None;
+			}
+			thread::sleep(POLL_INTERVAL);
+		}
+		true
+	}
+
+	/**
+	 * Get the stitched image.
+	 *
+	 * @return Image (Option)
+	 */
+	pub fn get_image(&self) -> Option<Image> {
+		if self.rows.is_empty() {
+			None
+		} else {
+			Some(Image::new(
+				self.data
+					.chunks_exact(4)
+					.map(|rgba| Rgba([rgba[0], rgba[1], rgba[2], rgba[3]]))
+					.collect(),
+				self.alpha_channel,
+				Geometry::new(0, 0, self.width, self.height),
+			))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_stitcher() {
+		let page = (0..60u32)
+			.flat_map(|y| {
+				(0..4u32).map(move |x| {
+					if y % 10 == 0 {
+						Rgba([255, 255, 255, 255])
+					} else {
+						Rgba([(x * 50) as u8, (y * 4) as u8, (x * y) as u8, 255])
+					}
+				})
+			})
+			.collect::<Vec<Rgba<u8>>>();
+		let get_frame = |y: usize| {
+			Image::new(
+				page[y * 4..(y + 16) * 4].to_vec(),
+				false,
+				Geometry::new(0, 0, 4, 16),
+			)
+		};
+		let mut stitcher = Stitcher::new(false);
+		assert!(stitcher.get_image().is_none());
+		assert_eq!(Some(16), stitcher.add(&get_frame(0)));
+		assert_eq!(Some(0), stitcher.add(&get_frame(0)));
+		assert_eq!(Some(6), stitcher.add(&get_frame(6)));
+		assert_eq!(None, stitcher.add(&get_frame(40)));
+		assert_eq!(Some(14), stitcher.add(&get_frame(20)));
+		assert_eq!(Some(14), stitcher.add(&get_frame(34)));
+		assert_eq!(Some(10), stitcher.add(&get_frame(44)));
+		assert_eq!(None, stitcher.add(&get_frame(10)));
+		assert_eq!(
+			None,
+			stitcher.add(&Image::new(Vec::new(), false, Geometry::new(0, 0, 2, 2)))
+		);
+		let image = stitcher.get_image().unwrap();
+		assert_eq!(Geometry::new(0, 0, 4, 60), image.geometry);
+		assert_eq!(
+			Image::new(page, false, Geometry::new(0, 0, 4, 60))
+				.get_data(ExtendedColorType::Rgba8),
+			image.get_data(ExtendedColorType::Rgba8)
+		);
+	}
+}
@@ -0,0 +1,123 @@
+use crate::image::Image;
+use crate::record::settings::RecordTrail;
+use image::Rgba;
+use std::collections::VecDeque;
+
+/* Radius of the newest point of the trail */
+const TRAIL_RADIUS: f32 = 4.;
+
+/* Fading trail of the last pointer positions */
+#[derive(Debug)]
+pub struct CursorTrail {
+	settings: RecordTrail,
+	color: Rgba<u8>,
+	points: VecDeque<(i32, i32)>,
+}
+
+impl CursorTrail {
+	/**
+	 * Create a new CursorTrail object.
+	 *
+	 * @param  settings
+	 * @param  color
+	 * @return CursorTrail
+	 */
+	pub fn new(settings: RecordTrail, color: u64) -> Self {
+		Self {
+			settings,
+			color: Rgba([
+				(color >> 16) as u8,
+				(color >> 8) as u8,
+				color as u8,
+				u8::MAX,
+			]),
+			points: VecDeque::with_capacity(settings.length),
+		}
+	}
+
+	/**
+	 * Add the pointer position to the trail.
+	 *
+	 * @param point (Option)
+	 */
+	pub fn update(&mut self, point: Option<(i32, i32)>) {
+		if let Some(point) = point {
+			/* Shorten the trail while the pointer is not moving */
+			if (self.points.back() == Some(&point) && self.points.len() > 1)
+				|| self.points.len() >= self.settings.length
+			{
+				self.points.pop_front();
+			}
+			self.points.push_back(point);
+		} else {
+			self.points.pop_front();
+		}
+	}
+
+	/**
+	 * Draw the trail on the image from the oldest to the newest point.
+	 *
+	 * @param image
+	 */
+	pub fn draw(&self, image: &mut Image) {
+		let count = self.points.len();
+		if count < 2 {
+			return;
+		}
+		for (i, (from, to)) in self
+			.points
+			.iter()
+			.zip(self.points.iter().skip(1))
+			.enumerate()
+		{
+			let position = (i + 1) as f32 / (count - 1) as f32;
+			let opacity = 1. - self.settings.fade * (1. - position);
+			let radius = (TRAIL_RADIUS * (0.5 + position / 2.)).round() as u32;
+			let steps = (from.0 - to.0).abs().max((from.1 - to.1).abs()).max(1);
+			for step in 1..=steps {
+				let t = step as f32 / steps as f32;
+				image.draw_dot(
+					(
+						from.0 + ((to.0 - from.0) as f32 * t).round() as i32,
+						from.1 + ((to.1 - from.1) as f32 * t).round() as i32,
+					),
+					radius,
+					self.color,
+					opacity,
+				);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::image::geometry::Geometry;
+	use image::ExtendedColorType;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_cursor_trail() {
+		let mut trail = CursorTrail::new(RecordTrail::new(3, 1.), 0xFF_0000);
+		for x in [2, 10, 20, 30] {
+			trail.update(Some((x, 5)));
+		}
+		assert_eq!(
+			vec![(10, 5), (20, 5), (30, 5)],
+			Vec::from(trail.points.clone())
+		);
+		let mut image = Image::new(
+			vec![Rgba([0, 0, 0, 255]); 40 * 10],
+			false,
+			Geometry::new(0, 0, 40, 10),
+		);
+		trail.draw(&mut image);
+		let data = image.get_data(ExtendedColorType::Rgba8);
+		let pixel = |x: usize| data[(5 * 40 + x) * 4];
+		assert_eq!(255, pixel(30));
+		assert!(pixel(15) < pixel(25));
+		assert_eq!(0, pixel(2));
+		trail.update(None);
+		assert_eq!(2, trail.points.len());
+	}
+}
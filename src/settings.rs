@@ -1,11 +1,18 @@
-use crate::analyze::settings::AnalyzeSettings;
-use crate::anim::settings::{AnimSettings, SplitSettings};
+use crate::analyze::settings::{AnalyzeSettings, HistogramSettings};
+use crate::anim::settings::{
+	AnimSettings, DumpSettings, SplitSettings, SpritesheetSettings,
+};
 use crate::args::matches::ArgMatches;
+use crate::args::parser::ArgParser;
 use crate::edit::settings::EditSettings;
 use crate::file::format::FileFormat;
 use crate::file::settings::SaveSettings;
 use crate::image::geometry::Geometry;
-use crate::image::settings::{JpgSettings, PngSettings, PnmSettings, WebPSettings};
+use crate::image::settings::{
+	JpgSettings, PngSettings, PnmSettings, TiffSettings, WebPSettings,
+};
+use crate::montage::settings::MontageSettings;
+use crate::pick::settings::PickSettings;
 use crate::record::settings::{RecordSettings, RecordWindow};
 use crate::util::keys::{ActionKeys, KeyType};
 use crate::util::state::InputState;
@@ -19,13 +26,19 @@ pub struct AppSettings<'a> {
 	pub record: RecordSettings,
 	pub anim: AnimSettings,
 	pub split: SplitSettings,
+	pub spritesheet: SpritesheetSettings,
+	pub dump: DumpSettings,
 	pub png: PngSettings,
 	pub jpg: JpgSettings,
 	pub webp: WebPSettings,
+	pub tiff: TiffSettings,
 	pub pnm: PnmSettings,
 	pub edit: EditSettings,
+	pub montage: MontageSettings,
 	pub analyze: AnalyzeSettings,
+	pub histogram: HistogramSettings,
 	pub view: ViewSettings,
+	pub pick: PickSettings,
 	pub save: SaveSettings,
 	pub input_state: Option<&'static InputState>,
 	pub window_required: bool,
@@ -39,31 +52,74 @@ impl<'a> AppSettings<'a> {
 	 * @return AppSettings
 	 */
 	pub fn new(args: &'a ArgMatches<'a>) -> Self {
-		let window_required =
-			args.is_present("record") || args.is_present("capture");
 		let record = RecordSettings::from_args(args);
+		let window_required = (args.is_present("record")
+			|| args.is_present("capture")
+			|| args.is_present("pick"))
+			&& !matches!(record.window, RecordWindow::Synthetic(_));
 		let pnm = PnmSettings::from_args(args);
 		let edit = EditSettings::from_args(args);
 		let save = SaveSettings::from_args(args, &edit, &pnm);
-		let input_state = Self::get_input_state(window_required, &record);
-		Self {
+		let mut settings = Self {
 			args,
 			record,
 			anim: AnimSettings::from_args(args, &save.file.format),
-			split: SplitSettings::from_args(args),
+			split: SplitSettings::from_args(args, &pnm),
+			spritesheet: SpritesheetSettings::from_args(args),
+			dump: DumpSettings::from_args(args),
 			png: PngSettings::from_args(args),
 			jpg: JpgSettings::from_args(args),
 			webp: WebPSettings::from_args(args),
+			tiff: TiffSettings::from_args(args),
 			analyze: AnalyzeSettings::from_args(args, Self::get_color(args)),
+			histogram: HistogramSettings::from_args(args),
 			view: ViewSettings::from_args(args),
+			pick: PickSettings::from_args(args),
 			pnm,
 			edit,
+			montage: MontageSettings::from_args(args),
 			save,
-			input_state,
+			input_state: None,
 			window_required,
+		};
+		settings.apply_quality();
+		settings
+	}
+
+	/* Apply the global quality to the formats without an explicit quality. */
+	fn apply_quality(&mut self) {
+		let quality = match self
+			.args
+			.value_of("quality")
+			.and_then(|v| v.parse::<u8>().ok())
+		{
+			Some(quality) => quality.clamp(1, 100),
+			None => return,
+		};
+		debug!("Global quality: {}", quality);
+		if !self.is_quality_set("gif") {
+			self.anim.quality = quality;
+		}
+		if !self.is_quality_set("jpg") {
+			self.jpg.quality = quality;
+		}
+		if !self.is_quality_set("webp") {
+			self.webp = self.webp.with_quality(quality);
 		}
 	}
 
+	/**
+	 * Check if the quality of the given format is specified explicitly.
+	 *
+	 * @param  format
+	 * @return bool
+	 */
+	fn is_quality_set(&self, format: &str) -> bool {
+		ArgParser::from_subcommand(self.args, format)
+			.args
+			.is_some_and(|matches| matches.args.occurrences_of("quality") > 0)
+	}
+
 	/**
 	 * Get the main color from parsed arguments. (exposed)
 	 *
@@ -94,6 +150,11 @@ impl<'a> AppSettings<'a> {
 		}
 	}
 
+	/* Initialize the input state after the display is available. */
+	pub fn init_input_state(&mut self) {
+		self.input_state = Self::get_input_state(self.window_required, &self.record);
+	}
+
 	/**
 	 * Get InputState if a window is required.
 	 *
@@ -133,7 +194,7 @@ impl<'a> AppSettings<'a> {
 		if self.jpg.quality <= 25 {
 			warn!("Image will be encoded in low quality.")
 		}
-		if self.anim.quality <= 20 {
+		if self.anim.quality <= 20 && self.save.file.format.is_animation() {
 			warn!("Animation will be encoded in low quality.")
 		}
 		if self.record.time.countdown > 99 {
@@ -144,23 +205,29 @@ impl<'a> AppSettings<'a> {
 			if self.record.flag.action_keys
 				!= Some(&ActionKeys::default(KeyType::ActionKeys).to_string())
 			{
-				info!(
-					"Using custom action keys: {}",
-					input_state.action_keys.to_string()
-				);
+				info!("Using custom action keys: {}", input_state.action_keys);
 			}
 			if self.record.flag.cancel_keys
 				!= Some(&ActionKeys::default(KeyType::CancelKeys).to_string())
 			{
-				info!(
-					"Using custom cancel keys: {}",
-					input_state.cancel_keys.to_string()
-				);
+				info!("Using custom cancel keys: {}", input_state.cancel_keys);
 			}
 		}
 		if !self.record.flag.select {
 			self.record.border = None;
 		}
+		if self.record.border_style.dim.is_some() && self.record.flag.mouse {
+			warn!("Dimming is not supported with mouse selection.");
+			self.record.border_style.dim = None;
+		}
+		if let RecordWindow::Class(class, true) = self.record.window {
+			if !self.args.is_present("capture") {
+				warn!(
+					"Capturing all matching windows is only supported with capture."
+				);
+				self.record.window = RecordWindow::Class(class, false);
+			}
+		}
 		if self.save.file.format == FileFormat::Ico {
 			self.set_icon_size()
 		}
@@ -177,7 +244,8 @@ impl<'a> AppSettings<'a> {
 				self.record.window = RecordWindow::Root(Some(ico_geometry))
 			}
 			RecordWindow::Focus(Some(ref mut geometry), _)
-			| RecordWindow::Root(Some(ref mut geometry)) => {
+			| RecordWindow::Root(Some(ref mut geometry))
+			| RecordWindow::Synthetic(ref mut geometry) => {
 				if geometry.width == 0 || geometry.width > ico_geometry.width {
 					geometry.width = ico_geometry.width;
 				}
@@ -185,6 +253,7 @@ impl<'a> AppSettings<'a> {
 					geometry.height = ico_geometry.height;
 				}
 			}
+			RecordWindow::Id(..) | RecordWindow::Class(..) => {}
 		}
 	}
 }
@@ -192,7 +261,7 @@ impl<'a> AppSettings<'a> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use clap::ArgMatches as Args;
+	use clap::{App, Arg, ArgMatches as Args, SubCommand};
 	use pretty_assertions::assert_eq;
 	use std::env;
 	#[test]
@@ -215,4 +284,36 @@ mod tests {
 			RecordWindow::Focus(Some(Geometry::default()), false);
 		settings.check();
 	}
+	#[test]
+	fn test_global_quality() {
+		let quality_arg = || {
+			Arg::with_name("quality")
+				.long("quality")
+				.default_value("90")
+				.takes_value(true)
+		};
+		let args = App::new("test")
+			.arg(Arg::with_name("quality").long("quality").takes_value(true))
+			.subcommand(SubCommand::with_name("jpg").arg(quality_arg()))
+			.get_matches_from(vec!["test", "--quality", "60", "jpg"]);
+		let matches = ArgMatches::new(&args);
+		let settings = AppSettings::new(&matches);
+		assert_eq!(60, settings.jpg.quality);
+		assert_eq!(60, settings.anim.quality);
+		let args = App::new("test")
+			.arg(Arg::with_name("quality").long("quality").takes_value(true))
+			.subcommand(SubCommand::with_name("jpg").arg(quality_arg()))
+			.get_matches_from(vec![
+				"test",
+				"--quality",
+				"200",
+				"jpg",
+				"--quality",
+				"95",
+			]);
+		let matches = ArgMatches::new(&args);
+		let settings = AppSettings::new(&matches);
+		assert_eq!(95, settings.jpg.quality);
+		assert_eq!(100, settings.anim.quality);
+	}
 }
@@ -1,5 +1,5 @@
 use std::io::Error;
-use std::process::Command as OsCommand;
+use std::process::{Command as OsCommand, ExitStatus};
 
 /* The command and its arguments */
 #[derive(Debug)]
@@ -34,18 +34,27 @@ impl<'a> Command<'a> {
 	/**
 	 * Execute the command and wait for it to exit.
 	 *
-	 * @return Result
+	 * @return ExitStatus (Result)
 	 */
-	pub fn execute(&self) -> Result<(), Error> {
+	pub fn execute(&self) -> Result<ExitStatus, Error> {
 		info!("Running the command...");
 		match OsCommand::new(self.cmd).args(&self.args).spawn() {
-			Ok(mut child) => {
-				child.wait()?;
-				Ok(())
-			}
+			Ok(mut child) => child.wait(),
 			Err(e) => Err(e),
 		}
 	}
+
+	/**
+	 * Start the command without waiting for it to exit.
+	 *
+	 * @return Result
+	 */
+	pub fn spawn(&self) -> Result<(), Error> {
+		OsCommand::new(self.cmd)
+			.args(&self.args)
+			.spawn()
+			.map(|_| ())
+	}
 }
 
 #[cfg(test)]
@@ -56,9 +65,14 @@ mod tests {
 	fn test_command() -> Result<(), Error> {
 		let sleep_time = Duration::from_millis(10);
 		let now = Instant::now();
-		Command::new("sleep", vec!["0.01"]).execute()?;
+		assert!(Command::new("sleep", vec!["0.01"]).execute()?.success());
 		assert!(now.elapsed() >= sleep_time);
+		assert!(!Command::from("exit 3").execute()?.success());
 		assert!(Command::from("xyz").execute().is_err());
+		let now = Instant::now();
+		Command::new("sleep", vec!["1"]).spawn()?;
+		assert!(now.elapsed() < Duration::from_secs(1));
+		assert!(Command::from("xyz").spawn().is_err());
 		Ok(())
 	}
 }
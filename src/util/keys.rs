@@ -1,7 +1,21 @@
+use crate::image::geometry::Geometry;
 use device_query::Keycode;
 use std::fmt;
 use std::str::FromStr;
 
+/* Maximum number of characters to accept as the typed size */
+const SIZE_INPUT_MAX_LEN: usize = 11;
+/* Modifier keys that disable the size input */
+const MODIFIER_KEYS: &[Keycode] = &[
+	Keycode::LAlt,
+	Keycode::RAlt,
+	Keycode::LControl,
+	Keycode::RControl,
+	Keycode::LShift,
+	Keycode::RShift,
+	Keycode::Meta,
+];
+
 /* Types of key bindings. */
 #[derive(Debug)]
 pub enum KeyType {
@@ -80,7 +94,7 @@ impl ActionKeys {
 	pub fn get_primary(&self) -> Vec<&Keycode> {
 		self.key_groups
 			.iter()
-			.filter_map(|keys| keys.get(0))
+			.filter_map(|keys| keys.first())
 			.collect()
 	}
 
@@ -128,6 +142,119 @@ impl ActionKeys {
 	}
 }
 
+/* Typed size of the selected area */
+#[derive(Debug, Default)]
+pub struct SizeInput {
+	value: String,
+	size: Option<Geometry>,
+	prev_keys: Vec<Keycode>,
+}
+
+/* Display implementation for user-facing output */
+impl fmt::Display for SizeInput {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.value)
+	}
+}
+
+impl SizeInput {
+	/**
+	 * Get the keys to type the size with.
+	 *
+	 * @return Vector of Keycode
+	 */
+	pub fn get_keys() -> Vec<Keycode> {
+		vec![
+			Keycode::Key0,
+			Keycode::Key1,
+			Keycode::Key2,
+			Keycode::Key3,
+			Keycode::Key4,
+			Keycode::Key5,
+			Keycode::Key6,
+			Keycode::Key7,
+			Keycode::Key8,
+			Keycode::Key9,
+			Keycode::X,
+		]
+	}
+
+	/**
+	 * Update the typed value with the newly pressed keys.
+	 *
+	 * @param  keys
+	 * @return bool
+	 */
+	pub fn update(&mut self, keys: Vec<Keycode>) -> bool {
+		let prev_value = self.value.clone();
+		if !keys.iter().any(|key| MODIFIER_KEYS.contains(key)) {
+			for key in keys.iter().filter(|key| !self.prev_keys.contains(key)) {
+				match key {
+					Keycode::X => {
+						if !self.value.is_empty() && !self.value.contains('x') {
+							self.value.push('x');
+						}
+					}
+					Keycode::Backspace => {
+						self.value.pop();
+					}
+					Keycode::Enter => {
+						self.size = self.parse();
+						self.value.clear();
+					}
+					key => {
+						let key = format!("{key:?}");
+						if let Some(digit) = key
+							.strip_prefix("Key")
+							.or_else(|| key.strip_prefix("Numpad"))
+							.filter(|v| v.len() == 1)
+						{
+							if self.value.len() < SIZE_INPUT_MAX_LEN {
+								self.value.push_str(digit);
+							}
+						}
+					}
+				}
+			}
+		}
+		self.prev_keys = keys;
+		self.value != prev_value || self.size.is_some()
+	}
+
+	/**
+	 * Take the committed size.
+	 *
+	 * @return Geometry (Option)
+	 */
+	pub fn take_size(&mut self) -> Option<Geometry> {
+		self.size.take()
+	}
+
+	/**
+	 * Check if there is no typed value.
+	 *
+	 * @return bool
+	 */
+	pub fn is_empty(&self) -> bool {
+		self.value.is_empty()
+	}
+
+	/**
+	 * Parse the typed value as size.
+	 *
+	 * @return Geometry (Option)
+	 */
+	fn parse(&self) -> Option<Geometry> {
+		let (width, height) = self.value.split_once('x')?;
+		match (width.parse(), height.parse()) {
+			(Ok(width), Ok(height)) if width != 0 && height != 0 => {
+				Some(Geometry::new(0, 0, width, height))
+			}
+			_ => None,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -176,4 +303,35 @@ mod tests {
 			ActionKeys::parse("A-B,C-D,...", KeyType::ActionKeys).get_primary()
 		);
 	}
+	#[test]
+	fn test_size_input() {
+		let mut input = SizeInput::default();
+		for keys in [
+			vec![Keycode::X],
+			vec![Keycode::Key6],
+			vec![Keycode::Key6, Keycode::Key4],
+			vec![],
+			vec![Keycode::Numpad0],
+			vec![Keycode::LAlt, Keycode::Key5],
+			vec![Keycode::X],
+			vec![],
+			vec![Keycode::X],
+			vec![Keycode::Key4],
+			vec![Keycode::Key8],
+			vec![Keycode::Key9],
+			vec![Keycode::Backspace],
+		] {
+			input.update(keys);
+		}
+		assert_eq!("640x48", input.to_string());
+		assert_eq!(None, input.take_size());
+		assert!(input.update(vec![Keycode::Enter]));
+		assert!(input.is_empty());
+		assert_eq!(Some(Geometry::new(0, 0, 640, 48)), input.take_size());
+		assert_eq!(None, input.take_size());
+		input.update(vec![Keycode::Key1]);
+		input.update(vec![Keycode::Enter]);
+		assert_eq!(None, input.take_size());
+		assert!(!input.update(vec![Keycode::Enter]));
+	}
 }
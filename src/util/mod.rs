@@ -1,4 +1,6 @@
 pub mod command;
 pub mod keys;
 pub mod logger;
+pub mod rng;
 pub mod state;
+pub mod summary;
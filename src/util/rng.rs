@@ -0,0 +1,69 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/* Pseudo-random number generator (xorshift64*) */
+#[derive(Clone, Copy, Debug)]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	/**
+	 * Create a new Rng object.
+	 *
+	 * @param  seed (Option)
+	 * @return Rng
+	 */
+	pub fn new(seed: Option<u64>) -> Self {
+		let seed = seed.unwrap_or_else(|| {
+			SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|v| v.as_nanos() as u64)
+				.unwrap_or_default()
+		});
+		Self {
+			state: seed ^ 0x9E37_79B9_7F4A_7C15,
+		}
+	}
+
+	/**
+	 * Get the next random number.
+	 *
+	 * @return u64
+	 */
+	pub fn next_u64(&mut self) -> u64 {
+		if self.state == 0 {
+			self.state = 0x9E37_79B9_7F4A_7C15;
+		}
+		self.state ^= self.state >> 12;
+		self.state ^= self.state << 25;
+		self.state ^= self.state >> 27;
+		self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+	}
+
+	/**
+	 * Get a random number in the range of [-amount, amount].
+	 *
+	 * @param  amount
+	 * @return i32
+	 */
+	pub fn next_offset(&mut self, amount: u8) -> i32 {
+		let range = u64::from(amount) * 2 + 1;
+		(self.next_u64() % range) as i32 - i32::from(amount)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::{assert_eq, assert_ne};
+	#[test]
+	fn test_rng() {
+		let (mut rng1, mut rng2) = (Rng::new(Some(7)), Rng::new(Some(7)));
+		assert_eq!(rng1.next_u64(), rng2.next_u64());
+		assert_ne!(rng1.next_u64(), Rng::new(Some(8)).next_u64());
+		for _ in 0..100 {
+			assert!((-5..=5).contains(&rng1.next_offset(5)));
+		}
+		assert_eq!(0, rng2.next_offset(0));
+	}
+}
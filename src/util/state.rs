@@ -79,6 +79,17 @@ impl InputState {
 		}
 	}
 
+	/**
+	 * Check if the mouse wheel is scrolled.
+	 *
+	 * @return bool
+	 */
+	pub fn check_scroll(&self) -> bool {
+		let buttons = self.state.get_mouse().button_pressed;
+		/* Buttons 4 and 5 are the wheel up and down events */
+		buttons.get(4) == Some(&true) || buttons.get(5) == Some(&true)
+	}
+
 	/**
 	 * Check if the cancel keys are pressed.
 	 *
@@ -98,6 +109,7 @@ mod tests {
 		let input_state = InputState::default().into_boxed_state();
 		assert!(!input_state.check_action());
 		assert!(!input_state.check_cancel_keys());
+		assert!(!input_state.check_scroll());
 		assert!(format!("{:?}", input_state).len() > 0);
 	}
 }
@@ -0,0 +1,75 @@
+use std::fmt;
+
+/* Summary of a run to show at the end */
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+	rows: Vec<(&'static str, String)>,
+}
+
+impl Summary {
+	/**
+	 * Create a new Summary object.
+	 *
+	 * @return Summary
+	 */
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/**
+	 * Add a row to the summary.
+	 *
+	 * @param key
+	 * @param value
+	 */
+	pub fn add<Value: ToString>(&mut self, key: &'static str, value: Value) {
+		self.rows.push((key, value.to_string()));
+	}
+}
+
+/* Display implementation for an aligned table */
+impl fmt::Display for Summary {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let key_width = self.rows.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+		let value_width = self
+			.rows
+			.iter()
+			.map(|(_, v)| v.chars().count())
+			.max()
+			.unwrap_or(0);
+		let border = format!(
+			"+{}+{}+",
+			"-".repeat(key_width + 2),
+			"-".repeat(value_width + 2)
+		);
+		writeln!(f, "{border}")?;
+		for (key, value) in &self.rows {
+			writeln!(
+				f,
+				"| {key:<key_width$} | {value:<value_width$} |",
+				key = key,
+				value = value,
+			)?;
+		}
+		write!(f, "{border}")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_summary() {
+		let mut summary = Summary::new();
+		summary.add("Command", "record");
+		summary.add("Frames", 42);
+		assert_eq!(
+			"+---------+--------+\n\
+			 | Command | record |\n\
+			 | Frames  | 42     |\n\
+			 +---------+--------+",
+			summary.to_string()
+		);
+	}
+}
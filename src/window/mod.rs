@@ -1,3 +1,4 @@
+pub mod synthetic;
 #[cfg(test)]
 pub mod test;
 
@@ -11,11 +12,16 @@ pub trait Access<'a, Window: Capture + Send + Sync + Copy + Debug + 'static> {
 	where
 		Self: Sized;
 	fn get_window(&mut self) -> Option<Window>;
+	fn get_windows(&mut self) -> Vec<Window>;
 }
 
 /* Window methods for capturing an image */
 pub trait Capture {
 	fn get_image(&self) -> Option<Image>;
 	fn show_countdown(&self);
+	fn preview(&self, images: &[&Image]) -> bool;
 	fn release(&self);
+	fn get_title(&self) -> Option<String>;
+	fn get_pointer(&self) -> Option<(i32, i32)>;
+	fn exists(&self) -> bool;
 }
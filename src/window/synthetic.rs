@@ -0,0 +1,118 @@
+use crate::image::geometry::Geometry;
+use crate::image::Image;
+use crate::window::Capture;
+use image::Rgba;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/* Number of frames generated by the synthetic windows */
+static FRAME_COUNT: AtomicU32 = AtomicU32::new(0);
+
+/* Synthetic window for capturing without a display */
+#[derive(Clone, Copy, Debug)]
+pub struct SyntheticWindow {
+	pub geometry: Geometry,
+}
+
+impl SyntheticWindow {
+	/**
+	 * Create a new SyntheticWindow object.
+	 *
+	 * @param  geometry
+	 * @return SyntheticWindow
+	 */
+	pub fn new(geometry: Geometry) -> Self {
+		Self {
+			geometry: Geometry::new(0, 0, geometry.width, geometry.height),
+		}
+	}
+
+	/**
+	 * Generate the pixels of the given frame.
+	 *
+	 * @param  frame
+	 * @return Vector of Rgba
+	 */
+	fn get_pixels(&self, frame: u32) -> Vec<Rgba<u8>> {
+		let (width, height) = (self.geometry.width, self.geometry.height);
+		let size = (width.min(height) / 4).max(1);
+		let (square_x, square_y) = (
+			frame.wrapping_mul(3) % width.max(1),
+			frame.wrapping_mul(2) % height.max(1),
+		);
+		(0..height)
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(|(x, y)| {
+				if x.wrapping_sub(square_x) < size && y.wrapping_sub(square_y) < size
+				{
+					Rgba([255, 255, 255, 255])
+				} else {
+					Rgba([
+						x.wrapping_add(frame) as u8,
+						y.wrapping_add(frame.wrapping_mul(2)) as u8,
+						(x ^ y).wrapping_sub(frame) as u8,
+						255,
+					])
+				}
+			})
+			.collect()
+	}
+}
+
+/* Capture implementation for SyntheticWindow */
+impl Capture for SyntheticWindow {
+	/**
+	 * Get the next generated image.
+	 *
+	 * @return Image (Option)
+	 */
+	fn get_image(&self) -> Option<Image> {
+		let frame = FRAME_COUNT.fetch_add(1, Ordering::Relaxed);
+		Some(Image::new(self.get_pixels(frame), false, self.geometry))
+	}
+
+	/* Do not show countdown for synthetic window. */
+	fn show_countdown(&self) {}
+
+	/* Accept the images without preview for synthetic window. */
+	fn preview(&self, _images: &[&Image]) -> bool {
+		true
+	}
+
+	/* Do not do anything with respect to release. */
+	fn release(&self) {}
+
+	/* Synthetic window does not have a title. */
+	fn get_title(&self) -> Option<String> {
+		None
+	}
+
+	/* Return the center of the synthetic window as the pointer position. */
+	fn get_pointer(&self) -> Option<(i32, i32)> {
+		Some((
+			i32::try_from(self.geometry.width / 2).unwrap_or_default(),
+			i32::try_from(self.geometry.height / 2).unwrap_or_default(),
+		))
+	}
+
+	/* Synthetic window always exists. */
+	fn exists(&self) -> bool {
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::{assert_eq, assert_ne};
+	#[test]
+	fn test_synthetic_window() {
+		let window = SyntheticWindow::new(Geometry::new(5, 5, 8, 4));
+		assert_eq!(Geometry::new(0, 0, 8, 4), window.geometry);
+		assert_eq!(32, window.get_pixels(0).len());
+		assert_eq!(window.get_pixels(1), window.get_pixels(1));
+		assert_ne!(window.get_pixels(1), window.get_pixels(2));
+		let image = window.get_image().unwrap();
+		assert_eq!(window.geometry, image.geometry);
+		assert_eq!(Some((4, 2)), window.get_pointer());
+	}
+}
@@ -46,6 +46,29 @@ impl Capture for TestWindow {
 	/* Do not show countdown for testing window. */
 	fn show_countdown(&self) {}
 
+	/* Accept the images without preview for testing window. */
+	fn preview(&self, _images: &[&Image]) -> bool {
+		true
+	}
+
 	/* Do not do anything with respect to release. */
 	fn release(&self) {}
+
+	/* Testing window does not have a title. */
+	fn get_title(&self) -> Option<String> {
+		None
+	}
+
+	/* Return the center of the testing window as the pointer position. */
+	fn get_pointer(&self) -> Option<(i32, i32)> {
+		Some((
+			i32::try_from(self.geometry.width / 2).unwrap_or_default(),
+			i32::try_from(self.geometry.height / 2).unwrap_or_default(),
+		))
+	}
+
+	/* Testing window always exists. */
+	fn exists(&self) -> bool {
+		true
+	}
 }
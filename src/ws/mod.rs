@@ -1,5 +1,6 @@
 pub mod window;
 
+use crate::record::settings::RecordSettings;
 use crate::settings::AppSettings;
 use crate::window::Access;
 use crate::ws::window::Window;
@@ -7,6 +8,18 @@ use crate::ws::window::Window;
 /* Window system implementation */
 pub struct WindowSystem {}
 
+impl WindowSystem {
+	/**
+	 * Wait for the display to be ready until the timeout.
+	 *
+	 * @param  settings
+	 * @return bool
+	 */
+	pub fn wait_for_display(_settings: &RecordSettings) -> bool {
+		unimplemented!()
+	}
+}
+
 impl<'a> Access<'a, Window> for WindowSystem {
 	/**
 	 * Initialize the window system.
@@ -26,4 +39,13 @@ impl<'a> Access<'a, Window> for WindowSystem {
 	fn get_window(&mut self) -> Option<Window> {
 		unimplemented!()
 	}
+
+	/**
+	 * Get all the windows to capture.
+	 *
+	 * @return Vector of Window
+	 */
+	fn get_windows(&mut self) -> Vec<Window> {
+		unimplemented!()
+	}
 }
@@ -33,8 +33,45 @@ impl Capture for Window {
 		unimplemented!()
 	}
 
+	/**
+	 * Preview the images and wait for confirmation.
+	 *
+	 * @param  images
+	 * @return bool
+	 */
+	fn preview(&self, _images: &[&Image]) -> bool {
+		unimplemented!()
+	}
+
 	/* Release the window. */
 	fn release(&self) {
 		unimplemented!()
 	}
+
+	/**
+	 * Get the title of the window.
+	 *
+	 * @return String (Option)
+	 */
+	fn get_title(&self) -> Option<String> {
+		unimplemented!()
+	}
+
+	/**
+	 * Get the pointer position relative to the window.
+	 *
+	 * @return Tuple (Option)
+	 */
+	fn get_pointer(&self) -> Option<(i32, i32)> {
+		unimplemented!()
+	}
+
+	/**
+	 * Check if the window still exists.
+	 *
+	 * @return bool
+	 */
+	fn exists(&self) -> bool {
+		unimplemented!()
+	}
 }
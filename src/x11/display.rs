@@ -1,17 +1,26 @@
 use crate::image::geometry::Geometry;
+use crate::image::monitor::{Monitor, MonitorLayout};
+use crate::image::padding::Padding;
 use crate::record::fps::FpsClock;
 use crate::record::settings::{RecordSettings, RecordWindow};
+use crate::record::snap::EdgeSnap;
+use crate::util::keys::SizeInput;
 use crate::util::state::InputState;
+use crate::x11::loupe::Loupe;
+use crate::x11::overlay::Overlay;
 use crate::x11::window::Window;
 use device_query::{DeviceQuery, Keycode};
 use std::ffi::CString;
 use std::io::{self, Write};
 use std::mem::MaybeUninit;
-use std::os::raw::{c_int, c_ulong};
+use std::os::raw::{c_char, c_int, c_ulong, c_void};
 use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 use std::thread;
 use std::time::{Duration, Instant};
-use x11::xlib;
+use x11::{xlib, xrandr};
 
 /* Constant for changing the area size */
 const AREA_CHANGE_FACTOR: u32 = 3;
@@ -19,8 +28,18 @@ const AREA_CHANGE_FACTOR: u32 = 3;
 const AREA_MAX_WIDTH: u32 = 10;
 /* Maximum height of the selected area */
 const AREA_MAX_HEIGHT: u32 = 10;
+/* Initial delay between the attempts of opening the display */
+const OPEN_RETRY_DELAY: u64 = 100;
+/* Maximum delay between the attempts of opening the display */
+const OPEN_MAX_RETRY_DELAY: u64 = 2000;
 /* Default miscellaneous font */
 const DEFAULT_FONT: &str = "-misc-fixed-*-*-*-*-*-*-*-*-*-*-*-*";
+/* Instance name of the created windows (WM_CLASS) */
+const WINDOW_NAME: &str = "menyoki";
+/* Class name of the created windows (WM_CLASS) */
+const WINDOW_CLASS: &str = "Menyoki";
+/* Millimeters in an inch */
+const MM_PER_INCH: f32 = 25.4;
 
 /* X11 display */
 #[derive(Clone, Copy, Debug)]
@@ -28,6 +47,8 @@ pub struct Display {
 	pub inner: *mut xlib::Display,
 	pub font: Option<*mut xlib::XFontStruct>,
 	pub settings: RecordSettings,
+	closed: &'static AtomicBool,
+	monitor_layout: &'static OnceLock<Option<MonitorLayout>>,
 }
 
 /* Implementation for thread-safe usage */
@@ -41,13 +62,27 @@ impl Display {
 	 * @return Display  (Option)
 	 */
 	pub fn open(settings: Option<RecordSettings>) -> Option<Self> {
-		let display = unsafe { xlib::XOpenDisplay(ptr::null()) };
+		let timeout = Duration::from_secs(settings.unwrap_or_default().time.wait);
+		let start_time = Instant::now();
+		let mut delay = OPEN_RETRY_DELAY;
+		let mut display = unsafe { xlib::XOpenDisplay(ptr::null()) };
+		while display.is_null() && start_time.elapsed() < timeout {
+			debug!("Waiting for the display... ({}ms)", delay);
+			thread::sleep(
+				Duration::from_millis(delay)
+					.min(timeout.saturating_sub(start_time.elapsed())),
+			);
+			delay = (delay * 2).min(OPEN_MAX_RETRY_DELAY);
+			display = unsafe { xlib::XOpenDisplay(ptr::null()) };
+		}
 		if !display.is_null() {
 			Some(
 				Self {
 					inner: display,
 					font: None,
 					settings: settings.unwrap_or_default(),
+					closed: Box::leak(Box::new(AtomicBool::new(false))),
+					monitor_layout: Box::leak(Box::new(OnceLock::new())),
 				}
 				.set_font(),
 			)
@@ -62,8 +97,16 @@ impl Display {
 	 * @return Display
 	 */
 	fn set_font(&mut self) -> Self {
-		if let Some(description) = self.settings.flag.font {
-			let desc = CString::new(description).expect("Failed to create CString");
+		let description =
+			match (self.settings.flag.font, self.settings.flag.font_size) {
+				(Some(font), Some(size)) => Some(Self::get_sized_font(font, size)),
+				(Some(font), None) => Some(font.to_string()),
+				(None, Some(size)) => Some(Self::get_sized_font(DEFAULT_FONT, size)),
+				(None, None) => None,
+			};
+		if let Some(description) = description {
+			let desc = CString::new(description.as_str())
+				.expect("Failed to create CString");
 			let font = unsafe { xlib::XLoadQueryFont(self.inner, desc.as_ptr()) };
 			if !font.is_null() {
 				self.font = Some(font);
@@ -74,6 +117,77 @@ impl Display {
 		*self
 	}
 
+	/**
+	 * Set the pixel size field of the given font description (XLFD).
+	 *
+	 * @param  description
+	 * @param  size
+	 * @return String
+	 */
+	fn get_sized_font(description: &str, size: u32) -> String {
+		let mut fields = description.split('-').collect::<Vec<&str>>();
+		let size = size.to_string();
+		if fields.len() == 15 {
+			fields[7] = &size;
+			fields.join("-")
+		} else {
+			warn!("Cannot set the size of the font: {}", description);
+			description.to_string()
+		}
+	}
+
+	/**
+	 * Free the loaded font and close the display.
+	 *
+	 * Display is copied into every Window, so the connection cannot be
+	 * closed on drop and needs to be released explicitly instead.
+	 * The closed state is shared between the copies and only the
+	 * first call closes the connection.
+	 */
+	pub fn close(&self) {
+		if self.closed.swap(true, Ordering::SeqCst) {
+			trace!("Display is already closed.");
+			return;
+		}
+		unsafe {
+			if let Some(font) = self.font {
+				xlib::XFreeFont(self.inner, font);
+			}
+			xlib::XCloseDisplay(self.inner);
+		}
+		trace!("Display closed.");
+	}
+
+	/**
+	 * Check if the display connection is closed.
+	 *
+	 * @return bool
+	 */
+	pub fn is_closed(&self) -> bool {
+		self.closed.load(Ordering::SeqCst)
+	}
+
+	/**
+	 * Check if a compositing manager is running on the default screen.
+	 *
+	 * @return bool
+	 */
+	pub fn has_compositor(&self) -> bool {
+		unsafe {
+			let atom = xlib::XInternAtom(
+				self.inner,
+				CString::new(format!(
+					"_NET_WM_CM_S{}",
+					xlib::XDefaultScreen(self.inner)
+				))
+				.unwrap_or_default()
+				.as_ptr(),
+				xlib::False,
+			);
+			atom != 0 && xlib::XGetSelectionOwner(self.inner, atom) != 0
+		}
+	}
+
 	/**
 	 * Get the root window of the default screen.
 	 *
@@ -88,6 +202,203 @@ impl Display {
 		}
 	}
 
+	/**
+	 * Get the layout of the monitors if they have different pixel densities.
+	 *
+	 * The layout is queried once and shared between the copies of the display.
+	 *
+	 * @return MonitorLayout (Option)
+	 */
+	pub fn get_monitor_layout(&self) -> Option<&'static MonitorLayout> {
+		self.monitor_layout
+			.get_or_init(|| {
+				let layout = self.query_monitor_layout();
+				debug!("Monitor layout: {:?}", layout);
+				layout
+			})
+			.as_ref()
+	}
+
+	/**
+	 * Query the monitors for creating their layout.
+	 *
+	 * Density is not known for the monitors without a physical size
+	 * (e.g. projectors) and the layout is not used in that case.
+	 *
+	 * @return MonitorLayout (Option)
+	 */
+	fn query_monitor_layout(&self) -> Option<MonitorLayout> {
+		let mut monitors = Vec::new();
+		unsafe {
+			let resources = xrandr::XRRGetScreenResourcesCurrent(
+				self.inner,
+				xlib::XRootWindowOfScreen(xlib::XDefaultScreenOfDisplay(self.inner)),
+			);
+			if resources.is_null() {
+				return None;
+			}
+			for crtc in slice::from_raw_parts(
+				(*resources).crtcs,
+				(*resources).ncrtc.try_into().unwrap_or_default(),
+			)
+			.iter()
+			.map(|v| xrandr::XRRGetCrtcInfo(self.inner, resources, *v))
+			.filter(|crtc| !crtc.is_null())
+			{
+				if (*crtc).noutput > 0 {
+					let output = xrandr::XRRGetOutputInfo(
+						self.inner,
+						resources,
+						*(*crtc).outputs,
+					);
+					let size = if output.is_null() {
+						0.
+					} else {
+						let size = ((*output).mm_width as f32)
+							.hypot((*output).mm_height as f32);
+						xrandr::XRRFreeOutputInfo(output);
+						size
+					};
+					monitors.push((size > 0.).then(|| {
+						Monitor::new(
+							Geometry::new(
+								(*crtc).x,
+								(*crtc).y,
+								(*crtc).width,
+								(*crtc).height,
+							),
+							((*crtc).width as f32).hypot((*crtc).height as f32)
+								/ (size / MM_PER_INCH),
+						)
+					}));
+				}
+				xrandr::XRRFreeCrtcInfo(crtc);
+			}
+			xrandr::XRRFreeScreenResources(resources);
+		}
+		let monitors = monitors.into_iter().collect::<Option<Vec<Monitor>>>()?;
+		debug!("Monitors: {:?}", monitors);
+		MonitorLayout::new(&monitors)
+	}
+
+	/**
+	 * Create a new top-level window with the name and class hints set.
+	 *
+	 * @param  geometry
+	 * @param  title
+	 * @return Window
+	 */
+	pub fn create_window(&self, geometry: Geometry, title: &str) -> Window {
+		unsafe {
+			let screen = xlib::XDefaultScreen(self.inner);
+			let xid = xlib::XCreateSimpleWindow(
+				self.inner,
+				xlib::XRootWindow(self.inner, screen),
+				geometry.x,
+				geometry.y,
+				geometry.width,
+				geometry.height,
+				0,
+				xlib::XBlackPixel(self.inner, screen),
+				xlib::XBlackPixel(self.inner, screen),
+			);
+			let title = CString::new(title).unwrap_or_default();
+			xlib::XStoreName(self.inner, xid, title.as_ptr());
+			let (name, class) = (
+				CString::new(WINDOW_NAME).unwrap_or_default(),
+				CString::new(WINDOW_CLASS).unwrap_or_default(),
+			);
+			let class_hint = xlib::XAllocClassHint();
+			if !class_hint.is_null() {
+				(*class_hint).res_name = name.as_ptr() as *mut c_char;
+				(*class_hint).res_class = class.as_ptr() as *mut c_char;
+				xlib::XSetClassHint(self.inner, xid, class_hint);
+				xlib::XFree(class_hint as *mut c_void);
+			}
+			Window::new(xid, *self)
+		}
+	}
+
+	/**
+	 * Get the root window with the area of the pixel under the pointer.
+	 *
+	 * @return Window (Option)
+	 */
+	pub fn get_pointer_window(&self) -> Option<Window> {
+		let mut window = self.get_root_window();
+		let (mut root, mut child) = (0, 0);
+		let (mut root_x, mut root_y, mut win_x, mut win_y) = (0, 0, 0, 0);
+		let mut mask = 0;
+		if unsafe {
+			xlib::XQueryPointer(
+				self.inner,
+				window.xid,
+				&mut root,
+				&mut child,
+				&mut root_x,
+				&mut root_y,
+				&mut win_x,
+				&mut win_y,
+				&mut mask,
+			)
+		} == xlib::True
+		{
+			debug!("Pointer position: {}x{}", root_x, root_y);
+			window.area = Geometry::new(root_x, root_y, 1, 1);
+			Some(window)
+		} else {
+			None
+		}
+	}
+
+	/**
+	 * Get the window with the given ID after validating it.
+	 *
+	 * @param  xid
+	 * @return Window (Option)
+	 */
+	pub fn get_window_by_id(&self, xid: c_ulong) -> Option<Window> {
+		let mut attributes = MaybeUninit::<xlib::XWindowAttributes>::uninit();
+		if xid != 0
+			&& unsafe {
+				xlib::XGetWindowAttributes(self.inner, xid, attributes.as_mut_ptr())
+			} != 0
+		{
+			Some(Window::new(xid, *self))
+		} else {
+			error!("Invalid window ID: {:#x}", xid);
+			None
+		}
+	}
+
+	/**
+	 * Get the visible windows with the given instance or class name.
+	 *
+	 * @param  class
+	 * @return Vector of Window
+	 */
+	pub fn get_windows_by_class(&self, class: &str) -> Vec<Window> {
+		let mut windows = Vec::new();
+		let mut parents = vec![self.get_root_window()];
+		while let Some(parent) = parents.pop() {
+			for window in parent.get_children() {
+				match window.get_class() {
+					Some((name, class_name))
+						if name.eq_ignore_ascii_case(class)
+							|| class_name.eq_ignore_ascii_case(class) =>
+					{
+						windows.push(window)
+					}
+					_ => parents.push(window),
+				}
+			}
+		}
+		if windows.is_empty() {
+			error!("No window found with the class: {:?}", class);
+		}
+		windows
+	}
+
 	/**
 	 * Get the focused window.
 	 *
@@ -133,33 +444,42 @@ impl Display {
 	/**
 	 * Get the type of Window given with RecordWindow enum.
 	 *
-	 * @return Tuple (Window, Geometry)
+	 * @return Tuple (Window, Geometry) (Option)
 	 */
-	fn get_window(&self) -> (Window, Geometry) {
+	fn get_window(&self) -> Option<(Window, Geometry)> {
 		match self.settings.window {
-			RecordWindow::Focus(geometry, parent) => (
-				self.get_focused_window(parent)
-					.expect("Failed to get the window"),
-				geometry.unwrap_or_default(),
-			),
+			RecordWindow::Focus(geometry, parent) => self
+				.get_focused_window(parent)
+				.map(|window| (window, geometry.unwrap_or_default())),
 			RecordWindow::Root(geometry) => {
-				(self.get_root_window(), geometry.unwrap_or_default())
+				Some((self.get_root_window(), geometry.unwrap_or_default()))
 			}
+			RecordWindow::Synthetic(geometry) => {
+				Some((self.get_root_window(), geometry))
+			}
+			RecordWindow::Id(xid, geometry) => self
+				.get_window_by_id(xid)
+				.map(|window| (window, geometry.unwrap_or_default())),
+			RecordWindow::Class(class, _) => self
+				.get_windows_by_class(class)
+				.into_iter()
+				.next()
+				.map(|window| (window, Geometry::default())),
 		}
 	}
 
 	/**
 	 * Get a window from monitor specified via settings.
 	 *
-	 * @return Tuple (Window, Geometry)
+	 * @return Tuple (Window, Geometry) (Option)
 	 */
-	fn get_window_from_monitor(&mut self) -> (Window, Geometry) {
-		let (window, mut size) = self.get_window();
+	fn get_window_from_monitor(&mut self) -> Option<(Window, Geometry)> {
+		let (window, mut size) = self.get_window()?;
 		if let RecordWindow::Root(_) = self.settings.window {
 			if let Some(monitor) = self.settings.flag.monitor {
 				let crtc = window.get_crtc_info();
 				let geometry = crtc
-					.get(monitor.checked_sub(1).unwrap_or_default())
+					.get(monitor.saturating_sub(1))
 					.expect("Invalid monitor number");
 				size = *geometry;
 				self.settings.padding.left =
@@ -168,7 +488,7 @@ impl Display {
 					geometry.y.try_into().unwrap_or_default();
 			}
 		}
-		(window, size)
+		Some((window, size))
 	}
 
 	/**
@@ -200,26 +520,67 @@ impl Display {
 	 * @return Window (Option)
 	 */
 	pub fn select_window(&mut self, input_state: &InputState) -> Option<Window> {
-		let (mut window, size) = self.get_window_from_monitor();
+		let (mut window, size) = self.get_window_from_monitor()?;
 		let mut xid = None;
 		let window_padding = self.settings.padding;
 		let mut change_factor = AREA_CHANGE_FACTOR;
-		let font = self.font.unwrap_or_else(|| unsafe {
-			xlib::XLoadQueryFont(
-				self.inner,
-				CString::new(DEFAULT_FONT).unwrap_or_default().as_ptr(),
-			)
-		});
+		let mut size_input = SizeInput::default();
+		let mut snap = None;
+		let default_font = match self.font {
+			Some(_) => ptr::null_mut(),
+			None => unsafe {
+				xlib::XLoadQueryFont(
+					self.inner,
+					CString::new(DEFAULT_FONT).unwrap_or_default().as_ptr(),
+				)
+			},
+		};
+		let font = self.font.unwrap_or(default_font);
+		let mut overlay = match self.settings.border_style.dim {
+			Some(alpha) if self.settings.flag.select => {
+				Overlay::create(*self, alpha)
+			}
+			_ => None,
+		};
+		let mut loupe = (self.settings.flag.loupe && self.settings.flag.select)
+			.then(|| Loupe::create(*self));
 		let start_time = Instant::now();
 		while !input_state.check_action() {
 			thread::sleep(Duration::from_millis(self.settings.time.interval));
-			window = self.get_window().0;
+			if let Some((current_window, _)) = self.get_window() {
+				window = current_window;
+			}
+			if let Some(overlay) = overlay.as_mut() {
+				overlay.update(window.get_root_area());
+			}
+			if let Some(loupe) = loupe.as_mut() {
+				loupe.update();
+			}
 			if self.settings.flag.select {
 				window.draw_borders();
-				window.show_text_centered(Some(window.area.to_string()), font);
+				window.show_text_centered(
+					Some(if size_input.is_empty() {
+						window.area.to_string()
+					} else {
+						format!("{} [{}]", window.area, size_input)
+					}),
+					font,
+				);
 			}
 			let reset_area =
-				self.update_area(window, input_state, &mut change_factor);
+				self.update_area(window, input_state, &mut change_factor, &mut snap);
+			if self.settings.flag.select
+				&& size_input.update(input_state.state.get_keys())
+			{
+				if let Some(size) = size_input.take_size() {
+					debug!("Typed size: {}x{}", size.width, size.height);
+					self.update_padding(
+						self.settings.ratio.map_or(size, |ratio| ratio.fit(size)),
+						window.geometry,
+					);
+				}
+				window.clear_area();
+			}
 			if input_state.check_cancel_keys() {
 				warn!("User interrupt detected.");
 				xid = None;
@@ -240,11 +601,14 @@ impl Display {
 				}
 				self.settings.padding = window_padding;
 				self.update_padding(size, window.geometry);
+				snap = None;
 				window.clear_area();
 				input_state
 					.action_keys
 					.get_primary()
 					.iter()
+					.copied()
+					.chain(SizeInput::get_keys().iter())
 					.for_each(|key| {
 						window.grab_key(self.get_symbol_from_keycode(key))
 					});
@@ -255,12 +619,21 @@ impl Display {
 		}
 		trace!("{:?}", input_state);
 		debug!("Selected window: {:?}", xid);
+		if let Some(overlay) = overlay {
+			overlay.destroy();
+		}
+		if let Some(loupe) = loupe {
+			loupe.destroy();
+		}
 		if (self.settings.border.is_some()
 			|| (self.settings.border.is_none() && self.settings.time.countdown == 0))
 			&& self.settings.flag.select
 		{
 			window.clear_area();
-			window.show_text(Some(String::from(" ")), FpsClock::new(500));
+			window.show_text(Some(String::from(" ")), FpsClock::new(500.));
+		}
+		if !default_font.is_null() {
+			unsafe { xlib::XFreeFont(self.inner, default_font) };
 		}
 		if let Some(id) = xid {
 			Window::new(id, *self).ungrab_keys();
@@ -280,12 +653,51 @@ impl Display {
 		if !size.is_zero() {
 			self.settings.padding.right = window_geometry
 				.width
-				.checked_sub(size.width + self.settings.padding.left)
-				.unwrap_or_default();
+				.saturating_sub(size.width + self.settings.padding.left);
 			self.settings.padding.bottom = window_geometry
 				.height
-				.checked_sub(size.height + self.settings.padding.top)
-				.unwrap_or_default();
+				.saturating_sub(size.height + self.settings.padding.top);
+		} else if let Some(ratio) = self.settings.ratio {
+			let area = ratio.fit(Geometry::new(
+				0,
+				0,
+				window_geometry
+					.width
+					.saturating_sub(self.settings.padding.left),
+				window_geometry
+					.height
+					.saturating_sub(self.settings.padding.top),
+			));
+			self.update_padding(area, window_geometry);
+		}
+	}
+
+	/**
+	 * Constrain the padding to keep the aspect ratio of the area.
+	 *
+	 * @param prev_padding
+	 * @param window_geometry
+	 */
+	fn constrain_padding(
+		&mut self,
+		prev_padding: Padding,
+		window_geometry: Geometry,
+	) {
+		if let Some(ratio) = self.settings.ratio {
+			let padding = self.settings.padding;
+			let mut area = window_geometry;
+			area.with_padding(padding);
+			if padding.top != prev_padding.top
+				|| padding.bottom != prev_padding.bottom
+			{
+				self.settings.padding.right = window_geometry
+					.width
+					.saturating_sub(ratio.get_width(area.height) + padding.left);
+			} else {
+				self.settings.padding.bottom = window_geometry
+					.height
+					.saturating_sub(ratio.get_height(area.width) + padding.top);
+			}
 		}
 	}
 
@@ -295,6 +707,7 @@ impl Display {
 	 * @param  window
 	 * @param  input_state
 	 * @param  change
+	 * @param  snap (Option)
 	 * @return bool
 	 */
 	fn update_area(
@@ -302,8 +715,10 @@ impl Display {
 		window: Window,
 		input_state: &InputState,
 		change: &mut u32,
+		snap: &mut Option<EdgeSnap>,
 	) -> bool {
 		let mut reset_area = false;
+		let prev_padding = self.settings.padding;
 		let modifiers = self.settings.padding.get_modifiers();
 		for (value, increase, decrease) in modifiers {
 			match input_state.state.get_keys().as_slice() {
@@ -326,11 +741,11 @@ impl Display {
 					}
 				}
 				[Keycode::LControl, Keycode::LAlt, key]
-				| [Keycode::LControl, key, Keycode::LAlt] => {
-					if key == &decrease[0] || key == &decrease[1] {
-						*value = value.checked_sub(*change).unwrap_or(*value);
-						window.clear_area();
-					}
+				| [Keycode::LControl, key, Keycode::LAlt]
+					if key == &decrease[0] || key == &decrease[1] =>
+				{
+					*value = value.checked_sub(*change).unwrap_or(*value);
+					window.clear_area();
 				}
 				[Keycode::LShift, Keycode::LAlt, key]
 				| [key, Keycode::LShift, Keycode::LAlt] => {
@@ -352,6 +767,20 @@ impl Display {
 				_ => {}
 			}
 		}
+		if self.settings.padding != prev_padding {
+			if let Some(threshold) = self.settings.snap {
+				let snap = snap.get_or_insert_with(|| {
+					EdgeSnap::new(
+						threshold,
+						window.geometry,
+						&window.get_child_areas(),
+					)
+				});
+				self.settings.padding =
+					snap.apply(prev_padding, self.settings.padding);
+			}
+			self.constrain_padding(prev_padding, window.geometry);
+		}
 		info!(
 			" Selected area -> [{}] {}\r#",
 			window.area,
@@ -377,8 +806,12 @@ mod tests {
 	#[test]
 	fn test_x11_display() {
 		let mut settings = RecordSettings::default();
-		settings.time = RecordTime::new(Some(0.0), 0, 0, 10);
+		settings.time = RecordTime::new(Some(0.0), None, 0, 0, 10, 0);
 		settings.flag.font = Some(DEFAULT_FONT);
+		assert_eq!(
+			"-misc-fixed-*-*-*-*-24-*-*-*-*-*-*-*",
+			Display::get_sized_font(DEFAULT_FONT, 24)
+		);
 		let mut display = Display::open(Some(settings)).unwrap();
 		display
 			.set_focused_window(display.get_root_window().xid, xlib::RevertToParent);
@@ -389,6 +822,8 @@ mod tests {
 		);
 		let input_state = InputState::default();
 		assert!(display.select_window(&input_state).is_none());
+		let window = display.create_window(Geometry::new(0, 0, 10, 20), "test");
+		assert_eq!(Geometry::new(0, 0, 10, 20), window.geometry);
 		assert_eq!(
 			u64::try_from(keysym::XK_Alt_L).unwrap(),
 			display
@@ -403,6 +838,9 @@ mod tests {
 			u64::try_from(keysym::XK_X).unwrap(),
 			display.get_symbol_from_keycode(&Keycode::X) as u64
 		);
-		display.get_root_window().release();
+		let root_window = display.get_root_window();
+		root_window.release();
+		assert!(display.is_closed());
+		root_window.release();
 	}
 }
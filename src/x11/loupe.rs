@@ -0,0 +1,254 @@
+use crate::image::geometry::Geometry;
+use crate::x11::display::Display;
+use std::mem::MaybeUninit;
+use std::os::raw::c_ulong;
+use std::ptr;
+use x11::xlib;
+
+/* Number of the sampled pixels on each side of the pointer */
+const LOUPE_RADIUS: u32 = 7;
+/* Scale factor of the sampled pixels */
+const LOUPE_ZOOM: u32 = 8;
+/* Distance of the loupe from the corner of the screen */
+const LOUPE_MARGIN: i32 = 20;
+
+/* Magnified view of the pixels around the pointer */
+#[derive(Debug)]
+pub struct Loupe {
+	display: Display,
+	xid: c_ulong,
+	gc: xlib::GC,
+	size: Geometry,
+	position: (i32, i32),
+}
+
+impl Loupe {
+	/**
+	 * Create and map a new loupe window.
+	 *
+	 * @param  display
+	 * @return Loupe
+	 */
+	pub fn create(display: Display) -> Self {
+		let root = display.get_root_window();
+		let side = Self::get_side() * LOUPE_ZOOM;
+		unsafe {
+			let mut attributes =
+				MaybeUninit::<xlib::XSetWindowAttributes>::zeroed().assume_init();
+			attributes.override_redirect = xlib::True;
+			let xid = xlib::XCreateWindow(
+				display.inner,
+				root.xid,
+				LOUPE_MARGIN,
+				LOUPE_MARGIN,
+				side,
+				side,
+				1,
+				xlib::CopyFromParent,
+				xlib::InputOutput as u32,
+				ptr::null_mut(),
+				xlib::CWOverrideRedirect,
+				&mut attributes,
+			);
+			xlib::XMapRaised(display.inner, xid);
+			let gc = xlib::XCreateGC(display.inner, xid, 0, ptr::null_mut());
+			debug!("Loupe window: {}", xid);
+			Self {
+				display,
+				xid,
+				gc,
+				size: root.geometry,
+				position: (LOUPE_MARGIN, LOUPE_MARGIN),
+			}
+		}
+	}
+
+	/**
+	 * Get the number of the sampled pixels on a side.
+	 *
+	 * @return u32
+	 */
+	fn get_side() -> u32 {
+		LOUPE_RADIUS * 2 + 1
+	}
+
+	/**
+	 * Get the area to sample around the pointer.
+	 *
+	 * @param  pointer
+	 * @param  size
+	 * @return Geometry
+	 */
+	fn get_sample_area(pointer: (i32, i32), size: Geometry) -> Geometry {
+		let side = Self::get_side();
+		let radius = i32::try_from(LOUPE_RADIUS).unwrap_or_default();
+		let (max_x, max_y) = (
+			i32::try_from(size.width.saturating_sub(side)).unwrap_or_default(),
+			i32::try_from(size.height.saturating_sub(side)).unwrap_or_default(),
+		);
+		Geometry::new(
+			(pointer.0 - radius).clamp(0, max_x),
+			(pointer.1 - radius).clamp(0, max_y),
+			side.min(size.width),
+			side.min(size.height),
+		)
+	}
+
+	/**
+	 * Get the position of the loupe on the corner away from the pointer.
+	 *
+	 * @param  pointer
+	 * @param  size
+	 * @return Tuple
+	 */
+	fn get_position(pointer: (i32, i32), size: Geometry) -> (i32, i32) {
+		let side = i32::try_from(Self::get_side() * LOUPE_ZOOM).unwrap_or_default();
+		let (width, height) = (
+			i32::try_from(size.width).unwrap_or_default(),
+			i32::try_from(size.height).unwrap_or_default(),
+		);
+		(
+			if pointer.0 < width / 2 {
+				width - side - LOUPE_MARGIN
+			} else {
+				LOUPE_MARGIN
+			},
+			if pointer.1 < height / 2 {
+				height - side - LOUPE_MARGIN
+			} else {
+				LOUPE_MARGIN
+			},
+		)
+	}
+
+	/**
+	 * Get the pointer position on the root window.
+	 *
+	 * @return Tuple (Option)
+	 */
+	fn get_pointer(&self) -> Option<(i32, i32)> {
+		let (mut root, mut child) = (0, 0);
+		let (mut root_x, mut root_y, mut win_x, mut win_y) = (0, 0, 0, 0);
+		let mut mask = 0;
+		(unsafe {
+			xlib::XQueryPointer(
+				self.display.inner,
+				self.xid,
+				&mut root,
+				&mut child,
+				&mut root_x,
+				&mut root_y,
+				&mut win_x,
+				&mut win_y,
+				&mut mask,
+			)
+		} == xlib::True)
+			.then_some((root_x, root_y))
+	}
+
+	/* Draw the magnified pixels around the pointer. */
+	pub fn update(&mut self) {
+		let pointer = match self.get_pointer() {
+			Some(pointer) => pointer,
+			None => return,
+		};
+		let position = Self::get_position(pointer, self.size);
+		let area = Self::get_sample_area(pointer, self.size);
+		let root = self.display.get_root_window();
+		unsafe {
+			if self.position != position {
+				xlib::XMoveWindow(
+					self.display.inner,
+					self.xid,
+					position.0,
+					position.1,
+				);
+				self.position = position;
+			}
+			let image = xlib::XGetImage(
+				self.display.inner,
+				root.xid,
+				area.x,
+				area.y,
+				area.width,
+				area.height,
+				xlib::XAllPlanes(),
+				xlib::ZPixmap,
+			);
+			if image.is_null() {
+				return;
+			}
+			let zoom = i32::try_from(LOUPE_ZOOM).unwrap_or_default();
+			for y in 0..i32::try_from(area.height).unwrap_or_default() {
+				for x in 0..i32::try_from(area.width).unwrap_or_default() {
+					xlib::XSetForeground(
+						self.display.inner,
+						self.gc,
+						xlib::XGetPixel(image, x, y),
+					);
+					xlib::XFillRectangle(
+						self.display.inner,
+						self.xid,
+						self.gc,
+						x * zoom,
+						y * zoom,
+						LOUPE_ZOOM,
+						LOUPE_ZOOM,
+					);
+				}
+			}
+			/* Mark the pixel under the pointer with its inverted color */
+			let (x, y) = (pointer.0 - area.x, pointer.1 - area.y);
+			xlib::XSetForeground(
+				self.display.inner,
+				self.gc,
+				!xlib::XGetPixel(image, x, y) & 0x00FF_FFFF,
+			);
+			xlib::XDestroyImage(image);
+			xlib::XDrawRectangle(
+				self.display.inner,
+				self.xid,
+				self.gc,
+				x * zoom,
+				y * zoom,
+				LOUPE_ZOOM - 1,
+				LOUPE_ZOOM - 1,
+			);
+			xlib::XFlush(self.display.inner);
+		}
+	}
+
+	/* Destroy the loupe window. */
+	pub fn destroy(self) {
+		unsafe {
+			xlib::XFreeGC(self.display.inner, self.gc);
+			xlib::XDestroyWindow(self.display.inner, self.xid);
+			xlib::XSync(self.display.inner, xlib::False);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	fn test_loupe_area() {
+		let size = Geometry::new(0, 0, 1920, 1080);
+		assert_eq!(
+			Geometry::new(93, 43, 15, 15),
+			Loupe::get_sample_area((100, 50), size)
+		);
+		assert_eq!(
+			Geometry::new(0, 1065, 15, 15),
+			Loupe::get_sample_area((2, 1079), size)
+		);
+		assert_eq!(
+			Geometry::new(0, 0, 10, 10),
+			Loupe::get_sample_area((5, 5), Geometry::new(0, 0, 10, 10))
+		);
+		assert_eq!((1780, 940), Loupe::get_position((100, 50), size));
+		assert_eq!((20, 20), Loupe::get_position((1900, 1000), size));
+		assert_eq!((1780, 20), Loupe::get_position((10, 1000), size));
+	}
+}
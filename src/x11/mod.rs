@@ -1,11 +1,16 @@
 pub mod display;
+pub mod loupe;
+pub mod overlay;
 pub mod window;
 
-use crate::record::settings::RecordWindow;
+use crate::image::geometry::Geometry;
+use crate::record::region::RegionStore;
+use crate::record::settings::{RecordSettings, RecordWindow};
 use crate::settings::AppSettings;
 use crate::window::Access;
 use crate::x11::display::Display;
 use crate::x11::window::Window;
+use std::env;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 use x11::xlib;
@@ -39,12 +44,30 @@ impl<'a> Access<'a, Window> for WindowSystem<'a> {
 	 * @return Window (Option)
 	 */
 	fn get_window(&mut self) -> Option<Window> {
+		if self.settings.args.is_present("pick") {
+			return self.display.get_pointer_window();
+		}
 		debug!("Record window: {:?}", self.settings.record.window);
 		match self.settings.record.window {
 			RecordWindow::Focus(None, parent) => {
 				self.display.get_focused_window(parent)
 			}
 			RecordWindow::Root(None) => Some(self.display.get_root_window()),
+			RecordWindow::Id(xid, None) => self.display.get_window_by_id(xid),
+			RecordWindow::Id(xid, Some(_))
+				if self.settings.record.command.is_some() =>
+			{
+				self.display.get_window_by_id(xid)
+			}
+			RecordWindow::Id(xid, Some(_))
+				if self.display.get_window_by_id(xid).is_none() =>
+			{
+				warn!("Window not found: {:#x}", xid);
+				None
+			}
+			RecordWindow::Class(class, _) => {
+				self.display.get_windows_by_class(class).into_iter().next()
+			}
 			_ => {
 				if self.settings.record.command.is_some() {
 					self.display.get_focused_window(
@@ -54,19 +77,82 @@ impl<'a> Access<'a, Window> for WindowSystem<'a> {
 						},
 					)
 				} else {
-					self.display.select_window(
+					let window = self.display.select_window(
 						self.settings
 							.input_state
 							.expect("Failed to get the input state"),
-					)
+					);
+					if let (Some(window), Some(name)) =
+						(window, self.settings.record.region.save)
+					{
+						Self::save_region(name, window.get_root_area());
+					}
+					window
 				}
 			}
 		}
 	}
+
+	/**
+	 * Get all the windows to capture.
+	 *
+	 * @return Vector of Window
+	 */
+	fn get_windows(&mut self) -> Vec<Window> {
+		match self.settings.record.window {
+			RecordWindow::Class(class, true) => {
+				self.display.get_windows_by_class(class)
+			}
+			_ => self.get_window().into_iter().collect(),
+		}
+	}
+}
+
+impl WindowSystem<'_> {
+	/**
+	 * Save the selected area as a named region.
+	 *
+	 * @param name
+	 * @param area
+	 */
+	fn save_region(name: &str, area: Geometry) {
+		match RegionStore::default().save(name, area) {
+			Ok(()) => info!(
+				"Region saved as {:?}. ({})",
+				name,
+				RegionStore::format(&area)
+			),
+			Err(e) => warn!("Failed to save the region: {}", e),
+		}
+	}
+
+	/**
+	 * Wait for the display to be ready until the timeout.
+	 *
+	 * @param  settings
+	 * @return bool
+	 */
+	pub fn wait_for_display(settings: &RecordSettings) -> bool {
+		if let Some(display) = Display::open(Some(*settings)) {
+			display.close();
+			true
+		} else {
+			error!(
+				"Cannot open display {:?}.{}",
+				env::var("DISPLAY").unwrap_or_default(),
+				if settings.time.wait != 0 {
+					format!(" (gave up after {}s)", settings.time.wait)
+				} else {
+					String::new()
+				}
+			);
+			false
+		}
+	}
 }
 
 /* X opcodes to trace */
-static TRACED_OPCODES: &[u8] = &[14, 55, 56, 67, 74];
+static TRACED_OPCODES: &[u8] = &[3, 14, 55, 56, 67, 74];
 
 /* Error handler implementation for X11 */
 unsafe extern "C" fn handle_x11_errors(
@@ -80,14 +166,15 @@ unsafe extern "C" fn handle_x11_errors(
 		"{}[Opcode: {}, Serial: {}]",
 		if xlib::XGetErrorText(
 			display,
-			(*error).error_code.try_into().unwrap_or_default(),
+			(*error).error_code.into(),
 			error_text.as_mut_ptr() as *mut c_char,
 			error_text.capacity().try_into().unwrap_or_default(),
 		) == 0
 		{
 			CStr::from_ptr(error_text.as_mut_ptr() as *mut c_char)
 				.to_string_lossy()
-				.into_owned() + " "
+				.into_owned()
+				+ " "
 		} else {
 			String::from("Unknown error ")
 		},
@@ -0,0 +1,185 @@
+use crate::image::geometry::Geometry;
+use crate::x11::display::Display;
+use std::mem::MaybeUninit;
+use std::os::raw::c_ulong;
+use std::ptr;
+use x11::{xfixes, xlib};
+
+/* Depth of the visual with an alpha channel */
+const ARGB_DEPTH: i32 = 32;
+/* Kind of the window shape that receives the input (ShapeInput) */
+const SHAPE_INPUT: i32 = 2;
+
+/* Fullscreen overlay for dimming everything except the selection */
+#[derive(Debug)]
+pub struct Overlay {
+	display: Display,
+	xid: c_ulong,
+	gc: xlib::GC,
+	colormap: xlib::Colormap,
+	size: Geometry,
+	alpha: u8,
+	hole: Option<Geometry>,
+}
+
+impl Overlay {
+	/**
+	 * Create and map a new overlay window.
+	 *
+	 * The overlay is only created when a compositor is running since
+	 * the transparency is not visible otherwise, and it has an empty
+	 * input region so that the events are passed to the windows below.
+	 *
+	 * @param  display
+	 * @param  alpha
+	 * @return Overlay (Option)
+	 */
+	pub fn create(display: Display, alpha: u8) -> Option<Self> {
+		if !display.has_compositor() {
+			warn!("No compositor is running, cannot dim.");
+			return None;
+		}
+		let root = display.get_root_window();
+		unsafe {
+			let screen = xlib::XDefaultScreen(display.inner);
+			let mut visual_info = MaybeUninit::<xlib::XVisualInfo>::uninit();
+			if xlib::XMatchVisualInfo(
+				display.inner,
+				screen,
+				ARGB_DEPTH,
+				xlib::TrueColor,
+				visual_info.as_mut_ptr(),
+			) == 0
+			{
+				warn!("No visual with an alpha channel found, cannot dim.");
+				return None;
+			}
+			let visual_info = visual_info.assume_init();
+			let colormap = xlib::XCreateColormap(
+				display.inner,
+				root.xid,
+				visual_info.visual,
+				xlib::AllocNone,
+			);
+			let mut attributes =
+				MaybeUninit::<xlib::XSetWindowAttributes>::zeroed().assume_init();
+			attributes.override_redirect = xlib::True;
+			attributes.colormap = colormap;
+			attributes.background_pixel = 0;
+			attributes.border_pixel = 0;
+			let xid = xlib::XCreateWindow(
+				display.inner,
+				root.xid,
+				0,
+				0,
+				root.geometry.width,
+				root.geometry.height,
+				0,
+				ARGB_DEPTH,
+				xlib::InputOutput as u32,
+				visual_info.visual,
+				xlib::CWOverrideRedirect
+					| xlib::CWColormap
+					| xlib::CWBackPixel
+					| xlib::CWBorderPixel,
+				&mut attributes,
+			);
+			let region =
+				xfixes::XFixesCreateRegion(display.inner, ptr::null_mut(), 0);
+			xfixes::XFixesSetWindowShapeRegion(
+				display.inner,
+				xid,
+				SHAPE_INPUT,
+				0,
+				0,
+				region,
+			);
+			xfixes::XFixesDestroyRegion(display.inner, region);
+			xlib::XMapRaised(display.inner, xid);
+			let gc = xlib::XCreateGC(display.inner, xid, 0, ptr::null_mut());
+			debug!("Overlay window: {} (alpha: {})", xid, alpha);
+			Some(Self {
+				display,
+				xid,
+				gc,
+				colormap,
+				size: root.geometry,
+				alpha,
+				hole: None,
+			})
+		}
+	}
+
+	/**
+	 * Dim the overlay except the given area.
+	 *
+	 * @param hole
+	 */
+	pub fn update(&mut self, hole: Geometry) {
+		if self.hole == Some(hole) {
+			return;
+		}
+		self.hole = Some(hole);
+		let (width, height) = (
+			i32::try_from(self.size.width).unwrap_or_default(),
+			i32::try_from(self.size.height).unwrap_or_default(),
+		);
+		let left = hole.x.clamp(0, width);
+		let top = hole.y.clamp(0, height);
+		let right = hole
+			.x
+			.saturating_add(i32::try_from(hole.width).unwrap_or_default())
+			.clamp(left, width);
+		let bottom = hole
+			.y
+			.saturating_add(i32::try_from(hole.height).unwrap_or_default())
+			.clamp(top, height);
+		unsafe {
+			/* Colors of the ARGB visual are premultiplied by alpha */
+			xlib::XSetForeground(
+				self.display.inner,
+				self.gc,
+				c_ulong::from(self.alpha) << 24,
+			);
+			for (x, y, w, h) in [
+				(0, 0, width, top),
+				(0, bottom, width, height - bottom),
+				(0, top, left, bottom - top),
+				(right, top, width - right, bottom - top),
+			] {
+				if w > 0 && h > 0 {
+					xlib::XFillRectangle(
+						self.display.inner,
+						self.xid,
+						self.gc,
+						x,
+						y,
+						w as u32,
+						h as u32,
+					);
+				}
+			}
+			xlib::XSetForeground(self.display.inner, self.gc, 0);
+			xlib::XFillRectangle(
+				self.display.inner,
+				self.xid,
+				self.gc,
+				left,
+				top,
+				(right - left) as u32,
+				(bottom - top) as u32,
+			);
+			xlib::XFlush(self.display.inner);
+		}
+	}
+
+	/* Destroy the overlay window. */
+	pub fn destroy(self) {
+		unsafe {
+			xlib::XFreeGC(self.display.inner, self.gc);
+			xlib::XDestroyWindow(self.display.inner, self.xid);
+			xlib::XFreeColormap(self.display.inner, self.colormap);
+			xlib::XSync(self.display.inner, xlib::False);
+		}
+	}
+}
@@ -1,31 +1,85 @@
 use crate::image::geometry::Geometry;
+use crate::image::padding::Padding;
 use crate::image::Image;
 use crate::record::fps::FpsClock;
+use crate::record::settings::{ExcludeMode, LineStyle, TextPosition};
 use crate::window::Capture;
 use crate::x11::display::Display;
-use image::Rgba;
-use std::ffi::CString;
+use image::{ExtendedColorType, Rgba};
+use std::ffi::{CStr, CString};
 use std::fmt;
 use std::io::{self, Write};
 use std::mem::MaybeUninit;
 use std::os::raw::{c_char, c_uint, c_ulong};
 use std::ptr;
 use std::slice;
-use x11::{xlib, xrandr};
+use x11::{keysym, xlib, xrandr};
 
 /* Maximum height of the text to show on window */
 const MAX_TEXT_HEIGHT: u32 = 40;
 /* Offset for placing the text on the corner of window */
 const TEXT_CORNER_OFFSET: i32 = 20;
+/* Character width of the default font */
+const DEFAULT_CHAR_WIDTH: i32 = 6;
+/* Character height of the default font */
+const DEFAULT_CHAR_HEIGHT: i32 = 13;
 /* Padding value to apply to window borders */
 const BORDER_PADDING: u32 = 1;
+/* Length of the dashes on dashed borders */
+const BORDER_DASH_LENGTH: c_char = 8;
+/* Maximum length of the corner markers */
+const CORNER_MARKER_LENGTH: u32 = 20;
+/* Width multiplier of the corner markers */
+const CORNER_MARKER_WIDTH: u32 = 3;
+/* Sigma value for blurring the excluded windows */
+const EXCLUDE_BLUR_SIGMA: f32 = 10.;
+/* Margin around the window frame for including the shadow */
+const SHADOW_MARGIN: u32 = 24;
+/* Title of the preview window */
+const PREVIEW_TITLE: &str = "menyoki (Enter: save, Escape: discard)";
+
+/* Position and size of a color channel in the pixel values */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ChannelMask {
+	shift: u32,
+	bits: u32,
+}
+
+impl ChannelMask {
+	/**
+	 * Create a new ChannelMask object.
+	 *
+	 * @param  mask
+	 * @return ChannelMask
+	 */
+	fn new(mask: c_ulong) -> Self {
+		Self {
+			shift: mask.trailing_zeros(),
+			bits: mask.count_ones().min(16),
+		}
+	}
+
+	/**
+	 * Get the value of the channel scaled to 16 bits.
+	 *
+	 * @param  pixel
+	 * @return u16
+	 */
+	fn get_value(&self, pixel: u32) -> u16 {
+		if self.bits == 0 || self.shift >= u32::BITS {
+			return 0;
+		}
+		let max = (1 << self.bits) - 1;
+		let value = (pixel >> self.shift) & max;
+		((value * u32::from(u16::MAX) + max / 2) / max) as u16
+	}
+}
 
 /* X11 window id, geometric properties and its display */
 #[derive(Clone, Copy, Debug)]
 pub struct Window {
 	pub xid: c_ulong,
 	display: Display,
-	gc: xlib::GC,
 	pub geometry: Geometry,
 	pub area: Geometry,
 }
@@ -60,12 +114,10 @@ impl Window {
 			Self {
 				xid,
 				display,
-				gc: ptr::null::<xlib::GC>() as xlib::GC,
 				geometry: Geometry::default(),
 				area: Geometry::default(),
 			}
 			.set_geometry()
-			.set_gc()
 		}
 	}
 
@@ -131,13 +183,16 @@ impl Window {
 	}
 
 	/**
-	 * Set the graphics context of Window object.
+	 * Run the given function with a temporary graphics context.
 	 *
-	 * @return Window
+	 * @param draw
 	 */
-	unsafe fn set_gc(&mut self) -> Self {
-		self.gc = self.get_gc();
-		*self
+	fn with_gc<F: FnOnce(xlib::GC)>(&self, draw: F) {
+		unsafe {
+			let gc = self.get_gc();
+			draw(gc);
+			xlib::XFreeGC(self.display.inner, gc);
+		}
 	}
 
 	/**
@@ -165,6 +220,121 @@ impl Window {
 		}
 	}
 
+	/**
+	 * Get the visible child windows along with their attributes.
+	 *
+	 * @return Vector of tuple (xid, XWindowAttributes)
+	 */
+	fn get_visible_children(&self) -> Vec<(c_ulong, xlib::XWindowAttributes)> {
+		let mut windows = Vec::new();
+		unsafe {
+			let mut root = MaybeUninit::<c_ulong>::uninit();
+			let mut parent = MaybeUninit::<c_ulong>::uninit();
+			let mut children = MaybeUninit::<*mut c_ulong>::uninit();
+			let mut nchildren = MaybeUninit::<c_uint>::uninit();
+			if xlib::XQueryTree(
+				self.display.inner,
+				self.xid,
+				root.as_mut_ptr(),
+				parent.as_mut_ptr(),
+				children.as_mut_ptr(),
+				nchildren.as_mut_ptr(),
+			) == 0
+			{
+				return windows;
+			}
+			let children = children.assume_init();
+			if children.is_null() {
+				return windows;
+			}
+			for child in slice::from_raw_parts(
+				children,
+				nchildren.assume_init().try_into().unwrap_or_default(),
+			) {
+				let mut attributes =
+					MaybeUninit::<xlib::XWindowAttributes>::uninit();
+				if xlib::XGetWindowAttributes(
+					self.display.inner,
+					*child,
+					attributes.as_mut_ptr(),
+				) != 0
+				{
+					let attributes = attributes.assume_init();
+					if attributes.map_state == xlib::IsViewable {
+						windows.push((*child, attributes));
+					}
+				}
+			}
+			xlib::XFree(children.cast());
+		}
+		windows
+	}
+
+	/**
+	 * Get the areas of the visible child windows.
+	 *
+	 * @return Vector of Geometry
+	 */
+	pub fn get_child_areas(&self) -> Vec<Geometry> {
+		self.get_visible_children()
+			.into_iter()
+			.map(|(_, attributes)| {
+				let border = attributes.border_width;
+				Geometry::new(
+					attributes.x,
+					attributes.y,
+					u32::try_from(attributes.width + border * 2).unwrap_or_default(),
+					u32::try_from(attributes.height + border * 2)
+						.unwrap_or_default(),
+				)
+			})
+			.collect()
+	}
+
+	/**
+	 * Get the visible child windows.
+	 *
+	 * @return Vector of Window
+	 */
+	pub fn get_children(&self) -> Vec<Self> {
+		self.get_visible_children()
+			.into_iter()
+			.map(|(xid, _)| Self::new(xid, self.display))
+			.collect()
+	}
+
+	/**
+	 * Get the instance and class names of the window (WM_CLASS).
+	 *
+	 * @return Tuple (Option)
+	 */
+	pub fn get_class(&self) -> Option<(String, String)> {
+		unsafe {
+			let mut class_hint = MaybeUninit::<xlib::XClassHint>::uninit();
+			if xlib::XGetClassHint(
+				self.display.inner,
+				self.xid,
+				class_hint.as_mut_ptr(),
+			) == 0
+			{
+				return None;
+			}
+			let class_hint = class_hint.assume_init();
+			let into_string = |value: *mut c_char| {
+				if value.is_null() {
+					return String::new();
+				}
+				let value_str = CStr::from_ptr(value).to_string_lossy().into_owned();
+				xlib::XFree(value.cast());
+				value_str
+			};
+			Some((
+				into_string(class_hint.res_name),
+				into_string(class_hint.res_class),
+			))
+		}
+	}
+
 	/**
 	 * Get CRTC (VDC) information of the window.
 	 *
@@ -197,6 +367,106 @@ impl Window {
 		crtc_info
 	}
 
+	/**
+	 * Get the area relative to the root window.
+	 *
+	 * @return Geometry
+	 */
+	pub fn get_root_area(&self) -> Geometry {
+		let (mut x, mut y, mut child) = (0, 0, 0);
+		unsafe {
+			xlib::XTranslateCoordinates(
+				self.display.inner,
+				self.xid,
+				self.display.get_root_window().xid,
+				self.area.x,
+				self.area.y,
+				&mut x,
+				&mut y,
+				&mut child,
+			);
+		}
+		Geometry::new(x, y, self.area.width, self.area.height)
+	}
+
+	/**
+	 * Get the extents of the window frame that is added by the window manager.
+	 *
+	 * @return Padding
+	 */
+	fn get_frame_extents(&self) -> Padding {
+		unsafe {
+			let atom = xlib::XInternAtom(
+				self.display.inner,
+				CString::new("_NET_FRAME_EXTENTS")
+					.unwrap_or_default()
+					.as_ptr(),
+				xlib::True,
+			);
+			if atom == 0 {
+				return Padding::default();
+			}
+			let (mut actual_type, mut actual_format) = (0, 0);
+			let (mut items, mut bytes_after) = (0, 0);
+			let mut data = ptr::null_mut();
+			let status = xlib::XGetWindowProperty(
+				self.display.inner,
+				self.xid,
+				atom,
+				0,
+				4,
+				xlib::False,
+				xlib::XA_CARDINAL,
+				&mut actual_type,
+				&mut actual_format,
+				&mut items,
+				&mut bytes_after,
+				&mut data,
+			);
+			let extents = if status == i32::from(xlib::Success)
+				&& actual_format == 32
+				&& items == 4
+				&& !data.is_null()
+			{
+				/* Order of the extents is left, right, top, bottom */
+				let extents = slice::from_raw_parts(data as *const c_ulong, 4)
+					.iter()
+					.map(|v| u32::try_from(*v).unwrap_or_default())
+					.collect::<Vec<u32>>();
+				Padding::new(extents[2], extents[1], extents[3], extents[0])
+			} else {
+				Padding::default()
+			};
+			if !data.is_null() {
+				xlib::XFree(data as *mut _);
+			}
+			extents
+		}
+	}
+
+	/**
+	 * Get the area of the root window that includes the frame and shadow.
+	 *
+	 * Shadows are drawn by the compositor outside of the window,
+	 * so they are only visible on the (composited) root window.
+	 *
+	 * @return Geometry (Option)
+	 */
+	fn get_shadow_area(&self) -> Option<Geometry> {
+		let area = self.get_root_area();
+		let extents = self.get_frame_extents();
+		debug!("Frame extents: {}", extents);
+		let (left, top) =
+			(extents.left + SHADOW_MARGIN, extents.top + SHADOW_MARGIN);
+		Geometry::new(
+			area.x - i32::try_from(left).unwrap_or_default(),
+			area.y - i32::try_from(top).unwrap_or_default(),
+			area.width + left + extents.right + SHADOW_MARGIN,
+			area.height + top + extents.bottom + SHADOW_MARGIN,
+		)
+		.intersect(self.display.get_root_window().geometry)
+	}
+
 	/**
 	 * Get the name of the window.
 	 *
@@ -222,39 +492,163 @@ impl Window {
 		}
 	}
 
+	/**
+	 * Get the areas of the excluded windows relative to the captured area.
+	 *
+	 * @return Vector of Geometry
+	 */
+	fn get_excluded_areas(&self) -> Vec<Geometry> {
+		self.display
+			.settings
+			.exclude
+			.windows
+			.iter()
+			.filter_map(|xid| unsafe {
+				let mut attributes =
+					MaybeUninit::<xlib::XWindowAttributes>::uninit();
+				if xlib::XGetWindowAttributes(
+					self.display.inner,
+					*xid,
+					attributes.as_mut_ptr(),
+				) == 0
+				{
+					debug!("Invalid window ID to exclude: {:#x}", xid);
+					return None;
+				}
+				let attributes = attributes.assume_init();
+				if attributes.map_state != xlib::IsViewable {
+					return None;
+				}
+				let (mut x, mut y, mut child) = (0, 0, 0);
+				xlib::XTranslateCoordinates(
+					self.display.inner,
+					*xid,
+					self.xid,
+					0,
+					0,
+					&mut x,
+					&mut y,
+					&mut child,
+				);
+				let border = attributes.border_width;
+				Some(Geometry::new(
+					x - border - self.area.x,
+					y - border - self.area.y,
+					u32::try_from(attributes.width + border * 2).unwrap_or_default(),
+					u32::try_from(attributes.height + border * 2)
+						.unwrap_or_default(),
+				))
+			})
+			.collect()
+	}
+
 	/* Draw a rectangle inside the window. */
 	pub fn draw_borders(&self) {
-		if self.display.settings.border.is_some() {
-			unsafe {
+		if let Some(border) = self.display.settings.border {
+			let area = Geometry::new(
+				self.area
+					.x
+					.checked_add(i32::try_from(BORDER_PADDING).unwrap_or_default())
+					.unwrap_or(self.area.x),
+				self.area
+					.y
+					.checked_add(i32::try_from(BORDER_PADDING).unwrap_or_default())
+					.unwrap_or(self.area.y),
+				self.area
+					.width
+					.checked_sub(BORDER_PADDING * 2)
+					.unwrap_or(self.area.width),
+				self.area
+					.height
+					.checked_sub(BORDER_PADDING * 2)
+					.unwrap_or(self.area.height),
+			);
+			let style = self.display.settings.border_style;
+			self.with_gc(|gc| unsafe {
+				if style.line == LineStyle::Dashed {
+					xlib::XSetLineAttributes(
+						self.display.inner,
+						gc,
+						border,
+						xlib::LineOnOffDash,
+						xlib::CapNotLast,
+						xlib::JoinMiter,
+					);
+					let dashes = [BORDER_DASH_LENGTH; 2];
+					xlib::XSetDashes(
+						self.display.inner,
+						gc,
+						0,
+						dashes.as_ptr(),
+						dashes.len().try_into().unwrap_or_default(),
+					);
+				}
 				xlib::XDrawRectangle(
 					self.display.inner,
 					self.xid,
-					self.gc,
-					self.area
-						.x
-						.checked_add(
-							i32::try_from(BORDER_PADDING).unwrap_or_default(),
-						)
-						.unwrap_or(self.area.x),
-					self.area
-						.y
-						.checked_add(
-							i32::try_from(BORDER_PADDING).unwrap_or_default(),
-						)
-						.unwrap_or(self.area.y),
-					self.area
-						.width
-						.checked_sub(BORDER_PADDING * 2)
-						.unwrap_or(self.area.width),
-					self.area
-						.height
-						.checked_sub(BORDER_PADDING * 2)
-						.unwrap_or(self.area.height),
+					gc,
+					area.x,
+					area.y,
+					area.width,
+					area.height,
 				);
-			}
+				if style.corners {
+					self.draw_corners(gc, area, border);
+				}
+			});
 		}
 	}
 
+	/**
+	 * Draw "L" shaped markers on the corners of the given area.
+	 *
+	 * @param gc
+	 * @param area
+	 * @param border
+	 */
+	unsafe fn draw_corners(&self, gc: xlib::GC, area: Geometry, border: u32) {
+		xlib::XSetLineAttributes(
+			self.display.inner,
+			gc,
+			border * CORNER_MARKER_WIDTH,
+			xlib::LineSolid,
+			xlib::CapProjecting,
+			xlib::JoinMiter,
+		);
+		let length = i32::try_from(
+			CORNER_MARKER_LENGTH
+				.min(area.width / 3)
+				.min(area.height / 3),
+		)
+		.unwrap_or_default();
+		let (left, top) = (area.x, area.y);
+		let right = left + i32::try_from(area.width).unwrap_or_default();
+		let bottom = top + i32::try_from(area.height).unwrap_or_default();
+		let mut segments = [
+			(left, top, left + length, top),
+			(left, top, left, top + length),
+			(right, top, right - length, top),
+			(right, top, right, top + length),
+			(left, bottom, left + length, bottom),
+			(left, bottom, left, bottom - length),
+			(right, bottom, right - length, bottom),
+			(right, bottom, right, bottom - length),
+		]
+		.map(|(x1, y1, x2, y2)| xlib::XSegment {
+			x1: x1.try_into().unwrap_or_default(),
+			y1: y1.try_into().unwrap_or_default(),
+			x2: x2.try_into().unwrap_or_default(),
+			y2: y2.try_into().unwrap_or_default(),
+		});
+		xlib::XDrawSegments(
+			self.display.inner,
+			self.xid,
+			gc,
+			segments.as_mut_ptr(),
+			segments.len().try_into().unwrap_or_default(),
+		);
+	}
+
 	/**
 	 * Draw a text on the window.
 	 *
@@ -263,17 +657,61 @@ impl Window {
 	 * @param y
 	 */
 	fn draw_text(&self, text: &str, x: i32, y: i32) {
-		unsafe {
+		let text_cstr = CString::new(text).unwrap_or_default();
+		self.with_gc(|gc| unsafe {
 			xlib::XDrawString(
 				self.display.inner,
 				self.xid,
-				self.get_gc(),
+				gc,
 				x,
 				y,
-				CString::new(text).unwrap_or_default().as_ptr(),
+				text_cstr.as_ptr(),
 				text.len().try_into().unwrap_or_default(),
 			);
-		}
+		});
+	}
+
+	/**
+	 * Draw a text on the given position of the window area.
+	 *
+	 * @param text
+	 * @param position
+	 * @param font (Option)
+	 */
+	fn draw_text_at(
+		&self,
+		text: &str,
+		position: TextPosition,
+		font: Option<*mut xlib::XFontStruct>,
+	) {
+		let text_cstr = CString::new(text).unwrap_or_default();
+		let (width, ascent, descent) = match font {
+			Some(font) => unsafe {
+				(
+					xlib::XTextWidth(
+						font,
+						text_cstr.as_ptr(),
+						text.len().try_into().unwrap_or_default(),
+					),
+					(*font).ascent,
+					(*font).descent,
+				)
+			},
+			None => (
+				i32::try_from(text.len()).unwrap_or_default() * DEFAULT_CHAR_WIDTH,
+				DEFAULT_CHAR_HEIGHT,
+				0,
+			),
+		};
+		let (x, y) = position.get_point(
+			self.area,
+			(
+				u32::try_from(width).unwrap_or_default(),
+				u32::try_from(ascent + descent).unwrap_or_default(),
+			),
+			TEXT_CORNER_OFFSET,
+		);
+		self.draw_text(text, x, y + ascent);
 	}
 
 	/**
@@ -284,8 +722,10 @@ impl Window {
 	 */
 	pub fn show_text(&self, text: Option<String>, mut clock: FpsClock) {
 		let text = text.unwrap_or_default();
-		for _ in 0..clock.fps {
-			if let Some(x_offset) = self.area.width.checked_sub(
+		for _ in 0..clock.fps as u32 {
+			if let Some(position) = self.display.settings.flag.text_position {
+				self.draw_text_at(text.as_str(), position, self.display.font);
+			} else if let Some(x_offset) = self.area.width.checked_sub(
 				u32::try_from(TEXT_CORNER_OFFSET).unwrap_or_default() + 5,
 			) {
 				self.draw_text(
@@ -309,6 +749,14 @@ impl Window {
 		text: Option<String>,
 		font: *mut xlib::XFontStruct,
 	) {
+		if let Some(position) = self.display.settings.flag.text_position {
+			self.draw_text_at(
+				text.as_deref().unwrap_or_default(),
+				position,
+				Some(font).filter(|font| !font.is_null()),
+			);
+			return;
+		}
 		let area_text = CString::new(self.area.to_string()).unwrap_or_default();
 		let text_width = unsafe {
 			xlib::XTextWidth(
@@ -387,14 +835,36 @@ impl Capture for Window {
 	 * @return Image (Option)
 	 */
 	fn get_image(&self) -> Option<Image> {
+		if self.display.is_closed() {
+			warn!("Display is closed, cannot capture the window.");
+			return None;
+		}
+		let (xid, area, offset) = match self
+			.display
+			.settings
+			.flag
+			.shadow
+			.then(|| self.get_shadow_area())
+			.flatten()
+		{
+			Some(shadow_area) => {
+				let root_area = self.get_root_area();
+				(
+					self.display.get_root_window().xid,
+					shadow_area,
+					(root_area.x - shadow_area.x, root_area.y - shadow_area.y),
+				)
+			}
+			None => (self.xid, self.area, (0, 0)),
+		};
 		unsafe {
 			let window_image = xlib::XGetImage(
 				self.display.inner,
-				self.xid,
-				self.area.x,
-				self.area.y,
-				self.area.width,
-				self.area.height,
+				xid,
+				area.x,
+				area.y,
+				area.width,
+				area.height,
 				xlib::XAllPlanes(),
 				xlib::ZPixmap,
 			);
@@ -405,14 +875,79 @@ impl Capture for Window {
 					image.width as usize * image.height as usize,
 				)
 				.to_vec();
+				/* Visuals with more than 8 bits per channel (e.g. 30-bit) */
+				let deep_masks = (image.depth > 24 && image.bits_per_pixel == 32)
+					.then(|| {
+						[image.red_mask, image.green_mask, image.blue_mask]
+							.map(ChannelMask::new)
+					});
 				xlib::XDestroyImage(window_image);
-				Some(Image::new(
-					data.iter()
-						.map(|bgra| Rgba::from([bgra[2], bgra[1], bgra[0], bgra[3]]))
-						.collect(),
-					self.display.settings.flag.alpha,
-					self.area,
-				))
+				let mut image = match deep_masks {
+					Some(masks) => {
+						debug!("Decoding the image with {:?}", masks);
+						let deep_data = data
+							.iter()
+							.map(|pixel| {
+								let pixel = u32::from_ne_bytes(*pixel);
+								let [r, g, b] =
+									masks.map(|mask| mask.get_value(pixel));
+								Rgba::from([r, g, b, u16::MAX])
+							})
+							.collect::<Vec<Rgba<u16>>>();
+						Image::new(
+							deep_data
+								.iter()
+								.map(|rgba| {
+									Rgba::from(rgba.0.map(|v| {
+										((u32::from(v) * 255 + 32767) / 65535) as u8
+									}))
+								})
+								.collect(),
+							self.display.settings.flag.alpha,
+							area,
+						)
+						.with_deep_data(deep_data)
+					}
+					None => Image::new(
+						data.iter()
+							.map(|bgra| {
+								Rgba::from([bgra[2], bgra[1], bgra[0], bgra[3]])
+							})
+							.collect(),
+						self.display.settings.flag.alpha,
+						area,
+					),
+				};
+				if self.display.settings.flag.opaque {
+					image.set_opaque();
+				}
+				/* Scale the monitors to the same density on the full root capture */
+				if self.display.settings.flag.scale_monitors
+					&& offset == (0, 0)
+					&& (area.x, area.y) == (0, 0)
+					&& (area.width, area.height)
+						== (self.geometry.width, self.geometry.height)
+					&& self.xid
+						== xlib::XRootWindowOfScreen(xlib::XDefaultScreenOfDisplay(
+							self.display.inner,
+						)) {
+					if let Some(layout) = self.display.get_monitor_layout() {
+						image = layout.get_image(&image);
+					}
+				}
+				for mut area in self.get_excluded_areas() {
+					area.x += offset.0;
+					area.y += offset.1;
+					match self.display.settings.exclude.mode {
+						ExcludeMode::Black => {
+							image.fill_area(area, Rgba::from([0, 0, 0, 255]))
+						}
+						ExcludeMode::Blur => {
+							image.blur_area(area, EXCLUDE_BLUR_SIGMA)
+						}
+					}
+				}
+				Some(image)
 			} else {
 				None
 			}
@@ -422,7 +957,7 @@ impl Capture for Window {
 	/* Show a countdown on the corner of window. */
 	fn show_countdown(&self) {
 		if self.display.settings.time.countdown != 0 {
-			let clock = FpsClock::new(1000);
+			let clock = FpsClock::new(1000.);
 			for i in 0..(self.display.settings.time.countdown + 1) {
 				self.clear_area();
 				self.show_text(
@@ -452,11 +987,187 @@ impl Capture for Window {
 		self.clear_area();
 	}
 
+	/**
+	 * Show the images on a preview window and wait for confirmation.
+	 *
+	 * @param  images
+	 * @return bool
+	 */
+	fn preview(&self, images: &[&Image]) -> bool {
+		let width = images.iter().map(|image| image.geometry.width).sum::<u32>();
+		let height = images
+			.iter()
+			.map(|image| image.geometry.height)
+			.max()
+			.unwrap_or_default();
+		if width == 0 || height == 0 {
+			return true;
+		}
+		unsafe {
+			let display = self.display.inner;
+			let screen = xlib::XDefaultScreen(display);
+			let window = self
+				.display
+				.create_window(Geometry::new(0, 0, width, height), PREVIEW_TITLE)
+				.xid;
+			let mut wm_delete_window = xlib::XInternAtom(
+				display,
+				CString::new("WM_DELETE_WINDOW")
+					.unwrap_or_default()
+					.as_ptr(),
+				xlib::False,
+			);
+			xlib::XSetWMProtocols(display, window, &mut wm_delete_window, 1);
+			xlib::XSelectInput(
+				display,
+				window,
+				xlib::ExposureMask | xlib::KeyPressMask,
+			);
+			xlib::XMapRaised(display, window);
+			let gc = xlib::XCreateGC(display, window, 0, ptr::null_mut());
+			let mut data = images
+				.iter()
+				.map(|image| {
+					image
+						.get_data(ExtendedColorType::Rgba8)
+						.chunks(4)
+						.flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]])
+						.collect::<Vec<u8>>()
+				})
+				.collect::<Vec<Vec<u8>>>();
+			let mut event = MaybeUninit::<xlib::XEvent>::uninit();
+			let confirmed = loop {
+				xlib::XNextEvent(display, event.as_mut_ptr());
+				let event = event.assume_init_mut();
+				match event.get_type() {
+					xlib::Expose => {
+						xlib::XGrabKeyboard(
+							display,
+							window,
+							xlib::False,
+							xlib::GrabModeAsync,
+							xlib::GrabModeAsync,
+							xlib::CurrentTime,
+						);
+						let mut x = 0;
+						for (image, data) in images.iter().zip(data.iter_mut()) {
+							let ximage = xlib::XCreateImage(
+								display,
+								xlib::XDefaultVisual(display, screen),
+								xlib::XDefaultDepth(display, screen) as c_uint,
+								xlib::ZPixmap,
+								0,
+								data.as_mut_ptr() as *mut c_char,
+								image.geometry.width,
+								image.geometry.height,
+								32,
+								0,
+							);
+							if !ximage.is_null() {
+								xlib::XPutImage(
+									display,
+									window,
+									gc,
+									ximage,
+									0,
+									0,
+									x,
+									0,
+									image.geometry.width,
+									image.geometry.height,
+								);
+								(*ximage).data = ptr::null_mut();
+								xlib::XDestroyImage(ximage);
+							}
+							x += i32::try_from(image.geometry.width)
+								.unwrap_or_default();
+						}
+					}
+					xlib::KeyPress => {
+						match u32::try_from(xlib::XLookupKeysym(&mut event.key, 0))
+							.unwrap_or_default()
+						{
+							keysym::XK_Return | keysym::XK_KP_Enter => break true,
+							keysym::XK_Escape => break false,
+							_ => {}
+						}
+					}
+					xlib::ClientMessage
+						if event.client_message.data.get_long(0) as c_ulong
+							== wm_delete_window =>
+					{
+						break false;
+					}
+					_ => {}
+				}
+			};
+			xlib::XUngrabKeyboard(display, xlib::CurrentTime);
+			xlib::XFreeGC(display, gc);
+			xlib::XDestroyWindow(display, window);
+			xlib::XSync(display, xlib::False);
+			confirmed
+		}
+	}
+
 	/* Close the display */
 	fn release(&self) {
-		trace!("Display closed.");
+		self.display.close();
+	}
+
+	/**
+	 * Get the title of the window.
+	 *
+	 * @return String (Option)
+	 */
+	fn get_title(&self) -> Option<String> {
+		self.get_name()
+	}
+
+	/**
+	 * Get the pointer position relative to the capture area.
+	 *
+	 * @return Tuple (Option)
+	 */
+	fn get_pointer(&self) -> Option<(i32, i32)> {
+		let (mut root, mut child) = (0, 0);
+		let (mut root_x, mut root_y, mut win_x, mut win_y) = (0, 0, 0, 0);
+		let mut mask = 0;
+		if unsafe {
+			xlib::XQueryPointer(
+				self.display.inner,
+				self.xid,
+				&mut root,
+				&mut child,
+				&mut root_x,
+				&mut root_y,
+				&mut win_x,
+				&mut win_y,
+				&mut mask,
+			)
+		} == xlib::True
+		{
+			Some((win_x - self.area.x, win_y - self.area.y))
+		} else {
+			None
+		}
+	}
+
+	/**
+	 * Check if the window still exists (i.e. not closed).
+	 *
+	 * @return bool
+	 */
+	fn exists(&self) -> bool {
+		if self.display.is_closed() {
+			return false;
+		}
+		let mut attributes = MaybeUninit::<xlib::XWindowAttributes>::uninit();
 		unsafe {
-			xlib::XCloseDisplay(self.display.inner);
+			xlib::XGetWindowAttributes(
+				self.display.inner,
+				self.xid,
+				attributes.as_mut_ptr(),
+			) != 0
 		}
 	}
 }
@@ -472,9 +1183,26 @@ mod tests {
 	use pretty_assertions::assert_eq;
 	use x11::keysym;
 	#[test]
+	fn test_channel_mask() {
+		let masks = [0x3FF0_0000, 0x000F_FC00, 0x0000_03FF].map(ChannelMask::new);
+		assert_eq!(
+			ChannelMask {
+				shift: 20,
+				bits: 10
+			},
+			masks[0]
+		);
+		let pixel = (1023 << 20) | (512 << 10) | 1;
+		assert_eq!(
+			[u16::MAX, 32800, 64],
+			masks.map(|mask| mask.get_value(pixel))
+		);
+		assert_eq!(0, ChannelMask::new(0).get_value(pixel));
+	}
+	#[test]
 	fn test_x11_window() {
 		let mut settings = RecordSettings::default();
-		settings.time = RecordTime::new(Some(0.0), 1, 0, 10);
+		settings.time = RecordTime::new(Some(0.0), None, 1, 0, 10, 0);
 		let display = Display::open(Some(settings)).unwrap();
 		let window = display.get_root_window();
 		unsafe {
@@ -497,6 +1225,7 @@ mod tests {
 		);
 		assert_eq!((0, 0), (window.geometry.x, window.geometry.y));
 		assert_eq!("root-window", window.get_name().unwrap());
+		assert!(window.exists());
 		assert_eq!(
 			1920 * 1080 * 3,
 			window